@@ -4,6 +4,7 @@
 //! This ensures we use bd's business logic, views, and sync mechanisms.
 
 use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
 use std::process::Command;
 use thiserror::Error;
 
@@ -19,6 +20,8 @@ pub struct BeadInfo {
     pub description: Option<String>,
     pub priority: i32,
     pub status: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub labels: Vec<String>,
 }
 
 /// Raw issue format from `bd --json` output
@@ -29,6 +32,8 @@ struct BdIssue {
     description: Option<String>,
     status: String,
     priority: i32,
+    #[serde(default)]
+    labels: Vec<String>,
     // Other fields we don't need: issue_type, created_at, updated_at, etc.
 }
 
@@ -40,10 +45,22 @@ impl From<BdIssue> for BeadInfo {
             description: issue.description,
             priority: issue.priority,
             status: issue.status,
+            labels: issue.labels,
         }
     }
 }
 
+/// Dependency edges of a bead as reported by `bd show --json`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BeadDependencies {
+    /// Beads this one depends on
+    #[serde(default, rename(deserialize = "dependencies"))]
+    pub depends_on: Vec<serde_json::Value>,
+    /// Beads that depend on this one
+    #[serde(default)]
+    pub dependents: Vec<serde_json::Value>,
+}
+
 /// Errors that can occur when interacting with beads
 #[derive(Debug, Error)]
 pub enum BeadsError {
@@ -63,6 +80,9 @@ pub enum BeadsError {
     IoError(#[from] std::io::Error),
 }
 
+/// Statuses bacchus sets that bd only accepts once registered as custom statuses
+pub const CUSTOM_STATUSES: &str = "paused,split";
+
 // ============================================================================
 // Public API
 // ============================================================================
@@ -121,6 +141,86 @@ pub fn get_ready_beads() -> Result<Vec<BeadInfo>, BeadsError> {
     Ok(issues.into_iter().map(BeadInfo::from).collect())
 }
 
+/// List every bead, including closed ones (via `bd list --all --json`)
+pub fn list_all_beads() -> Result<Vec<BeadInfo>, BeadsError> {
+    let output = Command::new("bd")
+        .args(["list", "--all", "--json", "--quiet"])
+        .output()
+        .map_err(|e| {
+            if e.kind() == std::io::ErrorKind::NotFound {
+                BeadsError::BdNotFound
+            } else {
+                BeadsError::IoError(e)
+            }
+        })?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(BeadsError::CommandFailed(stderr.to_string()));
+    }
+
+    let issues: Vec<BdIssue> = serde_json::from_slice(&output.stdout)
+        .map_err(|e| BeadsError::ParseError(e.to_string()))?;
+
+    Ok(issues.into_iter().map(BeadInfo::from).collect())
+}
+
+/// Create a bead with an explicit ID (via `bd create --id <id> --json`)
+pub fn create_bead(bead: &BeadInfo) -> Result<(), BeadsError> {
+    let priority = bead.priority.to_string();
+    let mut args = vec!["create", bead.title.as_str(), "--id", bead.id.as_str(), "--priority", priority.as_str()];
+    if let Some(desc) = &bead.description {
+        args.extend(["--description", desc.as_str()]);
+    }
+    let labels = bead.labels.join(",");
+    if !labels.is_empty() {
+        args.extend(["--labels", labels.as_str()]);
+    }
+    args.extend(["--json", "--quiet"]);
+
+    let output = Command::new("bd")
+        .args(&args)
+        .output()
+        .map_err(|e| {
+            if e.kind() == std::io::ErrorKind::NotFound {
+                BeadsError::BdNotFound
+            } else {
+                BeadsError::IoError(e)
+            }
+        })?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(BeadsError::CommandFailed(stderr.to_string()));
+    }
+
+    Ok(())
+}
+
+/// Record that `bead_id` depends on `depends_on` (via `bd dep add`)
+pub fn add_dependency(bead_id: &str, depends_on: &str) -> Result<(), BeadsError> {
+    let output = Command::new("bd")
+        .args(["dep", "add", bead_id, depends_on, "--quiet"])
+        .output()
+        .map_err(|e| {
+            if e.kind() == std::io::ErrorKind::NotFound {
+                BeadsError::BdNotFound
+            } else {
+                BeadsError::IoError(e)
+            }
+        })?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if stderr.contains("not found") || stderr.contains("no issue") {
+            return Err(BeadsError::BeadNotFound(bead_id.to_string()));
+        }
+        return Err(BeadsError::CommandFailed(stderr.to_string()));
+    }
+
+    Ok(())
+}
+
 /// Update a bead's status (via `bd update <id> --status <status>`)
 pub fn update_bead_status(bead_id: &str, status: &str) -> Result<(), BeadsError> {
     let output = Command::new("bd")
@@ -145,6 +245,17 @@ pub fn update_bead_status(bead_id: &str, status: &str) -> Result<(), BeadsError>
     Ok(())
 }
 
+/// Why setting one of bacchus's custom statuses failed, and how to fix it
+pub fn custom_status_error(bead_id: &str, status: &str, stderr: &str) -> String {
+    format!(
+        "bd refused to set {} to '{}' ({}); register bacchus's custom statuses with: bd config set status.custom \"{}\"",
+        bead_id,
+        status,
+        stderr.trim(),
+        CUSTOM_STATUSES
+    )
+}
+
 /// Get details for a specific bead (via `bd show <id> --json`)
 pub fn get_bead(bead_id: &str) -> Result<BeadInfo, BeadsError> {
     let output = Command::new("bd")
@@ -177,6 +288,65 @@ pub fn get_bead(bead_id: &str) -> Result<BeadInfo, BeadsError> {
         .ok_or_else(|| BeadsError::BeadNotFound(bead_id.to_string()))
 }
 
+/// Get dependencies and dependents of a bead (via `bd show <id> --json`)
+pub fn get_bead_dependencies(bead_id: &str) -> Result<BeadDependencies, BeadsError> {
+    let output = Command::new("bd")
+        .args(["show", bead_id, "--json", "--quiet"])
+        .output()
+        .map_err(|e| {
+            if e.kind() == std::io::ErrorKind::NotFound {
+                BeadsError::BdNotFound
+            } else {
+                BeadsError::IoError(e)
+            }
+        })?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if stderr.contains("not found") || stderr.contains("no issue") {
+            return Err(BeadsError::BeadNotFound(bead_id.to_string()));
+        }
+        return Err(BeadsError::CommandFailed(stderr.to_string()));
+    }
+
+    let issues: Vec<BeadDependencies> = serde_json::from_slice(&output.stdout)
+        .map_err(|e| BeadsError::ParseError(e.to_string()))?;
+
+    issues
+        .into_iter()
+        .next()
+        .ok_or_else(|| BeadsError::BeadNotFound(bead_id.to_string()))
+}
+
+// ============================================================================
+// Direct database access
+// ============================================================================
+
+/// Location of bd's SQLite database: `$BEADS_DB_PATH`, else `.beads/beads.db`
+pub fn beads_db_path(workspace_root: &Path) -> PathBuf {
+    std::env::var("BEADS_DB_PATH")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| workspace_root.join(".beads/beads.db"))
+}
+
+/// Create the `issues_fts` full-text index over issue titles and descriptions
+/// if it's missing, and rebuild it from `issues`
+///
+/// The index is external-content with no triggers: bd's own SQLite may lack
+/// FTS5, and a trigger would then break every bd write. Rebuilding on each use
+/// keeps it in step with whatever bd has written since.
+pub fn ensure_issues_fts(conn: &rusqlite::Connection) -> rusqlite::Result<()> {
+    conn.execute_batch(
+        "CREATE VIRTUAL TABLE IF NOT EXISTS issues_fts USING fts5(
+           title,
+           description,
+           content='issues',
+           content_rowid='rowid'
+         );
+         INSERT INTO issues_fts(issues_fts) VALUES('rebuild');",
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -189,6 +359,7 @@ mod tests {
             description: Some("A description".to_string()),
             status: "open".to_string(),
             priority: 1,
+            labels: vec!["backend".to_string()],
         };
 
         let bead: BeadInfo = bd_issue.into();
@@ -196,5 +367,17 @@ mod tests {
         assert_eq!(bead.title, "Test issue");
         assert_eq!(bead.priority, 1);
         assert_eq!(bead.status, "open");
+        assert_eq!(bead.labels, vec!["backend"]);
+    }
+
+    #[test]
+    fn test_parse_bead_dependencies() {
+        let json = r#"[{"id": "A", "dependencies": [{"id": "B"}], "dependents": []}]"#;
+        let deps: Vec<BeadDependencies> = serde_json::from_str(json).unwrap();
+        assert_eq!(deps[0].depends_on.len(), 1);
+        assert!(deps[0].dependents.is_empty());
+
+        let deps: Vec<BeadDependencies> = serde_json::from_str(r#"[{"id": "A"}]"#).unwrap();
+        assert!(deps[0].depends_on.is_empty());
     }
 }
@@ -3,6 +3,8 @@
 //! Defines command-line interface using clap.
 
 use clap::{Parser, Subcommand};
+use serde::Serialize;
+use std::path::PathBuf;
 
 #[derive(Parser)]
 #[command(name = "bacchus")]
@@ -13,7 +15,10 @@ pub struct Cli {
     pub command: Commands,
 }
 
-#[derive(Subcommand)]
+/// Serialized (kebab-case, externally tagged) into the audit log
+#[derive(Subcommand, Serialize)]
+#[serde(rename_all = "kebab-case")]
+#[allow(clippy::large_enum_variant)] // Parsed once per process; boxing buys nothing
 pub enum Commands {
     // ========================================================================
     // Coordination Commands (worktree-based)
@@ -23,6 +28,49 @@ pub enum Commands {
     Next {
         /// Your agent ID
         agent_id: String,
+        /// Poll every 5s until a bead is ready (Ctrl-C stops waiting)
+        #[arg(long)]
+        wait: bool,
+        /// Give up waiting after this many seconds (default: wait forever)
+        #[arg(long, requires = "wait")]
+        timeout: Option<u64>,
+        /// Prefer beads hinted at this file; skip beads hinted only at other files (repeatable)
+        #[arg(long, value_name = "PATH")]
+        filter_file: Vec<String>,
+        /// Branch to merge into on release (default: target_branch from .bacchus/config.toml, else main)
+        #[arg(long)]
+        target_branch: Option<String>,
+    },
+
+    /// Show the ready beads by priority with ETA estimates and what each one blocks
+    Queue {
+        /// Output format
+        #[arg(long, default_value = "json", value_parser = ["json", "table"])]
+        format: String,
+
+        /// Maximum entries to show (the summary still counts every ready bead)
+        #[arg(long, default_value_t = 50)]
+        limit: usize,
+    },
+
+    /// Reserve a bead for an agent without creating a worktree
+    Assign {
+        /// The bead ID to reserve
+        bead_id: String,
+
+        /// The agent `next` should hand the bead to
+        agent_id: String,
+
+        /// Move the bead's existing assignment to this agent
+        #[arg(long)]
+        reassign: bool,
+    },
+
+    /// Suggest (and apply) moving assigned or paused beads from busy agents to idle ones
+    Balance {
+        /// Only list the suggested commands
+        #[arg(long)]
+        dry_run: bool,
     },
 
     /// Claim a specific bead by ID, create worktree
@@ -34,6 +82,84 @@ pub enum Commands {
         /// Force claim even if bead is not ready (blocked/in_progress)
         #[arg(long)]
         force: bool,
+        /// Branch to merge into on release (default: target_branch from .bacchus/config.toml, else main)
+        #[arg(long)]
+        target_branch: Option<String>,
+    },
+
+    /// Claim several beads listed in a JSON spec file
+    ClaimFromFile {
+        /// Spec file: {"claims": [{"bead_id": "X", "agent_id": "Y"}, ...]}; a null bead_id claims the next ready bead
+        file: PathBuf,
+        /// Agent ID for entries that don't specify one
+        agent_id: String,
+    },
+
+    /// Create a worktree for a bead without going through next/claim
+    CreateWorktree {
+        /// The bead ID (branch is bacchus/<bead_id>)
+        bead_id: String,
+        /// Branch or commit to base the worktree on (default: HEAD)
+        #[arg(long)]
+        base_branch: Option<String>,
+        /// Only create the worktree; don't record a claim or touch beads
+        #[arg(long)]
+        no_claim: bool,
+        /// Agent ID to record the claim under
+        #[arg(long, default_value = "manual")]
+        agent_id: String,
+    },
+
+    /// Remove a bead's worktree, drop its claim, and reopen the bead
+    RemoveWorktree {
+        /// The bead ID
+        bead_id: String,
+        /// Keep the bacchus/<bead_id> branch
+        #[arg(long)]
+        keep_branch: bool,
+        /// Discard uncommitted changes and unmerged commits
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Move a finished bead's claim and symbol links to the archive tables
+    Archive {
+        /// The bead ID
+        bead_id: String,
+    },
+
+    /// Restore an archived bead's claim and symbol links
+    Unarchive {
+        /// The bead ID
+        bead_id: String,
+    },
+
+    /// Forcibly return a bead to open: drop its claim, worktree, and branch
+    Reset {
+        /// The bead ID
+        bead_id: String,
+        /// Required: reset discards the worktree without checking for changes
+        #[arg(long)]
+        confirm: bool,
+    },
+
+    /// Show git-level details for a bead's worktree
+    WorktreeInfo {
+        /// The bead ID
+        bead_id: String,
+    },
+
+    /// List managed worktrees with dirty state, ahead/behind counts, and claim age
+    WorktreeList {
+        /// Output format
+        #[arg(long, default_value = "json", value_parser = ["json", "table"])]
+        format: String,
+    },
+
+    /// Predict whether releasing a bead would hit merge conflicts, without starting a merge
+    Conflicts {
+        /// The bead ID
+        bead_id: String,
     },
 
     /// Release a claimed bead
@@ -43,28 +169,333 @@ pub enum Commands {
         /// Release status: done (merge), blocked (keep), or failed (discard)
         #[arg(long, default_value = "done")]
         status: String,
+        /// Merge commit template; supports {bead_id}, {title}, {agent_id}, {branch}, {files_changed}, {date}, {timestamp}
+        /// (default: merge_commit_template from .bacchus/config.toml, else "feat: {title} (bacchus/{bead_id})").
+        /// Giving one always creates a merge commit, unless require_linear_history is set
+        #[arg(long)]
+        commit_message: Option<String>,
+        /// Script to run after a successful merge (absolute or workspace-relative; default: $BACCHUS_POST_MERGE_SCRIPT)
+        #[arg(long)]
+        post_merge_script: Option<String>,
+        /// Squash the worktree's commits into one (titled after the bead) before merging
+        /// (always on with merge_strategy = "squash" in .bacchus/config.toml)
+        #[arg(long)]
+        squash: bool,
+        /// Tag the merge commit as bacchus/<bead_id>/<unix_timestamp> (annotated, with the bead title)
+        #[arg(long)]
+        tag: bool,
+        /// Run the tests for the bead's changed modules (as `bacchus test`) and refuse to merge if any fail
+        #[arg(long)]
+        run_tests: bool,
+    },
+
+    /// Undo a merged bead's changes on the current branch and reopen the bead
+    Revert {
+        /// The bead ID whose merge to revert
+        bead_id: String,
+
+        /// Leave the inverse patch staged instead of committing `revert: <bead_id>`
+        #[arg(long)]
+        no_commit: bool,
     },
 
-    /// Abort a failed merge for a bead
+    /// Set a claimed bead aside: drop the claim, keep the worktree and branch, mark it paused
+    Pause {
+        /// The bead ID to pause
+        bead_id: String,
+    },
+
+    /// Exempt a claim from stale detection (for beads that take hours or days)
+    Pin {
+        /// The bead ID to pin
+        bead_id: String,
+    },
+
+    /// Make a pinned claim subject to stale detection again
+    Unpin {
+        /// The bead ID to unpin
+        bead_id: String,
+    },
+
+    /// Reclaim a paused bead's existing worktree for an agent
+    Resume {
+        /// The bead ID to resume
+        bead_id: String,
+
+        /// Agent taking the work back up
+        agent_id: String,
+    },
+
+    /// Abort a failed merge (or a rebase stopped on conflicts) for a bead
     Abort {
         /// The bead ID with a failed merge
         bead_id: String,
     },
 
+    /// Push a bead's worktree branch to a remote (for CI or review)
+    Push {
+        /// The bead ID whose branch to push
+        bead_id: String,
+        /// Remote to push to (default: origin)
+        #[arg(long)]
+        remote: Option<String>,
+        /// Overwrite the remote branch (uses --force-with-lease)
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Show a bead's uncommitted worktree changes as JSON
+    Diff {
+        /// The bead ID whose worktree to diff
+        bead_id: String,
+
+        /// Only per-file addition/deletion counts, without patch text
+        #[arg(long)]
+        stat: bool,
+    },
+
+    /// Show which named symbols a bead's branch adds, modifies, or deletes vs its target branch
+    DiffSymbols {
+        /// The bead ID
+        bead_id: String,
+    },
+
+    /// Run the tests relevant to a bead's changed files in its worktree
+    Test {
+        /// The bead ID
+        bead_id: String,
+    },
+
+    /// Run the workspace's linter on a bead's changed files in its worktree
+    Lint {
+        /// The bead ID
+        bead_id: String,
+    },
+
+    /// Auto-format a bead's changed files in its worktree
+    Format {
+        /// The bead ID
+        bead_id: String,
+        /// Commit the formatted files as `style: auto-format for <bead_id>`
+        #[arg(long, conflicts_with = "check")]
+        commit: bool,
+        /// Fail if formatting is needed, without modifying files
+        #[arg(long)]
+        check: bool,
+    },
+
+    /// Suggest open beads that touch the same files as a bead, for follow-on work
+    FindRelated {
+        /// The bead ID
+        bead_id: String,
+    },
+
+    /// Estimate a bead's size in tokens, and whether it should be split, before claiming it
+    Estimate {
+        /// The bead ID
+        bead_id: String,
+    },
+
+    /// Split a claimed bead into new beads (the original is marked split)
+    Split {
+        /// The claimed bead to split
+        bead_id: String,
+
+        /// IDs for the new beads
+        #[arg(long, num_args = 1.., required = true)]
+        into: Vec<String>,
+
+        /// Share the original's registered symbols out among the new beads
+        #[arg(long)]
+        copy_symbols: bool,
+    },
+
+    /// Merge the latest origin/<target branch> into a bead's worktree
+    Sync {
+        /// The bead ID whose worktree to sync
+        bead_id: String,
+    },
+
+    /// Stash a bead's uncommitted changes, or `stash list` to show all stashes
+    #[command(args_conflicts_with_subcommands = true)]
+    Stash {
+        #[command(subcommand)]
+        command: Option<StashCommands>,
+
+        /// The bead ID whose worktree to stash
+        bead_id: Option<String>,
+
+        /// Stash message
+        #[arg(short, long)]
+        message: Option<String>,
+    },
+
+    /// Restore a bead's most recent stash into its worktree
+    Unstash {
+        /// The bead ID whose stash to pop
+        bead_id: String,
+    },
+
+    /// Save a restore point of a bead's uncommitted work, or `snapshot list <bead_id>`
+    #[command(args_conflicts_with_subcommands = true)]
+    Snapshot {
+        #[command(subcommand)]
+        command: Option<SnapshotCommands>,
+
+        /// The bead ID whose worktree to snapshot
+        bead_id: Option<String>,
+
+        /// Snapshot name (default: the current UTC time, e.g. 20260101-120000)
+        #[arg(long)]
+        name: Option<String>,
+    },
+
+    /// Apply a named snapshot to a bead's worktree (the snapshot is kept)
+    Restore {
+        /// The bead ID
+        bead_id: String,
+        /// Snapshot name
+        name: String,
+    },
+
+    /// Rebase a bead's worktree branch onto the latest target branch
+    Rebase {
+        /// The bead ID whose worktree to rebase
+        bead_id: String,
+        /// Branch to rebase onto (default: the claim's target branch)
+        #[arg(long)]
+        target: Option<String>,
+    },
+
     /// Resolve a merge conflict after manual resolution
     Resolve {
         /// The bead ID with resolved conflicts
         bead_id: String,
     },
 
+    /// Repair a bead's worktree, claim, and status after things go wrong
+    Recover {
+        /// The bead ID to recover
+        bead_id: String,
+        /// Only report what would be done
+        #[arg(long)]
+        dry_run: bool,
+    },
+
     /// Find stale claims and optionally clean them up
     Stale {
-        /// Minutes without activity to consider stale
-        #[arg(short, long, default_value = "15")]
-        minutes: i64,
+        /// Minutes without activity to consider stale (default: stale_threshold_minutes from config, else 15)
+        #[arg(short, long)]
+        minutes: Option<i64>,
         /// Clean up stale claims (remove worktrees, reset beads)
         #[arg(long)]
         cleanup: bool,
+        /// Threads for removing worktrees during cleanup (default: one per CPU)
+        #[arg(long, value_name = "N", requires = "cleanup")]
+        parallel: Option<usize>,
+    },
+
+    /// Autonomously clean up stale claims and claim ready beads up to N agents
+    Orchestrate {
+        /// Maximum number of concurrently active claims
+        #[arg(long, default_value = "3")]
+        max_agents: u32,
+        /// Minutes after which a claim is cleaned up as stale
+        #[arg(long, default_value = "30")]
+        stale_minutes: i64,
+        /// Seconds to sleep between cycles
+        #[arg(long, default_value = "30")]
+        poll_interval_seconds: u64,
+        /// Report one cycle without cleaning up or claiming
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Diagnose setup problems (tools, repository, databases, env overrides); exits 1 on errors
+    Doctor {
+        /// Output format
+        #[arg(long, default_value = "text", value_parser = ["text", "json"])]
+        format: String,
+        /// Skip the database integrity and foreign key check
+        #[arg(long)]
+        quick_doctor: bool,
+    },
+
+    /// Check workspace consistency (claims, worktrees, branches, database); exits 1 on errors
+    Verify {
+        /// Remove orphaned worktrees and broken claims, then check again
+        #[arg(long)]
+        fix: bool,
+    },
+
+    /// Install or remove git hooks that enforce the bacchus workflow
+    Hooks {
+        /// install or remove
+        #[arg(value_parser = ["install", "remove"])]
+        action: String,
+        /// Hooks to manage (default: all)
+        #[arg(value_parser = ["pre-push", "post-commit"])]
+        hook_types: Vec<String>,
+    },
+
+    /// Keep claiming ready beads for one agent, printing a JSON event per claim
+    /// (SIGINT/SIGTERM pauses the agent's claims and exits)
+    Watch {
+        /// Agent to claim for
+        #[arg(long = "agent")]
+        agent_id: String,
+        /// Seconds to sleep between polls
+        #[arg(long, default_value = "30")]
+        interval: u64,
+        /// Maximum claims the agent holds at once
+        #[arg(long, default_value = "1")]
+        max_concurrent: usize,
+    },
+
+    /// Queue a stale-claim warning for each agent holding an old claim
+    NotifyStaleAgents {
+        /// Minutes without activity to consider stale (default: stale_threshold_minutes from config, else 15)
+        #[arg(short, long)]
+        minutes: Option<i64>,
+    },
+
+    /// Report how far along a claimed bead is
+    Progress {
+        /// The claimed bead ID
+        bead_id: String,
+
+        /// Completion percentage (0-100)
+        #[arg(value_parser = clap::value_parser!(u8).range(0..=100))]
+        percent: u8,
+    },
+
+    /// Check in as an agent: keep its claims alive and receive pending notifications
+    Heartbeat {
+        /// Agent ID to deliver notifications for
+        agent_id: String,
+
+        /// Only refresh this claim (default: all of the agent's claims)
+        #[arg(long)]
+        bead_id: Option<String>,
+    },
+
+    /// List queued agent notifications (pending ones by default)
+    Notifications {
+        /// Only notifications for this agent
+        #[arg(long = "agent")]
+        agent_id: Option<String>,
+        /// pending, or all to include acknowledged ones
+        #[arg(long, default_value = "pending", value_parser = ["pending", "all"])]
+        status: String,
+        /// Maximum notifications to show, newest first
+        #[arg(long, default_value = "50")]
+        limit: i32,
+        /// Acknowledge this notification ID instead of listing
+        #[arg(long, value_name = "ID")]
+        acknowledge: Option<i64>,
+        /// Output format
+        #[arg(long, default_value = "json", value_parser = ["json", "table"])]
+        format: String,
     },
 
     /// List all active claims and worktrees
@@ -85,7 +516,7 @@ pub enum Commands {
         /// Filter by file path (supports * wildcards)
         #[arg(short, long)]
         file: Option<String>,
-        /// Filter by language (typescript, javascript, python, go, rust)
+        /// Filter by language (typescript, javascript, python, go, rust, java, sql)
         #[arg(short, long)]
         lang: Option<String>,
         /// Max results
@@ -97,33 +528,352 @@ pub enum Commands {
         /// Enable fuzzy matching for typo tolerance
         #[arg(long)]
         fuzzy: bool,
+        /// Only symbols indexed after this Unix timestamp (ms)
+        #[arg(long)]
+        since: Option<i64>,
+        /// Show symbols added/modified/deleted by the last index run
+        #[arg(long)]
+        changed: bool,
+        /// Only symbols with at least this cyclomatic complexity
+        #[arg(long)]
+        min_complexity: Option<u32>,
+        /// Sort order: location (default) or complexity
+        #[arg(long)]
+        sort: Option<String>,
+        /// Find complex symbols (shortcut for --min-complexity <threshold> --sort complexity)
+        #[arg(long)]
+        find_complex: bool,
+        /// Complexity threshold for --find-complex
+        #[arg(long, default_value = "10")]
+        threshold: u32,
+        /// Filter by Go/Python package or Rust module (e.g. auth, app.auth)
+        #[arg(long)]
+        package: Option<String>,
+        /// Find same-named symbols across all languages (ignores namespace and case style)
+        #[arg(long, value_name = "FQ_NAME")]
+        cross_ref: Option<String>,
+        /// Only symbols indexed recently (see --since-hours)
+        #[arg(long, conflicts_with = "since")]
+        recent: bool,
+        /// Window for --recent, in hours
+        #[arg(long, default_value = "1")]
+        since_hours: u32,
+        /// Only symbols re-indexed after this bead was claimed
+        #[arg(long, value_name = "BEAD_ID")]
+        recently_changed: Option<String>,
+        /// Only symbols registered to this bead (see register-symbol)
+        #[arg(long)]
+        in_bead: Option<String>,
+        /// Only symbols this file imports by name
+        #[arg(long, value_name = "FILE")]
+        imported_by: Option<String>,
+        /// Only symbols with this Python decorator (e.g. property, cached_property)
+        #[arg(long)]
+        decorator: Option<String>,
+        /// Only generic symbols (Rust/TypeScript) declaring this type parameter
+        #[arg(long, value_name = "T")]
+        has_type_param: Option<String>,
+        /// Report exact (same hash) and fuzzy (similar body) duplicate symbols
+        #[arg(long)]
+        find_duplicates: bool,
+        /// Rank symbols by TF-IDF similarity between this text and their docstrings
+        #[arg(long, value_name = "TEXT", conflicts_with = "search")]
+        semantic_search: Option<String>,
+        /// Minimum similarity (0-1) for --semantic-search
+        #[arg(long, default_value = "0.1")]
+        semantic_threshold: f64,
+        /// Render results through a Handlebars template (file path, or built-in: slack, markdown)
+        #[arg(long, conflicts_with_all = ["changed", "cross_ref", "find_duplicates"])]
+        output_template: Option<String>,
+    },
+
+    /// Manually register a virtual symbol (e.g. generated at runtime)
+    SymbolAdd {
+        /// Fully qualified name of the symbol
+        fq_name: String,
+        /// File the symbol is associated with
+        #[arg(short, long)]
+        file: String,
+        /// Symbol kind (function, class, method, interface, type, variable)
+        #[arg(short, long)]
+        kind: String,
+        /// Description stored as the symbol's docstring
+        #[arg(short, long)]
+        description: Option<String>,
+    },
+
+    /// Register a symbol as modified or created by a bead
+    RegisterSymbol {
+        /// The bead ID
+        bead_id: String,
+        /// Fully qualified name of the symbol
+        fq_name: String,
+        /// How the bead touches the symbol
+        #[arg(long, default_value = "modifies", value_parser = ["modifies", "creates"])]
+        relation: String,
+    },
+
+    /// Take an advisory lock on a symbol for a bead (expires after $BACCHUS_LOCK_EXPIRY_MINUTES, default 60)
+    LockSymbol {
+        /// Fully qualified name of the symbol
+        fq_name: String,
+        /// The bead taking the lock
+        #[arg(long = "bead")]
+        bead_id: String,
+    },
+
+    /// Release a bead's advisory lock on a symbol
+    UnlockSymbol {
+        /// Fully qualified name of the symbol
+        fq_name: String,
+        /// The bead holding the lock
+        #[arg(long = "bead")]
+        bead_id: String,
+    },
+
+    /// Take an advisory lock on a file for the current claim (held until unlock or release)
+    Lock {
+        /// File path, repository-relative or inside the worktree
+        file: String,
+        /// The claimed bead (default: the claim whose worktree contains the current directory)
+        #[arg(long = "bead")]
+        bead_id: Option<String>,
+    },
+
+    /// Release the current claim's advisory lock on a file
+    Unlock {
+        /// File path, repository-relative or inside the worktree
+        file: String,
+        /// The claimed bead (default: the claim whose worktree contains the current directory)
+        #[arg(long = "bead")]
+        bead_id: Option<String>,
+    },
+
+    /// Record that a bead is expected to touch files matching a pattern
+    RegisterFileHint {
+        /// The bead ID
+        bead_id: String,
+        /// File path or GLOB pattern (e.g. src/auth/*)
+        file_pattern: String,
+    },
+
+    /// Show direct and transitive callers of a symbol
+    Callers {
+        /// Fully-qualified symbol name (e.g. src/auth.rs::login)
+        fq_name: String,
+        /// How many levels of callers to follow
+        #[arg(long, default_value = "3")]
+        depth: u32,
+    },
+
+    /// Blast radius of changing a symbol: its callers and the claimed beads that touch them
+    Impact {
+        /// Fully-qualified symbol name (e.g. src/auth.rs::login)
+        fq_name: String,
+        /// How many levels of callers to follow
+        #[arg(long, default_value = "3")]
+        depth: u32,
+    },
+
+    /// List TODO/FIXME/HACK/XXX/NOTE markers found by `index`
+    Todos {
+        /// Only this marker (TODO, FIXME, HACK, XXX, or NOTE)
+        #[arg(long)]
+        kind: Option<String>,
+
+        /// Only files matching this GLOB pattern (e.g. "src/auth/*")
+        #[arg(long)]
+        file: Option<String>,
+
+        /// Maximum markers to list
+        #[arg(long, default_value_t = 100)]
+        limit: usize,
+
+        /// Create an open bead for each listed marker (comment as title, file:line as description)
+        #[arg(long)]
+        create_beads: bool,
+    },
+
+    /// Show when a symbol's body last changed, from index history
+    Blame {
+        /// Fully-qualified symbol name (e.g. src/auth.rs::login)
+        fq_name: String,
+    },
+
+    /// Show each line of a file with the bead whose release introduced it
+    Annotate {
+        /// File path, relative to the workspace root
+        file: String,
     },
 
     /// Index a file or directory for symbol search
     Index {
         /// Path to file or directory to index
         path: String,
+        /// Print extracted symbols as NDJSON instead of storing them
+        #[arg(long)]
+        emit_symbols: bool,
+        /// Only index these languages, comma-separated (default: $BACCHUS_INDEX_LANGUAGES, else all)
+        #[arg(long, value_name = "LIST")]
+        languages: Option<String>,
+        /// Skip these languages, comma-separated
+        #[arg(long, value_name = "LIST")]
+        exclude_languages: Option<String>,
+        /// Only re-index files changed (or deleted) since the last indexed commit
+        #[arg(long, conflicts_with = "emit_symbols")]
+        incremental: bool,
     },
 
     // ========================================================================
     // Info Commands
     // ========================================================================
 
+    /// Bootstrap a bacchus workspace (.bacchus/, database, config) in this git repo
+    Init {
+        /// Overwrite an existing .bacchus/ directory
+        #[arg(long)]
+        force: bool,
+
+        /// Don't create an empty beads database when .beads/ is missing
+        #[arg(long)]
+        no_beads: bool,
+    },
+
+    /// Read or change a setting in .bacchus/config.toml
+    Config {
+        /// get or set
+        #[arg(value_parser = ["get", "set"])]
+        action: String,
+        /// Setting name (nested tables as table.key, e.g. hooks.pre_merge)
+        key: String,
+        /// New value for set, as a TOML literal (bare words are strings)
+        value: Option<String>,
+    },
+
+    /// Remove orphaned worktrees and broken claims reported by `status`
+    Clean {
+        /// Report what would be removed without changing anything
+        #[arg(long)]
+        dry_run: bool,
+        /// Also remove worktrees of blocked beads and worktrees with uncommitted changes
+        #[arg(long)]
+        force: bool,
+    },
+
     /// Show current claims and status
-    Status,
+    Status {
+        /// Skip measuring worktree disk usage (faster with large worktrees)
+        #[arg(long)]
+        no_disk_usage: bool,
+    },
 
     /// Print workflow documentation
-    Workflow,
+    Workflow {
+        /// Output format: markdown or json
+        #[arg(long, default_value = "markdown", value_parser = ["markdown", "json"])]
+        format: String,
+    },
 
     /// Generate context for the current agent (global or task-specific)
     Context {
         /// Force context for a specific bead ID
         #[arg(long)]
         bead_id: Option<String>,
+        /// Tailor context to a role; remembered in the active session
+        #[arg(long, value_parser = ["implementer", "reviewer", "tester", "orchestrator"])]
+        agent_persona: Option<String>,
+    },
+
+    /// Export everything an agent sees for a bead as a JSON bundle
+    ExportContext {
+        /// The bead ID to export
+        bead_id: String,
+        /// Write the bundle to this file instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+
+    /// Export the bacchus database (claims, symbols, audit log, ...) for backup or external tooling
+    Export {
+        /// Output file for json (newline-delimited), or directory for csv (one file per table)
+        path: String,
+        /// Output format
+        #[arg(long, default_value = "json", value_parser = ["json", "csv"])]
+        format: String,
+        /// Include the symbols table (large; rebuildable with `bacchus index`)
+        #[arg(long)]
+        include_symbols: bool,
+    },
+
+    /// Dump all beads and their dependencies for backup or migration
+    ExportBeads {
+        /// Output format
+        #[arg(long, default_value = "json", value_parser = ["json", "csv", "markdown"])]
+        format: String,
+        /// Write the export to this file instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+        /// Include closed beads
+        #[arg(long)]
+        include_closed: bool,
+    },
+
+    /// Visualize bead dependencies (DOT for graphviz, indented chains, or JSON)
+    Graph {
+        /// Output format
+        #[arg(long, default_value = "text", value_parser = ["dot", "text", "json"])]
+        format: String,
+        /// Include closed beads
+        #[arg(long)]
+        include_closed: bool,
+    },
+
+    /// Show the tree of beads blocking a bead, recursively
+    DependsOn {
+        /// Bead ID
+        bead_id: String,
+        /// Levels of blockers to follow (default: 3)
+        #[arg(long)]
+        depth: Option<u32>,
+        /// Output format (text is colored by status on a terminal)
+        #[arg(long, default_value = "text", value_parser = ["text", "json"])]
+        format: String,
+    },
+
+    /// Restore workspace state from a `bacchus export` file or CSV directory
+    Import {
+        /// Export file (json) or directory (csv)
+        path: String,
+        /// Replace rows whose key already exists instead of skipping them
+        #[arg(long)]
+        overwrite: bool,
+    },
+
+    /// Bulk-create beads from an export-beads JSON or CSV file
+    ImportBeads {
+        /// File to import
+        file: PathBuf,
+        /// Input format (default: inferred from the file extension)
+        #[arg(long, value_parser = ["json", "csv"])]
+        format: Option<String>,
+        /// Show what would be imported without creating anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Show version and build metadata
+    Version {
+        /// Print git commit, build date, and library versions as JSON
+        #[arg(long)]
+        json: bool,
     },
 
     /// Update bacchus to the latest version
-    SelfUpdate,
+    SelfUpdate {
+        /// Only verify the running binary against the release checksum (BACCHUS_OFFLINE=1 uses the cached checksum)
+        #[arg(long)]
+        verify_only: bool,
+    },
 
     /// Check if a newer version is available
     CheckUpdate,
@@ -133,9 +883,129 @@ pub enum Commands {
         #[command(subcommand)]
         command: SessionCommands,
     },
+
+    /// Grep across the worktrees of all active claims
+    SearchCode {
+        /// Extended regular expression to search for
+        pattern: String,
+        /// Only search files matching this glob (e.g. "*.rs")
+        #[arg(long)]
+        file_pattern: Option<String>,
+        /// Only search these beads' worktrees (repeatable)
+        #[arg(long = "bead")]
+        bead_ids: Vec<String>,
+        /// Maximum number of matches to return
+        #[arg(long, default_value = "100")]
+        max_matches: usize,
+    },
+
+    /// Full-text search bead titles and descriptions (FTS5 syntax), best match first
+    SearchBeads {
+        /// Search query, e.g. `login`, `"merge conflict"`, `pars*`, `title:cache`
+        query: String,
+        /// Maximum number of results
+        #[arg(long, default_value = "20")]
+        limit: i32,
+        /// Only beads with this status (e.g. open, in_progress, closed)
+        #[arg(long)]
+        status: Option<String>,
+    },
+
+    /// Summarize completed and in-flight beads, agent throughput, and churn
+    Report {
+        /// Output format: markdown (GitHub-pasteable) or json
+        #[arg(long, default_value = "markdown", value_parser = ["markdown", "json"])]
+        format: String,
+        /// Start of the window: 7d, 12h, YYYY-MM-DD, or RFC 3339 (default: all history)
+        #[arg(long)]
+        since: Option<String>,
+        /// Write the report to this file instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+
+    /// Coordination metrics (claims, completions, merge success, retries) as JSON
+    Stats {
+        /// Start of the window: 7d, 12h, YYYY-MM-DD, or RFC 3339 (default: all history)
+        #[arg(long)]
+        since: Option<String>,
+        /// Output format: json (pretty) or compact (one line, for log shippers)
+        #[arg(long, default_value = "json", value_parser = ["json", "compact"])]
+        format: String,
+    },
+
+    /// Show the history of claims, releases, aborts, resolves, and stale cleanups
+    Log {
+        /// Only operations on this bead
+        #[arg(long = "bead")]
+        bead_id: Option<String>,
+        /// Only operations by this agent
+        #[arg(long = "agent")]
+        agent_id: Option<String>,
+        /// Maximum entries to return
+        #[arg(long, default_value = "50")]
+        limit: i32,
+        /// Start of the window: 7d, 12h, YYYY-MM-DD, or RFC 3339 (default: all history)
+        #[arg(long)]
+        since: Option<String>,
+        /// Output format: json or table
+        #[arg(long, default_value = "json", value_parser = ["json", "table"])]
+        format: String,
+    },
+
+    /// Query the audit log of CLI invocations
+    Audit {
+        /// Maximum entries to return (default: 50)
+        #[arg(long)]
+        limit: Option<u32>,
+        /// Only entries for this bead
+        #[arg(long)]
+        bead_id: Option<String>,
+        /// Only entries at or after this Unix timestamp (ms)
+        #[arg(long)]
+        since_ms: Option<i64>,
+    },
+
+    /// Inspect and maintain the bacchus database
+    Db {
+        #[command(subcommand)]
+        command: DbCommands,
+    },
+}
+
+#[derive(Subcommand, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum StashCommands {
+    /// List all stashes with their bead and age
+    List,
+}
+
+#[derive(Subcommand, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SnapshotCommands {
+    /// List a bead's snapshots, oldest first
+    List {
+        /// The bead ID
+        bead_id: String,
+    },
+}
+
+#[derive(Subcommand, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DbCommands {
+    /// Check the database for corruption and foreign key violations
+    IntegrityCheck {
+        /// Use the faster PRAGMA quick_check instead of a full integrity_check
+        #[arg(long)]
+        quick: bool,
+    },
+
+    /// Show database size, table row counts, and symbol index composition
+    Stats,
 }
 
-#[derive(Subcommand)]
+#[derive(Subcommand, Serialize)]
+#[serde(rename_all = "kebab-case")]
 pub enum SessionCommands {
     /// Start a session (agent or orchestrator mode)
     Start {
@@ -1,14 +1,212 @@
 //! Configuration management for Bacchus
 //!
-//! Supports environment variable overrides for database paths.
+//! Supports environment variable overrides for database paths, plus
+//! per-workspace settings in `.bacchus/config.toml`.
 //!
 //! # Environment Variables
 //!
 //! - `BEADS_DB_PATH`: Override path to beads database (default: `.beads/beads.db`)
 //! - `BACCHUS_DB_PATH`: Override path to bacchus database (default: `.bacchus/bacchus.db`)
-//! - `BACCHUS_WORKTREES`: Override path to worktrees directory (default: `.bacchus/worktrees`)
+//! - `BACCHUS_WORKTREES`: Override path to worktrees directory (default: `worktrees_dir`
+//!   from the config file, else `.bacchus/worktrees`)
+//! - `BACCHUS_AUDIT_RETENTION_DAYS`: Days of audit log to keep (default: 90, 0 keeps forever)
+//! - `BACCHUS_OFFLINE`: Set to `1` to make `self-update --verify-only` use the cached checksum
+//! - `BACCHUS_POST_MERGE_SCRIPT`: Script `release --status done` runs after merging
+//! - `BACCHUS_INDEX_LANGUAGES`: Default `index --languages` list (e.g. `rust,python`)
+//! - `BACCHUS_LOCK_EXPIRY_MINUTES`: Lifetime of `lock-symbol` locks (default: 60)
 //!
 //! These environment variables are checked directly in their respective modules:
 //! - `BEADS_DB_PATH` in `beads.rs`
 //! - `BACCHUS_DB_PATH` in `main.rs`
 //! - `BACCHUS_WORKTREES` in `worktree.rs`
+//! - `BACCHUS_AUDIT_RETENTION_DAYS` in `tools/audit.rs`
+//! - `BACCHUS_OFFLINE` in `updater.rs`
+//! - `BACCHUS_POST_MERGE_SCRIPT` in `tools/release.rs`
+//! - `BACCHUS_INDEX_LANGUAGES` in `main.rs`
+//! - `BACCHUS_LOCK_EXPIRY_MINUTES` in `tools/symbol_locks.rs`
+//!
+//! # Config File
+//!
+//! - `merge_commit_template` (alias `merge_message_template`): Merge commit
+//!   message for `release --status done` (default: `feat: {title} (bacchus/{bead_id})`);
+//!   unused when the release fast-forwards, so pair it with `merge_strategy = "no-ff"`
+//! - `target_branch`: Branch new claims merge into (default: `main`; overridden
+//!   by `next`/`claim --target-branch`)
+//! - `stale_threshold_minutes`: Default for `stale`/`notify-stale-agents --minutes` (default: 15)
+//! - `worktrees_dir`: Worktrees directory, absolute or workspace-relative
+//!   (default: `.bacchus/worktrees`; overridden by `BACCHUS_WORKTREES`)
+//! - `merge_strategy`: `merge` (default; fast-forwards when possible), `squash`,
+//!   which makes `release --status done` always squash as if given `--squash`,
+//!   or `no-ff`, which always creates a merge commit
+//! - `require_linear_history`: Refuse to release a bead whose target branch has
+//!   diverged, and fast-forward instead of creating merge commits (default: false)
+//! - `require_signatures`: Refuse to merge a worktree whose HEAD commit fails
+//!   `git verify-commit` (default: false)
+//! - `signed_commits`: Sign the commits bacchus creates (merges, squashes) with `-S`
+//!   (default: false)
+//! - `hooks.pre_merge`: Shell commands `release --status done` runs in the
+//!   worktree before merging; any failure stops the release
+//! - `index_ignore`: Directory/file names or workspace-relative paths `index` skips
+//!   (e.g. `["vendor", "src/generated"]`)
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    #[serde(alias = "merge_message_template")]
+    pub merge_commit_template: Option<String>,
+    pub target_branch: String,
+    pub stale_threshold_minutes: i64,
+    pub worktrees_dir: Option<String>,
+    pub merge_strategy: String,
+    pub require_linear_history: bool,
+    pub require_signatures: bool,
+    pub signed_commits: bool,
+    pub hooks: HooksConfig,
+    pub index_ignore: Vec<String>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            merge_commit_template: None,
+            target_branch: DEFAULT_TARGET_BRANCH.to_string(),
+            stale_threshold_minutes: DEFAULT_STALE_MINUTES,
+            worktrees_dir: None,
+            merge_strategy: MERGE_STRATEGIES[0].to_string(),
+            require_linear_history: false,
+            require_signatures: false,
+            signed_commits: false,
+            hooks: HooksConfig::default(),
+            index_ignore: Vec::new(),
+        }
+    }
+}
+
+impl Config {
+    /// Whether `index` should skip a workspace-relative path
+    pub fn is_index_ignored(&self, relative_path: &str) -> bool {
+        self.index_ignore.iter().any(|pattern| {
+            let pattern = pattern.trim_matches('/');
+            relative_path == pattern
+                || relative_path.starts_with(&format!("{}/", pattern))
+                || relative_path.split('/').any(|component| component == pattern)
+        })
+    }
+}
+
+/// `[hooks]` table of `.bacchus/config.toml`
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HooksConfig {
+    pub pre_merge: Vec<String>,
+}
+
+pub const DEFAULT_TARGET_BRANCH: &str = "main";
+pub const DEFAULT_STALE_MINUTES: i64 = 15;
+pub const MERGE_STRATEGIES: [&str; 3] = ["merge", "squash", "no-ff"];
+
+/// Keys `config get`/`config set` accept (nested tables as `table.key`)
+pub const CONFIG_KEYS: &[&str] = &[
+    "merge_commit_template",
+    "target_branch",
+    "stale_threshold_minutes",
+    "worktrees_dir",
+    "merge_strategy",
+    "require_linear_history",
+    "require_signatures",
+    "signed_commits",
+    "hooks.pre_merge",
+    "index_ignore",
+];
+
+pub fn config_path(workspace_root: &Path) -> PathBuf {
+    workspace_root.join(".bacchus/config.toml")
+}
+
+/// Load `.bacchus/config.toml`, falling back to defaults if it's missing
+///
+/// A malformed file is reported on stderr rather than failing the command.
+pub fn load_config(workspace_root: &Path) -> Config {
+    let path = config_path(workspace_root);
+    let Ok(content) = std::fs::read_to_string(&path) else {
+        return Config::default();
+    };
+
+    toml::from_str(&content).unwrap_or_else(|e| {
+        eprintln!("Warning: ignoring invalid {}: {}", path.display(), e);
+        Config::default()
+    })
+}
+
+/// Target branch for a new claim: the explicit argument, else config, else `main`
+pub fn resolve_target_branch(explicit: Option<&str>, workspace_root: &Path) -> String {
+    explicit
+        .map(String::from)
+        .unwrap_or_else(|| load_config(workspace_root).target_branch)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_config() {
+        let temp = tempfile::tempdir().unwrap();
+        assert!(load_config(temp.path()).merge_commit_template.is_none());
+
+        std::fs::create_dir_all(temp.path().join(".bacchus")).unwrap();
+        std::fs::write(
+            config_path(temp.path()),
+            "merge_commit_template = \"{bead_id} by {agent_id}\"\n",
+        )
+        .unwrap();
+        assert_eq!(
+            load_config(temp.path()).merge_commit_template.as_deref(),
+            Some("{bead_id} by {agent_id}")
+        );
+
+        std::fs::write(config_path(temp.path()), "merge_message_template = \"{title}\"\n").unwrap();
+        assert_eq!(load_config(temp.path()).merge_commit_template.as_deref(), Some("{title}"));
+    }
+
+    #[test]
+    fn test_load_hooks() {
+        let temp = tempfile::tempdir().unwrap();
+        assert!(load_config(temp.path()).hooks.pre_merge.is_empty());
+
+        std::fs::create_dir_all(temp.path().join(".bacchus")).unwrap();
+        std::fs::write(
+            config_path(temp.path()),
+            "[hooks]\npre_merge = [\"cargo test\", \"cargo clippy\"]\n",
+        )
+        .unwrap();
+        assert_eq!(load_config(temp.path()).hooks.pre_merge, vec!["cargo test", "cargo clippy"]);
+    }
+
+    #[test]
+    fn test_is_index_ignored() {
+        let config = Config {
+            index_ignore: vec!["vendor".to_string(), "src/generated/".to_string()],
+            ..Config::default()
+        };
+        assert!(config.is_index_ignored("vendor/lib.rs"));
+        assert!(config.is_index_ignored("crates/a/vendor/lib.rs"));
+        assert!(config.is_index_ignored("src/generated/proto.rs"));
+        assert!(!config.is_index_ignored("src/generated_by_hand.rs"));
+        assert!(!config.is_index_ignored("src/vendors.rs"));
+    }
+
+    #[test]
+    fn test_resolve_target_branch() {
+        let temp = tempfile::tempdir().unwrap();
+        assert_eq!(resolve_target_branch(None, temp.path()), "main");
+
+        std::fs::create_dir_all(temp.path().join(".bacchus")).unwrap();
+        std::fs::write(config_path(temp.path()), "target_branch = \"develop\"\n").unwrap();
+        assert_eq!(resolve_target_branch(None, temp.path()), "develop");
+        assert_eq!(resolve_target_branch(Some("release/2.0"), temp.path()), "release/2.0");
+    }
+}
@@ -7,7 +7,7 @@ use std::fs;
 use std::path::Path;
 use std::sync::{Mutex, OnceLock};
 
-use super::migrations::apply_migrations;
+use super::migrations::{apply_migrations, validate_schema};
 
 /// Global database connection pool (single connection for now)
 static DB_POOL: OnceLock<Mutex<Option<Connection>>> = OnceLock::new();
@@ -39,6 +39,14 @@ pub fn init_db(db_path: Option<&str>, silent: bool) -> Result<()> {
     // Apply migrations
     apply_migrations(&conn, silent)?;
 
+    // Detect schema drift (manual edits, older binaries)
+    let warnings = validate_schema(&conn)?;
+    if !silent {
+        for warning in &warnings {
+            eprintln!("Schema warning: {}", warning);
+        }
+    }
+
     // Store in global pool
     let pool = DB_POOL.get_or_init(|| Mutex::new(None));
     *pool.lock().unwrap() = Some(conn);
@@ -68,6 +76,13 @@ pub fn close_db() {
     *pool.lock().unwrap() = None;
 }
 
+/// Serializes unit tests that swap the global connection
+#[cfg(test)]
+pub fn test_lock() -> std::sync::MutexGuard<'static, ()> {
+    static TEST_LOCK: Mutex<()> = Mutex::new(());
+    TEST_LOCK.lock().unwrap_or_else(|e| e.into_inner())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -75,6 +90,7 @@ mod tests {
 
     #[test]
     fn test_init_db() {
+        let _lock = test_lock();
         let dir = tempdir().unwrap();
         let db_path = dir.path().join("test.db");
         let path_str = db_path.to_str().unwrap();
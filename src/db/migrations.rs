@@ -196,10 +196,589 @@ CREATE TRIGGER symbols_fts_update AFTER UPDATE ON symbols BEGIN
   INSERT INTO symbols_fts(rowid, fq_name, docstring)
   VALUES (new.id, new.fq_name, COALESCE(new.docstring, ''));
 END;
+"#,
+    },
+    Migration {
+        version: 5,
+        name: "add_symbol_index_tracking",
+        sql: r#"
+-- When each symbol was last written by the indexer (Unix ms)
+ALTER TABLE symbols ADD COLUMN last_indexed_at INTEGER;
+
+-- Symbol hashes captured just before the most recent index run
+CREATE TABLE symbol_hash_snapshot (
+  fq_name TEXT PRIMARY KEY,
+  hash TEXT NOT NULL
+);
+"#,
+    },
+    Migration {
+        version: 6,
+        name: "add_symbol_complexity",
+        sql: r#"
+-- Cyclomatic complexity (NULL for languages/kinds without scoring)
+ALTER TABLE symbols ADD COLUMN complexity INTEGER;
+"#,
+    },
+    Migration {
+        version: 7,
+        name: "restore_bead_symbols",
+        sql: r#"
+-- Lightweight bead -> symbol ownership (relation: modifies | creates)
+CREATE TABLE bead_symbols (
+  bead_id        TEXT NOT NULL,
+  symbol_fq_name TEXT NOT NULL,
+  relation       TEXT NOT NULL,
+  PRIMARY KEY (bead_id, symbol_fq_name, relation)
+);
+CREATE INDEX idx_bead_symbols_fq_name ON bead_symbols(symbol_fq_name);
+"#,
+    },
+    Migration {
+        version: 8,
+        name: "add_audit_log",
+        sql: r#"
+-- One row per CLI invocation (exit_code/duration_ms filled in on completion)
+CREATE TABLE audit_log (
+  id           INTEGER PRIMARY KEY,
+  timestamp_ms INTEGER NOT NULL,
+  command      TEXT NOT NULL,
+  args_json    TEXT,
+  bead_id      TEXT,
+  agent_id     TEXT,
+  exit_code    INTEGER,
+  duration_ms  INTEGER
+);
+CREATE INDEX idx_audit_log_timestamp ON audit_log(timestamp_ms);
+CREATE INDEX idx_audit_log_bead ON audit_log(bead_id);
+"#,
+    },
+    Migration {
+        version: 9,
+        name: "add_claim_events",
+        sql: r#"
+-- Lifecycle events for claimed beads (details is event-specific JSON)
+CREATE TABLE claim_events (
+  id         INTEGER PRIMARY KEY,
+  bead_id    TEXT NOT NULL,
+  agent_id   TEXT,
+  event_type TEXT NOT NULL,
+  details    TEXT,
+  created_at INTEGER NOT NULL
+);
+CREATE INDEX idx_claim_events_bead ON claim_events(bead_id);
+CREATE INDEX idx_claim_events_created ON claim_events(created_at);
+"#,
+    },
+    Migration {
+        version: 10,
+        name: "add_bead_file_hints",
+        sql: r#"
+-- Files a bead is expected to touch (file_pattern is a GLOB), used by next --filter-file
+CREATE TABLE bead_file_hints (
+  bead_id      TEXT NOT NULL,
+  file_pattern TEXT NOT NULL,
+  PRIMARY KEY (bead_id, file_pattern)
+);
+"#,
+    },
+    Migration {
+        version: 11,
+        name: "add_symbol_tfidf_cache",
+        sql: r#"
+-- IDF weight per docstring term for symbols --semantic-search
+-- (cleared whenever symbols change; rebuilt on the next search)
+CREATE TABLE symbol_tfidf_cache (
+  term TEXT PRIMARY KEY,
+  idf  REAL NOT NULL
+);
+"#,
+    },
+    Migration {
+        version: 12,
+        name: "add_symbol_locks",
+        sql: r#"
+-- Advisory per-symbol locks held by beads (expired rows are ignored and overwritten)
+CREATE TABLE symbol_locks (
+  fq_name    TEXT PRIMARY KEY,
+  bead_id    TEXT NOT NULL,
+  locked_at  INTEGER NOT NULL,
+  expires_at INTEGER NOT NULL
+);
+CREATE INDEX idx_symbol_locks_bead ON symbol_locks(bead_id);
+"#,
+    },
+    Migration {
+        version: 13,
+        name: "restore_notifications",
+        sql: r#"
+-- Messages queued for an agent, delivered (and acknowledged) on its next heartbeat
+CREATE TABLE notifications (
+  id                 INTEGER PRIMARY KEY,
+  notification_type  TEXT NOT NULL,
+  target_agent       TEXT NOT NULL,
+  target_bead        TEXT,
+  change_description TEXT NOT NULL,
+  status             TEXT NOT NULL DEFAULT 'pending',
+  created_at         INTEGER NOT NULL,
+  acknowledged_at    INTEGER
+);
+CREATE INDEX idx_notifications_target ON notifications(target_agent, status);
+"#,
+    },
+    Migration {
+        version: 14,
+        name: "add_index_state",
+        sql: r#"
+-- Indexer bookkeeping (e.g. last_indexed_commit for index --incremental)
+CREATE TABLE index_state (
+  key   TEXT PRIMARY KEY,
+  value TEXT NOT NULL
+);
+"#,
+    },
+    Migration {
+        version: 15,
+        name: "restore_symbol_calls",
+        sql: r#"
+-- Call graph edges written by the indexer (callee_fq_name is the bare callee
+-- name when it couldn't be resolved to a single indexed symbol)
+CREATE TABLE symbol_calls (
+  id               INTEGER PRIMARY KEY,
+  caller_symbol_id INTEGER NOT NULL,
+  callee_fq_name   TEXT NOT NULL,
+  call_site_file   TEXT NOT NULL,
+  call_site_line   INTEGER NOT NULL
+);
+CREATE INDEX idx_symbol_calls_callee ON symbol_calls(callee_fq_name);
+CREATE INDEX idx_symbol_calls_file ON symbol_calls(call_site_file);
+"#,
+    },
+    Migration {
+        version: 16,
+        name: "add_file_imports",
+        sql: r#"
+-- Modules each indexed file imports (imported_name is NULL for whole-module imports)
+CREATE TABLE file_imports (
+  importer_file TEXT NOT NULL,
+  imported_path TEXT NOT NULL,
+  imported_name TEXT
+);
+CREATE INDEX idx_file_imports_importer ON file_imports(importer_file);
+CREATE INDEX idx_file_imports_path ON file_imports(imported_path);
+"#,
+    },
+    Migration {
+        version: 17,
+        name: "add_symbol_decorators",
+        sql: r#"
+-- JSON array of Python decorator names (NULL when undecorated)
+ALTER TABLE symbols ADD COLUMN decorators TEXT;
+"#,
+    },
+    Migration {
+        version: 18,
+        name: "add_symbol_type_params",
+        sql: r#"
+-- JSON array of generic parameters as written, e.g. ["T: Trait", "'a"] (NULL when not generic)
+ALTER TABLE symbols ADD COLUMN type_params TEXT;
+"#,
+    },
+    Migration {
+        version: 19,
+        name: "add_symbol_history",
+        sql: r#"
+-- Superseded symbol body hashes; indexed_at/commit_hash are of the run that replaced them
+CREATE TABLE symbol_history (
+  id             INTEGER PRIMARY KEY AUTOINCREMENT,
+  symbol_fq_name TEXT NOT NULL,
+  hash           TEXT NOT NULL,
+  file           TEXT NOT NULL,
+  indexed_at     INTEGER NOT NULL,
+  commit_hash    TEXT
+);
+CREATE INDEX idx_symbol_history_fq_name ON symbol_history(symbol_fq_name);
+"#,
+    },
+    Migration {
+        version: 20,
+        name: "add_claim_target_branch",
+        sql: r#"
+-- Branch the claim merges into (release), and rebases/syncs against
+ALTER TABLE claims ADD COLUMN target_branch TEXT DEFAULT 'main';
+"#,
+    },
+    Migration {
+        version: 21,
+        name: "add_claim_title",
+        sql: r#"
+-- Bead title at claim time, for merge commit messages without a bd round-trip
+ALTER TABLE claims ADD COLUMN title TEXT;
+"#,
+    },
+    Migration {
+        version: 22,
+        name: "add_claim_stashed_at",
+        sql: r#"
+-- When the claim's worktree changes were stashed (NULL when not stashed)
+ALTER TABLE claims ADD COLUMN stashed_at INTEGER;
+"#,
+    },
+    Migration {
+        version: 23,
+        name: "add_audit_log_operations",
+        sql: r#"
+-- State-changing operations (claim, release, ...) share the audit log with
+-- invocations; operation is NULL for invocation rows. Operation rows are never pruned.
+ALTER TABLE audit_log ADD COLUMN operation TEXT;
+ALTER TABLE audit_log ADD COLUMN outcome TEXT;
+ALTER TABLE audit_log ADD COLUMN details TEXT;
+CREATE INDEX idx_audit_log_agent ON audit_log(agent_id);
+"#,
+    },
+    Migration {
+        version: 24,
+        name: "add_assignments",
+        sql: r#"
+-- Beads reserved for an agent ahead of claiming (removed when the bead is claimed)
+CREATE TABLE assignments (
+  bead_id     TEXT PRIMARY KEY,
+  agent_id    TEXT NOT NULL,
+  assigned_at INTEGER NOT NULL
+);
+CREATE INDEX idx_assignments_agent ON assignments(agent_id);
+"#,
+    },
+    Migration {
+        version: 25,
+        name: "add_claim_last_heartbeat",
+        sql: r#"
+-- Last `bacchus heartbeat` for the claim; stale detection counts from here
+ALTER TABLE claims ADD COLUMN last_heartbeat INTEGER;
+"#,
+    },
+    Migration {
+        version: 26,
+        name: "add_claim_progress",
+        sql: r#"
+-- Agent-reported completion percentage (0-100); a new claim starts at 0
+ALTER TABLE claims ADD COLUMN progress INTEGER NOT NULL DEFAULT 0;
+"#,
+    },
+    Migration {
+        version: 27,
+        name: "add_archive_tables",
+        sql: r#"
+-- Long-term storage for finished beads (`bacchus archive`); mirrors claims
+CREATE TABLE archived_claims (
+  bead_id        TEXT PRIMARY KEY,
+  agent_id       TEXT NOT NULL,
+  worktree_path  TEXT NOT NULL,
+  branch_name    TEXT NOT NULL,
+  start_commit   TEXT NOT NULL,
+  claimed_at     INTEGER NOT NULL,
+  target_branch  TEXT,
+  title          TEXT,
+  stashed_at     INTEGER,
+  last_heartbeat INTEGER,
+  progress       INTEGER NOT NULL DEFAULT 0,
+  archived_at    INTEGER NOT NULL
+);
+
+CREATE TABLE archived_bead_symbols (
+  bead_id        TEXT NOT NULL,
+  symbol_fq_name TEXT NOT NULL,
+  relation       TEXT NOT NULL,
+  archived_at    INTEGER NOT NULL,
+  PRIMARY KEY (bead_id, symbol_fq_name, relation)
+);
+"#,
+    },
+    Migration {
+        version: 28,
+        name: "add_file_locks",
+        sql: r#"
+-- Advisory file locks (`bacchus lock`), held until unlocked or the bead is released
+CREATE TABLE file_locks (
+  file_path TEXT PRIMARY KEY,
+  bead_id   TEXT NOT NULL,
+  agent_id  TEXT NOT NULL,
+  locked_at INTEGER NOT NULL
+);
+CREATE INDEX idx_file_locks_bead ON file_locks(bead_id);
+"#,
+    },
+    Migration {
+        version: 29,
+        name: "add_paused_claims",
+        sql: r#"
+-- Claims set aside by `bacchus pause`; the worktree stays until `bacchus resume`
+CREATE TABLE paused_claims (
+  bead_id       TEXT PRIMARY KEY,
+  agent_id      TEXT NOT NULL,
+  worktree_path TEXT NOT NULL,
+  branch_name   TEXT NOT NULL,
+  start_commit  TEXT NOT NULL,
+  target_branch TEXT,
+  title         TEXT,
+  progress      INTEGER NOT NULL DEFAULT 0,
+  paused_at     INTEGER NOT NULL
+);
+"#,
+    },
+    Migration {
+        version: 30,
+        name: "add_claim_pinned",
+        sql: r#"
+-- Pinned claims (`bacchus pin`) are skipped by stale detection
+ALTER TABLE claims ADD COLUMN pinned INTEGER NOT NULL DEFAULT 0;
+"#,
+    },
+    Migration {
+        version: 31,
+        name: "add_todos",
+        sql: r#"
+-- TODO/FIXME/HACK/XXX/NOTE markers found by the indexer (`bacchus todos`)
+CREATE TABLE todos (
+  id         INTEGER PRIMARY KEY AUTOINCREMENT,
+  file       TEXT NOT NULL,
+  line       INTEGER NOT NULL,
+  kind       TEXT NOT NULL,
+  message    TEXT NOT NULL,
+  indexed_at INTEGER NOT NULL
+);
+CREATE INDEX idx_todos_file ON todos(file);
+"#,
+    },
+    Migration {
+        version: 32,
+        name: "add_snapshots",
+        sql: r#"
+-- Worktree restore points (`bacchus snapshot`), kept as git stash commits
+CREATE TABLE snapshots (
+  id         INTEGER PRIMARY KEY AUTOINCREMENT,
+  bead_id    TEXT NOT NULL,
+  name       TEXT NOT NULL,
+  stash_sha  TEXT NOT NULL,
+  created_at INTEGER NOT NULL,
+  UNIQUE(bead_id, name)
+);
+"#,
+    },
+    Migration {
+        version: 33,
+        name: "release_file_locks_with_claims",
+        sql: r#"
+-- Every path that drops a claim must free its file locks, or the files stay
+-- locked for good; clear those already orphaned
+DELETE FROM file_locks WHERE bead_id NOT IN (SELECT bead_id FROM claims);
+CREATE TRIGGER claims_release_file_locks AFTER DELETE ON claims BEGIN
+  DELETE FROM file_locks WHERE bead_id = OLD.bead_id;
+END;
 "#,
     },
 ];
 
+/// Tables the code depends on, with the columns each must have
+const EXPECTED_TABLES: &[(&str, &[&str])] = &[
+    ("schema_version", &["version"]),
+    (
+        "claims",
+        &[
+            "bead_id",
+            "agent_id",
+            "worktree_path",
+            "branch_name",
+            "start_commit",
+            "claimed_at",
+            "target_branch",
+            "title",
+            "stashed_at",
+            "last_heartbeat",
+            "progress",
+            "pinned",
+        ],
+    ),
+    (
+        "symbols",
+        &[
+            "id",
+            "file",
+            "fq_name",
+            "kind",
+            "span_start_line",
+            "span_end_line",
+            "line_count",
+            "hash",
+            "docstring",
+            "language",
+            "last_indexed_at",
+            "complexity",
+            "decorators",
+            "type_params",
+        ],
+    ),
+    ("bead_symbols", &["bead_id", "symbol_fq_name", "relation"]),
+    ("symbol_hash_snapshot", &["fq_name", "hash"]),
+    (
+        "archived_claims",
+        &[
+            "bead_id",
+            "agent_id",
+            "worktree_path",
+            "branch_name",
+            "start_commit",
+            "claimed_at",
+            "target_branch",
+            "title",
+            "stashed_at",
+            "last_heartbeat",
+            "progress",
+            "archived_at",
+        ],
+    ),
+    ("archived_bead_symbols", &["bead_id", "symbol_fq_name", "relation", "archived_at"]),
+    ("file_locks", &["file_path", "bead_id", "agent_id", "locked_at"]),
+    ("todos", &["id", "file", "line", "kind", "message", "indexed_at"]),
+    ("snapshots", &["id", "bead_id", "name", "stash_sha", "created_at"]),
+    (
+        "paused_claims",
+        &[
+            "bead_id",
+            "agent_id",
+            "worktree_path",
+            "branch_name",
+            "start_commit",
+            "target_branch",
+            "title",
+            "progress",
+            "paused_at",
+        ],
+    ),
+    (
+        "audit_log",
+        &[
+            "id",
+            "timestamp_ms",
+            "command",
+            "args_json",
+            "bead_id",
+            "agent_id",
+            "exit_code",
+            "duration_ms",
+            "operation",
+            "outcome",
+            "details",
+        ],
+    ),
+    (
+        "claim_events",
+        &["id", "bead_id", "agent_id", "event_type", "details", "created_at"],
+    ),
+    ("bead_file_hints", &["bead_id", "file_pattern"]),
+    ("assignments", &["bead_id", "agent_id", "assigned_at"]),
+    ("symbol_tfidf_cache", &["term", "idf"]),
+    ("symbol_locks", &["fq_name", "bead_id", "locked_at", "expires_at"]),
+    (
+        "notifications",
+        &[
+            "id",
+            "notification_type",
+            "target_agent",
+            "target_bead",
+            "change_description",
+            "status",
+            "created_at",
+            "acknowledged_at",
+        ],
+    ),
+    ("index_state", &["key", "value"]),
+    (
+        "symbol_calls",
+        &["id", "caller_symbol_id", "callee_fq_name", "call_site_file", "call_site_line"],
+    ),
+    ("file_imports", &["importer_file", "imported_path", "imported_name"]),
+    (
+        "symbol_history",
+        &["id", "symbol_fq_name", "hash", "file", "indexed_at", "commit_hash"],
+    ),
+];
+
+/// Indices the migrations create (missing ones only slow queries down);
+/// add to this whenever a migration adds an index
+const EXPECTED_INDICES: &[&str] = &[
+    "idx_symbols_file",
+    "idx_symbols_fq_name",
+    "idx_symbols_language",
+    "idx_bead_symbols_fq_name",
+    "idx_audit_log_timestamp",
+    "idx_audit_log_bead",
+    "idx_claim_events_bead",
+    "idx_claim_events_created",
+    "idx_symbol_locks_bead",
+    "idx_notifications_target",
+    "idx_symbol_calls_callee",
+    "idx_symbol_calls_file",
+    "idx_file_imports_importer",
+    "idx_file_imports_path",
+    "idx_symbol_history_fq_name",
+    "idx_audit_log_agent",
+    "idx_assignments_agent",
+    "idx_file_locks_bead",
+    "idx_todos_file",
+];
+
+/// Validate that the database schema matches what the code expects
+///
+/// Missing tables or required columns are fatal and returned as errors.
+/// Non-fatal differences (extra columns, missing indices) are returned as warnings.
+pub fn validate_schema(conn: &Connection) -> Result<Vec<String>> {
+    let mut warnings = Vec::new();
+
+    for (table, expected_columns) in EXPECTED_TABLES {
+        let mut stmt = conn.prepare(&format!("PRAGMA table_info({})", table))?;
+        let columns: Vec<String> = stmt
+            .query_map([], |row| row.get::<_, String>(1))?
+            .collect::<Result<_>>()?;
+
+        if columns.is_empty() {
+            return Err(schema_error(format!("Missing table: {}", table)));
+        }
+
+        for column in *expected_columns {
+            if !columns.iter().any(|c| c == column) {
+                return Err(schema_error(format!(
+                    "Missing column {}.{} (database may be out of sync with this binary)",
+                    table, column
+                )));
+            }
+        }
+
+        for column in &columns {
+            if !expected_columns.contains(&column.as_str()) {
+                warnings.push(format!("Unexpected column {}.{}", table, column));
+            }
+        }
+    }
+
+    for index in EXPECTED_INDICES {
+        let exists: bool = conn.query_row(
+            "SELECT COUNT(*) FROM sqlite_master WHERE type = 'index' AND name = ?1",
+            [index],
+            |row| row.get::<_, i32>(0).map(|c| c > 0),
+        )?;
+        if !exists {
+            warnings.push(format!("Missing index: {}", index));
+        }
+    }
+
+    Ok(warnings)
+}
+
+fn schema_error(message: String) -> rusqlite::Error {
+    rusqlite::Error::SqliteFailure(rusqlite::ffi::Error::new(1), Some(message))
+}
+
 /// Get the current schema version from the database
 pub fn get_current_version(conn: &Connection) -> Result<i32> {
     // Try to get version, return 0 if table doesn't exist
@@ -215,6 +794,11 @@ pub fn get_current_version(conn: &Connection) -> Result<i32> {
     }
 }
 
+/// Version of the newest migration
+pub fn latest_version() -> i32 {
+    MIGRATIONS.last().map(|m| m.version).unwrap_or(0)
+}
+
 /// Apply all pending migrations
 pub fn apply_migrations(conn: &Connection, silent: bool) -> Result<()> {
     let current_version = get_current_version(conn).unwrap_or(0);
@@ -267,7 +851,7 @@ mod tests {
         apply_migrations(&conn, true).unwrap();
 
         let version = get_current_version(&conn).unwrap();
-        assert_eq!(version, 4); // Update to latest migration version
+        assert_eq!(version, 33); // Update to latest migration version
 
         // Verify claims table exists
         let count: i32 = conn
@@ -275,4 +859,39 @@ mod tests {
             .unwrap();
         assert_eq!(count, 1);
     }
+
+    #[test]
+    fn test_validate_schema() {
+        let conn = Connection::open_in_memory().unwrap();
+        apply_migrations(&conn, true).unwrap();
+        assert!(validate_schema(&conn).unwrap().is_empty());
+
+        // Extra columns and missing indices are warnings
+        conn.execute_batch(
+            "ALTER TABLE claims ADD COLUMN notes TEXT; DROP INDEX idx_symbols_language;",
+        )
+        .unwrap();
+        let warnings = validate_schema(&conn).unwrap();
+        assert!(warnings.iter().any(|w| w.contains("claims.notes")));
+        assert!(warnings.iter().any(|w| w.contains("idx_symbols_language")));
+
+        // Missing tables are fatal
+        conn.execute_batch("DROP TABLE claims;").unwrap();
+        assert!(validate_schema(&conn).is_err());
+    }
+
+    #[test]
+    fn test_expected_indices_match_migrations() {
+        let conn = Connection::open_in_memory().unwrap();
+        apply_migrations(&conn, true).unwrap();
+
+        // Automatic indices (sqlite_autoindex_*) back PRIMARY KEY and UNIQUE constraints
+        let mut stmt = conn
+            .prepare("SELECT name FROM sqlite_master WHERE type = 'index' AND name NOT LIKE 'sqlite_%' ORDER BY name")
+            .unwrap();
+        let created: Vec<String> = stmt.query_map([], |row| row.get(0)).unwrap().collect::<Result<_>>().unwrap();
+        let mut expected: Vec<&str> = EXPECTED_INDICES.to_vec();
+        expected.sort();
+        assert_eq!(created, expected, "EXPECTED_INDICES is out of date with the migrations");
+    }
 }
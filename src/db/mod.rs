@@ -6,3 +6,8 @@ mod migrations;
 mod connection;
 
 pub use connection::{init_db, close_db, with_db};
+pub use migrations::{get_current_version, latest_version};
+#[cfg(test)]
+pub use migrations::apply_migrations;
+#[cfg(test)]
+pub use connection::test_lock;
@@ -1,9 +1,29 @@
 //! Symbol extraction from AST nodes
 
-use super::types::{ExtractedSymbol, Language, SymbolKind};
+use super::parser::{Parser, ParserError};
+use super::types::{CallEdge, ExtractedSymbol, ImportRecord, Language, SymbolKind, TodoRecord};
 use sha2::{Digest, Sha256};
 use tree_sitter::{Node, Tree};
 
+/// Parse one file's source and extract its symbols and imports, picking the
+/// extractor by extension (SQL has no tree-sitter grammar, and no imports)
+pub fn extract_file(
+    parser: &mut Parser,
+    file_path: &str,
+    source: &str,
+) -> Result<(Vec<ExtractedSymbol>, Vec<ImportRecord>), ParserError> {
+    let ext = std::path::Path::new(file_path).extension().and_then(|e| e.to_str()).unwrap_or("");
+    if Language::from_extension(ext) == Some(Language::Sql) {
+        return Ok((extract_sql_symbols(file_path, source), Vec::new()));
+    }
+
+    let (tree, language) = parser.parse_file(source, file_path)?;
+    Ok((
+        extract_symbols(&tree, file_path, source, language),
+        extract_imports(&tree, file_path, source, language),
+    ))
+}
+
 /// Extract symbols from a parsed AST tree
 pub fn extract_symbols(
     tree: &Tree,
@@ -19,6 +39,166 @@ pub fn extract_symbols(
     symbols
 }
 
+/// Extract import relationships (TypeScript/JavaScript imports and `require`,
+/// Python imports, Rust `use`) from a parsed AST tree
+pub fn extract_imports(tree: &Tree, file_path: &str, source: &str, language: Language) -> Vec<ImportRecord> {
+    let mut pairs = Vec::new();
+    collect_imports(tree.root_node(), source, language, &mut pairs);
+
+    let mut records: Vec<ImportRecord> = Vec::new();
+    for (imported_path, imported_name) in pairs {
+        let record = ImportRecord {
+            importer_file: file_path.to_string(),
+            imported_path,
+            imported_name,
+        };
+        if !records.contains(&record) {
+            records.push(record);
+        }
+    }
+    records
+}
+
+/// Recursively collect (path, name) import pairs
+fn collect_imports(node: Node, source: &str, language: Language, out: &mut Vec<(String, Option<String>)>) {
+    let text = |n: Node| source[n.start_byte()..n.end_byte()].to_string();
+
+    match (language, node.kind()) {
+        (Language::TypeScript | Language::JavaScript, "import_statement") => {
+            let Some(path) = node.child_by_field_name("source").map(|s| unquote(&text(s))) else {
+                return;
+            };
+            let mut names = Vec::new();
+            let mut cursor = node.walk();
+            for clause in node.children(&mut cursor).filter(|c| c.kind() == "import_clause") {
+                collect_ts_import_names(clause, source, &mut names);
+            }
+            if names.is_empty() {
+                // Side-effect import: `import "./polyfills"`
+                out.push((path.clone(), None));
+            }
+            out.extend(names.into_iter().map(|n| (path.clone(), Some(n))));
+            return;
+        }
+        (Language::TypeScript | Language::JavaScript, "call_expression") => {
+            let is_require = node.child_by_field_name("function").is_some_and(|f| text(f) == "require");
+            let path = node
+                .child_by_field_name("arguments")
+                .and_then(|args| args.named_child(0))
+                .filter(|arg| arg.kind() == "string")
+                .map(|arg| unquote(&text(arg)));
+            if let (true, Some(path)) = (is_require, path) {
+                // `const auth = require("./auth")` names the binding; destructuring doesn't
+                let name = node
+                    .parent()
+                    .filter(|p| p.kind() == "variable_declarator")
+                    .and_then(|p| p.child_by_field_name("name"))
+                    .filter(|n| n.kind() == "identifier")
+                    .map(text);
+                out.push((path, name));
+            }
+        }
+        (Language::Python, "import_statement") => {
+            let mut cursor = node.walk();
+            for name in node.children_by_field_name("name", &mut cursor) {
+                let module = name.child_by_field_name("name").unwrap_or(name);
+                out.push((text(module), None));
+            }
+            return;
+        }
+        (Language::Python, "import_from_statement") => {
+            let Some(path) = node.child_by_field_name("module_name").map(text) else {
+                return;
+            };
+            let mut names: Vec<String> = {
+                let mut cursor = node.walk();
+                node.children_by_field_name("name", &mut cursor)
+                    .map(|n| text(n.child_by_field_name("name").unwrap_or(n)))
+                    .collect()
+            };
+            let mut cursor = node.walk();
+            if node.children(&mut cursor).any(|c| c.kind() == "wildcard_import") {
+                names.push("*".to_string());
+            }
+            out.extend(names.into_iter().map(|n| (path.clone(), Some(n))));
+            return;
+        }
+        (Language::Rust, "use_declaration") => {
+            if let Some(argument) = node.child_by_field_name("argument") {
+                collect_rust_use(argument, source, "", out);
+            }
+            return;
+        }
+        _ => {}
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        collect_imports(child, source, language, out);
+    }
+}
+
+/// Names bound by a TS/JS import clause (default, named, and namespace imports)
+fn collect_ts_import_names(node: Node, source: &str, names: &mut Vec<String>) {
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        match child.kind() {
+            "identifier" => names.push(source[child.start_byte()..child.end_byte()].to_string()),
+            "import_specifier" => {
+                if let Some(name) = child.child_by_field_name("name") {
+                    names.push(source[name.start_byte()..name.end_byte()].to_string());
+                }
+            }
+            "named_imports" | "namespace_import" => collect_ts_import_names(child, source, names),
+            _ => {}
+        }
+    }
+}
+
+/// Flatten a Rust `use` tree into (module path, item) pairs
+fn collect_rust_use(node: Node, source: &str, prefix: &str, out: &mut Vec<(String, Option<String>)>) {
+    let text = |n: Node| source[n.start_byte()..n.end_byte()].to_string();
+    let join = |a: &str, b: &str| if a.is_empty() { b.to_string() } else { format!("{}::{}", a, b) };
+
+    match node.kind() {
+        "scoped_identifier" => {
+            let path = node.child_by_field_name("path").map(text).unwrap_or_default();
+            let name = node.child_by_field_name("name").map(text);
+            out.push((join(prefix, &path), name));
+        }
+        "scoped_use_list" => {
+            let path = node.child_by_field_name("path").map(text).unwrap_or_default();
+            if let Some(list) = node.child_by_field_name("list") {
+                collect_rust_use(list, source, &join(prefix, &path), out);
+            }
+        }
+        "use_list" => {
+            let mut cursor = node.walk();
+            for child in node.named_children(&mut cursor) {
+                collect_rust_use(child, source, prefix, out);
+            }
+        }
+        "use_as_clause" => {
+            if let Some(path) = node.child_by_field_name("path") {
+                collect_rust_use(path, source, prefix, out);
+            }
+        }
+        "use_wildcard" => {
+            let path = text(node);
+            out.push((join(prefix, path.trim_end_matches('*').trim_end_matches("::")), Some("*".to_string())));
+        }
+        // `use foo;` imports a whole crate/module; `self` inside a list means the prefix itself
+        "identifier" | "crate" | "super" if prefix.is_empty() => out.push((text(node), None)),
+        "identifier" => out.push((prefix.to_string(), Some(text(node)))),
+        "self" => out.push((prefix.to_string(), None)),
+        _ => {}
+    }
+}
+
+fn unquote(s: &str) -> String {
+    s.trim_matches(|c| matches!(c, '"' | '\'' | '`')).to_string()
+}
+
 /// Recursively extract symbols from a node
 fn extract_from_node(
     node: Node,
@@ -28,12 +208,7 @@ fn extract_from_node(
     parent_names: &[String],
     symbols: &mut Vec<ExtractedSymbol>,
 ) {
-    let (kind, name) = match language {
-        Language::TypeScript | Language::JavaScript => extract_ts_symbol(&node, source),
-        Language::Python => extract_python_symbol(&node, source),
-        Language::Go => extract_go_symbol(&node, source),
-        Language::Rust => extract_rust_symbol(&node, source),
-    };
+    let (kind, name) = extract_symbol(&node, source, language);
 
     let mut new_parent_names = parent_names.to_vec();
 
@@ -56,6 +231,14 @@ fn extract_from_node(
         // Extract docstring
         let docstring = extract_docstring(&node, source);
 
+        let is_callable = matches!(kind, SymbolKind::Function | SymbolKind::Method);
+        let complexity = if is_callable { compute_complexity(&node, language) } else { None };
+
+        let mut calls = Vec::new();
+        if is_callable {
+            collect_calls(&node, source, language, &mut calls);
+        }
+
         symbols.push(ExtractedSymbol {
             file: file_path.to_string(),
             fq_name,
@@ -66,10 +249,20 @@ fn extract_from_node(
             hash,
             docstring,
             language,
+            complexity,
+            decorators: decorators(&node, source),
+            type_params: type_params(&node, source),
+            calls,
         });
 
         // Update parent names for nested symbols
-        if matches!(kind, SymbolKind::Class | SymbolKind::Struct | SymbolKind::Trait | SymbolKind::Impl) {
+        let is_container = match kind {
+            SymbolKind::Class | SymbolKind::Struct | SymbolKind::Trait | SymbolKind::Impl => true,
+            // Java interfaces and enums have method bodies of their own
+            SymbolKind::Interface | SymbolKind::Enum => language == Language::Java,
+            _ => false,
+        };
+        if is_container {
             new_parent_names.push(name);
         }
     }
@@ -81,6 +274,61 @@ fn extract_from_node(
     }
 }
 
+/// Symbol kind and name for a node, if it declares a symbol
+fn extract_symbol(node: &Node, source: &str, language: Language) -> (Option<SymbolKind>, Option<String>) {
+    match language {
+        Language::TypeScript | Language::JavaScript => extract_ts_symbol(node, source),
+        Language::Python => extract_python_symbol(node, source),
+        Language::Go => extract_go_symbol(node, source),
+        Language::Rust => extract_rust_symbol(node, source),
+        Language::Java => extract_java_symbol(node, source),
+        Language::Sql => (None, None),
+    }
+}
+
+/// Call node kinds across the supported grammars
+const CALL_KINDS: &[&str] = &["call_expression", "call", "function_call", "method_call", "method_invocation"];
+
+/// Collect call sites under `node`, skipping nested functions and methods
+fn collect_calls(node: &Node, source: &str, language: Language, calls: &mut Vec<CallEdge>) {
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        let (kind, _) = extract_symbol(&child, source, language);
+        if matches!(kind, Some(SymbolKind::Function | SymbolKind::Method)) {
+            continue;
+        }
+
+        if CALL_KINDS.contains(&child.kind()) {
+            if let Some(callee) = callee_name(&child, source) {
+                calls.push(CallEdge {
+                    callee,
+                    line: child.start_position().row as u32 + 1,
+                });
+            }
+        }
+
+        // Arguments can contain calls of their own
+        collect_calls(&child, source, language, calls);
+    }
+}
+
+/// Last path segment of the called expression, if it is a plain name
+fn callee_name(call: &Node, source: &str) -> Option<String> {
+    let target = ["function", "name", "method"]
+        .iter()
+        .find_map(|field| call.child_by_field_name(field))?;
+    let text = &source[target.start_byte()..target.end_byte()];
+
+    // Drop generic arguments (`parse::<T>`), then the receiver or module path
+    let text = text.split('<').next().unwrap_or(text).trim_end_matches(':');
+    let name = text.rsplit(['.', ':']).next()?.trim();
+
+    let is_identifier = !name.is_empty()
+        && !name.starts_with(|c: char| c.is_ascii_digit())
+        && name.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '$');
+    is_identifier.then(|| name.to_string())
+}
+
 /// Extract TypeScript/JavaScript symbol info from a node
 fn extract_ts_symbol(node: &Node, source: &str) -> (Option<SymbolKind>, Option<String>) {
     let kind = match node.kind() {
@@ -123,9 +371,13 @@ fn extract_python_symbol(node: &Node, source: &str) -> (Option<SymbolKind>, Opti
 
     let name = kind.and_then(|_| get_node_name(node, source));
 
-    // Check if function is a method (inside a class)
+    // Check if function is a method (inside a class, possibly wrapped by decorators)
     if kind == Some(SymbolKind::Function) {
-        if let Some(parent) = node.parent() {
+        let container = node.parent().map(|p| match p.kind() {
+            "decorated_definition" => p.parent(),
+            _ => Some(p),
+        });
+        if let Some(Some(parent)) = container {
             if parent.kind() == "block" {
                 if let Some(grandparent) = parent.parent() {
                     if grandparent.kind() == "class_definition" {
@@ -139,6 +391,46 @@ fn extract_python_symbol(node: &Node, source: &str) -> (Option<SymbolKind>, Opti
     (kind, name)
 }
 
+/// Generic parameters declared on a symbol (`type_parameters` field in the
+/// Rust and TypeScript grammars), with bounds and defaults kept as written
+fn type_params(node: &Node, source: &str) -> Option<Vec<String>> {
+    let list = node.child_by_field_name("type_parameters")?;
+    let mut cursor = list.walk();
+    let params: Vec<String> = list
+        .named_children(&mut cursor)
+        .filter(|p| p.kind() != "attribute_item" && !is_comment(p))
+        .map(|p| {
+            let text = &source[p.start_byte()..p.end_byte()];
+            text.split_whitespace().collect::<Vec<_>>().join(" ")
+        })
+        .collect();
+    (!params.is_empty()).then_some(params)
+}
+
+/// Names of the decorators applied to a Python definition, outermost first
+///
+/// Arguments are dropped: `@app.route("/")` is recorded as `app.route`.
+fn decorators(node: &Node, source: &str) -> Vec<String> {
+    let Some(parent) = node.parent().filter(|p| p.kind() == "decorated_definition") else {
+        return Vec::new();
+    };
+
+    let mut cursor = parent.walk();
+    let names = parent
+        .children(&mut cursor)
+        .filter(|c| c.kind() == "decorator")
+        .filter_map(|decorator| {
+            let expr = decorator.named_child(0)?;
+            let target = match expr.kind() {
+                "call" => expr.child_by_field_name("function")?,
+                _ => expr,
+            };
+            Some(source[target.start_byte()..target.end_byte()].to_string())
+        })
+        .collect();
+    names
+}
+
 /// Extract Go symbol info from a node
 fn extract_go_symbol(node: &Node, source: &str) -> (Option<SymbolKind>, Option<String>) {
     let kind = match node.kind() {
@@ -180,6 +472,173 @@ fn extract_rust_symbol(node: &Node, source: &str) -> (Option<SymbolKind>, Option
     (kind, name)
 }
 
+/// Extract Java symbol info from a node
+fn extract_java_symbol(node: &Node, source: &str) -> (Option<SymbolKind>, Option<String>) {
+    let kind = match node.kind() {
+        "class_declaration" => Some(SymbolKind::Class),
+        "interface_declaration" => Some(SymbolKind::Interface),
+        "enum_declaration" => Some(SymbolKind::Enum),
+        "method_declaration" | "constructor_declaration" => Some(SymbolKind::Method),
+        _ => None,
+    };
+
+    let name = kind.and_then(|_| get_node_name(node, source));
+    (kind, name)
+}
+
+/// Markers `extract_todos` looks for, each followed by a colon
+pub const TODO_KINDS: &[&str] = &["TODO", "FIXME", "HACK", "XXX", "NOTE"];
+
+/// Find `TODO:`, `FIXME:`, `HACK:`, `XXX:` and `NOTE:` markers in any language
+///
+/// Works on raw lines rather than comment nodes, so it also covers SQL and
+/// files tree-sitter can't parse. A marker inside a longer word (`MYTODO:`)
+/// doesn't count; the earliest marker on a line wins.
+pub fn extract_todos(file_path: &str, source: &str) -> Vec<TodoRecord> {
+    source
+        .lines()
+        .enumerate()
+        .filter_map(|(i, line)| {
+            let (pos, kind) = TODO_KINDS
+                .iter()
+                .filter_map(|kind| find_todo_marker(line, kind).map(|pos| (pos, *kind)))
+                .min_by_key(|(pos, _)| *pos)?;
+            let message = line[pos + kind.len() + 1..].trim();
+            let message = message.trim_end_matches("*/").trim_end_matches("-->").trim_end();
+            Some(TodoRecord {
+                file: file_path.to_string(),
+                line: i as u32 + 1,
+                kind: kind.to_string(),
+                message: message.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Byte offset of `<kind>:` in `line`, if it isn't the tail of a longer word
+fn find_todo_marker(line: &str, kind: &str) -> Option<usize> {
+    line.match_indices(&format!("{}:", kind))
+        .map(|(pos, _)| pos)
+        .find(|&pos| !line[..pos].ends_with(|c: char| c.is_alphanumeric() || c == '_'))
+}
+
+/// Extract symbols from SQL source
+///
+/// There is no tree-sitter grammar for SQL here, so this scans line by line for
+/// `CREATE ...` statements and treats each one as running to its terminating `;`.
+pub fn extract_sql_symbols(file_path: &str, source: &str) -> Vec<ExtractedSymbol> {
+    let lines: Vec<&str> = source.lines().collect();
+    let mut symbols = Vec::new();
+    let mut comments: Vec<String> = Vec::new();
+    let mut i = 0;
+
+    while i < lines.len() {
+        let trimmed = lines[i].trim();
+
+        if trimmed.starts_with("--") {
+            comments.push(trimmed.to_string());
+            i += 1;
+            continue;
+        }
+
+        let Some((kind, name)) = extract_sql_symbol(trimmed) else {
+            if !trimmed.is_empty() {
+                comments.clear();
+            }
+            i += 1;
+            continue;
+        };
+
+        let end = find_sql_statement_end(&lines, i);
+        let body = lines[i..=end].join("\n");
+        let start_line = i as u32 + 1;
+        let end_line = end as u32 + 1;
+
+        symbols.push(ExtractedSymbol {
+            file: file_path.to_string(),
+            fq_name: format!("{}::{}", file_path, name),
+            kind,
+            span_start_line: start_line,
+            span_end_line: end_line,
+            line_count: end_line - start_line + 1,
+            hash: compute_hash(&body),
+            docstring: if comments.is_empty() { None } else { Some(comments.join("\n")) },
+            language: Language::Sql,
+            complexity: None,
+            decorators: Vec::new(),
+            type_params: None,
+            calls: Vec::new(),
+        });
+
+        comments.clear();
+        i = end + 1;
+    }
+
+    symbols
+}
+
+/// Parse a `CREATE` statement header into a symbol kind and (schema-qualified) name
+fn extract_sql_symbol(line: &str) -> Option<(SymbolKind, String)> {
+    let mut words = line.split_whitespace().peekable();
+    if !words.next()?.eq_ignore_ascii_case("create") {
+        return None;
+    }
+
+    const MODIFIERS: &[&str] = &[
+        "or", "replace", "temp", "temporary", "unique", "materialized", "unlogged", "global", "local",
+    ];
+    while words.peek().is_some_and(|w| MODIFIERS.iter().any(|m| w.eq_ignore_ascii_case(m))) {
+        words.next();
+    }
+
+    let kind = match words.next()?.to_ascii_lowercase().as_str() {
+        "table" => SymbolKind::Struct,
+        "view" => SymbolKind::Type,
+        "index" => SymbolKind::Index,
+        "function" | "procedure" => SymbolKind::Function,
+        _ => return None,
+    };
+
+    const SKIP: &[&str] = &["if", "not", "exists", "concurrently"];
+    while words.peek().is_some_and(|w| SKIP.iter().any(|m| w.eq_ignore_ascii_case(m))) {
+        words.next();
+    }
+
+    let raw = words.next()?;
+    let raw = raw.split('(').next().unwrap_or(raw).trim_end_matches(';');
+    let name = raw
+        .split('.')
+        .map(|part| part.trim_matches(|c| matches!(c, '"' | '`' | '[' | ']')))
+        .collect::<Vec<_>>()
+        .join(".");
+
+    if name.is_empty() {
+        None
+    } else {
+        Some((kind, name))
+    }
+}
+
+/// Find the line holding the `;` that ends the statement starting at `start`.
+/// Semicolons inside `$$`-quoted function bodies are ignored.
+fn find_sql_statement_end(lines: &[&str], start: usize) -> usize {
+    let mut in_dollar_quote = false;
+
+    for (idx, line) in lines.iter().enumerate().skip(start) {
+        let code = line.split("--").next().unwrap_or("");
+        for (n, segment) in code.split("$$").enumerate() {
+            if n > 0 {
+                in_dollar_quote = !in_dollar_quote;
+            }
+            if !in_dollar_quote && segment.contains(';') {
+                return idx;
+            }
+        }
+    }
+
+    lines.len().saturating_sub(1).max(start)
+}
+
 /// Get the name identifier from a node
 fn get_node_name(node: &Node, source: &str) -> Option<String> {
     // Try common field names
@@ -204,9 +663,9 @@ fn get_node_name(node: &Node, source: &str) -> Option<String> {
 fn extract_docstring(node: &Node, source: &str) -> Option<String> {
     let mut target = *node;
 
-    // If inside export_statement, look before the export
+    // If inside export_statement (or behind Python decorators), look before the wrapper
     if let Some(parent) = node.parent() {
-        if parent.kind() == "export_statement" {
+        if matches!(parent.kind(), "export_statement" | "decorated_definition") {
             target = parent;
         }
     }
@@ -216,13 +675,13 @@ fn extract_docstring(node: &Node, source: &str) -> Option<String> {
 
     // Collect consecutive comments
     while let Some(sibling) = current {
-        if sibling.kind() == "comment" {
+        if is_comment(&sibling) {
             let comment_text = source[sibling.start_byte()..sibling.end_byte()].trim().to_string();
             comments.insert(0, comment_text);
 
             // Check for more comments
             if let Some(prev) = sibling.prev_sibling() {
-                if prev.kind() == "comment" {
+                if is_comment(&prev) {
                     let gap = sibling.start_position().row.saturating_sub(prev.end_position().row);
                     if gap <= 1 {
                         current = Some(prev);
@@ -241,6 +700,44 @@ fn extract_docstring(node: &Node, source: &str) -> Option<String> {
     }
 }
 
+/// Grammars name comment nodes differently (Java/Rust split line and block comments)
+fn is_comment(node: &Node) -> bool {
+    matches!(node.kind(), "comment" | "line_comment" | "block_comment")
+}
+
+/// Compute cyclomatic complexity (1 + number of branching nodes) for a symbol body
+fn compute_complexity(node: &Node, language: Language) -> Option<u32> {
+    let branch_kinds: &[&str] = match language {
+        Language::Rust => &[
+            "if_expression",
+            "match_expression",
+            "for_expression",
+            "while_expression",
+            "loop_expression",
+            "try_expression",
+        ],
+        Language::Python => &[
+            "if_statement",
+            "for_statement",
+            "while_statement",
+            "try_statement",
+            "except_clause",
+        ],
+        _ => return None,
+    };
+
+    Some(1 + count_nodes(node, branch_kinds))
+}
+
+fn count_nodes(node: &Node, kinds: &[&str]) -> u32 {
+    let mut count = if kinds.contains(&node.kind()) { 1 } else { 0 };
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        count += count_nodes(&child, kinds);
+    }
+    count
+}
+
 /// Compute SHA256 hash of text
 fn compute_hash(text: &str) -> String {
     let mut hasher = Sha256::new();
@@ -307,4 +804,341 @@ fn main() {
         assert!(symbols.iter().any(|s| s.kind == SymbolKind::Impl));
         assert!(symbols.iter().any(|s| s.kind == SymbolKind::Function && s.fq_name.contains("main")));
     }
+
+    #[test]
+    fn test_extract_todos() {
+        let source = "fn a() {} // TODO: handle errors\n\
+                      /* FIXME: off by one */\n\
+                      let MYTODO: u8 = 0;\n\
+                      -- HACK:skip the check\n\
+                      # NOTE: see XXX: below\n";
+        let todos = extract_todos("src/a.rs", source);
+
+        let found: Vec<(u32, &str, &str)> =
+            todos.iter().map(|t| (t.line, t.kind.as_str(), t.message.as_str())).collect();
+        assert_eq!(
+            found,
+            [
+                (1, "TODO", "handle errors"),
+                (2, "FIXME", "off by one"),
+                (4, "HACK", "skip the check"),
+                (5, "NOTE", "see XXX: below"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_extract_java_symbols() {
+        let mut parser = Parser::new().unwrap();
+        let source = r#"
+package com.example;
+
+/**
+ * Handles user sessions.
+ */
+public class SessionManager {
+    private final Store store;
+
+    public SessionManager(Store store) {
+        this.store = store;
+    }
+
+    /** Start a new session */
+    @Override
+    @Deprecated
+    public Session start(String user) {
+        return store.create(user);
+    }
+
+    @Test
+    void expire() {}
+
+    static class Entry {
+        @Nullable
+        String key() { return null; }
+    }
+
+    enum State { OPEN, CLOSED }
+}
+
+interface Store {
+    Session create(String user);
+}
+"#;
+        let tree = parser.parse(source, Language::Java).unwrap();
+        let symbols = extract_symbols(&tree, "Session.java", source, Language::Java);
+        let find = |fq: &str| symbols.iter().find(|s| s.fq_name == format!("Session.java::{}", fq)).unwrap();
+
+        let class = find("SessionManager");
+        assert_eq!(class.kind, SymbolKind::Class);
+        assert_eq!(class.docstring.as_deref(), Some("/**\n * Handles user sessions.\n */"));
+
+        assert_eq!(find("SessionManager::SessionManager").kind, SymbolKind::Method);
+        let start = find("SessionManager::start");
+        assert_eq!(start.kind, SymbolKind::Method);
+        assert_eq!(start.docstring.as_deref(), Some("/** Start a new session */"));
+        assert_eq!(find("SessionManager::expire").kind, SymbolKind::Method);
+
+        assert_eq!(find("SessionManager::Entry").kind, SymbolKind::Class);
+        assert_eq!(find("SessionManager::Entry::key").kind, SymbolKind::Method);
+        assert_eq!(find("SessionManager::State").kind, SymbolKind::Enum);
+
+        assert_eq!(find("Store").kind, SymbolKind::Interface);
+        assert_eq!(find("Store::create").kind, SymbolKind::Method);
+    }
+
+    #[test]
+    fn test_extract_calls() {
+        let mut parser = Parser::new().unwrap();
+        let source = r#"
+fn outer(store: &Store) -> u32 {
+    let n = helper(parse::<u32>("1"));
+    store.save(n);
+
+    fn inner() {
+        nested();
+    }
+
+    crate::util::log(n)
+}
+"#;
+        let tree = parser.parse(source, Language::Rust).unwrap();
+        let symbols = extract_symbols(&tree, "lib.rs", source, Language::Rust);
+        let calls = |name: &str| -> Vec<(String, u32)> {
+            let sym = symbols.iter().find(|s| s.fq_name.ends_with(name)).unwrap();
+            sym.calls.iter().map(|c| (c.callee.clone(), c.line)).collect()
+        };
+
+        assert_eq!(
+            calls("::outer"),
+            vec![
+                ("helper".to_string(), 3),
+                ("parse".to_string(), 3),
+                ("save".to_string(), 4),
+                ("log".to_string(), 10),
+            ]
+        );
+        assert_eq!(calls("::inner"), vec![("nested".to_string(), 7)]);
+
+        let source = "class A:\n    def run(self):\n        self.step()\n        print(len([]))\n";
+        let tree = parser.parse(source, Language::Python).unwrap();
+        let symbols = extract_symbols(&tree, "a.py", source, Language::Python);
+        let run = symbols.iter().find(|s| s.fq_name == "a.py::A::run").unwrap();
+        let callees: Vec<&str> = run.calls.iter().map(|c| c.callee.as_str()).collect();
+        assert_eq!(callees, vec!["step", "print", "len"]);
+    }
+
+    #[test]
+    fn test_extract_imports() {
+        let mut parser = Parser::new().unwrap();
+        let mut pairs = |source: &str, language: Language| -> Vec<(String, Option<String>)> {
+            let tree = parser.parse(source, language).unwrap();
+            extract_imports(&tree, "f", source, language)
+                .into_iter()
+                .map(|r| (r.imported_path, r.imported_name))
+                .collect()
+        };
+        let pair = |path: &str, name: Option<&str>| (path.to_string(), name.map(String::from));
+
+        let ts = r#"
+import React, { useState as useLocal, useEffect } from "react";
+import * as auth from './auth';
+import "./polyfills";
+"#;
+        assert_eq!(
+            pairs(ts, Language::TypeScript),
+            vec![
+                pair("react", Some("React")),
+                pair("react", Some("useState")),
+                pair("react", Some("useEffect")),
+                pair("./auth", Some("auth")),
+                pair("./polyfills", None),
+            ]
+        );
+
+        let js = "const db = require('./db');\nconst { a } = require(\"lodash\");\n";
+        assert_eq!(pairs(js, Language::JavaScript), vec![pair("./db", Some("db")), pair("lodash", None)]);
+
+        let py = "import os.path, json as j\nfrom .models import User, Group as G\nfrom x import *\n";
+        assert_eq!(
+            pairs(py, Language::Python),
+            vec![
+                pair("os.path", None),
+                pair("json", None),
+                pair(".models", Some("User")),
+                pair(".models", Some("Group")),
+                pair("x", Some("*")),
+            ]
+        );
+
+        let rs = "use std::collections::{HashMap, hash_map::Entry};\nuse crate::db::with_db as w;\nuse super::types::*;\nuse serde;\nuse a::{self, b};\n";
+        assert_eq!(
+            pairs(rs, Language::Rust),
+            vec![
+                pair("std::collections", Some("HashMap")),
+                pair("std::collections::hash_map", Some("Entry")),
+                pair("crate::db", Some("with_db")),
+                pair("super::types", Some("*")),
+                pair("serde", None),
+                pair("a", None),
+                pair("a", Some("b")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_extract_python_decorators() {
+        let mut parser = Parser::new().unwrap();
+        let source = r#"
+@dataclass
+class User:
+    @property
+    def name(self):
+        return self._name
+
+    @functools.lru_cache(maxsize=None)
+    @staticmethod
+    def lookup(key):
+        pass
+
+    def plain(self):
+        pass
+
+# All users
+@app.route("/users")
+def list_users():
+    pass
+"#;
+        let tree = parser.parse(source, Language::Python).unwrap();
+        let symbols = extract_symbols(&tree, "models.py", source, Language::Python);
+        let find = |fq: &str| symbols.iter().find(|s| s.fq_name == format!("models.py::{}", fq)).unwrap();
+
+        assert_eq!(find("User").decorators, vec!["dataclass"]);
+
+        let name = find("User::name");
+        assert_eq!(name.kind, SymbolKind::Method);
+        assert_eq!(name.decorators, vec!["property"]);
+
+        let lookup = find("User::lookup");
+        assert_eq!(lookup.kind, SymbolKind::Method);
+        assert_eq!(lookup.decorators, vec!["functools.lru_cache", "staticmethod"]);
+
+        assert!(find("User::plain").decorators.is_empty());
+        assert_eq!(find("list_users").kind, SymbolKind::Function);
+        assert_eq!(find("list_users").decorators, vec!["app.route"]);
+        assert_eq!(find("list_users").docstring.as_deref(), Some("# All users"));
+    }
+
+    #[test]
+    fn test_extract_type_params() {
+        let mut parser = Parser::new().unwrap();
+        let source = r#"
+fn process<'a, T: Trait + Send, const N: usize>(items: &'a [T; N]) {}
+
+struct Cache<K, V = String> where K: Hash { map: HashMap<K, V> }
+
+fn plain() {}
+"#;
+        let tree = parser.parse(source, Language::Rust).unwrap();
+        let symbols = extract_symbols(&tree, "lib.rs", source, Language::Rust);
+        let params = |name: &str| symbols.iter().find(|s| s.fq_name.ends_with(name)).unwrap().type_params.clone();
+
+        assert_eq!(
+            params("::process"),
+            Some(vec!["'a".to_string(), "T: Trait + Send".to_string(), "const N: usize".to_string()])
+        );
+        assert_eq!(params("::Cache"), Some(vec!["K".to_string(), "V = String".to_string()]));
+        assert_eq!(params("::plain"), None);
+
+        let source = "function wrap<T extends object, U = T>(value: T): U { return value as U; }\nclass Box<T> {}\n";
+        let tree = parser.parse(source, Language::TypeScript).unwrap();
+        let symbols = extract_symbols(&tree, "a.ts", source, Language::TypeScript);
+        assert_eq!(
+            symbols[0].type_params,
+            Some(vec!["T extends object".to_string(), "U = T".to_string()])
+        );
+        assert_eq!(symbols[1].type_params, Some(vec!["T".to_string()]));
+    }
+
+    #[test]
+    fn test_extract_sql_symbols() {
+        let source = r#"
+-- Registered users
+CREATE TABLE IF NOT EXISTS public.users (
+    id INTEGER PRIMARY KEY,
+    email TEXT NOT NULL
+);
+
+CREATE UNIQUE INDEX idx_users_email ON public.users (email);
+
+CREATE OR REPLACE VIEW active_users AS
+    SELECT * FROM public.users WHERE active;
+
+CREATE FUNCTION "auth"."touch"() RETURNS trigger AS $$
+BEGIN
+    NEW.updated_at = now();
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+"#;
+        let symbols = extract_sql_symbols("schema.sql", source);
+        let find = |name: &str| symbols.iter().find(|s| s.fq_name == format!("schema.sql::{}", name)).unwrap();
+
+        assert_eq!(symbols.len(), 4);
+
+        let users = find("public.users");
+        assert_eq!(users.kind, SymbolKind::Struct);
+        assert_eq!((users.span_start_line, users.span_end_line), (3, 6));
+        assert_eq!(users.docstring.as_deref(), Some("-- Registered users"));
+
+        assert_eq!(find("idx_users_email").kind, SymbolKind::Index);
+        assert_eq!(find("active_users").kind, SymbolKind::Type);
+
+        let touch = find("auth.touch");
+        assert_eq!(touch.kind, SymbolKind::Function);
+        assert_eq!((touch.span_start_line, touch.span_end_line), (13, 18));
+    }
+
+    #[test]
+    fn test_complexity() {
+        let mut parser = Parser::new().unwrap();
+        let source = r#"
+fn simple() -> i32 {
+    1
+}
+
+fn branchy(x: Option<i32>) -> Result<i32, ()> {
+    let v = x.ok_or(())?;
+    if v > 0 {
+        for i in 0..v {
+            match i {
+                0 => {}
+                _ => {}
+            }
+        }
+    }
+    Ok(v)
+}
+"#;
+        let tree = parser.parse(source, Language::Rust).unwrap();
+        let symbols = extract_symbols(&tree, "test.rs", source, Language::Rust);
+        let complexity = |name: &str| symbols.iter().find(|s| s.fq_name.ends_with(name)).unwrap().complexity;
+
+        assert_eq!(complexity("::simple"), Some(1));
+        // ?, if, for, match
+        assert_eq!(complexity("::branchy"), Some(5));
+
+        let source = r#"
+def handler(x):
+    try:
+        if x:
+            return 1
+    except ValueError:
+        return 0
+"#;
+        let tree = parser.parse(source, Language::Python).unwrap();
+        let symbols = extract_symbols(&tree, "test.py", source, Language::Python);
+        // try, if, except
+        assert_eq!(symbols[0].complexity, Some(4));
+    }
 }
@@ -7,5 +7,5 @@ mod extractor;
 mod types;
 
 pub use parser::Parser;
-pub use extractor::extract_symbols;
-pub use types::{ExtractedSymbol, Language};
+pub use extractor::{extract_file, extract_todos, TODO_KINDS};
+pub use types::{ExtractedSymbol, ImportRecord, Language, LanguageFilter, TodoRecord};
@@ -34,6 +34,9 @@ impl Parser {
             Language::Python => tree_sitter_python::LANGUAGE,
             Language::Go => tree_sitter_go::LANGUAGE,
             Language::Rust => tree_sitter_rust::LANGUAGE,
+            Language::Java => tree_sitter_java::LANGUAGE,
+            // SQL is handled by the line-based extractor, not tree-sitter
+            Language::Sql => return Err(ParserError::UnsupportedLanguage("sql".to_string())),
         };
 
         self.ts_parser
@@ -11,6 +11,8 @@ pub enum Language {
     Python,
     Go,
     Rust,
+    Java,
+    Sql,
 }
 
 impl Language {
@@ -22,6 +24,22 @@ impl Language {
             "py" => Some(Language::Python),
             "go" => Some(Language::Go),
             "rs" => Some(Language::Rust),
+            "java" => Some(Language::Java),
+            "sql" => Some(Language::Sql),
+            _ => None,
+        }
+    }
+
+    /// Parse a language name as printed by `as_str` (case-insensitive)
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "typescript" => Some(Language::TypeScript),
+            "javascript" => Some(Language::JavaScript),
+            "python" => Some(Language::Python),
+            "go" => Some(Language::Go),
+            "rust" => Some(Language::Rust),
+            "java" => Some(Language::Java),
+            "sql" => Some(Language::Sql),
             _ => None,
         }
     }
@@ -34,10 +52,54 @@ impl Language {
             Language::Python => "python",
             Language::Go => "go",
             Language::Rust => "rust",
+            Language::Java => "java",
+            Language::Sql => "sql",
         }
     }
 }
 
+/// Allow/block lists of languages for `index --languages/--exclude-languages`
+#[derive(Debug, Clone, Default)]
+pub struct LanguageFilter {
+    /// Only index these languages (`None` means all)
+    pub only: Option<Vec<Language>>,
+    /// Never index these languages
+    pub exclude: Vec<Language>,
+}
+
+impl LanguageFilter {
+    /// Build a filter from comma-separated lists, e.g. `"rust,python"`
+    pub fn parse(only: Option<&str>, exclude: Option<&str>) -> Result<Self, String> {
+        Ok(LanguageFilter {
+            only: only.map(parse_language_list).transpose()?,
+            exclude: exclude.map(parse_language_list).transpose()?.unwrap_or_default(),
+        })
+    }
+
+    pub fn allows(&self, lang: Language) -> bool {
+        self.only.as_ref().is_none_or(|only| only.contains(&lang)) && !self.exclude.contains(&lang)
+    }
+
+    /// True if every language is allowed
+    pub fn allows_all(&self) -> bool {
+        self.only.is_none() && self.exclude.is_empty()
+    }
+}
+
+fn parse_language_list(list: &str) -> Result<Vec<Language>, String> {
+    list.split(',')
+        .filter(|name| !name.trim().is_empty())
+        .map(|name| {
+            Language::from_name(name).ok_or_else(|| {
+                format!(
+                    "Unknown language: {} (expected typescript, javascript, python, go, rust, java, sql)",
+                    name.trim()
+                )
+            })
+        })
+        .collect()
+}
+
 impl std::fmt::Display for Language {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.as_str())
@@ -58,6 +120,7 @@ pub enum SymbolKind {
     Enum,
     Trait,
     Impl,
+    Index,
 }
 
 impl SymbolKind {
@@ -73,6 +136,7 @@ impl SymbolKind {
             SymbolKind::Enum => "enum",
             SymbolKind::Trait => "trait",
             SymbolKind::Impl => "impl",
+            SymbolKind::Index => "index",
         }
     }
 }
@@ -104,4 +168,48 @@ pub struct ExtractedSymbol {
     pub docstring: Option<String>,
     /// Programming language
     pub language: Language,
+    /// Cyclomatic complexity (Rust and Python functions/methods only)
+    pub complexity: Option<u32>,
+    /// Decorator names (Python only), e.g. `property`, `app.route`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub decorators: Vec<String>,
+    /// Generic parameters as written (`T: Trait`, `'a`, `K extends string`), if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub type_params: Option<Vec<String>>,
+    /// Calls made from this function/method's body (nested functions own their calls)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub calls: Vec<CallEdge>,
+}
+
+/// A module imported by a file (one record per imported name)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImportRecord {
+    /// File containing the import, relative to workspace root
+    pub importer_file: String,
+    /// Module path as written (`./auth`, `crate::db`, `os.path`)
+    pub imported_path: String,
+    /// Imported item, `*` for wildcards; None for whole-module imports
+    pub imported_name: Option<String>,
+}
+
+/// A `TODO:`-style marker comment (at most one per line)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TodoRecord {
+    /// File path relative to workspace root
+    pub file: String,
+    /// Line of the marker (1-indexed)
+    pub line: u32,
+    /// `TODO`, `FIXME`, `HACK`, `XXX`, or `NOTE`
+    pub kind: String,
+    /// Rest of the line after the marker
+    pub message: String,
+}
+
+/// A call site inside a symbol body
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CallEdge {
+    /// Called name as written, without receiver or path (`self.store.save()` -> `save`)
+    pub callee: String,
+    /// Line of the call (1-indexed)
+    pub line: u32,
 }
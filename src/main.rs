@@ -10,7 +10,8 @@ mod updater;
 mod worktree;
 
 use clap::Parser;
-use cli::{Cli, Commands, SessionCommands};
+use cli::{Cli, Commands, DbCommands, SessionCommands, SnapshotCommands, StashCommands};
+use std::io::IsTerminal;
 use std::path::PathBuf;
 
 fn main() {
@@ -31,45 +32,644 @@ fn main() {
     
     let db_path_str = db_path_buf.to_str().unwrap_or(".bacchus/bacchus.db");
 
-    if let Err(e) = db::init_db(Some(db_path_str), true) {
-        eprintln!("Failed to initialize database: {}", e);
-        std::process::exit(1);
+    // Dry-run indexing and version queries must not touch the database, not even
+    // to be audited
+    let audited = !matches!(
+        cli.command,
+        Commands::Index { emit_symbols: true, .. } | Commands::Version { .. }
+    );
+    // These don't create or migrate the database (init creates it itself, after
+    // --force has cleared the old one); they are audited once they finish
+    let needs_db = audited
+        && !matches!(
+            cli.command,
+            Commands::Init { .. }
+                | Commands::Hooks { .. }
+                | Commands::Config { .. }
+                | Commands::Doctor { .. }
+                | Commands::SearchBeads { .. }
+                | Commands::DependsOn { .. }
+        );
+
+    if needs_db {
+        if let Err(e) = db::init_db(Some(db_path_str), true) {
+            eprintln!("Failed to initialize database: {}", e);
+            std::process::exit(1);
+        }
     }
 
+    // Record the invocation; auditing must never block the command itself
+    let started = std::time::Instant::now();
+    let audit_args = serde_json::to_value(&cli.command).ok().filter(|_| audited);
+    let audit_id = if needs_db {
+        audit_args.as_ref().and_then(|args| tools::audit::start_audit(args).ok())
+    } else {
+        None
+    };
+
+    // Commands that print a report but should still fail (e.g. checks) set this
+    let mut exit_code = 0;
+
     let result = match cli.command {
         // ====================================================================
         // Coordination Commands
         // ====================================================================
-        Commands::Next { agent_id } => {
-            tools::next_task(&agent_id, &workspace_root)
+        Commands::Next { agent_id, wait, timeout, filter_file, target_branch } => {
+            if wait {
+                tools::next_task_wait(&agent_id, &filter_file, timeout, target_branch.as_deref(), &workspace_root)
+            } else {
+                tools::next_task(&agent_id, &filter_file, target_branch.as_deref(), &workspace_root)
+            }
+            .map(|r| serde_json::to_string_pretty(&r).unwrap())
+        }
+
+        Commands::Claim { bead_id, agent_id, force, target_branch } => {
+            tools::claim_task(&bead_id, &agent_id, force, target_branch.as_deref(), &workspace_root)
+                .map(|r| serde_json::to_string_pretty(&r).unwrap())
+        }
+
+        Commands::Balance { dry_run } => {
+            tools::balance(dry_run)
+                .map(|r| serde_json::to_string_pretty(&r).unwrap())
+                .map_err(|e| rusqlite::Error::SqliteFailure(
+                    rusqlite::ffi::Error::new(1),
+                    Some(e.to_string()),
+                ))
+        }
+
+        Commands::Assign { bead_id, agent_id, reassign } => {
+            tools::assign_bead(&bead_id, &agent_id, reassign)
+                .map(|r| serde_json::to_string_pretty(&r).unwrap())
+        }
+
+        Commands::ClaimFromFile { file, agent_id } => {
+            tools::claim_from_file(&file, &agent_id, &workspace_root)
+                .map(|r| serde_json::to_string_pretty(&r).unwrap())
+        }
+
+        Commands::CreateWorktree { bead_id, base_branch, no_claim, agent_id } => {
+            tools::create_worktree(&bead_id, base_branch.as_deref(), no_claim, &agent_id, &workspace_root)
+                .map(|r| serde_json::to_string_pretty(&r).unwrap())
+                .map_err(|e| rusqlite::Error::SqliteFailure(
+                    rusqlite::ffi::Error::new(1),
+                    Some(e.to_string()),
+                ))
+        }
+
+        Commands::RemoveWorktree { bead_id, keep_branch, force } => {
+            tools::remove_worktree(&bead_id, keep_branch, force, &workspace_root)
+                .map(|r| serde_json::to_string_pretty(&r).unwrap())
+                .map_err(|e| rusqlite::Error::SqliteFailure(
+                    rusqlite::ffi::Error::new(1),
+                    Some(e.to_string()),
+                ))
+        }
+
+        Commands::Archive { bead_id } => {
+            tools::archive_bead(&bead_id, &workspace_root)
+                .map(|r| serde_json::to_string_pretty(&r).unwrap())
+                .map_err(|e| rusqlite::Error::SqliteFailure(
+                    rusqlite::ffi::Error::new(1),
+                    Some(e.to_string()),
+                ))
+        }
+
+        Commands::Unarchive { bead_id } => {
+            tools::unarchive_bead(&bead_id, &workspace_root)
+                .map(|r| serde_json::to_string_pretty(&r).unwrap())
+                .map_err(|e| rusqlite::Error::SqliteFailure(
+                    rusqlite::ffi::Error::new(1),
+                    Some(e.to_string()),
+                ))
+        }
+
+        Commands::Reset { bead_id, confirm } => {
+            tools::reset_bead(&bead_id, confirm, &workspace_root)
+                .map(|r| serde_json::to_string_pretty(&r).unwrap())
+                .map_err(|e| rusqlite::Error::SqliteFailure(
+                    rusqlite::ffi::Error::new(1),
+                    Some(e.to_string()),
+                ))
+        }
+
+        Commands::WorktreeList { format } => {
+            tools::worktree_list(&format, &workspace_root)
+                .map_err(|e| rusqlite::Error::SqliteFailure(
+                    rusqlite::ffi::Error::new(1),
+                    Some(e.to_string()),
+                ))
+        }
+
+        Commands::WorktreeInfo { bead_id } => {
+            tools::worktree_info(&bead_id, &workspace_root)
+                .map(|r| serde_json::to_string_pretty(&r).unwrap())
+                .map_err(|e| rusqlite::Error::SqliteFailure(
+                    rusqlite::ffi::Error::new(1),
+                    Some(e.to_string()),
+                ))
+        }
+
+        Commands::Release { bead_id, status, commit_message, post_merge_script, squash, tag, run_tests } => {
+            tools::release_bead(
+                &bead_id,
+                &status,
+                commit_message.as_deref(),
+                post_merge_script.as_deref(),
+                squash,
+                tag,
+                run_tests,
+                &workspace_root,
+            )
+                .map(|r| serde_json::to_string_pretty(&r).unwrap())
+                .map_err(|e| rusqlite::Error::SqliteFailure(
+                    rusqlite::ffi::Error::new(1),
+                    Some(e.to_string()),
+                ))
+        }
+
+        Commands::Conflicts { bead_id } => {
+            tools::predict_conflicts(&workspace_root, &bead_id)
+                .map(|r| serde_json::to_string_pretty(&r).unwrap())
+                .map_err(|e| rusqlite::Error::SqliteFailure(
+                    rusqlite::ffi::Error::new(1),
+                    Some(e.to_string()),
+                ))
+        }
+
+        Commands::Queue { format, limit } => {
+            tools::bead_queue(&format, limit)
+                .map_err(|e| rusqlite::Error::SqliteFailure(
+                    rusqlite::ffi::Error::new(1),
+                    Some(e.to_string()),
+                ))
+        }
+
+        Commands::Revert { bead_id, no_commit } => {
+            tools::revert_bead(&bead_id, no_commit, &workspace_root)
+                .map(|r| serde_json::to_string_pretty(&r).unwrap())
+                .map_err(|e| rusqlite::Error::SqliteFailure(
+                    rusqlite::ffi::Error::new(1),
+                    Some(e.to_string()),
+                ))
+        }
+
+        Commands::Pause { bead_id } => {
+            tools::pause_bead(&bead_id)
+                .map(|r| serde_json::to_string_pretty(&r).unwrap())
+                .map_err(|e| rusqlite::Error::SqliteFailure(
+                    rusqlite::ffi::Error::new(1),
+                    Some(e.to_string()),
+                ))
+        }
+
+        Commands::Pin { bead_id } => {
+            let minutes = config::load_config(&workspace_root).stale_threshold_minutes;
+            tools::pin_claim(&bead_id, minutes)
+                .map(|r| serde_json::to_string_pretty(&r).unwrap())
+        }
+
+        Commands::Unpin { bead_id } => {
+            tools::unpin_claim(&bead_id)
+                .map(|r| serde_json::to_string_pretty(&r).unwrap())
+        }
+
+        Commands::Resume { bead_id, agent_id } => {
+            tools::resume_bead(&bead_id, &agent_id)
+                .map(|r| serde_json::to_string_pretty(&r).unwrap())
+                .map_err(|e| rusqlite::Error::SqliteFailure(
+                    rusqlite::ffi::Error::new(1),
+                    Some(e.to_string()),
+                ))
+        }
+
+        Commands::Push { bead_id, remote, force } => {
+            tools::push_bead(&bead_id, remote.as_deref().unwrap_or("origin"), force, &workspace_root)
+                .map(|r| serde_json::to_string_pretty(&r).unwrap())
+                .map_err(|e| rusqlite::Error::SqliteFailure(
+                    rusqlite::ffi::Error::new(1),
+                    Some(e.to_string()),
+                ))
+        }
+
+        Commands::Diff { bead_id, stat } => {
+            tools::diff_bead(&bead_id, stat)
+                .map(|r| serde_json::to_string_pretty(&r).unwrap())
+                .map_err(|e| rusqlite::Error::SqliteFailure(
+                    rusqlite::ffi::Error::new(1),
+                    Some(e.to_string()),
+                ))
+        }
+
+        Commands::DiffSymbols { bead_id } => {
+            tools::diff_symbols(&workspace_root, &bead_id)
+                .map(|r| serde_json::to_string_pretty(&r).unwrap())
+                .map_err(|e| rusqlite::Error::SqliteFailure(
+                    rusqlite::ffi::Error::new(1),
+                    Some(e.to_string()),
+                ))
+        }
+
+        Commands::Test { bead_id } => {
+            tools::test_bead(&bead_id, &workspace_root)
+                .map(|r| {
+                    if !r.success {
+                        exit_code = 1;
+                    }
+                    serde_json::to_string_pretty(&r).unwrap()
+                })
+                .map_err(|e| rusqlite::Error::SqliteFailure(
+                    rusqlite::ffi::Error::new(1),
+                    Some(e.to_string()),
+                ))
+        }
+
+        Commands::Lint { bead_id } => {
+            tools::lint_bead(&bead_id, &workspace_root)
+                .map(|r| {
+                    if !r.success {
+                        exit_code = 1;
+                    }
+                    serde_json::to_string_pretty(&r).unwrap()
+                })
+                .map_err(|e| rusqlite::Error::SqliteFailure(
+                    rusqlite::ffi::Error::new(1),
+                    Some(e.to_string()),
+                ))
+        }
+
+        Commands::Format { bead_id, commit, check } => {
+            tools::format_bead(&bead_id, commit, check, &workspace_root)
+                .map(|r| {
+                    if !r.success {
+                        exit_code = 1;
+                    }
+                    serde_json::to_string_pretty(&r).unwrap()
+                })
+                .map_err(|e| rusqlite::Error::SqliteFailure(
+                    rusqlite::ffi::Error::new(1),
+                    Some(e.to_string()),
+                ))
+        }
+
+        Commands::FindRelated { bead_id } => {
+            tools::find_related_beads(&bead_id)
+                .map(|r| serde_json::to_string_pretty(&r).unwrap())
+                .map_err(|e| rusqlite::Error::SqliteFailure(
+                    rusqlite::ffi::Error::new(1),
+                    Some(e.to_string()),
+                ))
+        }
+
+        Commands::Estimate { bead_id } => {
+            tools::estimate_task_size(&bead_id, &workspace_root)
+                .map(|r| serde_json::to_string_pretty(&r).unwrap())
+        }
+
+        Commands::Split { bead_id, into, copy_symbols } => {
+            tools::split_bead(&bead_id, &into, copy_symbols)
+                .map(|r| serde_json::to_string_pretty(&r).unwrap())
+                .map_err(|e| rusqlite::Error::SqliteFailure(
+                    rusqlite::ffi::Error::new(1),
+                    Some(e.to_string()),
+                ))
+        }
+
+        Commands::Sync { bead_id } => {
+            tools::sync_bead(&bead_id, &workspace_root)
+                .map(|r| serde_json::to_string_pretty(&r).unwrap())
+                .map_err(|e| rusqlite::Error::SqliteFailure(
+                    rusqlite::ffi::Error::new(1),
+                    Some(e.to_string()),
+                ))
+        }
+
+        Commands::Stash { command: Some(StashCommands::List), .. } => {
+            tools::list_stashes(&workspace_root)
+                .map(|r| serde_json::to_string_pretty(&r).unwrap())
+                .map_err(|e| rusqlite::Error::SqliteFailure(
+                    rusqlite::ffi::Error::new(1),
+                    Some(e.to_string()),
+                ))
+        }
+
+        Commands::Stash { command: None, bead_id, message } => {
+            match bead_id {
+                Some(bead_id) => tools::stash_bead(&bead_id, message.as_deref(), &workspace_root)
+                    .map(|r| serde_json::to_string_pretty(&r).unwrap())
+                    .map_err(|e| rusqlite::Error::SqliteFailure(
+                        rusqlite::ffi::Error::new(1),
+                        Some(e.to_string()),
+                    )),
+                None => Err(rusqlite::Error::SqliteFailure(
+                    rusqlite::ffi::Error::new(1),
+                    Some("stash requires a bead ID (or `stash list`)".to_string()),
+                )),
+            }
+        }
+
+        Commands::Unstash { bead_id } => {
+            tools::unstash_bead(&bead_id, &workspace_root)
+                .map(|r| serde_json::to_string_pretty(&r).unwrap())
+                .map_err(|e| rusqlite::Error::SqliteFailure(
+                    rusqlite::ffi::Error::new(1),
+                    Some(e.to_string()),
+                ))
+        }
+
+        Commands::Snapshot { command: Some(SnapshotCommands::List { bead_id }), .. } => {
+            tools::list_snapshots(&bead_id)
+                .map(|r| serde_json::to_string_pretty(&r).unwrap())
+        }
+
+        Commands::Snapshot { command: None, bead_id, name } => {
+            match bead_id {
+                Some(bead_id) => tools::snapshot_worktree(&bead_id, name.as_deref(), &workspace_root)
+                    .map(|r| {
+                        if !r.success {
+                            exit_code = 1;
+                        }
+                        serde_json::to_string_pretty(&r).unwrap()
+                    })
+                    .map_err(|e| rusqlite::Error::SqliteFailure(
+                        rusqlite::ffi::Error::new(1),
+                        Some(e.to_string()),
+                    )),
+                None => Err(rusqlite::Error::SqliteFailure(
+                    rusqlite::ffi::Error::new(1),
+                    Some("snapshot requires a bead ID (or `snapshot list <bead_id>`)".to_string()),
+                )),
+            }
+        }
+
+        Commands::Restore { bead_id, name } => {
+            tools::restore_worktree_snapshot(&bead_id, &name, &workspace_root)
+                .map(|r| {
+                    if !r.success {
+                        exit_code = 1;
+                    }
+                    serde_json::to_string_pretty(&r).unwrap()
+                })
+                .map_err(|e| rusqlite::Error::SqliteFailure(
+                    rusqlite::ffi::Error::new(1),
+                    Some(e.to_string()),
+                ))
+        }
+
+        Commands::Rebase { bead_id, target } => {
+            tools::rebase_bead(&bead_id, target.as_deref(), &workspace_root)
+                .map(|r| serde_json::to_string_pretty(&r).unwrap())
+                .map_err(|e| rusqlite::Error::SqliteFailure(
+                    rusqlite::ffi::Error::new(1),
+                    Some(e.to_string()),
+                ))
+        }
+
+        Commands::Abort { bead_id } => {
+            tools::abort_merge(&bead_id, &workspace_root)
+                .map(|r| serde_json::to_string_pretty(&r).unwrap())
+                .map_err(|e| rusqlite::Error::SqliteFailure(
+                    rusqlite::ffi::Error::new(1),
+                    Some(e.to_string()),
+                ))
+        }
+
+        Commands::Resolve { bead_id } => {
+            tools::resolve_merge(&bead_id, &workspace_root)
+                .map(|r| serde_json::to_string_pretty(&r).unwrap())
+                .map_err(|e| rusqlite::Error::SqliteFailure(
+                    rusqlite::ffi::Error::new(1),
+                    Some(e.to_string()),
+                ))
+        }
+
+        Commands::Recover { bead_id, dry_run } => {
+            tools::recover(&bead_id, dry_run, &workspace_root)
+                .map(|r| serde_json::to_string_pretty(&r).unwrap())
+                .map_err(|e| rusqlite::Error::SqliteFailure(
+                    rusqlite::ffi::Error::new(1),
+                    Some(e.to_string()),
+                ))
+        }
+
+        Commands::Stale { minutes, cleanup, parallel } => {
+            let minutes = minutes.unwrap_or_else(|| config::load_config(&workspace_root).stale_threshold_minutes);
+            tools::find_stale(minutes, cleanup, parallel, &workspace_root)
+                .map(|r| serde_json::to_string_pretty(&r).unwrap())
+                .map_err(|e| rusqlite::Error::SqliteFailure(
+                    rusqlite::ffi::Error::new(1),
+                    Some(e.to_string()),
+                ))
+        }
+
+        Commands::Orchestrate { max_agents, stale_minutes, poll_interval_seconds, dry_run } => {
+            tools::orchestrate(max_agents, stale_minutes, poll_interval_seconds, dry_run, &workspace_root)
+                .map(|r| serde_json::to_string_pretty(&r).unwrap())
+                .map_err(|e| rusqlite::Error::SqliteFailure(
+                    rusqlite::ffi::Error::new(1),
+                    Some(e.to_string()),
+                ))
+        }
+
+        Commands::Doctor { format, quick_doctor } => {
+            let report = tools::run_doctor(&db_path_buf, &workspace_root, quick_doctor);
+            if !report.ok {
+                exit_code = 1;
+            }
+            Ok(match format.as_str() {
+                "json" => serde_json::to_string_pretty(&report).unwrap(),
+                _ => tools::render_doctor(&report),
+            })
+        }
+
+        Commands::Verify { fix } => {
+            tools::verify_workspace(fix, &workspace_root)
+                .map(|r| {
+                    if !r.ok {
+                        exit_code = 1;
+                    }
+                    serde_json::to_string_pretty(&r).unwrap()
+                })
+                .map_err(|e| rusqlite::Error::SqliteFailure(
+                    rusqlite::ffi::Error::new(1),
+                    Some(e.to_string()),
+                ))
+        }
+
+        Commands::Hooks { action, hook_types } => {
+            tools::manage_hooks(&action, &hook_types, &workspace_root)
+                .map(|r| serde_json::to_string_pretty(&r).unwrap())
+                .map_err(|e| rusqlite::Error::SqliteFailure(
+                    rusqlite::ffi::Error::new(1),
+                    Some(e.to_string()),
+                ))
+        }
+
+        Commands::Watch { agent_id, interval, max_concurrent } => {
+            tools::watch(&agent_id, interval, max_concurrent, &workspace_root)
+                .map(|r| serde_json::to_string_pretty(&r).unwrap())
+                .map_err(|e| rusqlite::Error::SqliteFailure(
+                    rusqlite::ffi::Error::new(1),
+                    Some(e.to_string()),
+                ))
+        }
+
+        Commands::NotifyStaleAgents { minutes } => {
+            let minutes = minutes.unwrap_or_else(|| config::load_config(&workspace_root).stale_threshold_minutes);
+            tools::notify_stale_agents(minutes, &workspace_root)
+                .map(|r| serde_json::to_string_pretty(&r).unwrap())
+                .map_err(|e| rusqlite::Error::SqliteFailure(
+                    rusqlite::ffi::Error::new(1),
+                    Some(e.to_string()),
+                ))
+        }
+
+        Commands::Progress { bead_id, percent } => {
+            tools::set_progress(&bead_id, percent)
+                .map(|r| serde_json::to_string_pretty(&r).unwrap())
+        }
+
+        Commands::Heartbeat { agent_id, bead_id } => {
+            tools::heartbeat(&agent_id, bead_id.as_deref())
+                .map(|r| serde_json::to_string_pretty(&r).unwrap())
+        }
+
+        Commands::Notifications { agent_id, status, limit, acknowledge, format } => match acknowledge {
+            Some(id) => tools::acknowledge_notification(id).map(|r| serde_json::to_string_pretty(&r).unwrap()),
+            None => tools::list_notifications(agent_id.as_deref(), status == "pending", limit)
+                .map(|n| tools::render_notifications(n, &format)),
+        },
+
+        Commands::List => {
+            tools::list_claims().map(|r| serde_json::to_string_pretty(&r).unwrap())
+        }
+
+        // ====================================================================
+        // Symbol Commands
+        // ====================================================================
+        Commands::Symbols {
+            pattern,
+            kind,
+            file,
+            lang,
+            limit,
+            search,
+            fuzzy,
+            since,
+            changed,
+            min_complexity,
+            sort,
+            find_complex,
+            threshold,
+            package,
+            cross_ref,
+            recent,
+            since_hours,
+            recently_changed,
+            in_bead,
+            imported_by,
+            decorator,
+            has_type_param,
+            find_duplicates,
+            semantic_search,
+            semantic_threshold,
+            output_template,
+        } => {
+            // --find-complex is shorthand for --min-complexity N --sort complexity
+            let (min_complexity, sort) = if find_complex {
+                (Some(threshold), Some("complexity".to_string()))
+            } else {
+                (min_complexity, sort)
+            };
+
+            // --recent is shorthand for --since <now - N hours>
+            let since = if recent {
+                let now_ms = chrono::Utc::now().timestamp_millis();
+                Some(now_ms - i64::from(since_hours) * 60 * 60 * 1000)
+            } else {
+                since
+            };
+
+            if changed {
+                tools::find_changed_symbols().map(|r| serde_json::to_string_pretty(&r).unwrap())
+            } else if find_duplicates {
+                tools::find_duplicate_symbols(&workspace_root).map(|r| serde_json::to_string_pretty(&r).unwrap())
+            } else if let Some(name) = cross_ref {
+                tools::find_cross_references(&name).map(|r| serde_json::to_string_pretty(&r).unwrap())
+            } else {
+                let input = tools::FindSymbolsInput {
+                    pattern,
+                    kind,
+                    file,
+                    language: lang,
+                    limit: Some(limit),
+                    search,
+                    fuzzy,
+                    since,
+                    min_complexity,
+                    sort,
+                    package,
+                    in_bead,
+                    changed_since_claim: recently_changed,
+                    semantic_search,
+                    semantic_threshold: Some(semantic_threshold),
+                    imported_by,
+                    decorator,
+                    has_type_param,
+                };
+                tools::find_symbols(&input).and_then(|r| match output_template {
+                    Some(template) => tools::render_symbols(&r, &template, &workspace_root).map_err(|e| {
+                        rusqlite::Error::SqliteFailure(rusqlite::ffi::Error::new(1), Some(e))
+                    }),
+                    None => Ok(serde_json::to_string_pretty(&r).unwrap()),
+                })
+            }
+        }
+
+        Commands::SymbolAdd { fq_name, file, kind, description } => {
+            tools::register_virtual_symbol(&fq_name, &file, &kind, description.as_deref())
+                .map(|r| serde_json::to_string_pretty(&r).unwrap())
+        }
+
+        Commands::RegisterSymbol { bead_id, fq_name, relation } => {
+            tools::register_bead_symbol(&bead_id, &fq_name, &relation)
+                .map(|r| serde_json::to_string_pretty(&r).unwrap())
+        }
+
+        Commands::LockSymbol { fq_name, bead_id } => {
+            tools::lock_symbol(&fq_name, &bead_id).map(|r| serde_json::to_string_pretty(&r).unwrap())
+        }
+
+        Commands::UnlockSymbol { fq_name, bead_id } => {
+            tools::unlock_symbol(&fq_name, &bead_id).map(|r| serde_json::to_string_pretty(&r).unwrap())
+        }
+
+        Commands::Lock { file, bead_id } => {
+            tools::lock_file(&file, bead_id.as_deref(), &workspace_root)
+                .map(|r| serde_json::to_string_pretty(&r).unwrap())
+        }
+
+        Commands::Unlock { file, bead_id } => {
+            tools::unlock_file(&file, bead_id.as_deref(), &workspace_root)
                 .map(|r| serde_json::to_string_pretty(&r).unwrap())
         }
 
-        Commands::Claim { bead_id, agent_id, force } => {
-            tools::claim_task(&bead_id, &agent_id, force, &workspace_root)
+        Commands::RegisterFileHint { bead_id, file_pattern } => {
+            tools::register_file_hint(&bead_id, &file_pattern)
                 .map(|r| serde_json::to_string_pretty(&r).unwrap())
         }
 
-        Commands::Release { bead_id, status } => {
-            tools::release_bead(&bead_id, &status, &workspace_root)
+        Commands::Callers { fq_name, depth } => {
+            tools::find_callers(&fq_name, depth)
                 .map(|r| serde_json::to_string_pretty(&r).unwrap())
-                .map_err(|e| rusqlite::Error::SqliteFailure(
-                    rusqlite::ffi::Error::new(1),
-                    Some(e.to_string()),
-                ))
         }
 
-        Commands::Abort { bead_id } => {
-            tools::abort_merge(&bead_id, &workspace_root)
+        Commands::Impact { fq_name, depth } => {
+            tools::impact_analysis(&fq_name, depth)
                 .map(|r| serde_json::to_string_pretty(&r).unwrap())
-                .map_err(|e| rusqlite::Error::SqliteFailure(
-                    rusqlite::ffi::Error::new(1),
-                    Some(e.to_string()),
-                ))
         }
 
-        Commands::Resolve { bead_id } => {
-            tools::resolve_merge(&bead_id, &workspace_root)
+        Commands::Todos { kind, file, limit, create_beads } => {
+            tools::list_todos(kind.as_deref(), file.as_deref(), limit, create_beads)
                 .map(|r| serde_json::to_string_pretty(&r).unwrap())
                 .map_err(|e| rusqlite::Error::SqliteFailure(
                     rusqlite::ffi::Error::new(1),
@@ -77,40 +677,30 @@ fn main() {
                 ))
         }
 
-        Commands::Stale { minutes, cleanup } => {
-            tools::find_stale(minutes, cleanup, &workspace_root)
+        Commands::Blame { fq_name } => {
+            tools::blame_symbol(&fq_name)
                 .map(|r| serde_json::to_string_pretty(&r).unwrap())
+        }
+
+        Commands::Annotate { file } => {
+            tools::annotate_file(&file, &workspace_root)
                 .map_err(|e| rusqlite::Error::SqliteFailure(
                     rusqlite::ffi::Error::new(1),
-                    Some(e.to_string()),
+                    Some(e),
                 ))
         }
 
-        Commands::List => {
-            tools::list_claims().map(|r| serde_json::to_string_pretty(&r).unwrap())
-        }
-
-        // ====================================================================
-        // Symbol Commands
-        // ====================================================================
-        Commands::Symbols { pattern, kind, file, lang, limit, search, fuzzy } => {
-            let input = tools::FindSymbolsInput {
-                pattern,
-                kind,
-                file,
-                language: lang,
-                limit: Some(limit),
-                search,
-                fuzzy,
-            };
-            tools::find_symbols(&input).map(|r| serde_json::to_string_pretty(&r).unwrap())
-        }
+        Commands::Index { path, emit_symbols, languages, exclude_languages, incremental } => {
+            let languages = languages.or_else(|| std::env::var("BACCHUS_INDEX_LANGUAGES").ok());
+            let filter = indexer::LanguageFilter::parse(languages.as_deref(), exclude_languages.as_deref());
 
-        Commands::Index { path } => {
-            match index_path(&path, &workspace_root) {
-                Ok(count) => Ok(serde_json::json!({
+            match filter.and_then(|filter| index_path(&path, &workspace_root, emit_symbols, incremental, &filter)) {
+                // NDJSON output is the whole result; no summary line
+                Ok(_) if emit_symbols => Ok(String::new()),
+                Ok(stats) => Ok(serde_json::json!({
                     "success": true,
-                    "files_indexed": count,
+                    "files_indexed": stats.files_indexed,
+                    "files_removed": stats.files_removed,
                     "path": path
                 }).to_string()),
                 Err(e) => Err(rusqlite::Error::SqliteFailure(
@@ -123,19 +713,79 @@ fn main() {
         // ====================================================================
         // Info Commands
         // ====================================================================
-        Commands::Status => {
-            get_status().map(|r| serde_json::to_string_pretty(&r).unwrap())
+        Commands::Config { action, key, value } => {
+            tools::config_command(&action, &key, value.as_deref(), &workspace_root)
+                .map(|r| serde_json::to_string_pretty(&r).unwrap())
+                .map_err(|e| rusqlite::Error::SqliteFailure(
+                    rusqlite::ffi::Error::new(1),
+                    Some(e),
+                ))
         }
 
-        Commands::Workflow => {
-            println!("{}", WORKFLOW_DOC);
-            Ok(String::new())
+        Commands::Init { force, no_beads } => {
+            tools::init_workspace(force, !no_beads, &db_path_buf, &workspace_root)
+                .map(|r| {
+                    if r.success {
+                        // Stdout stays JSON; the protocol is for the human running init
+                        if let Ok(doc) = tools::workflow_doc("markdown") {
+                            eprintln!("{}", doc);
+                        }
+                    } else {
+                        exit_code = 1;
+                    }
+                    serde_json::to_string_pretty(&r).unwrap()
+                })
+                .map_err(|e| rusqlite::Error::SqliteFailure(
+                    rusqlite::ffi::Error::new(1),
+                    Some(e.to_string()),
+                ))
+        }
+
+        Commands::Clean { dry_run, force } => {
+            tools::clean_workspace(&workspace_root, dry_run, force)
+                .map(|r| serde_json::to_string_pretty(&r).unwrap())
+                .map_err(|e| rusqlite::Error::SqliteFailure(
+                    rusqlite::ffi::Error::new(1),
+                    Some(e.to_string()),
+                ))
+        }
+
+        Commands::Status { no_disk_usage } => {
+            get_status(!no_disk_usage).map(|r| serde_json::to_string_pretty(&r).unwrap())
+        }
+
+        Commands::Workflow { format } => {
+            tools::workflow_doc(&format)
+                .map_err(|e| rusqlite::Error::SqliteFailure(
+                    rusqlite::ffi::Error::new(1),
+                    Some(e),
+                ))
         }
 
         // ====================================================================
         // Update Commands
         // ====================================================================
-        Commands::SelfUpdate => {
+        Commands::Version { json: true } => {
+            Ok(serde_json::to_string_pretty(&tools::version_info()).unwrap())
+        }
+
+        Commands::Version { json: false } => {
+            Ok(format!("bacchus {}", tools::version_info().version))
+        }
+
+        Commands::SelfUpdate { verify_only: true } => {
+            updater::verify_binary().map(|info| {
+                if !info.verified {
+                    exit_code = 1;
+                }
+                serde_json::to_string_pretty(&info).unwrap()
+            }).map_err(|e| rusqlite::Error::SqliteFailure(
+                rusqlite::ffi::Error::new(1),
+                Some(e.to_string()),
+            ))
+        }
+
+        Commands::SelfUpdate { verify_only: false } => {
             updater::self_update().map(|v| {
                 serde_json::json!({
                     "success": true,
@@ -147,8 +797,96 @@ fn main() {
             ))
         }
 
-        Commands::Context { bead_id } => {
-            tools::generate_context(bead_id, &workspace_root)
+        Commands::Context { bead_id, agent_persona } => {
+            tools::generate_context(bead_id, agent_persona.as_deref(), &workspace_root)
+                .map_err(|e| rusqlite::Error::SqliteFailure(
+                    rusqlite::ffi::Error::new(1),
+                    Some(e),
+                ))
+        }
+
+        Commands::SearchBeads { query, limit, status } => {
+            tools::search_beads(&beads::beads_db_path(&workspace_root), &query, limit, status.as_deref())
+                .map(|r| serde_json::to_string_pretty(&r).unwrap())
+                .map_err(|e| rusqlite::Error::SqliteFailure(
+                    rusqlite::ffi::Error::new(1),
+                    Some(e.to_string()),
+                ))
+        }
+
+        Commands::SearchCode { pattern, file_pattern, bead_ids, max_matches } => {
+            tools::search_code(&pattern, file_pattern.as_deref(), &bead_ids, max_matches)
+                .map(|r| serde_json::to_string_pretty(&r).unwrap())
+                .map_err(|e| rusqlite::Error::SqliteFailure(
+                    rusqlite::ffi::Error::new(1),
+                    Some(e),
+                ))
+        }
+
+        Commands::Export { path, format, include_symbols } => {
+            tools::export_workspace(std::path::Path::new(&path), &format, include_symbols)
+                .map_err(|e| rusqlite::Error::SqliteFailure(
+                    rusqlite::ffi::Error::new(1),
+                    Some(e),
+                ))
+        }
+
+        Commands::ExportBeads { format, output, include_closed } => {
+            tools::export_beads(&format, output.as_ref(), include_closed)
+                .map_err(|e| rusqlite::Error::SqliteFailure(
+                    rusqlite::ffi::Error::new(1),
+                    Some(e),
+                ))
+        }
+
+        Commands::Graph { format, include_closed } => {
+            tools::bead_graph(&format, include_closed)
+                .map_err(|e| rusqlite::Error::SqliteFailure(
+                    rusqlite::ffi::Error::new(1),
+                    Some(e),
+                ))
+        }
+
+        Commands::DependsOn { bead_id, depth, format } => {
+            let depth = depth.unwrap_or(tools::depends_on::DEFAULT_DEPTH);
+            tools::bead_blockers(&beads::beads_db_path(&workspace_root), &bead_id, depth)
+                .map(|r| match format.as_str() {
+                    "json" => serde_json::to_string_pretty(&r).unwrap(),
+                    _ => tools::render_blocker_tree(&r, std::io::stdout().is_terminal()),
+                })
+                .map_err(|e| rusqlite::Error::SqliteFailure(
+                    rusqlite::ffi::Error::new(1),
+                    Some(e.to_string()),
+                ))
+        }
+
+        Commands::Import { path, overwrite } => {
+            tools::import_workspace(std::path::Path::new(&path), overwrite, &workspace_root)
+                .map_err(|e| rusqlite::Error::SqliteFailure(
+                    rusqlite::ffi::Error::new(1),
+                    Some(e),
+                ))
+        }
+
+        Commands::ImportBeads { file, format, dry_run } => {
+            tools::import_beads(&file, format.as_deref(), dry_run)
+                .map(|r| serde_json::to_string_pretty(&r).unwrap())
+                .map_err(|e| rusqlite::Error::SqliteFailure(
+                    rusqlite::ffi::Error::new(1),
+                    Some(e),
+                ))
+        }
+
+        Commands::Report { format, since, output } => {
+            tools::generate_report(&format, since.as_deref(), output.as_ref())
+                .map_err(|e| rusqlite::Error::SqliteFailure(
+                    rusqlite::ffi::Error::new(1),
+                    Some(e),
+                ))
+        }
+
+        Commands::ExportContext { bead_id, output } => {
+            tools::export_context(&bead_id, output.as_ref(), &workspace_root)
                 .map_err(|e| rusqlite::Error::SqliteFailure(
                     rusqlite::ffi::Error::new(1),
                     Some(e),
@@ -199,8 +937,62 @@ fn main() {
                 }
             }
         }
+
+        Commands::Stats { since, format } => {
+            let now = chrono::Utc::now().timestamp_millis();
+            since
+                .as_deref()
+                .map(|s| tools::report::parse_since(s, now))
+                .transpose()
+                .map_err(|e| rusqlite::Error::SqliteFailure(rusqlite::ffi::Error::new(1), Some(e)))
+                .and_then(tools::compute_stats)
+                .map(|r| match format.as_str() {
+                    "compact" => serde_json::to_string(&r).unwrap(),
+                    _ => serde_json::to_string_pretty(&r).unwrap(),
+                })
+        }
+
+        Commands::Log { bead_id, agent_id, limit, since, format } => {
+            tools::operation_log(bead_id.as_deref(), agent_id.as_deref(), limit, since.as_deref(), &format)
+                .map_err(|e| rusqlite::Error::SqliteFailure(
+                    rusqlite::ffi::Error::new(1),
+                    Some(e),
+                ))
+        }
+
+        Commands::Audit { limit, bead_id, since_ms } => {
+            tools::query_audit_log(limit, bead_id.as_deref(), since_ms)
+                .map(|r| serde_json::to_string_pretty(&r).unwrap())
+        }
+
+        // ====================================================================
+        // Database Commands
+        // ====================================================================
+        Commands::Db { command } => {
+            match command {
+                DbCommands::IntegrityCheck { quick } => {
+                    tools::check_integrity(quick).map(|r| {
+                        if !r.ok {
+                            exit_code = 1;
+                        }
+                        serde_json::to_string_pretty(&r).unwrap()
+                    })
+                }
+                DbCommands::Stats => {
+                    tools::db_stats(&db_path_buf).map(|r| serde_json::to_string_pretty(&r).unwrap())
+                }
+            }
+        }
     };
 
+    let code = if result.is_err() { 1 } else { exit_code };
+    let duration_ms = started.elapsed().as_millis() as i64;
+    if let Some(id) = audit_id {
+        let _ = tools::audit::finish_audit(id, code, duration_ms);
+    } else if let Some(args) = audit_args.filter(|_| !needs_db && db_path_buf.exists()) {
+        let _ = tools::audit::record_invocation(&db_path_buf, &args, code, duration_ms);
+    }
+
     match result {
         Ok(output) => {
             if !output.is_empty() {
@@ -214,56 +1006,198 @@ fn main() {
     }
 
     db::close_db();
+
+    if exit_code != 0 {
+        std::process::exit(exit_code);
+    }
 }
 
 /// Index a file or directory (parallelized with rayon)
-fn index_path(path: &str, workspace_root: &PathBuf) -> Result<usize, String> {
+///
+/// With `emit_symbols`, extracted symbols are printed as NDJSON instead of stored.
+/// With `incremental`, only files under `path` changed since the last indexed
+/// commit are parsed, and symbols of deleted files are removed. Only runs over
+/// the whole workspace with every language advance that commit, since a
+/// partial run leaves files outside it unindexed.
+fn index_path(
+    path: &str,
+    workspace_root: &PathBuf,
+    emit_symbols: bool,
+    incremental: bool,
+    filter: &indexer::LanguageFilter,
+) -> Result<IndexStats, String> {
     use rayon::prelude::*;
     use walkdir::WalkDir;
 
     let target = workspace_root.join(path);
+    let config = config::load_config(workspace_root);
+    let is_indexable = |file: &std::path::Path| {
+        let ext = file.extension().and_then(|e| e.to_str()).unwrap_or("");
+        indexer::Language::from_extension(ext).is_some_and(|lang| filter.allows(lang))
+            && !config.is_index_ignored(&relative_path(file, workspace_root))
+    };
 
-    if target.is_file() {
-        // Single file - no parallelization needed
-        let mut parser = indexer::Parser::new().map_err(|e| e.to_string())?;
-        let symbols = parse_file(&mut parser, &target, workspace_root)?;
-        store_symbols(&symbols)?;
-        return Ok(1);
-    }
-
-    if !target.is_dir() {
-        return Err(format!("Path not found: {}", path));
-    }
+    let last_commit = if incremental {
+        tools::index_state::get_index_state(tools::index_state::LAST_INDEXED_COMMIT).map_err(|e| e.to_string())?
+    } else {
+        None
+    };
 
-    // Collect all indexable files first
-    let files: Vec<PathBuf> = WalkDir::new(&target)
-        .into_iter()
-        .filter_map(|e| e.ok())
-        .filter(|e| e.file_type().is_file())
-        .filter(|e| {
-            let ext = e.path().extension().and_then(|e| e.to_str()).unwrap_or("");
-            indexer::Language::from_extension(ext).is_some()
-        })
-        .map(|e| e.path().to_path_buf())
-        .collect();
+    // Without a previous run to diff against, --incremental falls back to a full index
+    let (files, removed): (Vec<PathBuf>, Vec<String>) = match &last_commit {
+        Some(commit) => {
+            let changed = git_changed_files(workspace_root, commit)?;
+            let (present, removed): (Vec<PathBuf>, Vec<PathBuf>) = changed
+                .into_iter()
+                .map(|f| workspace_root.join(f))
+                .filter(|f| f.starts_with(&target) && is_indexable(f))
+                .partition(|f| f.is_file());
+            let removed = removed.iter().map(|f| relative_path(f, workspace_root)).collect();
+            (present, removed)
+        }
+        None if target.is_file() => {
+            if !is_indexable(&target) {
+                return Ok(IndexStats::default());
+            }
+            (vec![target.clone()], Vec::new())
+        }
+        None if target.is_dir() => {
+            let files = WalkDir::new(&target)
+                .into_iter()
+                .filter_map(|e| e.ok())
+                .filter(|e| e.file_type().is_file())
+                .filter(|e| is_indexable(e.path()))
+                .map(|e| e.path().to_path_buf())
+                .collect();
+            // Drop symbols of files under the directory that were deleted or renamed away
+            let removed = if emit_symbols {
+                Vec::new()
+            } else {
+                stale_indexed_files(&target, workspace_root)?
+            };
+            (files, removed)
+        }
+        None => return Err(format!("Path not found: {}", path)),
+    };
 
     // Parse files in parallel (each thread gets its own parser)
-    let all_symbols: Vec<indexer::ExtractedSymbol> = files
+    let parsed: Vec<ParsedFile> = files
         .par_iter()
         .filter_map(|file_path| {
             // Create parser per thread (tree-sitter parsers aren't thread-safe)
             let mut parser = indexer::Parser::new().ok()?;
             parse_file(&mut parser, file_path, workspace_root).ok()
         })
-        .flatten()
         .collect();
+    let mut all_symbols: Vec<indexer::ExtractedSymbol> = Vec::new();
+    let mut all_imports: Vec<indexer::ImportRecord> = Vec::new();
+    let mut all_todos: Vec<indexer::TodoRecord> = Vec::new();
+    for (symbols, imports, todos) in parsed {
+        all_symbols.extend(symbols);
+        all_imports.extend(imports);
+        all_todos.extend(todos);
+    }
+
+    let stats = IndexStats {
+        files_indexed: files.len(),
+        files_removed: removed.len(),
+    };
+
+    if emit_symbols {
+        emit_symbols_ndjson(&all_symbols)?;
+        return Ok(stats);
+    }
+
+    // Outside a git repo there's no commit to attribute changes to or diff against next time
+    let head = worktree::get_head_commit(workspace_root).ok();
+
+    // Replace every re-parsed or deleted file's symbols (single DB transaction)
+    let mut replaced: Vec<String> = files.iter().map(|f| relative_path(f, workspace_root)).collect();
+    replaced.extend(removed);
+    store_symbols(&all_symbols, &all_imports, &all_todos, &replaced, head.as_deref())?;
+
+    let whole_workspace = target.canonicalize().ok() == workspace_root.canonicalize().ok();
+    if let Some(head) = head.as_ref().filter(|_| whole_workspace && filter.allows_all()) {
+        tools::index_state::set_index_state(tools::index_state::LAST_INDEXED_COMMIT, head)
+            .map_err(|e| e.to_string())?;
+    }
+
+    Ok(stats)
+}
+
+#[derive(Debug, Default)]
+struct IndexStats {
+    files_indexed: usize,
+    files_removed: usize,
+}
+
+/// Indexed files under `dir` that no longer exist on disk
+fn stale_indexed_files(dir: &std::path::Path, workspace_root: &PathBuf) -> Result<Vec<String>, String> {
+    let prefix = match relative_path(dir, workspace_root) {
+        p if p.is_empty() => p,
+        p => format!("{}/", p.trim_end_matches('/')),
+    };
+
+    let indexed: Vec<String> = db::with_db(|conn| {
+        let mut stmt = conn.prepare(
+            "SELECT DISTINCT file FROM symbols WHERE substr(file, 1, length(?1)) = ?1 AND language != ?2",
+        )?;
+        let rows = stmt.query_map(
+            rusqlite::params![prefix, tools::symbols::VIRTUAL_LANGUAGE],
+            |row| row.get(0),
+        )?;
+        rows.collect()
+    })
+    .map_err(|e| e.to_string())?;
+
+    Ok(indexed
+        .into_iter()
+        .filter(|file| !workspace_root.join(file).is_file())
+        .collect())
+}
+
+/// Files changed between `since` and HEAD, relative to the workspace root
+fn git_changed_files(workspace_root: &PathBuf, since: &str) -> Result<Vec<String>, String> {
+    let output = std::process::Command::new("git")
+        .args(["diff", "--name-only", since, "HEAD"])
+        .current_dir(workspace_root)
+        .output()
+        .map_err(|e| e.to_string())?;
+
+    if !output.status.success() {
+        return Err(format!(
+            "git diff {}..HEAD failed: {}",
+            since,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
 
-    let file_count = files.len();
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter(|l| !l.is_empty())
+        .map(String::from)
+        .collect())
+}
+
+fn relative_path(file_path: &std::path::Path, workspace_root: &PathBuf) -> String {
+    file_path
+        .strip_prefix(workspace_root)
+        .unwrap_or(file_path)
+        .to_string_lossy()
+        .to_string()
+}
 
-    // Batch insert all symbols (single DB transaction)
-    store_symbols(&all_symbols)?;
+/// Print symbols to stdout, one JSON object per line
+fn emit_symbols_ndjson(symbols: &[indexer::ExtractedSymbol]) -> Result<(), String> {
+    use std::io::Write;
 
-    Ok(file_count)
+    let stdout = std::io::stdout();
+    let mut out = stdout.lock();
+    for sym in symbols {
+        let line = serde_json::to_string(sym).map_err(|e| e.to_string())?;
+        writeln!(out, "{}", line).map_err(|e| e.to_string())?;
+    }
+    Ok(())
 }
 
 /// Find workspace root by looking for .bacchus or .git directories walking up
@@ -288,15 +1222,13 @@ fn find_workspace_root() -> Option<PathBuf> {
 
         // If we hit .git, we are likely at root, UNLESS it's a worktree .git file
         let git_path = current.join(".git");
-        if git_path.exists() {
-            if git_path.is_dir() {
-                return Some(current);
-            }
-            // If .git is a file, it's a submodule or worktree.
-            // If worktree, we should keep going up to find the real root.
-            // But we might be in a submodule which IS a root for its own context?
-            // For bacchus, we care about where .bacchus is.
+        if git_path.is_dir() {
+            return Some(current);
         }
+        // If .git is a file, it's a submodule or worktree.
+        // If worktree, we should keep going up to find the real root.
+        // But we might be in a submodule which IS a root for its own context?
+        // For bacchus, we care about where .bacchus is.
 
         if !current.pop() {
             break;
@@ -305,29 +1237,83 @@ fn find_workspace_root() -> Option<PathBuf> {
     None
 }
 
-/// Parse a single file and extract symbols
+/// Symbols, imports, and TODO markers extracted from one file
+type ParsedFile = (Vec<indexer::ExtractedSymbol>, Vec<indexer::ImportRecord>, Vec<indexer::TodoRecord>);
+
+/// Parse a single file and extract its symbols, imports, and TODO markers
 fn parse_file(
     parser: &mut indexer::Parser,
     file_path: &std::path::Path,
     workspace_root: &PathBuf,
-) -> Result<Vec<indexer::ExtractedSymbol>, String> {
+) -> Result<ParsedFile, String> {
     let content = std::fs::read_to_string(file_path).map_err(|e| e.to_string())?;
-    let relative_path = file_path
-        .strip_prefix(workspace_root)
-        .unwrap_or(file_path)
-        .to_string_lossy()
-        .to_string();
-
-    let (tree, language) = parser.parse_file(&content, &relative_path).map_err(|e| e.to_string())?;
-    Ok(indexer::extract_symbols(&tree, &relative_path, &content, language))
+    let relative_path = relative_path(file_path, workspace_root);
+    let todos = indexer::extract_todos(&relative_path, &content);
+    let (symbols, imports) = indexer::extract_file(parser, &relative_path, &content).map_err(|e| e.to_string())?;
+    Ok((symbols, imports, todos))
 }
 
 /// Store symbols in database (batched in single transaction)
-fn store_symbols(symbols: &[indexer::ExtractedSymbol]) -> Result<(), String> {
+///
+/// Existing symbols, call edges, imports, and TODOs for each of `files` are
+/// deleted first so re-indexed files don't accumulate stale entries. Virtual
+/// symbols are registered by hand and kept.
+fn store_symbols(
+    symbols: &[indexer::ExtractedSymbol],
+    imports: &[indexer::ImportRecord],
+    todos: &[indexer::TodoRecord],
+    files: &[String],
+    commit: Option<&str>,
+) -> Result<(), String> {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis() as i64)
+        .unwrap_or(0);
+
     db::with_db(|conn| {
+        // All or nothing: a failure mid-run must not leave files half-indexed
+        let tx = conn.unchecked_transaction()?;
+
+        // Snapshot hashes first so `symbols --changed` can diff this run
+        let snapshot = tools::symbols::snapshot_symbol_hashes(&tx)?;
+        tools::symbols::save_symbol_snapshot(&tx, &snapshot)?;
+        tools::symbols::invalidate_tfidf_cache(&tx)?;
+
+        for file in files {
+            tx.execute(
+                "DELETE FROM symbols WHERE file = ?1 AND language != ?2",
+                rusqlite::params![file, tools::symbols::VIRTUAL_LANGUAGE],
+            )?;
+            tx.execute("DELETE FROM symbol_calls WHERE call_site_file = ?1", [file])?;
+            tx.execute("DELETE FROM file_imports WHERE importer_file = ?1", [file])?;
+            tx.execute("DELETE FROM todos WHERE file = ?1", [file])?;
+        }
+
+        for todo in todos {
+            tx.execute(
+                "INSERT INTO todos (file, line, kind, message, indexed_at) VALUES (?1, ?2, ?3, ?4, ?5)",
+                rusqlite::params![todo.file, todo.line, todo.kind, todo.message, now],
+            )?;
+        }
+
+        for import in imports {
+            tx.execute(
+                "INSERT INTO file_imports (importer_file, imported_path, imported_name) VALUES (?1, ?2, ?3)",
+                rusqlite::params![import.importer_file, import.imported_path, import.imported_name],
+            )?;
+        }
+
+        let mut callers = Vec::new();
         for sym in symbols {
-            conn.execute(
-                "INSERT OR REPLACE INTO symbols (file, fq_name, kind, span_start_line, span_end_line, line_count, hash, docstring, language) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            // Keep the body hash this run supersedes so `blame` can tell when it changed
+            if let Some(old_hash) = snapshot.get(&sym.fq_name).filter(|h| **h != sym.hash) {
+                tx.execute(
+                    "INSERT INTO symbol_history (symbol_fq_name, hash, file, indexed_at, commit_hash) VALUES (?1, ?2, ?3, ?4, ?5)",
+                    rusqlite::params![sym.fq_name, old_hash, sym.file, now, commit],
+                )?;
+            }
+            tx.execute(
+                "INSERT OR REPLACE INTO symbols (file, fq_name, kind, span_start_line, span_end_line, line_count, hash, docstring, language, last_indexed_at, complexity, decorators, type_params) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
                 rusqlite::params![
                     sym.file,
                     sym.fq_name,
@@ -337,16 +1323,36 @@ fn store_symbols(symbols: &[indexer::ExtractedSymbol]) -> Result<(), String> {
                     sym.line_count,
                     sym.hash,
                     sym.docstring,
-                    sym.language.as_str()
+                    sym.language.as_str(),
+                    now,
+                    sym.complexity,
+                    (!sym.decorators.is_empty()).then(|| serde_json::to_string(&sym.decorators).unwrap_or_default()),
+                    sym.type_params.as_ref().map(|t| serde_json::to_string(t).unwrap_or_default())
                 ],
             )?;
+            if !sym.calls.is_empty() {
+                callers.push((tx.last_insert_rowid(), sym));
+            }
+        }
+
+        // Resolve callees once every symbol in the batch exists
+        let resolver = tools::callers::CalleeResolver::load(&tx)?;
+        for (caller_id, sym) in callers {
+            for call in &sym.calls {
+                let callee = resolver.resolve(&sym.file, &call.callee);
+                tx.execute(
+                    "INSERT INTO symbol_calls (caller_symbol_id, callee_fq_name, call_site_file, call_site_line) VALUES (?1, ?2, ?3, ?4)",
+                    rusqlite::params![caller_id, callee, sym.file, call.line],
+                )?;
+            }
         }
-        Ok(())
+
+        tx.commit()
     }).map_err(|e: rusqlite::Error| e.to_string())
 }
 
 /// Get current status
-fn get_status() -> rusqlite::Result<serde_json::Value> {
+fn get_status(disk_usage: bool) -> rusqlite::Result<serde_json::Value> {
     let workspace_root = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
 
     db::with_db(|conn| {
@@ -360,7 +1366,8 @@ fn get_status() -> rusqlite::Result<serde_json::Value> {
         // Get active claims with worktree paths
         let mut stmt = conn.prepare(
             "SELECT bead_id, agent_id, worktree_path, branch_name,
-                    (strftime('%s', 'now') * 1000 - claimed_at) / 60000 as age_minutes
+                    (strftime('%s', 'now') * 1000 - claimed_at) / 60000 as age_minutes,
+                    stashed_at, progress
              FROM claims"
         )?;
         let claims: Vec<(serde_json::Value, String)> = stmt
@@ -371,15 +1378,31 @@ fn get_status() -> rusqlite::Result<serde_json::Value> {
                     "agent_id": row.get::<_, String>(1)?,
                     "worktree_path": &worktree_path,
                     "branch": row.get::<_, String>(3)?,
-                    "age_minutes": row.get::<_, i64>(4)?
+                    "age_minutes": row.get::<_, i64>(4)?,
+                    "stashed": row.get::<_, Option<i64>>(5)?.is_some(),
+                    "progress": row.get::<_, i64>(6)?
                 }), worktree_path))
             })?
             .filter_map(|r| r.ok())
             .collect();
 
-        let claim_values: Vec<serde_json::Value> = claims.iter().map(|(v, _)| v.clone()).collect();
-        let claimed_worktrees: std::collections::HashSet<String> =
-            claims.iter().map(|(_, p)| p.clone()).collect();
+        let claim_values: Vec<serde_json::Value> = claims
+            .iter()
+            .map(|(v, path)| {
+                let mut v = v.clone();
+                if disk_usage {
+                    v["disk_bytes"] = serde_json::json!(dir_size(std::path::Path::new(path)));
+                }
+                v
+            })
+            .collect();
+        // Paused beads keep their worktrees, so those aren't orphaned either
+        let paused = tools::pause::paused_claims(conn)?;
+        let claimed_worktrees: std::collections::HashSet<String> = claims
+            .iter()
+            .map(|(_, p)| p.clone())
+            .chain(paused.iter().map(|(_, p)| p.clone()))
+            .collect();
 
         // Count symbols indexed
         let symbols_count: i32 = conn.query_row(
@@ -394,9 +1417,7 @@ fn get_status() -> rusqlite::Result<serde_json::Value> {
             .unwrap_or(0);
 
         // Check for orphaned worktrees (worktrees on disk without claims)
-        let worktrees_dir = std::env::var("BACCHUS_WORKTREES")
-            .map(PathBuf::from)
-            .unwrap_or_else(|_| workspace_root.join(".bacchus/worktrees"));
+        let worktrees_dir = worktree::get_worktrees_dir(&workspace_root);
 
         let mut orphaned_worktrees: Vec<String> = Vec::new();
         if worktrees_dir.exists() {
@@ -423,7 +1444,7 @@ fn get_status() -> rusqlite::Result<serde_json::Value> {
             .filter_map(|(v, _)| v.get("bead_id").and_then(|b| b.as_str()).map(String::from))
             .collect();
 
-        Ok(serde_json::json!({
+        let mut status = serde_json::json!({
             "claims": {
                 "count": claims_count,
                 "active": claim_values
@@ -431,80 +1452,34 @@ fn get_status() -> rusqlite::Result<serde_json::Value> {
             "symbols_indexed": symbols_count,
             "ready_beads": ready_count,
             "orphaned_worktrees": orphaned_worktrees,
-            "broken_claims": broken_claims
-        }))
+            "broken_claims": broken_claims,
+            "paused_beads": paused.iter().map(|(bead_id, _)| bead_id).collect::<Vec<_>>(),
+            "archived_beads_count": tools::archive::archived_beads_count(conn)?
+        });
+        if !orphaned_worktrees.is_empty() || !broken_claims.is_empty() {
+            status["suggestion"] = serde_json::json!(
+                "Run `bacchus clean --dry-run` to review, then `bacchus clean` to remove orphaned worktrees and broken claims"
+            );
+        }
+        if disk_usage {
+            // Includes orphaned worktrees, which use disk too
+            status["worktrees_total_bytes"] = serde_json::json!(dir_size(&worktrees_dir));
+        }
+
+        Ok(status)
     })
 }
 
-const WORKFLOW_DOC: &str = r#"
-# Bacchus Coordination Protocol
-
-## Agent Workflow
-
-1. **Get Work**
-   ```bash
-   bacchus next <agent_id>
-   ```
-   - Finds ready bead from beads DB (open, no blockers)
-   - Creates worktree at .bacchus/worktrees/{bead_id}/
-   - Claims bead, updates status to in_progress
-
-2. **Do Work**
-   Work in the worktree. All changes are isolated on branch bacchus/{bead_id}.
-
-3. **Release When Done**
-   ```bash
-   # Success - merge to main and cleanup
-   bacchus release <bead_id> --status done
-
-   # Blocked - keep worktree, release claim
-   bacchus release <bead_id> --status blocked
-
-   # Failed - discard worktree, reset bead
-   bacchus release <bead_id> --status failed
-   ```
-
-4. **Handle Merge Conflicts**
-   If release fails due to conflicts:
-   ```bash
-   # Option 1: Resolve manually then complete
-   # ... fix conflicts, git add resolved files ...
-   bacchus resolve <bead_id>
-
-   # Option 2: Abort and keep working
-   bacchus abort <bead_id>
-   ```
-
-## Stale Detection
-
-Find abandoned claims:
-```bash
-bacchus stale --minutes 30
-
-# Auto-cleanup stale claims
-bacchus stale --minutes 30 --cleanup
-```
-
-## Code Search
-
-```bash
-bacchus index src/
-bacchus symbols --pattern "User*" --kind class
-```
-
-## Context
-
-```bash
-bacchus context
-bacchus context --bead-id <bead_id>
-```
-
-- Run from repo root for global context.
-- Run inside a worktree for task context.
-
-## Status
-
-```bash
-bacchus status
-```
-"#;
+/// Total size in bytes of the files under `dir` (0 if it doesn't exist)
+///
+/// Walks the tree rather than shelling out to `du` so it works everywhere.
+/// Symlinks are counted by their own size, not followed.
+fn dir_size(dir: &std::path::Path) -> u64 {
+    walkdir::WalkDir::new(dir)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter_map(|e| e.metadata().ok())
+        .map(|m| m.len())
+        .sum()
+}
@@ -1,9 +1,12 @@
-//! Abort tool - abort a failed merge for a bead
+//! Abort tool - abort a failed merge or rebase for a bead
 //!
-//! Restores the repository to pre-merge state when a merge conflict occurs.
+//! Restores the repository to pre-merge state when a merge conflict occurs,
+//! or the bead's worktree to its pre-rebase state when a rebase stopped.
 
+use super::audit::{claim_agent, log_operation};
 use crate::db::with_db;
 use crate::worktree;
+use rusqlite::OptionalExtension;
 use serde::{Deserialize, Serialize};
 use std::path::Path;
 
@@ -17,24 +20,46 @@ pub struct AbortOutput {
 pub fn abort_merge(
     bead_id: &str,
     workspace_root: &Path,
+) -> Result<AbortOutput, Box<dyn std::error::Error>> {
+    let agent_id = claim_agent(bead_id);
+    let result = try_abort_merge(bead_id, workspace_root);
+    log_operation("abort", bead_id, agent_id.as_deref(), &result, |r| {
+        (r.success, serde_json::json!({ "message": r.message }))
+    });
+    result
+}
+
+fn try_abort_merge(
+    bead_id: &str,
+    workspace_root: &Path,
 ) -> Result<AbortOutput, Box<dyn std::error::Error>> {
     // 1. Check claim exists
-    let claim_exists = with_db(|conn| {
-        Ok(conn
-            .query_row(
-                "SELECT 1 FROM claims WHERE bead_id = ?1",
-                [bead_id],
-                |_| Ok(true),
-            )
-            .unwrap_or(false))
+    let worktree_path: Option<String> = with_db(|conn| {
+        conn.query_row(
+            "SELECT worktree_path FROM claims WHERE bead_id = ?1",
+            [bead_id],
+            |row| row.get(0),
+        )
+        .optional()
     })?;
 
-    if !claim_exists {
+    let Some(worktree_path) = worktree_path else {
         return Ok(AbortOutput {
             success: false,
             bead_id: bead_id.to_string(),
             message: format!("No claim found for {}", bead_id),
         });
+    };
+
+    // A rebase that stopped on conflicts lives in the bead's own worktree
+    let worktree_path = Path::new(&worktree_path);
+    if worktree_path.exists() && worktree::is_rebase_in_progress(worktree_path)? {
+        worktree::abort_rebase(worktree_path)?;
+        return Ok(AbortOutput {
+            success: true,
+            bead_id: bead_id.to_string(),
+            message: format!("Aborted rebase for {}. Branch restored to its pre-rebase state.", bead_id),
+        });
     }
 
     // 2. Check we're in a merge conflict state
@@ -0,0 +1,172 @@
+//! Annotate tool - `git blame` at the bead level
+//!
+//! Lines are blamed along the first-parent history of HEAD, so each lands on
+//! the commit that brought it into the branch: for bacchus releases, the merge
+//! commit recorded in the bead's `released` claim event. Commits without a
+//! recorded release fall back to a `bacchus/<bead_id>` reference in their
+//! subject (the default merge message), and commits from before the first
+//! claim event are marked `<pre-bacchus>`.
+
+use crate::db::with_db;
+use std::collections::HashMap;
+use std::path::Path;
+use std::process::Command;
+
+const PRE_BACCHUS: &str = "<pre-bacchus>";
+/// Bead IDs are cut to this many characters so the column stays narrow
+const BEAD_WIDTH: usize = 10;
+
+#[derive(Debug, PartialEq)]
+struct BlameLine {
+    commit: String,
+    summary: String,
+    /// Committer time (Unix seconds)
+    committed_at: i64,
+    content: String,
+}
+
+/// Print `file` with each line prefixed by the bead that introduced it
+pub fn annotate_file(file: &str, workspace_root: &Path) -> Result<String, String> {
+    let output = Command::new("git")
+        .args(["blame", "--first-parent", "--line-porcelain", "--", file])
+        .current_dir(workspace_root)
+        .output()
+        .map_err(|e| format!("Failed to run git blame: {}", e))?;
+    if !output.status.success() {
+        return Err(format!("git blame failed: {}", String::from_utf8_lossy(&output.stderr).trim()));
+    }
+    let lines = parse_blame(&String::from_utf8_lossy(&output.stdout));
+
+    let (releases, tracking_started) = with_db(|conn| {
+        let mut stmt = conn.prepare(
+            "SELECT json_extract(details, '$.merge_commit'), bead_id FROM claim_events
+             WHERE event_type = 'released' AND json_extract(details, '$.merge_commit') IS NOT NULL",
+        )?;
+        let releases = stmt
+            .query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))?
+            .collect::<rusqlite::Result<HashMap<_, _>>>()?;
+        let started: Option<i64> = conn.query_row("SELECT MIN(created_at) FROM claim_events", [], |r| r.get(0))?;
+        Ok((releases, started))
+    })
+    .map_err(|e| e.to_string())?;
+
+    let number_width = lines.len().to_string().len();
+    let rendered: Vec<String> = lines
+        .iter()
+        .enumerate()
+        .map(|(i, line)| {
+            format!(
+                "{:<label$} {:>number$} {}",
+                bead_label(line, &releases, tracking_started),
+                i + 1,
+                line.content,
+                label = PRE_BACCHUS.len(),
+                number = number_width
+            )
+        })
+        .collect();
+    Ok(rendered.join("\n"))
+}
+
+/// Bead column for a line: the releasing bead, `<pre-bacchus>`, or `-`
+fn bead_label(line: &BlameLine, releases: &HashMap<String, String>, tracking_started_ms: Option<i64>) -> String {
+    let bead = releases
+        .get(&line.commit)
+        .cloned()
+        .or_else(|| bead_from_subject(&line.summary));
+    match bead {
+        Some(bead) => bead.chars().take(BEAD_WIDTH).collect(),
+        None if tracking_started_ms.is_none_or(|start| line.committed_at * 1000 < start) => PRE_BACCHUS.to_string(),
+        None => "-".to_string(),
+    }
+}
+
+/// `bacchus/<bead_id>` in a commit subject, e.g. `feat: Add login (bacchus/bd-12)`
+fn bead_from_subject(summary: &str) -> Option<String> {
+    let start = summary.find("bacchus/")? + "bacchus/".len();
+    let bead: String = summary[start..]
+        .chars()
+        .take_while(|c| c.is_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        .collect();
+    let bead = bead.trim_end_matches('.');
+    (!bead.is_empty()).then(|| bead.to_string())
+}
+
+/// Parse `git blame --line-porcelain`, which repeats the commit headers for every line
+fn parse_blame(output: &str) -> Vec<BlameLine> {
+    let mut lines = Vec::new();
+    let mut commit = String::new();
+    let mut summary = String::new();
+    let mut committed_at = 0;
+
+    for line in output.lines() {
+        if let Some(content) = line.strip_prefix('\t') {
+            lines.push(BlameLine {
+                commit: commit.clone(),
+                summary: summary.clone(),
+                committed_at,
+                content: content.to_string(),
+            });
+        } else if let Some(value) = line.strip_prefix("summary ") {
+            summary = value.to_string();
+        } else if let Some(value) = line.strip_prefix("committer-time ") {
+            committed_at = value.parse().unwrap_or(0);
+        } else if let Some(hash) = line.split(' ').next().filter(|h| h.len() == 40 && h.chars().all(|c| c.is_ascii_hexdigit())) {
+            commit = hash.to_string();
+        }
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "\
+1111111111111111111111111111111111111111 1 1 1
+author A
+committer-time 100
+summary Initial commit
+filename src/lib.rs
+\tfn a() {}
+2222222222222222222222222222222222222222 2 2 1
+author B
+committer-time 5000
+summary Merge branch 'bacchus/bd-login-form-rework'
+filename src/lib.rs
+\tfn b() {}
+3333333333333333333333333333333333333333 3 3 1
+committer-time 6000
+summary feat: something (bacchus/bd-7)
+filename src/lib.rs
+\tfn c() {}
+4444444444444444444444444444444444444444 4 4 1
+committer-time 7000
+summary Hand-written fix
+filename src/lib.rs
+\tfn d() {}
+";
+
+    #[test]
+    fn test_parse_blame() {
+        let lines = parse_blame(SAMPLE);
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[0].commit, "1".repeat(40));
+        assert_eq!(lines[0].committed_at, 100);
+        assert_eq!(lines[1].summary, "Merge branch 'bacchus/bd-login-form-rework'");
+        assert_eq!(lines[3].content, "fn d() {}");
+    }
+
+    #[test]
+    fn test_bead_label() {
+        let lines = parse_blame(SAMPLE);
+        let releases = HashMap::from([("3".repeat(40), "bd-recorded".to_string())]);
+        let labels: Vec<String> = lines.iter().map(|l| bead_label(l, &releases, Some(1_000_000))).collect();
+        assert_eq!(labels, vec![PRE_BACCHUS, "bd-login-f", "bd-recorde", "-"]);
+
+        // Before any claim event, untracked lines all predate bacchus
+        assert_eq!(bead_label(&lines[3], &HashMap::new(), None), PRE_BACCHUS);
+        assert_eq!(bead_from_subject("feat: x (bacchus/bd-3)."), Some("bd-3".to_string()));
+        assert_eq!(bead_from_subject("no bead here"), None);
+    }
+}
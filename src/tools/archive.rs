@@ -0,0 +1,265 @@
+//! Archive tool - move a finished bead's coordination data to long-term tables
+//!
+//! The claim row (if any) goes to `archived_claims` and the bead's symbol
+//! links to `archived_bead_symbols`, and a JSON summary including its claim
+//! events is written to `.bacchus/archive/<bead_id>.json`. `unarchive` moves
+//! the rows back.
+
+use super::audit::{claim_agent, log_operation};
+use crate::db::with_db;
+use rusqlite::{Connection, OptionalExtension, Params};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Columns shared by `claims` and `archived_claims`
+const CLAIM_COLUMNS: &str = "bead_id, agent_id, worktree_path, branch_name, start_commit, claimed_at, \
+                             target_branch, title, stashed_at, last_heartbeat, progress";
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ArchiveOutput {
+    pub success: bool,
+    pub bead_id: String,
+    pub claim_moved: bool,
+    pub symbols_moved: usize,
+    /// Summary file written by archive (removed by unarchive)
+    pub summary_path: Option<String>,
+    pub message: String,
+}
+
+fn archive_dir(workspace_root: &Path) -> PathBuf {
+    workspace_root.join(".bacchus/archive")
+}
+
+pub fn archive_bead(bead_id: &str, workspace_root: &Path) -> Result<ArchiveOutput, Box<dyn std::error::Error>> {
+    let agent_id = claim_agent(bead_id);
+    let result = try_archive(bead_id, workspace_root);
+    log_operation("archive", bead_id, agent_id.as_deref(), &result, |r| {
+        (r.success, serde_json::json!({ "symbols_moved": r.symbols_moved, "message": r.message }))
+    });
+    result
+}
+
+pub fn unarchive_bead(bead_id: &str, workspace_root: &Path) -> Result<ArchiveOutput, Box<dyn std::error::Error>> {
+    let result = try_unarchive(bead_id, workspace_root);
+    log_operation("unarchive", bead_id, None, &result, |r| {
+        (r.success, serde_json::json!({ "symbols_moved": r.symbols_moved, "message": r.message }))
+    });
+    result
+}
+
+fn try_archive(bead_id: &str, workspace_root: &Path) -> Result<ArchiveOutput, Box<dyn std::error::Error>> {
+    let output = |success: bool, message: String| ArchiveOutput {
+        success,
+        bead_id: bead_id.to_string(),
+        claim_moved: false,
+        symbols_moved: 0,
+        summary_path: None,
+        message,
+    };
+
+    let worktree_path: Option<String> = with_db(|conn| {
+        conn.query_row("SELECT worktree_path FROM claims WHERE bead_id = ?1", [bead_id], |row| row.get(0))
+            .optional()
+    })?;
+    if worktree_path.as_deref().is_some_and(|p| Path::new(p).exists()) {
+        return Ok(output(
+            false,
+            format!("{} still has a worktree; release it before archiving", bead_id),
+        ));
+    }
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as i64)
+        .unwrap_or(0);
+
+    let (summary, claim_moved, symbols_moved) = with_db(|conn| {
+        let claim = rows_to_json(conn, &format!("SELECT {} FROM claims WHERE bead_id = ?1", CLAIM_COLUMNS), [bead_id])?;
+        let symbols = rows_to_json(
+            conn,
+            "SELECT symbol_fq_name, relation FROM bead_symbols WHERE bead_id = ?1 ORDER BY symbol_fq_name",
+            [bead_id],
+        )?;
+        let events = rows_to_json(
+            conn,
+            "SELECT agent_id, event_type, details, created_at FROM claim_events WHERE bead_id = ?1 ORDER BY created_at, id",
+            [bead_id],
+        )?;
+
+        let tx = conn.unchecked_transaction()?;
+        let claim_moved = tx.execute(
+            &format!(
+                "INSERT OR REPLACE INTO archived_claims ({cols}, archived_at) SELECT {cols}, ?2 FROM claims WHERE bead_id = ?1",
+                cols = CLAIM_COLUMNS
+            ),
+            rusqlite::params![bead_id, now],
+        )? > 0;
+        let symbols_moved = tx.execute(
+            "INSERT OR REPLACE INTO archived_bead_symbols (bead_id, symbol_fq_name, relation, archived_at)
+             SELECT bead_id, symbol_fq_name, relation, ?2 FROM bead_symbols WHERE bead_id = ?1",
+            rusqlite::params![bead_id, now],
+        )?;
+        tx.execute("DELETE FROM claims WHERE bead_id = ?1", [bead_id])?;
+        tx.execute("DELETE FROM bead_symbols WHERE bead_id = ?1", [bead_id])?;
+        tx.commit()?;
+
+        let summary = serde_json::json!({
+            "bead_id": bead_id,
+            "archived_at": now,
+            "claim": claim.into_iter().next(),
+            "symbols": symbols,
+            "events": events,
+        });
+        Ok((summary, claim_moved, symbols_moved))
+    })?;
+
+    if !claim_moved && symbols_moved == 0 {
+        return Ok(output(false, format!("Nothing to archive for {}", bead_id)));
+    }
+
+    let dir = archive_dir(workspace_root);
+    std::fs::create_dir_all(&dir)?;
+    let summary_path = dir.join(format!("{}.json", bead_id));
+    std::fs::write(&summary_path, serde_json::to_string_pretty(&summary)?)?;
+
+    Ok(ArchiveOutput {
+        success: true,
+        bead_id: bead_id.to_string(),
+        claim_moved,
+        symbols_moved,
+        summary_path: Some(summary_path.to_string_lossy().to_string()),
+        message: format!("Archived {} ({} symbol link(s))", bead_id, symbols_moved),
+    })
+}
+
+fn try_unarchive(bead_id: &str, workspace_root: &Path) -> Result<ArchiveOutput, Box<dyn std::error::Error>> {
+    let (claim_moved, symbols_moved) = with_db(|conn| {
+        let tx = conn.unchecked_transaction()?;
+        // A bead claimed again since archiving keeps its live claim
+        let claim_moved = tx.execute(
+            &format!(
+                "INSERT OR IGNORE INTO claims ({cols}) SELECT {cols} FROM archived_claims WHERE bead_id = ?1",
+                cols = CLAIM_COLUMNS
+            ),
+            [bead_id],
+        )? > 0;
+        let symbols_moved = tx.execute(
+            "INSERT OR IGNORE INTO bead_symbols (bead_id, symbol_fq_name, relation)
+             SELECT bead_id, symbol_fq_name, relation FROM archived_bead_symbols WHERE bead_id = ?1",
+            [bead_id],
+        )?;
+        let archived = tx.execute("DELETE FROM archived_claims WHERE bead_id = ?1", [bead_id])?
+            + tx.execute("DELETE FROM archived_bead_symbols WHERE bead_id = ?1", [bead_id])?;
+        tx.commit()?;
+        Ok(if archived > 0 { Some((claim_moved, symbols_moved)) } else { None })
+    })?
+    .unzip();
+
+    let Some(claim_moved) = claim_moved else {
+        return Ok(ArchiveOutput {
+            success: false,
+            bead_id: bead_id.to_string(),
+            claim_moved: false,
+            symbols_moved: 0,
+            summary_path: None,
+            message: format!("{} is not archived", bead_id),
+        });
+    };
+    let symbols_moved = symbols_moved.unwrap_or(0);
+
+    let summary_path = archive_dir(workspace_root).join(format!("{}.json", bead_id));
+    if summary_path.exists() {
+        std::fs::remove_file(&summary_path)?;
+    }
+
+    Ok(ArchiveOutput {
+        success: true,
+        bead_id: bead_id.to_string(),
+        claim_moved,
+        symbols_moved,
+        summary_path: None,
+        message: format!("Restored {} ({} symbol link(s))", bead_id, symbols_moved),
+    })
+}
+
+/// Number of distinct beads with archived data
+pub fn archived_beads_count(conn: &Connection) -> rusqlite::Result<i64> {
+    conn.query_row(
+        "SELECT COUNT(*) FROM (SELECT bead_id FROM archived_claims UNION SELECT bead_id FROM archived_bead_symbols)",
+        [],
+        |r| r.get(0),
+    )
+}
+
+/// Run a query and return each row as a JSON object keyed by column name
+pub(crate) fn rows_to_json<P: Params>(conn: &Connection, sql: &str, params: P) -> rusqlite::Result<Vec<serde_json::Value>> {
+    let mut stmt = conn.prepare(sql)?;
+    let names: Vec<String> = stmt.column_names().into_iter().map(String::from).collect();
+    let rows = stmt.query_map(params, |row| {
+        let mut object = serde_json::Map::new();
+        for (i, name) in names.iter().enumerate() {
+            let value = match row.get_ref(i)? {
+                rusqlite::types::ValueRef::Null => serde_json::Value::Null,
+                rusqlite::types::ValueRef::Integer(n) => n.into(),
+                rusqlite::types::ValueRef::Real(f) => f.into(),
+                rusqlite::types::ValueRef::Text(t) => String::from_utf8_lossy(t).into(),
+                rusqlite::types::ValueRef::Blob(b) => serde_json::Value::from(b.to_vec()),
+            };
+            object.insert(name.clone(), value);
+        }
+        Ok(serde_json::Value::Object(object))
+    })?;
+    rows.collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{close_db, init_db, test_lock};
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_archive_roundtrip() {
+        let _lock = test_lock();
+        let dir = tempdir().unwrap();
+        init_db(Some(dir.path().join("test.db").to_str().unwrap()), true).unwrap();
+
+        with_db(|conn| {
+            conn.execute_batch(
+                "INSERT INTO claims (bead_id, agent_id, worktree_path, branch_name, start_commit, claimed_at, progress)
+                 VALUES ('BEAD-1', 'agent-a', '/nonexistent/wt', 'bacchus/BEAD-1', 'abc', 5, 80);
+                 INSERT INTO bead_symbols (bead_id, symbol_fq_name, relation) VALUES ('BEAD-1', 'src/a.rs::f', 'modifies');",
+            )
+        })
+        .unwrap();
+
+        let archived = archive_bead("BEAD-1", dir.path()).unwrap();
+        assert!(archived.success);
+        assert!(archived.claim_moved);
+        assert_eq!(archived.symbols_moved, 1);
+        let summary: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(archived.summary_path.unwrap()).unwrap()).unwrap();
+        assert_eq!(summary["claim"]["progress"], 80);
+        assert_eq!(summary["symbols"][0]["symbol_fq_name"], "src/a.rs::f");
+
+        let live: i64 = with_db(|conn| conn.query_row("SELECT COUNT(*) FROM claims", [], |r| r.get(0))).unwrap();
+        assert_eq!(live, 0);
+        assert_eq!(with_db(archived_beads_count).unwrap(), 1);
+        assert!(!archive_bead("BEAD-1", dir.path()).unwrap().success);
+
+        let restored = unarchive_bead("BEAD-1", dir.path()).unwrap();
+        assert!(restored.success);
+        assert!(restored.claim_moved);
+        let progress: i64 = with_db(|conn| {
+            conn.query_row("SELECT progress FROM claims WHERE bead_id = 'BEAD-1'", [], |r| r.get(0))
+        })
+        .unwrap();
+        assert_eq!(progress, 80);
+        assert_eq!(with_db(archived_beads_count).unwrap(), 0);
+        assert!(!dir.path().join(".bacchus/archive/BEAD-1.json").exists());
+        assert!(!unarchive_bead("BEAD-1", dir.path()).unwrap().success);
+
+        close_db();
+    }
+}
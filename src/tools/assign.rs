@@ -0,0 +1,179 @@
+//! Assign tool - reserve a bead for an agent without creating a worktree
+//!
+//! The bead's status becomes `assigned`, which keeps it out of everyone
+//! else's ready list. `next` for the assigned agent takes its assignments
+//! before other ready beads; claiming the bead removes the assignment.
+//! `--reassign` moves an existing assignment to another agent.
+
+use super::audit::log_operation;
+use crate::beads::{self, BeadInfo};
+use crate::db::with_db;
+use rusqlite::{OptionalExtension, Result};
+use serde::{Deserialize, Serialize};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AssignOutput {
+    pub success: bool,
+    pub bead_id: String,
+    pub agent_id: String,
+    pub message: String,
+}
+
+pub fn assign_bead(bead_id: &str, agent_id: &str, reassign: bool) -> Result<AssignOutput> {
+    let result = try_assign_bead(bead_id, agent_id, reassign);
+    log_operation("assign", bead_id, Some(agent_id), &result, |r| {
+        (r.success, serde_json::json!({ "message": r.message }))
+    });
+    result
+}
+
+fn try_assign_bead(bead_id: &str, agent_id: &str, reassign: bool) -> Result<AssignOutput> {
+    let output = |success: bool, message: String| AssignOutput {
+        success,
+        bead_id: bead_id.to_string(),
+        agent_id: agent_id.to_string(),
+        message,
+    };
+
+    let bead = match beads::get_bead(bead_id) {
+        Ok(bead) => bead,
+        Err(e) => return Ok(output(false, format!("Failed to get bead {}: {}", bead_id, e))),
+    };
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as i64)
+        .unwrap_or(0);
+
+    match assigned_agent(bead_id)? {
+        Some(holder) if holder == agent_id => {
+            return Ok(output(false, format!("Bead {} is already assigned to {}", bead_id, holder)));
+        }
+        Some(holder) if reassign => {
+            with_db(|conn| {
+                conn.execute(
+                    "UPDATE assignments SET agent_id = ?1, assigned_at = ?2 WHERE bead_id = ?3",
+                    rusqlite::params![agent_id, now, bead_id],
+                )
+            })?;
+            return Ok(output(true, format!("Reassigned {} from {} to {}", bead_id, holder, agent_id)));
+        }
+        Some(holder) => {
+            return Ok(output(
+                false,
+                format!("Bead {} is already assigned to {}. Use --reassign to move it.", bead_id, holder),
+            ));
+        }
+        None if reassign => {
+            return Ok(output(false, format!("Bead {} is not assigned; nothing to reassign", bead_id)));
+        }
+        None => {}
+    }
+
+    if bead.status != "open" {
+        return Ok(output(
+            false,
+            format!("Bead {} is not open (status: {})", bead_id, bead.status),
+        ));
+    }
+
+    with_db(|conn| {
+        conn.execute(
+            "INSERT INTO assignments (bead_id, agent_id, assigned_at) VALUES (?1, ?2, ?3)",
+            rusqlite::params![bead_id, agent_id, now],
+        )
+    })?;
+
+    // Rollback on failure so the bead isn't reserved while still open
+    if let Err(e) = beads::update_bead_status(bead_id, "assigned") {
+        let _ = clear_assignment(bead_id);
+        return Err(rusqlite::Error::SqliteFailure(
+            rusqlite::ffi::Error::new(1),
+            Some(format!("Failed to update bead status: {}", e)),
+        ));
+    }
+
+    Ok(output(true, format!("Assigned {} to {}", bead_id, agent_id)))
+}
+
+/// Agent a bead is reserved for, if any
+pub fn assigned_agent(bead_id: &str) -> Result<Option<String>> {
+    with_db(|conn| {
+        conn.query_row(
+            "SELECT agent_id FROM assignments WHERE bead_id = ?1",
+            [bead_id],
+            |row| row.get(0),
+        )
+        .optional()
+    })
+}
+
+/// Beads reserved for `agent_id`, oldest assignment first
+///
+/// Assignments whose bead can no longer be read from beads are skipped.
+pub fn assigned_beads(agent_id: &str) -> Result<Vec<BeadInfo>> {
+    let bead_ids: Vec<String> = with_db(|conn| {
+        let mut stmt = conn.prepare(
+            "SELECT bead_id FROM assignments WHERE agent_id = ?1 ORDER BY assigned_at, bead_id",
+        )?;
+        let rows = stmt.query_map([agent_id], |row| row.get(0))?;
+        rows.collect()
+    })?;
+
+    Ok(bead_ids
+        .iter()
+        .filter_map(|id| match beads::get_bead(id) {
+            Ok(bead) => Some(bead),
+            Err(e) => {
+                eprintln!("Warning: skipping assignment of {}: {}", id, e);
+                None
+            }
+        })
+        .collect())
+}
+
+/// Drop a bead's assignment (once it's claimed)
+pub fn clear_assignment(bead_id: &str) -> Result<()> {
+    with_db(|conn| conn.execute("DELETE FROM assignments WHERE bead_id = ?1", [bead_id]))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{close_db, init_db, test_lock};
+    use crate::tools::list::list_claims;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_assignments_listed_until_claimed() {
+        let _lock = test_lock();
+        let dir = tempdir().unwrap();
+        init_db(Some(dir.path().join("test.db").to_str().unwrap()), true).unwrap();
+
+        with_db(|conn| {
+            conn.execute_batch(
+                "INSERT INTO assignments (bead_id, agent_id, assigned_at) VALUES ('BEAD-1', 'agent-a', 0);
+                 INSERT INTO assignments (bead_id, agent_id, assigned_at) VALUES ('BEAD-2', 'agent-b', 0);
+                 INSERT INTO claims (bead_id, agent_id, worktree_path, branch_name, start_commit, claimed_at)
+                 VALUES ('BEAD-2', 'agent-b', '/tmp/wt', 'bacchus/BEAD-2', 'abc', 0);",
+            )
+        })
+        .unwrap();
+
+        assert_eq!(assigned_agent("BEAD-1").unwrap().as_deref(), Some("agent-a"));
+        assert_eq!(assigned_agent("BEAD-3").unwrap(), None);
+
+        // BEAD-2 is claimed, so only BEAD-1 is still waiting
+        let list = list_claims().unwrap();
+        assert_eq!(list.assigned.len(), 1);
+        assert_eq!(list.assigned[0].bead_id, "BEAD-1");
+
+        clear_assignment("BEAD-1").unwrap();
+        assert_eq!(assigned_agent("BEAD-1").unwrap(), None);
+        assert!(list_claims().unwrap().assigned.is_empty());
+
+        close_db();
+    }
+}
@@ -0,0 +1,260 @@
+//! Audit log - records every CLI invocation for compliance
+//!
+//! Rows are inserted before a command runs and completed with its exit code
+//! and duration afterwards. Records older than `BACCHUS_AUDIT_RETENTION_DAYS`
+//! (default 90) are pruned on each invocation.
+//!
+//! Commands that run without initializing the database (`init`, `doctor`, ...)
+//! are recorded after they finish with `record_invocation`, if a migrated
+//! database already exists. Only `version` and `index --emit-symbols` are never
+//! recorded: they must not touch the database at all.
+//!
+//! State-changing tools also record operation rows (`operation` set, with an
+//! outcome and JSON details). These are the coordination history behind
+//! `bacchus log` and are never pruned.
+
+use crate::db::with_db;
+use rusqlite::{Connection, OpenFlags, OptionalExtension, Result};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const DEFAULT_RETENTION_DAYS: i64 = 90;
+const DEFAULT_LIMIT: u32 = 50;
+const MS_PER_DAY: i64 = 24 * 60 * 60 * 1000;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub id: i64,
+    pub timestamp_ms: i64,
+    pub command: String,
+    pub args: serde_json::Value,
+    pub bead_id: Option<String>,
+    pub agent_id: Option<String>,
+    /// NULL while the command is still running (or if it crashed)
+    pub exit_code: Option<i32>,
+    pub duration_ms: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AuditOutput {
+    pub entries: Vec<AuditEntry>,
+    pub total: usize,
+}
+
+fn now_ms() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as i64)
+        .unwrap_or(0)
+}
+
+/// Insert a pending audit row for a serialized command; returns the row ID
+///
+/// `args` is the externally tagged serde form of `Commands`, e.g.
+/// `{"claim": {"bead_id": "X", ...}}` or `"status"` for unit variants.
+pub fn start_audit(args: &serde_json::Value) -> Result<i64> {
+    with_db(|conn| insert_audit(conn, args))
+}
+
+/// Record a finished command straight into the database at `db_path`
+///
+/// For commands that run without the shared connection. The database must
+/// already exist with the audit table; it is never created or migrated here.
+pub fn record_invocation(db_path: &Path, args: &serde_json::Value, exit_code: i32, duration_ms: i64) -> Result<()> {
+    let conn = Connection::open_with_flags(db_path, OpenFlags::SQLITE_OPEN_READ_WRITE)?;
+    let id = insert_audit(&conn, args)?;
+    conn.execute(
+        "UPDATE audit_log SET exit_code = ?1, duration_ms = ?2 WHERE id = ?3",
+        rusqlite::params![exit_code, duration_ms, id],
+    )?;
+    Ok(())
+}
+
+fn insert_audit(conn: &Connection, args: &serde_json::Value) -> Result<i64> {
+    let (command, fields) = match args {
+        serde_json::Value::Object(map) if map.len() == 1 => {
+            let (name, fields) = map.iter().next().unwrap();
+            (name.clone(), Some(fields))
+        }
+        serde_json::Value::String(name) => (name.clone(), None),
+        other => (other.to_string(), None),
+    };
+
+    let field = |key: &str| {
+        fields
+            .and_then(|f| f.get(key))
+            .and_then(|v| v.as_str())
+            .map(String::from)
+    };
+
+    conn.execute(
+        "INSERT INTO audit_log (timestamp_ms, command, args_json, bead_id, agent_id) VALUES (?1, ?2, ?3, ?4, ?5)",
+        rusqlite::params![now_ms(), command, args.to_string(), field("bead_id"), field("agent_id")],
+    )?;
+    Ok(conn.last_insert_rowid())
+}
+
+/// Complete an audit row and prune expired records
+pub fn finish_audit(id: i64, exit_code: i32, duration_ms: i64) -> Result<()> {
+    let retention_days = std::env::var("BACCHUS_AUDIT_RETENTION_DAYS")
+        .ok()
+        .and_then(|v| v.parse::<i64>().ok())
+        .unwrap_or(DEFAULT_RETENTION_DAYS);
+
+    with_db(|conn| {
+        conn.execute(
+            "UPDATE audit_log SET exit_code = ?1, duration_ms = ?2 WHERE id = ?3",
+            rusqlite::params![exit_code, duration_ms, id],
+        )?;
+
+        // 0 or negative keeps records forever
+        if retention_days > 0 {
+            conn.execute(
+                "DELETE FROM audit_log WHERE timestamp_ms < ?1 AND operation IS NULL",
+                [now_ms() - retention_days * MS_PER_DAY],
+            )?;
+        }
+        Ok(())
+    })
+}
+
+/// Append an operation row; `details` is stored as JSON
+pub fn record_operation(
+    operation: &str,
+    bead_id: Option<&str>,
+    agent_id: Option<&str>,
+    outcome: &str,
+    details: Option<&serde_json::Value>,
+) -> Result<()> {
+    with_db(|conn| {
+        conn.execute(
+            "INSERT INTO audit_log (timestamp_ms, command, bead_id, agent_id, operation, outcome, details)
+             VALUES (?1, ?2, ?3, ?4, ?2, ?5, ?6)",
+            rusqlite::params![now_ms(), operation, bead_id, agent_id, outcome, details.map(|d| d.to_string())],
+        )
+    })?;
+    Ok(())
+}
+
+/// Record the result of a tool call, warning instead of failing
+///
+/// `summarize` maps a successful return to (succeeded, details); tools report
+/// refusals like "No claim found" as `Ok` with `success: false`.
+pub fn log_operation<T, E: std::fmt::Display>(
+    operation: &str,
+    bead_id: &str,
+    agent_id: Option<&str>,
+    result: &std::result::Result<T, E>,
+    summarize: impl FnOnce(&T) -> (bool, serde_json::Value),
+) {
+    let (outcome, details) = match result {
+        Ok(output) => {
+            let (succeeded, details) = summarize(output);
+            (if succeeded { "success" } else { "failure" }, details)
+        }
+        Err(e) => ("error", serde_json::json!({ "error": e.to_string() })),
+    };
+
+    if let Err(e) = record_operation(operation, Some(bead_id), agent_id, outcome, Some(&details)) {
+        eprintln!("Warning: failed to record {} of {} in audit log: {}", operation, bead_id, e);
+    }
+}
+
+/// Agent holding the claim on a bead, for operations that only take a bead ID
+pub fn claim_agent(bead_id: &str) -> Option<String> {
+    with_db(|conn| {
+        conn.query_row("SELECT agent_id FROM claims WHERE bead_id = ?1", [bead_id], |row| row.get(0))
+            .optional()
+    })
+    .ok()
+    .flatten()
+}
+
+/// Query the audit log, newest first
+pub fn query_audit_log(limit: Option<u32>, bead_id: Option<&str>, since_ms: Option<i64>) -> Result<AuditOutput> {
+    with_db(|conn| {
+        let mut stmt = conn.prepare(
+            "SELECT id, timestamp_ms, command, args_json, bead_id, agent_id, exit_code, duration_ms
+             FROM audit_log
+             WHERE operation IS NULL
+               AND (?1 IS NULL OR bead_id = ?1) AND (?2 IS NULL OR timestamp_ms >= ?2)
+             ORDER BY timestamp_ms DESC, id DESC
+             LIMIT ?3",
+        )?;
+
+        let entries: Vec<AuditEntry> = stmt
+            .query_map(
+                rusqlite::params![bead_id, since_ms, limit.unwrap_or(DEFAULT_LIMIT)],
+                |row| {
+                    let args: Option<String> = row.get(3)?;
+                    Ok(AuditEntry {
+                        id: row.get(0)?,
+                        timestamp_ms: row.get(1)?,
+                        command: row.get(2)?,
+                        args: args
+                            .and_then(|a| serde_json::from_str(&a).ok())
+                            .unwrap_or(serde_json::Value::Null),
+                        bead_id: row.get(4)?,
+                        agent_id: row.get(5)?,
+                        exit_code: row.get(6)?,
+                        duration_ms: row.get(7)?,
+                    })
+                },
+            )?
+            .filter_map(|r| r.ok())
+            .collect();
+
+        Ok(AuditOutput {
+            total: entries.len(),
+            entries,
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{close_db, init_db, test_lock};
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_audit_roundtrip() {
+        let _lock = test_lock();
+        let dir = tempdir().unwrap();
+        init_db(Some(dir.path().join("test.db").to_str().unwrap()), true).unwrap();
+
+        let args = serde_json::json!({"claim": {"bead_id": "BEAD-1", "agent_id": "agent-a", "force": false}});
+        let id = start_audit(&args).unwrap();
+        start_audit(&serde_json::json!("status")).unwrap();
+        finish_audit(id, 0, 12).unwrap();
+
+        let all = query_audit_log(None, None, None).unwrap();
+        assert_eq!(all.total, 2);
+
+        let claim = query_audit_log(None, Some("BEAD-1"), None).unwrap();
+        assert_eq!(claim.total, 1);
+        assert_eq!(claim.entries[0].command, "claim");
+        assert_eq!(claim.entries[0].agent_id.as_deref(), Some("agent-a"));
+        assert_eq!(claim.entries[0].exit_code, Some(0));
+        assert_eq!(claim.entries[0].duration_ms, Some(12));
+
+        // Old records are pruned
+        with_db(|conn| conn.execute("UPDATE audit_log SET timestamp_ms = 0", [])).unwrap();
+        finish_audit(id, 0, 12).unwrap();
+        assert_eq!(query_audit_log(None, None, None).unwrap().total, 0);
+
+        // ...but operation rows are kept, and don't show up as invocations
+        record_operation("claim", Some("BEAD-1"), Some("agent-a"), "success", None).unwrap();
+        with_db(|conn| conn.execute("UPDATE audit_log SET timestamp_ms = 0", [])).unwrap();
+        finish_audit(id, 0, 12).unwrap();
+        assert_eq!(query_audit_log(None, None, None).unwrap().total, 0);
+        let kept: i64 = with_db(|conn| {
+            conn.query_row("SELECT COUNT(*) FROM audit_log WHERE operation = 'claim'", [], |r| r.get(0))
+        })
+        .unwrap();
+        assert_eq!(kept, 1);
+
+        close_db();
+    }
+}
@@ -0,0 +1,204 @@
+//! Balance tool - move queued work from busy agents to idle ones
+//!
+//! An agent's load is its number of active claims. Only work nobody is doing
+//! right now is moved: assigned beads without a worktree (reassigned with
+//! `assign --reassign`) and paused beads (resumed for the new agent). Active
+//! claims are never touched. Beads go out by priority, each to the
+//! least-loaded agent still below the average load.
+
+use super::assign::assign_bead;
+use super::pause::resume_bead;
+use crate::beads;
+use crate::db::with_db;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BalanceSuggestion {
+    pub bead_id: String,
+    /// None when bd couldn't be asked
+    pub priority: Option<i32>,
+    /// `assigned` or `paused`
+    pub kind: String,
+    /// Current assignee, or the agent that paused the bead
+    pub from_agent: String,
+    pub to_agent: String,
+    pub command: String,
+    /// Outcome when applied (absent with --dry-run)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BalanceOutput {
+    pub dry_run: bool,
+    /// Active claims per known agent
+    pub agent_loads: BTreeMap<String, usize>,
+    pub average_load: f64,
+    pub suggestions: Vec<BalanceSuggestion>,
+    pub message: String,
+}
+
+/// Work that can move: a bead, its kind, and who holds it
+struct Movable {
+    bead_id: String,
+    priority: Option<i32>,
+    kind: &'static str,
+    holder: String,
+}
+
+pub fn balance(dry_run: bool) -> Result<BalanceOutput, Box<dyn std::error::Error>> {
+    let (claims, assigned, paused) = with_db(|conn| {
+        let mut stmt = conn.prepare("SELECT agent_id FROM claims")?;
+        let claims: Vec<String> = stmt.query_map([], |row| row.get(0))?.collect::<rusqlite::Result<_>>()?;
+        let mut stmt = conn.prepare(
+            "SELECT bead_id, agent_id FROM assignments WHERE bead_id NOT IN (SELECT bead_id FROM claims)",
+        )?;
+        let assigned: Vec<(String, String)> = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<rusqlite::Result<_>>()?;
+        let mut stmt = conn.prepare("SELECT bead_id, agent_id FROM paused_claims")?;
+        let paused: Vec<(String, String)> = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<rusqlite::Result<_>>()?;
+        Ok((claims, assigned, paused))
+    })?;
+
+    // Every agent bacchus knows of, loaded or not
+    let mut loads: BTreeMap<String, usize> = BTreeMap::new();
+    for agent in assigned.iter().chain(&paused).map(|(_, agent)| agent) {
+        loads.entry(agent.clone()).or_default();
+    }
+    for agent in claims {
+        *loads.entry(agent).or_default() += 1;
+    }
+
+    let priority = |bead_id: &str| beads::get_bead(bead_id).ok().map(|b| b.priority);
+    let movable: Vec<Movable> = assigned
+        .into_iter()
+        .map(|(bead_id, holder)| Movable { priority: priority(&bead_id), bead_id, kind: "assigned", holder })
+        .chain(
+            paused
+                .into_iter()
+                .map(|(bead_id, holder)| Movable { priority: priority(&bead_id), bead_id, kind: "paused", holder }),
+        )
+        .collect();
+
+    let average_load = if loads.is_empty() {
+        0.0
+    } else {
+        loads.values().sum::<usize>() as f64 / loads.len() as f64
+    };
+    let mut suggestions = plan_moves(&loads, movable);
+
+    if !dry_run {
+        for s in &mut suggestions {
+            let outcome = match s.kind.as_str() {
+                "assigned" => assign_bead(&s.bead_id, &s.to_agent, true).map(|r| r.message).map_err(|e| e.to_string()),
+                _ => resume_bead(&s.bead_id, &s.to_agent).map(|r| r.message).map_err(|e| e.to_string()),
+            };
+            s.result = Some(outcome.unwrap_or_else(|e| format!("Failed: {}", e)));
+        }
+    }
+
+    let message = match (suggestions.len(), dry_run) {
+        (0, _) => "Load is balanced; nothing to move".to_string(),
+        (n, true) => format!("{} suggested move(s); run without --dry-run to apply", n),
+        (n, false) => format!("Applied {} move(s); see each result", n),
+    };
+
+    Ok(BalanceOutput {
+        dry_run,
+        agent_loads: loads,
+        average_load,
+        suggestions,
+        message,
+    })
+}
+
+/// Hand movable beads, highest priority first, to agents below the average load
+///
+/// Each move counts toward the receiving agent's load. An assigned bead only
+/// moves to an agent less loaded than its assignee.
+fn plan_moves(loads: &BTreeMap<String, usize>, mut movable: Vec<Movable>) -> Vec<BalanceSuggestion> {
+    if loads.is_empty() {
+        return Vec::new();
+    }
+    let average = loads.values().sum::<usize>() as f64 / loads.len() as f64;
+    movable.sort_by(|a, b| {
+        (a.priority.is_none(), a.priority, &a.bead_id).cmp(&(b.priority.is_none(), b.priority, &b.bead_id))
+    });
+
+    let mut projected = loads.clone();
+    let mut suggestions = Vec::new();
+    for bead in movable {
+        // BTreeMap order breaks ties by agent name
+        let Some((target, load)) = projected
+            .iter()
+            .filter(|(agent, load)| **agent != bead.holder && (**load as f64) < average)
+            .min_by_key(|(_, load)| **load)
+            .map(|(agent, load)| (agent.clone(), *load))
+        else {
+            continue;
+        };
+        if bead.kind == "assigned" && load >= projected.get(&bead.holder).copied().unwrap_or(0) {
+            continue;
+        }
+
+        let command = match bead.kind {
+            "assigned" => format!("bacchus assign {} {} --reassign", bead.bead_id, target),
+            _ => format!("bacchus resume {} {}", bead.bead_id, target),
+        };
+        *projected.entry(target.clone()).or_default() += 1;
+        suggestions.push(BalanceSuggestion {
+            bead_id: bead.bead_id,
+            priority: bead.priority,
+            kind: bead.kind.to_string(),
+            from_agent: bead.holder,
+            to_agent: target,
+            command,
+            result: None,
+        });
+    }
+    suggestions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn movable(bead_id: &str, priority: i32, kind: &'static str, holder: &str) -> Movable {
+        Movable {
+            bead_id: bead_id.to_string(),
+            priority: Some(priority),
+            kind,
+            holder: holder.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_plan_moves() {
+        let loads: BTreeMap<String, usize> =
+            [("busy", 4), ("idle", 0), ("light", 1)].into_iter().map(|(a, n)| (a.to_string(), n)).collect();
+        let suggestions = plan_moves(
+            &loads,
+            vec![
+                movable("b-low", 3, "assigned", "busy"),
+                movable("b-high", 1, "assigned", "busy"),
+                movable("p-1", 2, "paused", "busy"),
+                movable("b-light", 1, "assigned", "light"),
+            ],
+        );
+
+        let moves: Vec<(&str, &str)> =
+            suggestions.iter().map(|s| (s.bead_id.as_str(), s.to_agent.as_str())).collect();
+        // Average is 5/3. b-light stays: nobody below average is less loaded than light,
+        // and ties go to the first agent by name
+        assert_eq!(moves, [("b-high", "idle"), ("p-1", "idle"), ("b-low", "light")]);
+        assert_eq!(suggestions[0].command, "bacchus assign b-high idle --reassign");
+        assert_eq!(suggestions[1].command, "bacchus resume p-1 idle");
+
+        let even: BTreeMap<String, usize> = [("a".to_string(), 1), ("b".to_string(), 1)].into_iter().collect();
+        assert!(plan_moves(&even, vec![movable("x", 1, "assigned", "a")]).is_empty());
+    }
+}
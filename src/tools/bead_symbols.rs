@@ -0,0 +1,45 @@
+//! Bead symbol registration - records which symbols a bead modifies or creates
+
+use crate::db::with_db;
+use rusqlite::Result;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RegisterBeadSymbolOutput {
+    pub success: bool,
+    pub bead_id: String,
+    pub fq_name: String,
+    pub relation: String,
+    pub message: String,
+}
+
+/// Link a symbol to a bead (relation: modifies or creates)
+pub fn register_bead_symbol(bead_id: &str, fq_name: &str, relation: &str) -> Result<RegisterBeadSymbolOutput> {
+    let inserted = with_db(|conn| {
+        conn.execute(
+            "INSERT OR IGNORE INTO bead_symbols (bead_id, symbol_fq_name, relation) VALUES (?1, ?2, ?3)",
+            [bead_id, fq_name, relation],
+        )
+    })?;
+
+    let message = if inserted > 0 {
+        format!("Registered {} as {} by {}", fq_name, relation_past(relation), bead_id)
+    } else {
+        format!("{} already registered to {} ({})", fq_name, bead_id, relation)
+    };
+
+    Ok(RegisterBeadSymbolOutput {
+        success: inserted > 0,
+        bead_id: bead_id.to_string(),
+        fq_name: fq_name.to_string(),
+        relation: relation.to_string(),
+        message,
+    })
+}
+
+fn relation_past(relation: &str) -> &str {
+    match relation {
+        "creates" => "created",
+        _ => "modified",
+    }
+}
@@ -0,0 +1,182 @@
+//! Blame tool - when a symbol's body last changed
+//!
+//! The indexer records every body hash it replaces in `symbol_history`, along
+//! with the time and commit of the run that saw the new body. The change is
+//! attributed to the last merged release in the audit log between the previous
+//! change and that index run, preferring beads that registered the symbol.
+
+use crate::db::with_db;
+use rusqlite::{OptionalExtension, Result};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SymbolChange {
+    /// Body hash before the change
+    pub previous_hash: String,
+    pub file: String,
+    pub changed_at: Option<String>,
+    /// HEAD of the index run that picked up the change (None outside git)
+    pub commit: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BlameOutput {
+    pub fq_name: String,
+    /// Current body hash (None if the symbol is no longer indexed)
+    pub hash: Option<String>,
+    pub file: Option<String>,
+    pub last_changed: Option<SymbolChange>,
+    /// All recorded changes, newest first
+    pub history: Vec<SymbolChange>,
+    /// `release` (attributed to a bead), `reindex` (changed, no release found),
+    /// `first_indexed` (no recorded change), or `unknown` (not indexed)
+    pub source: String,
+    pub last_modified_bead: Option<String>,
+    pub last_modified_agent: Option<String>,
+    pub message: String,
+}
+
+pub fn blame_symbol(fq_name: &str) -> Result<BlameOutput> {
+    with_db(|conn| {
+        let current: Option<(String, String)> = conn
+            .query_row(
+                "SELECT hash, file FROM symbols WHERE fq_name = ?1",
+                [fq_name],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()?;
+
+        let mut stmt = conn.prepare(
+            "SELECT hash, file, indexed_at, commit_hash FROM symbol_history
+             WHERE symbol_fq_name = ?1 ORDER BY indexed_at DESC, id DESC",
+        )?;
+        let (indexed_at, history): (Vec<i64>, Vec<SymbolChange>) = stmt
+            .query_map([fq_name], |row| {
+                let indexed_at: i64 = row.get(2)?;
+                Ok((
+                    indexed_at,
+                    SymbolChange {
+                        previous_hash: row.get(0)?,
+                        file: row.get(1)?,
+                        changed_at: chrono::DateTime::from_timestamp_millis(indexed_at).map(|d| d.to_rfc3339()),
+                        commit: row.get(3)?,
+                    },
+                ))
+            })?
+            .collect::<Result<Vec<_>>>()?
+            .into_iter()
+            .unzip();
+
+        // Window for the latest change: after the one before it, up to its index run
+        let release: Option<(String, Option<String>)> = match indexed_at.first() {
+            Some(&until) => conn
+                .query_row(
+                    "SELECT bead_id, agent_id FROM audit_log
+                     WHERE operation = 'release' AND outcome = 'success' AND bead_id IS NOT NULL
+                       AND json_extract(details, '$.merged') = 1
+                       AND timestamp_ms > ?2 AND timestamp_ms <= ?3
+                     ORDER BY EXISTS(SELECT 1 FROM bead_symbols bs
+                                     WHERE bs.bead_id = audit_log.bead_id AND bs.symbol_fq_name = ?1) DESC,
+                              timestamp_ms DESC
+                     LIMIT 1",
+                    rusqlite::params![fq_name, indexed_at.get(1).copied().unwrap_or(0), until],
+                    |row| Ok((row.get(0)?, row.get(1)?)),
+                )
+                .optional()?,
+            None => None,
+        };
+
+        let source = match (&current, history.is_empty(), &release) {
+            (_, false, Some(_)) => "release",
+            (_, false, None) => "reindex",
+            (Some(_), true, _) => "first_indexed",
+            (None, true, _) => "unknown",
+        };
+
+        let message = match (&current, history.first()) {
+            (None, None) => format!("No symbol or history found for {}", fq_name),
+            (Some(_), None) => format!("{} has not changed since it was first indexed", fq_name),
+            (_, Some(change)) => format!(
+                "{} last changed at {}{}{}",
+                fq_name,
+                change.changed_at.as_deref().unwrap_or("unknown time"),
+                change.commit.as_deref().map(|c| format!(" (commit {})", c)).unwrap_or_default(),
+                release
+                    .as_ref()
+                    .map(|(bead, agent)| match agent {
+                        Some(agent) => format!(" by {} ({})", bead, agent),
+                        None => format!(" by {}", bead),
+                    })
+                    .unwrap_or_default()
+            ),
+        };
+        let (last_modified_bead, last_modified_agent) = release.unzip();
+
+        let (hash, file) = current.unzip();
+        Ok(BlameOutput {
+            fq_name: fq_name.to_string(),
+            hash,
+            file,
+            last_changed: history.first().cloned(),
+            history,
+            source: source.to_string(),
+            last_modified_bead,
+            last_modified_agent: last_modified_agent.flatten(),
+            message,
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{close_db, init_db, test_lock};
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_blame_attribution() {
+        let _lock = test_lock();
+        let dir = tempdir().unwrap();
+        init_db(Some(dir.path().join("test.db").to_str().unwrap()), true).unwrap();
+
+        with_db(|conn| {
+            conn.execute_batch(
+                "INSERT INTO symbols (file, fq_name, kind, span_start_line, span_end_line, line_count, hash)
+                 VALUES ('src/a.rs', 'src/a.rs::f', 'function', 1, 3, 3, 'h2');",
+            )
+        })
+        .unwrap();
+        let blame = blame_symbol("src/a.rs::f").unwrap();
+        assert_eq!(blame.source, "first_indexed");
+        assert!(blame.last_modified_bead.is_none());
+        assert_eq!(blame_symbol("src/a.rs::nope").unwrap().source, "unknown");
+
+        with_db(|conn| {
+            conn.execute_batch(
+                "INSERT INTO symbol_history (symbol_fq_name, hash, file, indexed_at) VALUES ('src/a.rs::f', 'h1', 'src/a.rs', 1000);
+                 INSERT INTO bead_symbols (bead_id, symbol_fq_name, relation) VALUES ('bead-a', 'src/a.rs::f', 'modifies');
+                 INSERT INTO audit_log (timestamp_ms, command, bead_id, agent_id, operation, outcome, details) VALUES
+                   (500, 'release', 'bead-a', 'agent-a', 'release', 'success', '{\"merged\":true}'),
+                   (700, 'release', 'bead-b', 'agent-b', 'release', 'success', '{\"merged\":true}'),
+                   (800, 'release', 'bead-c', 'agent-c', 'release', 'success', '{\"merged\":false}'),
+                   (1500, 'release', 'bead-d', 'agent-d', 'release', 'success', '{\"merged\":true}');",
+            )
+        })
+        .unwrap();
+
+        // bead-a registered the symbol, so it wins over the later bead-b
+        let blame = blame_symbol("src/a.rs::f").unwrap();
+        assert_eq!(blame.source, "release");
+        assert_eq!(blame.last_modified_bead.as_deref(), Some("bead-a"));
+        assert_eq!(blame.last_modified_agent.as_deref(), Some("agent-a"));
+        assert_eq!(blame.last_changed.unwrap().previous_hash, "h1");
+
+        with_db(|conn| conn.execute("DELETE FROM bead_symbols", [])).unwrap();
+        assert_eq!(blame_symbol("src/a.rs::f").unwrap().last_modified_bead.as_deref(), Some("bead-b"));
+
+        with_db(|conn| conn.execute("DELETE FROM audit_log WHERE bead_id IN ('bead-a', 'bead-b')", [])).unwrap();
+        assert_eq!(blame_symbol("src/a.rs::f").unwrap().source, "reindex");
+
+        close_db();
+    }
+}
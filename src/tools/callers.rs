@@ -0,0 +1,161 @@
+//! Callers tool - walk the symbol call graph backwards
+//!
+//! Call edges are written by the indexer into `symbol_calls`. Callee names are
+//! resolved to fully-qualified names at index time (see `CalleeResolver`).
+
+use crate::db::with_db;
+use rusqlite::{Connection, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CallerInfo {
+    pub fq_name: String,
+    /// The symbol this caller calls (the queried symbol at depth 1)
+    pub calls: String,
+    pub call_site_file: String,
+    pub call_site_line: i64,
+    pub depth: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CallersOutput {
+    pub fq_name: String,
+    pub callers: Vec<CallerInfo>,
+    pub total: usize,
+}
+
+/// Maps bare symbol names to the fully-qualified names that define them
+pub struct CalleeResolver {
+    by_name: HashMap<String, Vec<(String, String)>>,
+}
+
+impl CalleeResolver {
+    /// Load every indexed symbol's (file, fq_name), keyed by its last path segment
+    pub fn load(conn: &Connection) -> Result<Self> {
+        let mut stmt = conn.prepare("SELECT file, fq_name FROM symbols")?;
+        let rows = stmt.query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))?;
+
+        let mut by_name: HashMap<String, Vec<(String, String)>> = HashMap::new();
+        for row in rows {
+            let (file, fq_name) = row?;
+            let name = fq_name.rsplit("::").next().unwrap_or(&fq_name).to_string();
+            by_name.entry(name).or_default().push((file, fq_name));
+        }
+        Ok(CalleeResolver { by_name })
+    }
+
+    /// A definition in the calling file wins; otherwise the name must be unique
+    /// across the index. Ambiguous or unknown callees keep their bare name.
+    pub fn resolve(&self, caller_file: &str, callee: &str) -> String {
+        let Some(candidates) = self.by_name.get(callee) else {
+            return callee.to_string();
+        };
+
+        if let Some((_, fq_name)) = candidates.iter().find(|(file, _)| file == caller_file) {
+            return fq_name.clone();
+        }
+        match candidates.as_slice() {
+            [(_, fq_name)] => fq_name.clone(),
+            _ => callee.to_string(),
+        }
+    }
+}
+
+/// Direct and transitive callers of `fq_name`, up to `depth` levels
+pub fn find_callers(fq_name: &str, depth: u32) -> Result<CallersOutput> {
+    with_db(|conn| {
+        let mut stmt = conn.prepare(
+            "SELECT s.fq_name, c.call_site_file, c.call_site_line
+             FROM symbol_calls c JOIN symbols s ON s.id = c.caller_symbol_id
+             WHERE c.callee_fq_name = ?1
+             ORDER BY s.fq_name, c.call_site_line",
+        )?;
+
+        let mut callers = Vec::new();
+        let mut seen: HashSet<String> = HashSet::from([fq_name.to_string()]);
+        let mut frontier = vec![fq_name.to_string()];
+
+        for level in 1..=depth {
+            let mut next = Vec::new();
+            for callee in &frontier {
+                let rows = stmt.query_map([callee], |row| {
+                    Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?, row.get::<_, i64>(2)?))
+                })?;
+                for row in rows {
+                    let (caller, file, line) = row?;
+                    callers.push(CallerInfo {
+                        fq_name: caller.clone(),
+                        calls: callee.clone(),
+                        call_site_file: file,
+                        call_site_line: line,
+                        depth: level,
+                    });
+                    // Recursion and cycles: only expand each caller once
+                    if seen.insert(caller.clone()) {
+                        next.push(caller);
+                    }
+                }
+            }
+            if next.is_empty() {
+                break;
+            }
+            frontier = next;
+        }
+
+        Ok(CallersOutput {
+            fq_name: fq_name.to_string(),
+            total: callers.len(),
+            callers,
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{close_db, init_db, test_lock};
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_find_callers() {
+        let _lock = test_lock();
+        let dir = tempdir().unwrap();
+        init_db(Some(dir.path().join("test.db").to_str().unwrap()), true).unwrap();
+
+        with_db(|conn| {
+            for (id, fq_name) in [(1, "a.rs::main"), (2, "a.rs::run"), (3, "b.rs::save"), (4, "b.rs::retry")] {
+                conn.execute(
+                    "INSERT INTO symbols (id, file, fq_name, kind, span_start_line, span_end_line, line_count, hash, language)
+                     VALUES (?1, ?2, ?3, 'function', 1, 1, 1, 'h', 'rust')",
+                    rusqlite::params![id, fq_name.split("::").next().unwrap(), fq_name],
+                )?;
+            }
+            // main -> run -> save, retry -> save, retry -> retry
+            for (caller, callee, line) in [(1, "a.rs::run", 2), (2, "b.rs::save", 5), (4, "b.rs::save", 9), (4, "b.rs::retry", 10)] {
+                conn.execute(
+                    "INSERT INTO symbol_calls (caller_symbol_id, callee_fq_name, call_site_file, call_site_line)
+                     VALUES (?1, ?2, 'x', ?3)",
+                    rusqlite::params![caller, callee, line],
+                )?;
+            }
+            Ok(())
+        })
+        .unwrap();
+
+        let direct = find_callers("b.rs::save", 1).unwrap();
+        let names: Vec<&str> = direct.callers.iter().map(|c| c.fq_name.as_str()).collect();
+        assert_eq!(names, vec!["a.rs::run", "b.rs::retry"]);
+
+        let all = find_callers("b.rs::save", 5).unwrap();
+        assert_eq!(all.total, 4);
+        let main = all.callers.iter().find(|c| c.fq_name == "a.rs::main").unwrap();
+        assert_eq!((main.depth, main.calls.as_str()), (2, "a.rs::run"));
+
+        let resolver = with_db(CalleeResolver::load).unwrap();
+        assert_eq!(resolver.resolve("a.rs", "save"), "b.rs::save");
+        assert_eq!(resolver.resolve("a.rs", "unknown"), "unknown");
+
+        close_db();
+    }
+}
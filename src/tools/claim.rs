@@ -3,12 +3,17 @@
 //! Unlike `next`, this claims a specific bead rather than the next ready one.
 //! By default, only claims ready beads (open, no blockers). Use --force to override.
 
+use super::assign::{assigned_agent, clear_assignment};
+use super::audit::log_operation;
+use super::claim_events::log_claim_event;
+use super::next::next_task;
 use crate::beads;
+use crate::config::resolve_target_branch;
 use crate::db::with_db;
 use crate::worktree;
 use rusqlite::Result;
 use serde::{Deserialize, Serialize};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::time::{SystemTime, UNIX_EPOCH};
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -22,7 +27,27 @@ pub struct ClaimOutput {
     pub message: String,
 }
 
-pub fn claim_task(bead_id: &str, agent_id: &str, force: bool, workspace_root: &Path) -> Result<ClaimOutput> {
+pub fn claim_task(
+    bead_id: &str,
+    agent_id: &str,
+    force: bool,
+    target_branch: Option<&str>,
+    workspace_root: &Path,
+) -> Result<ClaimOutput> {
+    let result = try_claim_task(bead_id, agent_id, force, target_branch, workspace_root);
+    log_operation("claim", bead_id, Some(agent_id), &result, |r| {
+        (r.success, serde_json::json!({ "message": r.message }))
+    });
+    result
+}
+
+fn try_claim_task(
+    bead_id: &str,
+    agent_id: &str,
+    force: bool,
+    target_branch: Option<&str>,
+    workspace_root: &Path,
+) -> Result<ClaimOutput> {
     // 1. Get bead details from beads DB
     let bead = beads::get_bead(bead_id).map_err(|e| {
         rusqlite::Error::SqliteFailure(
@@ -44,8 +69,22 @@ pub fn claim_task(bead_id: &str, agent_id: &str, force: bool, workspace_root: &P
         });
     }
 
-    // 3. Check if bead is ready (unless --force)
-    if !force {
+    // 3. Check if bead is ready (unless --force); a bead assigned to this agent
+    //    is ready for it, one assigned to someone else isn't
+    let assignee = assigned_agent(bead_id)?;
+    if let Some(assignee) = assignee.as_deref().filter(|a| *a != agent_id && !force) {
+        return Ok(ClaimOutput {
+            success: false,
+            bead_id: bead_id.to_string(),
+            title: Some(bead.title),
+            description: bead.description,
+            worktree_path: None,
+            branch: None,
+            message: format!("Bead {} is assigned to {}. Use --force to override.", bead_id, assignee),
+        });
+    }
+
+    if !force && assignee.is_none() {
         let is_ready = beads::is_bead_ready(bead_id).map_err(|e| {
             rusqlite::Error::SqliteFailure(
                 rusqlite::ffi::Error::new(1),
@@ -106,16 +145,19 @@ pub fn claim_task(bead_id: &str, agent_id: &str, force: bool, workspace_root: &P
         .map(|d| d.as_millis() as i64)
         .unwrap_or(0);
 
+    let target_branch = resolve_target_branch(target_branch, workspace_root);
     let claim_result = with_db(|conn| {
         conn.execute(
-            "INSERT INTO claims (bead_id, agent_id, worktree_path, branch_name, start_commit, claimed_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            "INSERT INTO claims (bead_id, agent_id, worktree_path, branch_name, start_commit, claimed_at, target_branch, title) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
             rusqlite::params![
                 bead_id,
                 agent_id,
                 wt.path.to_string_lossy().to_string(),
                 &wt.branch,
                 &wt.head_commit,
-                now
+                now,
+                target_branch,
+                &bead.title
             ],
         )
     });
@@ -139,6 +181,11 @@ pub fn claim_task(bead_id: &str, agent_id: &str, force: bool, workspace_root: &P
         ));
     }
 
+    log_claim_event(bead_id, Some(agent_id), "claimed", None);
+    if let Err(e) = clear_assignment(bead_id) {
+        eprintln!("Warning: failed to clear assignment of {}: {}", bead_id, e);
+    }
+
     Ok(ClaimOutput {
         success: true,
         bead_id: bead_id.to_string(),
@@ -149,3 +196,158 @@ pub fn claim_task(bead_id: &str, agent_id: &str, force: bool, workspace_root: &P
         message: format!("Claimed {} - work in {}", bead_id, wt.path.display()),
     })
 }
+
+/// Bulk claim spec file: `{ "claims": [{ "bead_id": "BEAD-1", "agent_id": "agent-a" }, ...] }`
+#[derive(Debug, Deserialize)]
+pub struct ClaimSpec {
+    pub claims: Vec<ClaimSpecEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ClaimSpecEntry {
+    /// Bead to claim; `null` claims the next ready bead
+    pub bead_id: Option<String>,
+    /// Agent to claim for; defaults to the command's agent ID
+    pub agent_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BulkClaimResult {
+    pub bead_id: Option<String>,
+    pub agent_id: String,
+    pub worktree_path: Option<String>,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ClaimFromFileOutput {
+    pub succeeded: Vec<BulkClaimResult>,
+    pub failed: Vec<BulkClaimResult>,
+    pub skipped_already_claimed: Vec<BulkClaimResult>,
+}
+
+/// Claim every entry in a spec file, collecting per-entry results
+pub fn claim_from_file(file: &PathBuf, agent_id: &str, workspace_root: &Path) -> Result<ClaimFromFileOutput> {
+    let spec_error = |msg: String| rusqlite::Error::SqliteFailure(rusqlite::ffi::Error::new(1), Some(msg));
+
+    let content = std::fs::read_to_string(file)
+        .map_err(|e| spec_error(format!("Failed to read {}: {}", file.display(), e)))?;
+    let spec: ClaimSpec = serde_json::from_str(&content)
+        .map_err(|e| spec_error(format!("Invalid claim spec {}: {}", file.display(), e)))?;
+
+    let mut output = ClaimFromFileOutput {
+        succeeded: Vec::new(),
+        failed: Vec::new(),
+        skipped_already_claimed: Vec::new(),
+    };
+
+    for entry in spec.claims {
+        let agent = entry.agent_id.unwrap_or_else(|| agent_id.to_string());
+
+        let Some(bead_id) = entry.bead_id else {
+            match next_task(&agent, &[], None, workspace_root) {
+                Ok(r) => {
+                    let success = r.success;
+                    let result = BulkClaimResult {
+                        bead_id: r.bead_id,
+                        agent_id: agent,
+                        worktree_path: r.worktree_path,
+                        message: r.message,
+                    };
+                    if success {
+                        output.succeeded.push(result);
+                    } else {
+                        output.failed.push(result);
+                    }
+                }
+                Err(e) => output.failed.push(BulkClaimResult {
+                    bead_id: None,
+                    agent_id: agent,
+                    worktree_path: None,
+                    message: e.to_string(),
+                }),
+            }
+            continue;
+        };
+
+        if is_claimed(&bead_id)? {
+            output.skipped_already_claimed.push(BulkClaimResult {
+                message: format!("Bead {} is already claimed", bead_id),
+                bead_id: Some(bead_id),
+                agent_id: agent,
+                worktree_path: None,
+            });
+            continue;
+        }
+
+        match claim_task(&bead_id, &agent, false, None, workspace_root) {
+            Ok(r) => {
+                let result = BulkClaimResult {
+                    bead_id: Some(r.bead_id),
+                    agent_id: agent,
+                    worktree_path: r.worktree_path,
+                    message: r.message,
+                };
+                if r.success {
+                    output.succeeded.push(result);
+                } else {
+                    output.failed.push(result);
+                }
+            }
+            Err(e) => output.failed.push(BulkClaimResult {
+                bead_id: Some(bead_id),
+                agent_id: agent,
+                worktree_path: None,
+                message: e.to_string(),
+            }),
+        }
+    }
+
+    Ok(output)
+}
+
+fn is_claimed(bead_id: &str) -> Result<bool> {
+    with_db(|conn| {
+        Ok(conn
+            .query_row("SELECT 1 FROM claims WHERE bead_id = ?1", [bead_id], |_| Ok(true))
+            .unwrap_or(false))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{close_db, init_db, test_lock};
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_claim_from_file_skips_claimed() {
+        let _lock = test_lock();
+        let dir = tempdir().unwrap();
+        let db_path = dir.path().join("test.db");
+        init_db(Some(db_path.to_str().unwrap()), true).unwrap();
+
+        with_db(|conn| {
+            conn.execute(
+                "INSERT INTO claims (bead_id, agent_id, worktree_path, branch_name, start_commit, claimed_at)
+                 VALUES ('BEAD-1', 'agent-a', '/tmp/wt', 'bacchus/BEAD-1', 'abc', 0)",
+                [],
+            )
+        })
+        .unwrap();
+
+        let spec = dir.path().join("claims.json");
+        std::fs::write(&spec, r#"{ "claims": [{ "bead_id": "BEAD-1" }] }"#).unwrap();
+
+        let output = claim_from_file(&spec, "agent-b", dir.path()).unwrap();
+        assert!(output.succeeded.is_empty());
+        assert!(output.failed.is_empty());
+        assert_eq!(output.skipped_already_claimed.len(), 1);
+        assert_eq!(output.skipped_already_claimed[0].agent_id, "agent-b");
+
+        std::fs::write(&spec, r#"{ "entries": [] }"#).unwrap();
+        assert!(claim_from_file(&spec, "agent-b", dir.path()).is_err());
+
+        close_db();
+    }
+}
@@ -0,0 +1,38 @@
+//! Claim events - lifecycle history for claimed beads
+
+use crate::db::with_db;
+use rusqlite::Result;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Append an event for a bead; `details` is stored as JSON
+pub fn record_claim_event(
+    bead_id: &str,
+    agent_id: Option<&str>,
+    event_type: &str,
+    details: Option<&serde_json::Value>,
+) -> Result<()> {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as i64)
+        .unwrap_or(0);
+
+    with_db(|conn| {
+        conn.execute(
+            "INSERT INTO claim_events (bead_id, agent_id, event_type, details, created_at) VALUES (?1, ?2, ?3, ?4, ?5)",
+            rusqlite::params![bead_id, agent_id, event_type, details.map(|d| d.to_string()), now],
+        )
+    })?;
+    Ok(())
+}
+
+/// Record an event, warning instead of failing; events are history, not state
+pub fn log_claim_event(
+    bead_id: &str,
+    agent_id: Option<&str>,
+    event_type: &str,
+    details: Option<&serde_json::Value>,
+) {
+    if let Err(e) = record_claim_event(bead_id, agent_id, event_type, details) {
+        eprintln!("Warning: failed to record {} event for {}: {}", event_type, bead_id, e);
+    }
+}
@@ -0,0 +1,148 @@
+//! Clean tool - remove orphaned worktrees and broken claims in one pass
+//!
+//! The cleanup counterpart of the `orphaned_worktrees` and `broken_claims`
+//! reported by `bacchus status`. Branches of orphaned worktrees are kept, since
+//! they may hold the only copy of committed work. Unclaimed worktrees can still
+//! be wanted - `release --status blocked` keeps them for resuming, and
+//! `create-worktree --no-claim` makes them - so without `force`, worktrees of
+//! blocked beads and worktrees with uncommitted changes are skipped.
+
+use super::audit::record_operation;
+use super::pause::paused_claims;
+use crate::beads;
+use crate::db::with_db;
+use crate::worktree;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CleanOutput {
+    pub dry_run: bool,
+    /// Stale git worktree records (`worktrees/<name>: <reason>`)
+    pub pruned_worktree_refs: Vec<String>,
+    /// Worktree directories with no claim (removed unless `dry_run`)
+    pub orphaned_worktrees: Vec<String>,
+    /// Unclaimed worktrees left in place (`<name>: <reason>`); `--force` removes them
+    pub skipped_worktrees: Vec<String>,
+    /// Claims whose worktree no longer exists (their beads go back to open)
+    pub broken_claims: Vec<String>,
+    pub message: String,
+}
+
+pub fn clean_workspace(
+    workspace_root: &Path,
+    dry_run: bool,
+    force: bool,
+) -> Result<CleanOutput, Box<dyn std::error::Error>> {
+    let claims: Vec<(String, String, PathBuf)> = with_db(|conn| {
+        let mut stmt = conn.prepare("SELECT bead_id, agent_id, worktree_path FROM claims")?;
+        let rows = stmt.query_map([], |row| {
+            Ok((row.get(0)?, row.get(1)?, PathBuf::from(row.get::<_, String>(2)?)))
+        })?;
+        rows.collect()
+    })?;
+
+    // Broken claims are found before pruning so their git records count as stale too
+    let broken: Vec<&(String, String, PathBuf)> = claims.iter().filter(|(_, _, path)| !path.exists()).collect();
+    // Paused beads keep their worktrees for `resume`
+    let paused = with_db(paused_claims)?;
+    let claimed: HashSet<PathBuf> = claims
+        .iter()
+        .map(|(_, _, path)| canonical(path))
+        .chain(paused.iter().map(|(_, path)| canonical(Path::new(path))))
+        .collect();
+
+    let mut unclaimed = Vec::new();
+    let worktrees_dir = worktree::get_worktrees_dir(workspace_root);
+    if let Ok(entries) = std::fs::read_dir(&worktrees_dir) {
+        for entry in entries.filter_map(|e| e.ok()) {
+            let path = entry.path();
+            if path.is_dir() && !claimed.contains(&canonical(&path)) {
+                unclaimed.push(entry.file_name().to_string_lossy().to_string());
+            }
+        }
+    }
+    unclaimed.sort();
+
+    let mut orphaned = Vec::new();
+    let mut skipped = Vec::new();
+    for name in unclaimed {
+        let path = worktrees_dir.join(&name);
+        // Registered worktrees have a `.git` file; leftovers are plain directories
+        let registered = path.join(".git").exists();
+        if !force {
+            if beads::get_bead(&name).is_ok_and(|b| b.status == "blocked") {
+                skipped.push(format!("{}: bead is blocked", name));
+                continue;
+            }
+            if registered && has_uncommitted_changes(&path) {
+                skipped.push(format!("{}: uncommitted changes", name));
+                continue;
+            }
+        }
+        if !dry_run {
+            if registered {
+                if let Err(e) = worktree::remove_worktree_dir(workspace_root, &name, force) {
+                    skipped.push(format!("{}: {}", name, e.to_string().trim()));
+                    continue;
+                }
+            } else {
+                std::fs::remove_dir_all(&path)?;
+            }
+        }
+        orphaned.push(name);
+    }
+
+    let pruned_worktree_refs = worktree::prune_worktrees(workspace_root, dry_run)?;
+
+    if !dry_run {
+        for (bead_id, agent_id, path) in &broken {
+            with_db(|conn| conn.execute("DELETE FROM claims WHERE bead_id = ?1", [bead_id]))?;
+            if let Err(e) = beads::update_bead_status(bead_id, "open") {
+                eprintln!("Warning: Failed to reset bead status for {}: {}", bead_id, e);
+            }
+            let details = serde_json::json!({ "missing_worktree": path });
+            if let Err(e) = record_operation("clean", Some(bead_id), Some(agent_id), "success", Some(&details)) {
+                eprintln!("Warning: failed to record clean of {} in audit log: {}", bead_id, e);
+            }
+        }
+    }
+
+    let broken_claims: Vec<String> = broken.iter().map(|(bead_id, _, _)| bead_id.clone()).collect();
+    let mut summary = format!(
+        "{} orphaned worktree(s), {} broken claim(s), {} stale git worktree record(s)",
+        orphaned.len(),
+        broken_claims.len(),
+        pruned_worktree_refs.len()
+    );
+    if !skipped.is_empty() {
+        summary.push_str(&format!("; skipped {} worktree(s) (use --force to remove)", skipped.len()));
+    }
+
+    Ok(CleanOutput {
+        dry_run,
+        pruned_worktree_refs,
+        orphaned_worktrees: orphaned,
+        skipped_worktrees: skipped,
+        broken_claims,
+        message: if dry_run {
+            format!("Would clean {}", summary)
+        } else {
+            format!("Cleaned {}", summary)
+        },
+    })
+}
+
+fn has_uncommitted_changes(worktree_path: &Path) -> bool {
+    Command::new("git")
+        .args(["status", "--porcelain"])
+        .current_dir(worktree_path)
+        .output()
+        .is_ok_and(|o| !o.status.success() || !o.stdout.is_empty())
+}
+
+fn canonical(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
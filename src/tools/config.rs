@@ -0,0 +1,136 @@
+//! Config tool - read and write `.bacchus/config.toml` from the command line
+//!
+//! `get` reports the effective value (the file merged over defaults); `set`
+//! edits the file in place so comments and layout survive, and refuses
+//! values that would make the file fail to load.
+
+use crate::config::{config_path, load_config, Config, CONFIG_KEYS, MERGE_STRATEGIES};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ConfigOutput {
+    pub success: bool,
+    pub key: String,
+    /// Effective value after the command (null when unset)
+    pub value: serde_json::Value,
+    pub path: String,
+    pub message: String,
+}
+
+/// Run `get` or `set` for `key`
+pub fn config_command(
+    action: &str,
+    key: &str,
+    value: Option<&str>,
+    workspace_root: &Path,
+) -> Result<ConfigOutput, String> {
+    if !CONFIG_KEYS.contains(&key) {
+        return Err(format!("Unknown config key: {}. Known keys: {}", key, CONFIG_KEYS.join(", ")));
+    }
+
+    let message = match (action, value) {
+        ("get", _) => format!("{} from {}", key, config_path(workspace_root).display()),
+        ("set", Some(value)) => {
+            set_value(key, value, workspace_root)?;
+            format!("Set {} in {}", key, config_path(workspace_root).display())
+        }
+        ("set", None) => return Err(format!("config set {} needs a value", key)),
+        _ => return Err(format!("Unknown action: {}. Use get or set", action)),
+    };
+
+    Ok(ConfigOutput {
+        success: true,
+        key: key.to_string(),
+        value: effective_value(&load_config(workspace_root), key),
+        path: config_path(workspace_root).to_string_lossy().to_string(),
+        message,
+    })
+}
+
+fn effective_value(config: &Config, key: &str) -> serde_json::Value {
+    let config = serde_json::to_value(config).unwrap_or_default();
+    key.split('.')
+        .try_fold(&config, |value, part| value.get(part))
+        .cloned()
+        .unwrap_or(serde_json::Value::Null)
+}
+
+fn set_value(key: &str, raw: &str, workspace_root: &Path) -> Result<(), String> {
+    let path = config_path(workspace_root);
+    let content = std::fs::read_to_string(&path).unwrap_or_default();
+    let mut doc: toml_edit::DocumentMut =
+        content.parse().map_err(|e| format!("Failed to parse {}: {}", path.display(), e))?;
+
+    // TOML literals (numbers, booleans, arrays, quoted strings) as written, anything else as a string
+    let value = raw.parse::<toml_edit::Value>().unwrap_or_else(|_| raw.into());
+
+    let (table, field) = match key.split_once('.') {
+        Some((table, field)) => {
+            let item = doc.entry(table).or_insert_with(toml_edit::table);
+            let table = item
+                .as_table_like_mut()
+                .ok_or_else(|| format!("{} in {} is not a table", table, path.display()))?;
+            (table, field)
+        }
+        None => (doc.as_table_mut() as &mut dyn toml_edit::TableLike, key),
+    };
+    // Replace the value in place so the key's leading comments stay with it
+    match table.get_mut(field) {
+        Some(item) => *item = toml_edit::value(value),
+        None => {
+            table.insert(field, toml_edit::value(value));
+        }
+    }
+
+    let updated = doc.to_string();
+    let config: Config =
+        toml::from_str(&updated).map_err(|e| format!("Invalid value for {}: {}", key, e))?;
+    if !MERGE_STRATEGIES.contains(&config.merge_strategy.as_str()) {
+        return Err(format!(
+            "Invalid merge_strategy: {}. Use one of: {}",
+            config.merge_strategy,
+            MERGE_STRATEGIES.join(", ")
+        ));
+    }
+
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    std::fs::write(&path, updated).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_and_set() {
+        let temp = tempfile::tempdir().unwrap();
+        let root = temp.path();
+
+        assert_eq!(config_command("get", "target_branch", None, root).unwrap().value, "main");
+        assert_eq!(config_command("get", "worktrees_dir", None, root).unwrap().value, serde_json::Value::Null);
+
+        std::fs::create_dir_all(root.join(".bacchus")).unwrap();
+        std::fs::write(config_path(root), "# keep me\ntarget_branch = \"main\"\n").unwrap();
+
+        config_command("set", "target_branch", Some("develop"), root).unwrap();
+        config_command("set", "stale_threshold_minutes", Some("45"), root).unwrap();
+        config_command("set", "hooks.pre_merge", Some("[\"cargo test\"]"), root).unwrap();
+
+        let config = load_config(root);
+        assert_eq!(config.target_branch, "develop");
+        assert_eq!(config.stale_threshold_minutes, 45);
+        assert_eq!(config.hooks.pre_merge, vec!["cargo test"]);
+        assert!(std::fs::read_to_string(config_path(root)).unwrap().starts_with("# keep me\n"));
+        assert_eq!(config_command("get", "hooks.pre_merge", None, root).unwrap().value[0], "cargo test");
+
+        // Wrong types and unknown values leave the file untouched
+        assert!(config_command("set", "stale_threshold_minutes", Some("soon"), root).is_err());
+        assert!(config_command("set", "merge_strategy", Some("octopus"), root).is_err());
+        assert!(config_command("set", "no_such_key", Some("1"), root).is_err());
+        assert!(config_command("set", "target_branch", None, root).is_err());
+        assert_eq!(load_config(root).stale_threshold_minutes, 45);
+    }
+}
@@ -0,0 +1,170 @@
+//! Conflicts tool - predict whether releasing a bead will hit merge conflicts
+//!
+//! `git merge-tree --write-tree` (git 2.38+) performs the merge of the bead's
+//! branch into its target entirely in the object store, so nothing is left
+//! half-merged. Older gits fall back to a diff-based guess: files changed on
+//! both sides since the merge base may conflict.
+
+use crate::config::DEFAULT_TARGET_BRANCH;
+use crate::db::with_db;
+use crate::worktree;
+use rusqlite::OptionalExtension;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Exit code git uses for usage errors, e.g. `--write-tree` on git < 2.38
+const GIT_USAGE_ERROR: i32 = 129;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ConflictPrediction {
+    pub bead_id: String,
+    pub target_branch: String,
+    pub will_conflict: bool,
+    pub conflicting_files: Vec<String>,
+    pub merge_base: String,
+    /// `merge-tree` (exact) or `diff` (files changed on both sides; may overlap cleanly)
+    pub method: String,
+    pub message: String,
+}
+
+/// Predict conflicts between a bead's committed work and its target branch
+pub fn predict_conflicts(workspace_root: &Path, bead_id: &str) -> Result<ConflictPrediction, Box<dyn std::error::Error>> {
+    let claim: Option<(String, Option<String>)> = with_db(|conn| {
+        conn.query_row(
+            "SELECT worktree_path, target_branch FROM claims WHERE bead_id = ?1",
+            [bead_id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .optional()
+    })?;
+    let (worktree_path, target_branch) = match claim {
+        Some((path, target)) => (PathBuf::from(path), target.unwrap_or_else(|| DEFAULT_TARGET_BRANCH.to_string())),
+        None => (
+            worktree::get_worktrees_dir(workspace_root).join(bead_id),
+            DEFAULT_TARGET_BRANCH.to_string(),
+        ),
+    };
+    if !worktree_path.is_dir() {
+        return Err(format!("No worktree for {} at {}", bead_id, worktree_path.display()).into());
+    }
+
+    let merge_base = worktree::git_output(&worktree_path, &["merge-base", "HEAD", &target_branch])?;
+
+    let (method, conflicting_files) = match merge_tree_conflicts(&worktree_path, &target_branch)? {
+        Some(files) => ("merge-tree", files),
+        None => ("diff", overlapping_files(&worktree_path, &target_branch)?),
+    };
+    let will_conflict = !conflicting_files.is_empty();
+
+    let message = match (will_conflict, method) {
+        (false, _) => format!("{} merges cleanly into {}", bead_id, target_branch),
+        (true, "merge-tree") => format!(
+            "Merging {} into {} will conflict in {} files; rebase first with `bacchus rebase {}`",
+            bead_id,
+            target_branch,
+            conflicting_files.len(),
+            bead_id
+        ),
+        (true, _) => format!(
+            "{} files changed on both {} and {} may conflict (git < 2.38, so this is a guess)",
+            conflicting_files.len(),
+            bead_id,
+            target_branch
+        ),
+    };
+
+    Ok(ConflictPrediction {
+        bead_id: bead_id.to_string(),
+        target_branch,
+        will_conflict,
+        conflicting_files,
+        merge_base,
+        method: method.to_string(),
+        message,
+    })
+}
+
+/// Conflicted paths from a trial merge, or None if this git lacks `merge-tree --write-tree`
+fn merge_tree_conflicts(worktree_path: &Path, target_branch: &str) -> Result<Option<Vec<String>>, Box<dyn std::error::Error>> {
+    let output = Command::new("git")
+        .args(["merge-tree", "--write-tree", "--name-only", "--no-messages", "HEAD", target_branch])
+        .current_dir(worktree_path)
+        .output()?;
+
+    match output.status.code() {
+        // Clean merge (0) or conflicts (1): the first line is the resulting tree
+        Some(0) | Some(1) => Ok(Some(
+            String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .skip(1)
+                .take_while(|l| !l.is_empty())
+                .map(String::from)
+                .collect(),
+        )),
+        Some(GIT_USAGE_ERROR) => Ok(None),
+        _ => Err(format!("git merge-tree failed: {}", String::from_utf8_lossy(&output.stderr).trim()).into()),
+    }
+}
+
+/// Files changed on both the branch and the target since their merge base
+fn overlapping_files(worktree_path: &Path, target_branch: &str) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    let ours = worktree::git_output(worktree_path, &["diff", "--name-only", &format!("{}...HEAD", target_branch)])?;
+    let theirs = worktree::git_output(worktree_path, &["diff", "--name-only", &format!("HEAD...{}", target_branch)])?;
+    let theirs: BTreeSet<&str> = theirs.lines().collect();
+    Ok(ours
+        .lines()
+        .filter(|f| theirs.contains(f))
+        .map(String::from)
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{close_db, init_db, test_lock};
+    use crate::worktree::test_git;
+    use tempfile::tempdir;
+
+    fn commit_file(dir: &Path, file: &str, content: &str) {
+        std::fs::write(dir.join(file), content).unwrap();
+        test_git(dir, &["add", file]);
+        test_git(dir, &["commit", "-qm", file]);
+    }
+
+    #[test]
+    fn test_predict_conflicts() {
+        let _lock = test_lock();
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        test_git(root, &["init", "-q", "-b", "main"]);
+        test_git(root, &["config", "user.email", "test@example.com"]);
+        test_git(root, &["config", "user.name", "Test"]);
+        commit_file(root, "a.txt", "one\n");
+        commit_file(root, "b.txt", "one\n");
+        init_db(Some(root.join("test.db").to_str().unwrap()), true).unwrap();
+
+        let worktree_path = worktree::get_worktrees_dir(root).join("bd-1");
+        test_git(root, &["worktree", "add", "-q", "-b", "bacchus/bd-1", worktree_path.to_str().unwrap()]);
+        let base = test_git(root, &["rev-parse", "HEAD"]);
+
+        commit_file(&worktree_path, "a.txt", "bead\n");
+        commit_file(root, "b.txt", "main\n");
+        let prediction = predict_conflicts(root, "bd-1").unwrap();
+        assert!(!prediction.will_conflict, "{}", prediction.message);
+        assert_eq!(prediction.merge_base, base);
+
+        commit_file(root, "a.txt", "main\n");
+        let prediction = predict_conflicts(root, "bd-1").unwrap();
+        assert!(prediction.will_conflict);
+        assert_eq!(prediction.conflicting_files, ["a.txt"]);
+        // The trial merge leaves nothing behind
+        assert!(!worktree::is_in_merge_conflict(root).unwrap());
+
+        assert_eq!(overlapping_files(&worktree_path, "main").unwrap(), ["a.txt"]);
+        assert!(predict_conflicts(root, "bd-missing").is_err());
+
+        close_db();
+    }
+}
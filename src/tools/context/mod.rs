@@ -1,12 +1,32 @@
 use std::path::Path;
 
+use super::session::{session_persona, set_session_persona};
 use crate::worktree;
 
 mod global;
 mod task;
 
 /// Generate context for the current agent
-pub fn generate_context(bead_id_opt: Option<String>, workspace_root: &Path) -> Result<String, String> {
+///
+/// An explicit `persona` is remembered in the session file; otherwise the
+/// session's persona (if any) is used.
+pub fn generate_context(
+    bead_id_opt: Option<String>,
+    persona: Option<&str>,
+    workspace_root: &Path,
+) -> Result<String, String> {
+    let persona = match persona {
+        Some(p) => {
+            set_session_persona(p)?;
+            Some(p.to_string())
+        }
+        None => session_persona(),
+    };
+
+    if persona.as_deref() == Some("orchestrator") {
+        return global::generate_global_context(workspace_root);
+    }
+
     let current_dir = std::env::current_dir().map_err(|e| e.to_string())?;
     
     // Check if we are inside the .bacchus/worktrees directory of the workspace
@@ -28,7 +48,7 @@ pub fn generate_context(bead_id_opt: Option<String>, workspace_root: &Path) -> R
     };
 
     if let Some(bead_id) = target_bead_id {
-        task::generate_task_context(&bead_id, workspace_root)
+        task::generate_task_context(&bead_id, persona.as_deref(), workspace_root)
     } else {
         global::generate_global_context(workspace_root)
     }
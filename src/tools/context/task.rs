@@ -1,17 +1,22 @@
 use crate::db;
+use crate::tools::export_context::git_lines;
+use crate::tools::symbols::{find_symbols, FindSymbolsInput, SymbolInfo};
 use std::path::Path;
 use rusqlite::OptionalExtension;
+use walkdir::WalkDir;
 
-pub fn generate_task_context(bead_id: &str, _workspace_root: &Path) -> Result<String, String> {
+pub fn generate_task_context(bead_id: &str, persona: Option<&str>, _workspace_root: &Path) -> Result<String, String> {
     let claim_info = db::with_db(|conn| {
         conn.query_row(
-            "SELECT agent_id, branch_name, claimed_at FROM claims WHERE bead_id = ?1",
+            "SELECT agent_id, branch_name, claimed_at, worktree_path, start_commit FROM claims WHERE bead_id = ?1",
             [bead_id],
             |row| {
                 Ok((
                     row.get::<_, String>(0)?,
                     row.get::<_, String>(1)?,
                     row.get::<_, i64>(2)?,
+                    row.get::<_, String>(3)?,
+                    row.get::<_, String>(4)?,
                 ))
             }
         ).optional()
@@ -21,18 +26,250 @@ pub fn generate_task_context(bead_id: &str, _workspace_root: &Path) -> Result<St
     out.push_str(&format!("# Task Context: {}\n\n", bead_id));
 
 
-    if let Some((agent, branch, _ts)) = claim_info {
+    let mut worktree = None;
+    if let Some((agent, branch, _ts, worktree_path, start_commit)) = claim_info {
         out.push_str(&format!("- **Status**: In Progress (Claimed by {})\n", agent));
         out.push_str(&format!("- **Branch**: `{}`\n", branch));
+        worktree = Some((worktree_path, start_commit));
     } else {
         out.push_str("- **Status**: Unknown / Not Claimed\n");
     }
+    if let Some(persona) = persona {
+        out.push_str(&format!("- **Persona**: {}\n", persona));
+    }
 
     out.push_str("\n## Objectives\n");
     out.push_str("1. Fulfill the requirements of this specific bead.\n");
     out.push_str("2. Ensure all tests pass within this isolated worktree.\n");
     out.push_str("3. Release the bead when done using `bacchus release`.\n");
 
+    let worktree = worktree.as_ref().map(|(path, start)| (Path::new(path), start.as_str()));
+    imports_section(&mut out, worktree)?;
+
+    match persona {
+        Some("implementer") => implementer_sections(&mut out, bead_id, worktree)?,
+        Some("reviewer") => reviewer_sections(&mut out, bead_id)?,
+        Some("tester") => tester_sections(&mut out, bead_id, worktree)?,
+        _ => {}
+    }
+
     Ok(out)
 }
 
+/// Modules imported by the files this bead has changed
+fn imports_section(out: &mut String, worktree: Option<(&Path, &str)>) -> Result<(), String> {
+    let changed = match worktree {
+        Some((path, start_commit)) => git_lines(path, &["diff", "--name-only", start_commit]),
+        None => Vec::new(),
+    };
+
+    let imports = db::with_db(|conn| {
+        let mut stmt = conn.prepare(
+            "SELECT imported_path, GROUP_CONCAT(imported_name, ', ') FROM file_imports
+             WHERE importer_file = ?1 GROUP BY imported_path ORDER BY imported_path",
+        )?;
+        let mut imports = Vec::new();
+        for file in &changed {
+            let rows = stmt.query_map([file], |row| Ok((row.get::<_, String>(0)?, row.get::<_, Option<String>>(1)?)))?;
+            let modules = rows.collect::<Result<Vec<_>, _>>()?;
+            if !modules.is_empty() {
+                imports.push((file, modules));
+            }
+        }
+        Ok(imports)
+    }).map_err(|e| e.to_string())?;
+
+    out.push_str("\n## Files this bead imports\n");
+    if imports.is_empty() {
+        out.push_str("_No imports recorded for this bead's changed files._\n");
+    }
+    for (file, modules) in imports {
+        let modules: Vec<String> = modules
+            .into_iter()
+            .map(|(path, names)| match names {
+                Some(names) => format!("`{}` ({})", path, names),
+                None => format!("`{}`", path),
+            })
+            .collect();
+        out.push_str(&format!("- `{}`: {}\n", file, modules.join(", ")));
+    }
+    Ok(())
+}
+
+/// Full symbol list plus the worktree diff
+fn implementer_sections(out: &mut String, bead_id: &str, worktree: Option<(&Path, &str)>) -> Result<(), String> {
+    out.push_str("\n## Changes\n");
+    match worktree {
+        Some((path, start_commit)) => {
+            let stat = git_lines(path, &["diff", "--stat", start_commit]);
+            if stat.is_empty() {
+                out.push_str("_No changes yet._\n");
+            } else {
+                out.push_str("```\n");
+                for line in stat {
+                    out.push_str(&line);
+                    out.push('\n');
+                }
+                out.push_str("```\n");
+            }
+        }
+        None => out.push_str("_No worktree._\n"),
+    }
+
+    out.push_str("\n## Symbols\n");
+    let symbols = bead_symbols(bead_id, worktree)?;
+    if symbols.is_empty() {
+        out.push_str("_No symbols registered or changed._\n");
+    } else {
+        for sym in &symbols {
+            out.push_str(&format!("- `{}` ({}, {}:{})\n", sym.fq_name, sym.kind, sym.file, sym.span_start_line));
+        }
+    }
+    Ok(())
+}
+
+/// Symbols this bead shares with other beads, and who holds those beads
+fn reviewer_sections(out: &mut String, bead_id: &str) -> Result<(), String> {
+    let related = db::with_db(|conn| {
+        let mut stmt = conn.prepare(
+            "SELECT DISTINCT other.bead_id, COALESCE(c.agent_id, '-'), other.symbol_fq_name, other.relation
+             FROM bead_symbols mine
+             JOIN bead_symbols other
+               ON other.symbol_fq_name = mine.symbol_fq_name AND other.bead_id != mine.bead_id
+             LEFT JOIN claims c ON c.bead_id = other.bead_id
+             WHERE mine.bead_id = ?1
+             ORDER BY other.bead_id, other.symbol_fq_name",
+        )?;
+        let rows = stmt.query_map([bead_id], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, String>(3)?,
+            ))
+        })?;
+        rows.collect::<Result<Vec<_>, _>>()
+    }).map_err(|e| e.to_string())?;
+
+    out.push_str("\n## Related Work by Other Agents\n");
+    if related.is_empty() {
+        out.push_str("_No other beads touch this bead's registered symbols._\n");
+    } else {
+        out.push_str("| Bead ID | Agent | Symbol | Relation |\n|---|---|---|---|\n");
+        for (bead, agent, symbol, relation) in related {
+            out.push_str(&format!("| {} | {} | `{}` | {} |\n", bead, agent, symbol, relation));
+        }
+    }
+    Ok(())
+}
+
+/// Test files in the worktree, and changed symbols no test mentions
+fn tester_sections(out: &mut String, bead_id: &str, worktree: Option<(&Path, &str)>) -> Result<(), String> {
+    let test_files: Vec<String> = match worktree {
+        Some((path, _)) => WalkDir::new(path)
+            .into_iter()
+            .filter_entry(|e| !matches!(e.file_name().to_str(), Some(".git" | "node_modules" | "target")))
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file() && is_test_file(e.path()))
+            .filter_map(|e| e.path().strip_prefix(path).ok().map(|p| p.to_string_lossy().to_string()))
+            .collect(),
+        None => Vec::new(),
+    };
+
+    out.push_str("\n## Test Files\n");
+    if test_files.is_empty() {
+        out.push_str("_No test files found._\n");
+    } else {
+        for file in &test_files {
+            out.push_str(&format!("- `{}`\n", file));
+        }
+    }
+
+    // A symbol counts as covered if its short name appears in any test file
+    let test_sources: Vec<String> = match worktree {
+        Some((path, _)) => test_files
+            .iter()
+            .filter_map(|f| std::fs::read_to_string(path.join(f)).ok())
+            .collect(),
+        None => Vec::new(),
+    };
+    let uncovered: Vec<SymbolInfo> = bead_symbols(bead_id, worktree)?
+        .into_iter()
+        .filter(|s| matches!(s.kind.as_str(), "function" | "method"))
+        .filter(|s| !test_sources.iter().any(|src| src.contains(short_name(&s.fq_name))))
+        .collect();
+
+    out.push_str("\n## Uncovered Symbols\n");
+    if uncovered.is_empty() {
+        out.push_str("_Every changed function is referenced by a test._\n");
+    } else {
+        for sym in &uncovered {
+            out.push_str(&format!("- `{}` ({}:{})\n", sym.fq_name, sym.file, sym.span_start_line));
+        }
+    }
+    Ok(())
+}
+
+/// Symbols registered to the bead plus those in files changed in its worktree
+fn bead_symbols(bead_id: &str, worktree: Option<(&Path, &str)>) -> Result<Vec<SymbolInfo>, String> {
+    let mut inputs = vec![FindSymbolsInput {
+        in_bead: Some(bead_id.to_string()),
+        limit: Some(1000),
+        ..Default::default()
+    }];
+    if let Some((path, start_commit)) = worktree {
+        inputs.extend(git_lines(path, &["diff", "--name-only", start_commit]).into_iter().map(|file| {
+            FindSymbolsInput {
+                file: Some(file),
+                limit: Some(1000),
+                ..Default::default()
+            }
+        }));
+    }
+
+    let mut symbols: Vec<SymbolInfo> = Vec::new();
+    for input in &inputs {
+        for sym in find_symbols(input).map_err(|e| e.to_string())?.symbols {
+            if !symbols.iter().any(|s| s.fq_name == sym.fq_name) {
+                symbols.push(sym);
+            }
+        }
+    }
+    Ok(symbols)
+}
+
+fn is_test_file(path: &Path) -> bool {
+    let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+    let in_test_dir = path
+        .components()
+        .any(|c| matches!(c.as_os_str().to_str(), Some("tests" | "test" | "__tests__")));
+
+    in_test_dir
+        || name.starts_with("test_")
+        || name.ends_with("_test.go")
+        || name.ends_with("_test.py")
+        || name.contains(".test.")
+        || name.contains(".spec.")
+}
+
+/// Last segment of a fully qualified name (`src/a.rs::Foo.bar` -> `bar`)
+fn short_name(fq_name: &str) -> &str {
+    let tail = fq_name.rsplit("::").next().unwrap_or(fq_name);
+    tail.rsplit('.').next().unwrap_or(tail)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tester_helpers() {
+        assert!(is_test_file(Path::new("tests/integration.rs")));
+        assert!(is_test_file(Path::new("auth/login_test.go")));
+        assert!(is_test_file(Path::new("src/app.spec.ts")));
+        assert!(!is_test_file(Path::new("src/auth.rs")));
+
+        assert_eq!(short_name("src/a.rs::Foo.bar"), "bar");
+        assert_eq!(short_name("src/a.rs::login"), "login");
+    }
+}
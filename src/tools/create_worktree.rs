@@ -0,0 +1,117 @@
+//! Create worktree tool - explicit worktree creation outside of `next`/`claim`
+//!
+//! With `--no-claim`, only the git worktree and branch are created; the bead
+//! need not exist in beads and nothing is recorded in the claims table.
+
+use super::claim_events::log_claim_event;
+use crate::beads;
+use crate::config::resolve_target_branch;
+use crate::db::with_db;
+use crate::worktree;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateWorktreeOutput {
+    pub success: bool,
+    pub bead_id: String,
+    pub worktree_path: Option<String>,
+    pub branch: Option<String>,
+    pub claimed: bool,
+    pub message: String,
+}
+
+pub fn create_worktree(
+    bead_id: &str,
+    base_branch: Option<&str>,
+    no_claim: bool,
+    agent_id: &str,
+    workspace_root: &Path,
+) -> Result<CreateWorktreeOutput, Box<dyn std::error::Error>> {
+    let mut title = None;
+    if !no_claim {
+        // Make sure the bead exists before touching git
+        title = Some(beads::get_bead(bead_id)?.title);
+
+        let already_claimed = with_db(|conn| {
+            Ok(conn
+                .query_row(
+                    "SELECT 1 FROM claims WHERE bead_id = ?1",
+                    [bead_id],
+                    |_| Ok(true),
+                )
+                .unwrap_or(false))
+        })?;
+
+        if already_claimed {
+            return Ok(CreateWorktreeOutput {
+                success: false,
+                bead_id: bead_id.to_string(),
+                worktree_path: None,
+                branch: None,
+                claimed: false,
+                message: format!("Bead {} is already claimed", bead_id),
+            });
+        }
+    }
+
+    let wt = worktree::create_worktree_from(workspace_root, bead_id, base_branch)?;
+    let worktree_path = wt.path.to_string_lossy().to_string();
+
+    if no_claim {
+        return Ok(CreateWorktreeOutput {
+            success: true,
+            bead_id: bead_id.to_string(),
+            message: format!("Created worktree {} on {}", worktree_path, wt.branch),
+            worktree_path: Some(worktree_path),
+            branch: Some(wt.branch),
+            claimed: false,
+        });
+    }
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as i64)
+        .unwrap_or(0);
+
+    let claim_result = with_db(|conn| {
+        conn.execute(
+            "INSERT INTO claims (bead_id, agent_id, worktree_path, branch_name, start_commit, claimed_at, target_branch, title) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            rusqlite::params![
+                bead_id,
+                agent_id,
+                &worktree_path,
+                &wt.branch,
+                &wt.head_commit,
+                now,
+                resolve_target_branch(None, workspace_root),
+                title
+            ],
+        )
+    });
+
+    if let Err(e) = claim_result {
+        // Rollback: remove orphaned worktree
+        let _ = worktree::remove_worktree(workspace_root, bead_id, true);
+        return Err(e.into());
+    }
+
+    if let Err(e) = beads::update_bead_status(bead_id, "in_progress") {
+        // Rollback: remove worktree and claim
+        let _ = worktree::remove_worktree(workspace_root, bead_id, true);
+        let _ = with_db(|conn| conn.execute("DELETE FROM claims WHERE bead_id = ?1", [bead_id]));
+        return Err(e.into());
+    }
+
+    log_claim_event(bead_id, Some(agent_id), "claimed", None);
+
+    Ok(CreateWorktreeOutput {
+        success: true,
+        bead_id: bead_id.to_string(),
+        message: format!("Created worktree {} and claimed {} for {}", worktree_path, bead_id, agent_id),
+        worktree_path: Some(worktree_path),
+        branch: Some(wt.branch),
+        claimed: true,
+    })
+}
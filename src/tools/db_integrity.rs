@@ -0,0 +1,55 @@
+//! DB integrity check tool - detects SQLite corruption
+//!
+//! Runs `PRAGMA integrity_check` (or `quick_check`) and `PRAGMA foreign_key_check`.
+
+use crate::db::with_db;
+use rusqlite::{Connection, Result};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct IntegrityCheckOutput {
+    pub ok: bool,
+    pub issues: Vec<String>,
+}
+
+/// Check the bacchus database for corruption and foreign key violations
+pub fn check_integrity(quick: bool) -> Result<IntegrityCheckOutput> {
+    with_db(|conn| {
+        let issues = integrity_issues(conn, quick)?;
+        Ok(IntegrityCheckOutput {
+            ok: issues.is_empty(),
+            issues,
+        })
+    })
+}
+
+/// Corruption and foreign key problems in `conn`'s database, one line each
+pub fn integrity_issues(conn: &Connection, quick: bool) -> Result<Vec<String>> {
+    let pragma = if quick { "quick_check" } else { "integrity_check" };
+
+    let mut stmt = conn.prepare(&format!("PRAGMA {}", pragma))?;
+    let mut issues: Vec<String> = stmt
+        .query_map([], |row| row.get::<_, String>(0))?
+        .filter_map(|r| r.ok())
+        .filter(|line| line != "ok")
+        .collect();
+
+    // Each row: (table, rowid, parent table, foreign key index)
+    let mut stmt = conn.prepare("PRAGMA foreign_key_check")?;
+    let fk_violations: Vec<String> = stmt
+        .query_map([], |row| {
+            let table: String = row.get(0)?;
+            let rowid: Option<i64> = row.get(1)?;
+            let parent: String = row.get(2)?;
+            Ok(format!(
+                "row {} of table {} violates foreign key to {}",
+                rowid.map(|r| r.to_string()).unwrap_or_else(|| "?".to_string()),
+                table,
+                parent
+            ))
+        })?
+        .filter_map(|r| r.ok())
+        .collect();
+    issues.extend(fk_violations);
+    Ok(issues)
+}
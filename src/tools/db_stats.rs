@@ -0,0 +1,125 @@
+//! DB stats tool - reports database size and table composition
+//!
+//! Useful for debugging performance and sizing issues.
+
+use crate::db::with_db;
+use rusqlite::{Connection, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DbStatsOutput {
+    pub db_path: String,
+    pub db_size_bytes: u64,
+    pub wal_size_bytes: u64,
+    pub page_count: i64,
+    pub page_size: i64,
+    pub tables: BTreeMap<String, i64>,
+    pub indices: Vec<IndexStats>,
+    pub symbols_by_language: BTreeMap<String, i64>,
+    pub symbols_by_kind: BTreeMap<String, i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct IndexStats {
+    pub name: String,
+    pub table: String,
+    pub columns: Vec<String>,
+    /// Size on disk from the `dbstat` virtual table (None if unavailable)
+    pub size_bytes: Option<i64>,
+}
+
+/// Collect size and row count statistics for the database at `db_path`
+pub fn db_stats(db_path: &Path) -> Result<DbStatsOutput> {
+    let db_size_bytes = std::fs::metadata(db_path).map(|m| m.len()).unwrap_or(0);
+    let wal_path = format!("{}-wal", db_path.display());
+    let wal_size_bytes = std::fs::metadata(&wal_path).map(|m| m.len()).unwrap_or(0);
+
+    with_db(|conn| {
+        let page_count: i64 = conn.query_row("PRAGMA page_count", [], |r| r.get(0))?;
+        let page_size: i64 = conn.query_row("PRAGMA page_size", [], |r| r.get(0))?;
+
+        // Row counts for every regular table (skip SQLite internals and FTS shadow tables)
+        let table_names: Vec<String> = {
+            let mut stmt = conn.prepare(
+                "SELECT name FROM sqlite_master
+                 WHERE type = 'table' AND name NOT LIKE 'sqlite_%' AND name NOT LIKE 'symbols_fts%'
+                 ORDER BY name",
+            )?;
+            let names = stmt.query_map([], |r| r.get(0))?.filter_map(|r| r.ok()).collect();
+            names
+        };
+
+        let mut tables = BTreeMap::new();
+        for table in table_names {
+            let count: i64 =
+                conn.query_row(&format!("SELECT COUNT(*) FROM \"{}\"", table), [], |r| r.get(0))?;
+            tables.insert(table, count);
+        }
+
+        let indices = index_stats(conn)?;
+
+        let symbols_by_language = group_counts(conn, "language")?;
+        let symbols_by_kind = group_counts(conn, "kind")?;
+
+        Ok(DbStatsOutput {
+            db_path: db_path.to_string_lossy().to_string(),
+            db_size_bytes,
+            wal_size_bytes,
+            page_count,
+            page_size,
+            tables,
+            indices,
+            symbols_by_language,
+            symbols_by_kind,
+        })
+    })
+}
+
+fn index_stats(conn: &Connection) -> Result<Vec<IndexStats>> {
+    let mut stmt = conn.prepare(
+        "SELECT name, tbl_name FROM sqlite_master
+         WHERE type = 'index' AND name NOT LIKE 'sqlite_%'
+         ORDER BY tbl_name, name",
+    )?;
+    let indices: Vec<(String, String)> = stmt
+        .query_map([], |r| Ok((r.get(0)?, r.get(1)?)))?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    let mut stats = Vec::new();
+    for (name, table) in indices {
+        let mut info = conn.prepare(&format!("PRAGMA index_info(\"{}\")", name))?;
+        let columns: Vec<String> = info
+            .query_map([], |r| r.get::<_, Option<String>>(2))?
+            .filter_map(|r| r.ok().flatten())
+            .collect();
+
+        // dbstat may be compiled out; treat failure as "unknown"
+        let size_bytes = conn
+            .query_row("SELECT SUM(pgsize) FROM dbstat WHERE name = ?1", [&name], |r| r.get(0))
+            .ok();
+
+        stats.push(IndexStats {
+            name,
+            table,
+            columns,
+            size_bytes,
+        });
+    }
+
+    Ok(stats)
+}
+
+fn group_counts(conn: &Connection, column: &str) -> Result<BTreeMap<String, i64>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {}, COUNT(*) FROM symbols GROUP BY {}",
+        column, column
+    ))?;
+    let counts = stmt
+        .query_map([], |r| Ok((r.get::<_, String>(0)?, r.get::<_, i64>(1)?)))?
+        .filter_map(|r| r.ok())
+        .collect();
+    Ok(counts)
+}
@@ -0,0 +1,192 @@
+//! Depends-on tool - the chain of beads blocking a bead
+//!
+//! Walks `blocks` edges in bd's `dependencies` table directly, so the whole
+//! tree costs one query per bead rather than one `bd show` each. A bead that
+//! reappears among its own blockers is reported as a cycle and not expanded.
+
+use rusqlite::{Connection, OpenFlags, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+pub const DEFAULT_DEPTH: u32 = 3;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BlockerInfo {
+    pub bead_id: String,
+    pub title: String,
+    /// `unknown` for a dependency on a bead missing from the database
+    pub status: String,
+    pub blockers: Vec<BlockerInfo>,
+    /// Already an ancestor in this chain; not expanded
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub cycle: bool,
+    /// Has blockers beyond the depth limit
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub truncated: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DependsOnOutput {
+    pub bead: BlockerInfo,
+    /// Each cycle found, as `a -> b -> a`
+    pub cycles: Vec<String>,
+}
+
+/// Blockers of `bead_id`, recursively up to `depth` levels
+pub fn bead_blockers(beads_db: &Path, bead_id: &str, depth: u32) -> Result<DependsOnOutput, Box<dyn std::error::Error>> {
+    if !beads_db.exists() {
+        return Err(format!("Beads database not found at {} (run `bd init`)", beads_db.display()).into());
+    }
+    let conn = Connection::open_with_flags(beads_db, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+
+    let (title, status) = conn
+        .query_row("SELECT title, status FROM issues WHERE id = ?1", [bead_id], |row| {
+            Ok((row.get(0)?, row.get(1)?))
+        })
+        .optional()?
+        .ok_or_else(|| format!("Bead not found: {}", bead_id))?;
+
+    let mut stmt = conn.prepare(
+        "SELECT d.depends_on_id, COALESCE(i.title, ''), COALESCE(i.status, 'unknown')
+         FROM dependencies d LEFT JOIN issues i ON i.id = d.depends_on_id
+         WHERE d.issue_id = ?1 AND d.type = 'blocks'
+         ORDER BY d.depends_on_id",
+    )?;
+    let mut direct_blockers = |id: &str| -> rusqlite::Result<Vec<(String, String, String)>> {
+        stmt.query_map([id], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?
+            .collect()
+    };
+
+    let mut cycles = Vec::new();
+    let mut path = vec![bead_id.to_string()];
+    let mut bead = BlockerInfo {
+        bead_id: bead_id.to_string(),
+        title,
+        status,
+        blockers: Vec::new(),
+        cycle: false,
+        truncated: false,
+    };
+    expand(&mut bead, depth, &mut path, &mut cycles, &mut direct_blockers)?;
+
+    Ok(DependsOnOutput { bead, cycles })
+}
+
+fn expand(
+    node: &mut BlockerInfo,
+    depth: u32,
+    path: &mut Vec<String>,
+    cycles: &mut Vec<String>,
+    direct_blockers: &mut impl FnMut(&str) -> rusqlite::Result<Vec<(String, String, String)>>,
+) -> rusqlite::Result<()> {
+    let blockers = direct_blockers(&node.bead_id)?;
+    if depth == 0 {
+        node.truncated = !blockers.is_empty();
+        return Ok(());
+    }
+
+    for (bead_id, title, status) in blockers {
+        let mut child = BlockerInfo {
+            bead_id,
+            title,
+            status,
+            blockers: Vec::new(),
+            cycle: false,
+            truncated: false,
+        };
+        if path.contains(&child.bead_id) {
+            let start = path.iter().position(|id| *id == child.bead_id).unwrap_or(0);
+            cycles.push(format!("{} -> {}", path[start..].join(" -> "), child.bead_id));
+            child.cycle = true;
+        } else {
+            path.push(child.bead_id.clone());
+            expand(&mut child, depth - 1, path, cycles, direct_blockers)?;
+            path.pop();
+        }
+        node.blockers.push(child);
+    }
+    Ok(())
+}
+
+/// ASCII tree of the blockers; `color` adds ANSI status colors
+pub fn render_blocker_tree(output: &DependsOnOutput, color: bool) -> String {
+    fn line(node: &BlockerInfo, color: bool) -> String {
+        let status = match (color, node.status.as_str()) {
+            (true, "open") => format!("\x1b[31m{}\x1b[0m", node.status),
+            (true, "in_progress") => format!("\x1b[33m{}\x1b[0m", node.status),
+            (true, "closed") => format!("\x1b[32m{}\x1b[0m", node.status),
+            _ => node.status.clone(),
+        };
+        let mut text = format!("{} [{}] {}", node.bead_id, status, node.title);
+        if node.cycle {
+            text.push_str(" (cycle)");
+        } else if node.truncated {
+            text.push_str(" (more blockers; raise --depth)");
+        }
+        text.trim_end().to_string()
+    }
+
+    fn walk(node: &BlockerInfo, prefix: &str, color: bool, out: &mut Vec<String>) {
+        for (i, child) in node.blockers.iter().enumerate() {
+            let last = i + 1 == node.blockers.len();
+            out.push(format!("{}{}{}", prefix, if last { "└── " } else { "├── " }, line(child, color)));
+            walk(child, &format!("{}{}", prefix, if last { "    " } else { "│   " }), color, out);
+        }
+    }
+
+    let mut out = vec![line(&output.bead, color)];
+    walk(&output.bead, "", color, &mut out);
+    if output.bead.blockers.is_empty() {
+        out.push("(no blockers)".to_string());
+    }
+    for cycle in &output.cycles {
+        out.push(format!("cycle: {}", cycle));
+    }
+    out.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_bead_blockers() {
+        let dir = tempdir().unwrap();
+        let db = dir.path().join("beads.db");
+        Connection::open(&db)
+            .unwrap()
+            .execute_batch(
+                "CREATE TABLE issues (id TEXT PRIMARY KEY, title TEXT NOT NULL, status TEXT NOT NULL);
+                 CREATE TABLE dependencies (issue_id TEXT, depends_on_id TEXT, type TEXT NOT NULL DEFAULT 'blocks');
+                 INSERT INTO issues VALUES
+                   ('bd-1', 'Ship', 'open'), ('bd-2', 'API', 'in_progress'),
+                   ('bd-3', 'Schema', 'closed'), ('bd-4', 'Auth', 'open'), ('bd-5', 'Docs', 'open');
+                 -- bd-1 <- bd-2 <- bd-3 <- bd-4 <- bd-2 (cycle); related edges don't block
+                 INSERT INTO dependencies VALUES
+                   ('bd-1', 'bd-2', 'blocks'), ('bd-1', 'bd-5', 'related'),
+                   ('bd-2', 'bd-3', 'blocks'), ('bd-3', 'bd-4', 'blocks'), ('bd-4', 'bd-2', 'blocks');",
+            )
+            .unwrap();
+
+        let output = bead_blockers(&db, "bd-1", 5).unwrap();
+        assert_eq!(output.cycles, ["bd-2 -> bd-3 -> bd-4 -> bd-2"]);
+        assert_eq!(
+            render_blocker_tree(&output, false),
+            "bd-1 [open] Ship\n\
+             └── bd-2 [in_progress] API\n    \
+                 └── bd-3 [closed] Schema\n        \
+                     └── bd-4 [open] Auth\n            \
+                         └── bd-2 [in_progress] API (cycle)\n\
+             cycle: bd-2 -> bd-3 -> bd-4 -> bd-2"
+        );
+
+        let shallow = bead_blockers(&db, "bd-1", 1).unwrap();
+        assert!(shallow.bead.blockers[0].truncated);
+        assert!(shallow.cycles.is_empty());
+        assert!(render_blocker_tree(&shallow, true).contains("\x1b[33min_progress\x1b[0m"));
+
+        assert_eq!(render_blocker_tree(&bead_blockers(&db, "bd-5", 3).unwrap(), false), "bd-5 [open] Docs\n(no blockers)");
+        assert!(bead_blockers(&db, "bd-missing", 3).is_err());
+    }
+}
@@ -0,0 +1,180 @@
+//! Diff tool - uncommitted changes in a bead's worktree as structured JSON
+//!
+//! Runs `git diff HEAD` in the claim's worktree and splits the patch per file.
+//! Untracked files don't appear until they're added.
+
+use crate::db::with_db;
+use rusqlite::OptionalExtension;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::process::Command;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FileDiff {
+    pub path: String,
+    pub additions: u32,
+    pub deletions: u32,
+    /// Empty with `--stat`
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub patch: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DiffOutput {
+    pub success: bool,
+    pub bead_id: String,
+    pub files_changed: Vec<FileDiff>,
+    pub message: String,
+}
+
+pub fn diff_bead(bead_id: &str, stat: bool) -> Result<DiffOutput, Box<dyn std::error::Error>> {
+    let worktree_path: Option<String> = with_db(|conn| {
+        conn.query_row(
+            "SELECT worktree_path FROM claims WHERE bead_id = ?1",
+            [bead_id],
+            |row| row.get(0),
+        )
+        .optional()
+    })?;
+
+    let Some(worktree_path) = worktree_path else {
+        return Ok(DiffOutput {
+            success: false,
+            bead_id: bead_id.to_string(),
+            files_changed: Vec::new(),
+            message: format!("No claim found for {}", bead_id),
+        });
+    };
+
+    if !Path::new(&worktree_path).exists() {
+        return Ok(DiffOutput {
+            success: false,
+            bead_id: bead_id.to_string(),
+            files_changed: Vec::new(),
+            message: format!("Worktree for {} is missing: {}", bead_id, worktree_path),
+        });
+    }
+
+    let output = Command::new("git")
+        .args(["diff", "HEAD", "--no-color", "--no-ext-diff"])
+        .current_dir(&worktree_path)
+        .output()?;
+
+    if !output.status.success() {
+        return Ok(DiffOutput {
+            success: false,
+            bead_id: bead_id.to_string(),
+            files_changed: Vec::new(),
+            message: format!("git diff failed: {}", String::from_utf8_lossy(&output.stderr).trim()),
+        });
+    }
+
+    let mut files = parse_diff(&String::from_utf8_lossy(&output.stdout));
+    if stat {
+        for file in &mut files {
+            file.patch.clear();
+        }
+    }
+
+    let (additions, deletions) = files
+        .iter()
+        .fold((0, 0), |(a, d), f| (a + f.additions, d + f.deletions));
+    let message = if files.is_empty() {
+        format!("No uncommitted changes in {}", bead_id)
+    } else {
+        format!(
+            "{} file(s) changed, {} insertion(s), {} deletion(s)",
+            files.len(),
+            additions,
+            deletions
+        )
+    };
+
+    Ok(DiffOutput {
+        success: true,
+        bead_id: bead_id.to_string(),
+        files_changed: files,
+        message,
+    })
+}
+
+/// Split a unified diff into one entry per `diff --git` section
+fn parse_diff(diff: &str) -> Vec<FileDiff> {
+    let mut files: Vec<FileDiff> = Vec::new();
+    let mut in_hunk = false;
+
+    for line in diff.lines() {
+        if let Some(header) = line.strip_prefix("diff --git ") {
+            in_hunk = false;
+            files.push(FileDiff {
+                // "a/<path> b/<path>"; refined below by the +++/--- lines
+                path: header
+                    .split_once(" b/")
+                    .map_or(header, |(_, b)| b)
+                    .to_string(),
+                additions: 0,
+                deletions: 0,
+                patch: String::new(),
+            });
+        }
+        let Some(file) = files.last_mut() else {
+            continue;
+        };
+        file.patch.push_str(line);
+        file.patch.push('\n');
+
+        if line.starts_with("@@") {
+            in_hunk = true;
+        } else if !in_hunk {
+            if let Some(path) = line.strip_prefix("+++ b/") {
+                file.path = path.to_string();
+            } else if let Some(path) = line.strip_prefix("rename to ") {
+                file.path = path.to_string();
+            }
+        } else if line.starts_with('+') {
+            file.additions += 1;
+        } else if line.starts_with('-') {
+            file.deletions += 1;
+        }
+    }
+
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_diff() {
+        let diff = "\
+diff --git a/src/lib.rs b/src/lib.rs
+index 1111111..2222222 100644
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -1,3 +1,3 @@
+ fn a() {}
+--- removed comment
++fn b() {}
++++ added comment
+diff --git a/old.txt b/old.txt
+deleted file mode 100644
+index 3333333..0000000
+--- a/old.txt
++++ /dev/null
+@@ -1 +0,0 @@
+-gone
+";
+        let files = parse_diff(diff);
+
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0].path, "src/lib.rs");
+        // Lines that look like ---/+++ headers inside a hunk are still content
+        assert_eq!((files[0].additions, files[0].deletions), (2, 1));
+        assert!(files[0].patch.starts_with("diff --git a/src/lib.rs"));
+        assert!(!files[0].patch.contains("old.txt"));
+        assert_eq!(files[1].path, "old.txt");
+        assert_eq!((files[1].additions, files[1].deletions), (0, 1));
+        assert!(parse_diff("").is_empty());
+    }
+}
@@ -0,0 +1,182 @@
+//! Diff symbols tool - named symbols a bead's branch adds, modifies, or deletes
+//!
+//! Both sides are parsed from git rather than the symbols table, which only
+//! reflects the indexed workspace: each file the branch changed is read at the
+//! merge base with the target branch and at the branch tip, and symbol hashes
+//! are compared by fq_name. Measuring from the merge base keeps work landed on
+//! the target since the bead was claimed from showing up as the bead's own.
+//! Only committed work counts.
+
+use crate::config::DEFAULT_TARGET_BRANCH;
+use crate::db::with_db;
+use crate::indexer::{self, Language};
+use crate::worktree;
+use rusqlite::OptionalExtension;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::process::Command;
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct SymbolChange {
+    pub fq_name: String,
+    pub old_hash: String,
+    pub new_hash: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BranchSymbolDiff {
+    pub bead_id: String,
+    pub target_branch: String,
+    pub merge_base: String,
+    /// Changed files the indexer can parse
+    pub files: Vec<String>,
+    pub added: Vec<String>,
+    pub modified: Vec<SymbolChange>,
+    pub deleted: Vec<String>,
+    pub message: String,
+}
+
+/// Compare the symbols in a bead's branch against its merge base with the target
+pub fn diff_symbols(workspace_root: &Path, bead_id: &str) -> Result<BranchSymbolDiff, Box<dyn std::error::Error>> {
+    let target_branch: Option<String> = with_db(|conn| {
+        conn.query_row(
+            "SELECT target_branch FROM claims WHERE bead_id = ?1",
+            [bead_id],
+            |row| row.get(0),
+        )
+        .optional()
+    })?
+    .flatten();
+    let target_branch = target_branch.unwrap_or_else(|| DEFAULT_TARGET_BRANCH.to_string());
+    let branch = worktree::branch_name(bead_id);
+
+    let merge_base = worktree::git_output(workspace_root, &["merge-base", &target_branch, &branch])?;
+    let files: Vec<String> = worktree::git_output(workspace_root, &["diff", "--name-only", &merge_base, &branch])?
+        .lines()
+        .filter(|f| {
+            Path::new(f)
+                .extension()
+                .and_then(|e| e.to_str())
+                .and_then(Language::from_extension)
+                .is_some()
+        })
+        .map(String::from)
+        .collect();
+
+    let mut parser = indexer::Parser::new()?;
+    let mut old = BTreeMap::new();
+    let mut new = BTreeMap::new();
+    for file in &files {
+        // A file missing on one side was added or deleted wholesale
+        for (rev, hashes) in [(&merge_base, &mut old), (&branch, &mut new)] {
+            if let Some(source) = show_file(workspace_root, rev, file) {
+                // A file that doesn't parse contributes no symbols
+                let symbols = indexer::extract_file(&mut parser, file, &source)
+                    .map(|(symbols, _)| symbols)
+                    .unwrap_or_default();
+                hashes.extend(symbols.into_iter().map(|s| (s.fq_name, s.hash)));
+            }
+        }
+    }
+
+    let (added, modified, deleted) = compare_hashes(&old, &new);
+    let message = format!(
+        "{} added, {} modified, {} deleted across {} files vs {}",
+        added.len(),
+        modified.len(),
+        deleted.len(),
+        files.len(),
+        target_branch
+    );
+
+    Ok(BranchSymbolDiff {
+        bead_id: bead_id.to_string(),
+        target_branch,
+        merge_base,
+        files,
+        added,
+        modified,
+        deleted,
+        message,
+    })
+}
+
+/// (added, modified, deleted) between two fq_name -> hash maps, sorted by name
+fn compare_hashes(
+    old: &BTreeMap<String, String>,
+    new: &BTreeMap<String, String>,
+) -> (Vec<String>, Vec<SymbolChange>, Vec<String>) {
+    let added = new.keys().filter(|k| !old.contains_key(*k)).cloned().collect();
+    let deleted = old.keys().filter(|k| !new.contains_key(*k)).cloned().collect();
+    let modified = new
+        .iter()
+        .filter_map(|(fq_name, new_hash)| {
+            let old_hash = old.get(fq_name).filter(|h| *h != new_hash)?;
+            Some(SymbolChange {
+                fq_name: fq_name.clone(),
+                old_hash: old_hash.clone(),
+                new_hash: new_hash.clone(),
+            })
+        })
+        .collect();
+    (added, modified, deleted)
+}
+
+/// A file's content at `rev`, or None if it doesn't exist there
+fn show_file(workspace_root: &Path, rev: &str, file: &str) -> Option<String> {
+    let output = Command::new("git")
+        .args(["show", &format!("{}:{}", rev, file)])
+        .current_dir(workspace_root)
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{close_db, init_db, test_lock};
+    use crate::worktree::test_git;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_diff_symbols() {
+        let _lock = test_lock();
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        test_git(root, &["init", "-q", "-b", "main"]);
+        test_git(root, &["config", "user.email", "test@example.com"]);
+        test_git(root, &["config", "user.name", "Test"]);
+        std::fs::create_dir(root.join("src")).unwrap();
+        std::fs::write(root.join("src/a.rs"), "fn keep() {}\nfn change() { 1; }\nfn remove() {}\n").unwrap();
+        std::fs::write(root.join("notes.txt"), "x\n").unwrap();
+        test_git(root, &["add", "."]);
+        test_git(root, &["commit", "-qm", "init"]);
+        init_db(Some(root.join("test.db").to_str().unwrap()), true).unwrap();
+
+        test_git(root, &["checkout", "-q", "-b", "bacchus/bd-1"]);
+        std::fs::write(root.join("src/a.rs"), "fn keep() {}\nfn change() { 2; }\nfn added() {}\n").unwrap();
+        std::fs::write(root.join("notes.txt"), "y\n").unwrap();
+        test_git(root, &["commit", "-qam", "bead work"]);
+        // Work landing on main afterwards isn't the bead's
+        test_git(root, &["checkout", "-q", "main"]);
+        std::fs::write(root.join("src/b.rs"), "fn other() {}\n").unwrap();
+        test_git(root, &["add", "."]);
+        test_git(root, &["commit", "-qm", "main work"]);
+
+        let diff = diff_symbols(root, "bd-1").unwrap();
+        assert_eq!(diff.files, ["src/a.rs"]);
+        assert_eq!(diff.added, ["src/a.rs::added"]);
+        assert_eq!(diff.deleted, ["src/a.rs::remove"]);
+        assert_eq!(diff.modified.len(), 1);
+        assert_eq!(diff.modified[0].fq_name, "src/a.rs::change");
+        assert_ne!(diff.modified[0].old_hash, diff.modified[0].new_hash);
+
+        assert!(diff_symbols(root, "bd-missing").is_err());
+        close_db();
+    }
+}
@@ -0,0 +1,267 @@
+//! Doctor tool - diagnose setup problems before they surface as odd failures
+//!
+//! Unlike `verify`, which checks a working workspace for drift, doctor checks
+//! the prerequisites: tools on PATH, the repository, both databases, and the
+//! path overrides in the environment. It never creates or migrates anything.
+
+use super::db_integrity::integrity_issues;
+use super::verify::Severity;
+use crate::beads;
+use crate::db::{get_current_version, latest_version};
+use rusqlite::{Connection, OpenFlags};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::process::Command;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DoctorCheck {
+    pub name: String,
+    pub severity: Severity,
+    pub passed: bool,
+    pub detail: String,
+    /// How to fix a failed check
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hint: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DoctorOutput {
+    /// False if any error-severity check failed
+    pub ok: bool,
+    pub checks: Vec<DoctorCheck>,
+}
+
+/// `quick` skips the database integrity check, which reads every page
+pub fn run_doctor(db_path: &Path, workspace_root: &Path, quick: bool) -> DoctorOutput {
+    let mut checks = vec![
+        tool_check("git", Severity::Error, "Install git and make sure it is on PATH"),
+        tool_check("bd", Severity::Warning, "Install the beads CLI (bd); bacchus uses it to read and update beads"),
+        repo_check(),
+        beads_db_check(workspace_root),
+        bacchus_db_check(db_path),
+    ];
+    checks.extend(env_checks(workspace_root));
+    checks.push(schema_check(db_path));
+    if !quick {
+        checks.push(integrity_check(db_path));
+    }
+
+    DoctorOutput {
+        ok: !checks.iter().any(|c| !c.passed && c.severity == Severity::Error),
+        checks,
+    }
+}
+
+/// One line per check, `✓`/`✗` (or `!` for a failed warning) plus the hint
+pub fn render_doctor(output: &DoctorOutput) -> String {
+    let mut lines = Vec::new();
+    for check in &output.checks {
+        let mark = match (check.passed, check.severity) {
+            (true, _) => "✓",
+            (false, Severity::Error) => "✗",
+            (false, Severity::Warning) => "!",
+        };
+        lines.push(format!("{} {}: {}", mark, check.name, check.detail));
+        if let (false, Some(hint)) = (check.passed, &check.hint) {
+            lines.push(format!("    hint: {}", hint));
+        }
+    }
+    lines.push(if output.ok { "No problems found" } else { "Problems found" }.to_string());
+    lines.join("\n")
+}
+
+fn check(name: &str, severity: Severity, result: Result<String, (String, String)>) -> DoctorCheck {
+    let (passed, detail, hint) = match result {
+        Ok(detail) => (true, detail, None),
+        Err((detail, hint)) => (false, detail, Some(hint)),
+    };
+    DoctorCheck {
+        name: name.to_string(),
+        severity,
+        passed,
+        detail,
+        hint,
+    }
+}
+
+fn tool_check(binary: &str, severity: Severity, hint: &str) -> DoctorCheck {
+    let result = match Command::new(binary).arg("--version").output() {
+        Ok(output) if output.status.success() => {
+            Ok(String::from_utf8_lossy(&output.stdout).lines().next().unwrap_or("").trim().to_string())
+        }
+        _ => Err((format!("{} not found", binary), hint.to_string())),
+    };
+    check(&format!("{} available", binary), severity, result)
+}
+
+fn repo_check() -> DoctorCheck {
+    let inside = Command::new("git")
+        .args(["rev-parse", "--is-inside-work-tree"])
+        .output()
+        .is_ok_and(|o| o.status.success());
+    let result = if inside {
+        Ok("current directory is inside a git repository".to_string())
+    } else {
+        Err((
+            "current directory is not inside a git repository".to_string(),
+            "Run bacchus from your project's git checkout".to_string(),
+        ))
+    };
+    check("git repository", Severity::Error, result)
+}
+
+fn beads_db_check(workspace_root: &Path) -> DoctorCheck {
+    let path = beads::beads_db_path(workspace_root);
+    let result = match std::fs::File::open(&path) {
+        Ok(_) => Ok(format!("{} is readable", path.display())),
+        Err(e) => Err((
+            format!("{}: {}", path.display(), e),
+            "Run `bacchus init` (or `bd init`) in the workspace root".to_string(),
+        )),
+    };
+    check("beads database", Severity::Error, result)
+}
+
+fn bacchus_db_check(db_path: &Path) -> DoctorCheck {
+    let result = if db_path.exists() {
+        match std::fs::OpenOptions::new().write(true).open(db_path) {
+            Ok(_) => Ok(format!("{} is writable", db_path.display())),
+            Err(e) => Err((
+                format!("{}: {}", db_path.display(), e),
+                "Fix the file's permissions so your user can write it".to_string(),
+            )),
+        }
+    } else {
+        let parent = db_path.parent().filter(|p| !p.as_os_str().is_empty()).unwrap_or(Path::new("."));
+        match std::fs::metadata(parent) {
+            Ok(meta) if meta.is_dir() && !meta.permissions().readonly() => {
+                Ok(format!("{} will be created on first use", db_path.display()))
+            }
+            _ => Err((
+                format!("{} does not exist and {} is not a writable directory", db_path.display(), parent.display()),
+                "Run `bacchus init` in the workspace root".to_string(),
+            )),
+        }
+    };
+    check("bacchus database", Severity::Error, result)
+}
+
+fn env_checks(workspace_root: &Path) -> Vec<DoctorCheck> {
+    let db_path = match std::env::var("BACCHUS_DB_PATH") {
+        Err(_) => Ok("not set".to_string()),
+        Ok(value) => {
+            let parent = Path::new(&value).parent().filter(|p| !p.as_os_str().is_empty());
+            if parent.is_none_or(|p| p.is_dir()) {
+                Ok(value)
+            } else {
+                Err((
+                    format!("{}: parent directory does not exist", value),
+                    "Point BACCHUS_DB_PATH at a file in an existing directory, or unset it".to_string(),
+                ))
+            }
+        }
+    };
+
+    let worktrees = match std::env::var("BACCHUS_WORKTREES") {
+        Err(_) => Ok("not set".to_string()),
+        Ok(value) if workspace_root.join(&value).is_dir() => Ok(value),
+        Ok(value) => Err((
+            format!("{}: not a directory", value),
+            "Create the directory or unset BACCHUS_WORKTREES".to_string(),
+        )),
+    };
+
+    vec![
+        check("BACCHUS_DB_PATH", Severity::Error, db_path),
+        check("BACCHUS_WORKTREES", Severity::Error, worktrees),
+    ]
+}
+
+fn schema_check(db_path: &Path) -> DoctorCheck {
+    let latest = latest_version();
+    let version = Connection::open_with_flags(db_path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+        .and_then(|conn| get_current_version(&conn));
+    let result = match version {
+        Ok(v) if v == latest => Ok(format!("version {} (latest)", v)),
+        Ok(v) if v > latest => Err((
+            format!("version {} is newer than this bacchus ({})", v, latest),
+            "Upgrade bacchus with `bacchus self-update`".to_string(),
+        )),
+        Ok(v) => Err((
+            format!("version {}, latest is {}", v, latest),
+            "Run any bacchus command that uses the database (e.g. `bacchus status`) to migrate".to_string(),
+        )),
+        Err(e) => Err((
+            format!("can't open {}: {}", db_path.display(), e),
+            "Run `bacchus init` in the workspace root".to_string(),
+        )),
+    };
+    check("schema version", Severity::Error, result)
+}
+
+fn integrity_check(db_path: &Path) -> DoctorCheck {
+    let result = if !db_path.exists() {
+        Ok("no database yet".to_string())
+    } else {
+        match Connection::open_with_flags(db_path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+            .and_then(|conn| integrity_issues(&conn, false))
+        {
+            Ok(issues) if issues.is_empty() => Ok("no corruption or foreign key violations".to_string()),
+            Ok(issues) => Err((
+                format!("{} issue(s), first: {}", issues.len(), issues[0]),
+                "Run `bacchus db integrity-check` for the full list".to_string(),
+            )),
+            Err(e) => Err((
+                format!("can't check {}: {}", db_path.display(), e),
+                "Run `bacchus db integrity-check` for details".to_string(),
+            )),
+        }
+    };
+    check("database integrity", Severity::Error, result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bacchus_db_and_schema_checks() {
+        let temp = tempfile::tempdir().unwrap();
+        let db_path = temp.path().join("bacchus.db");
+
+        assert!(bacchus_db_check(&db_path).passed);
+        assert!(!bacchus_db_check(&temp.path().join("missing/bacchus.db")).passed);
+        assert!(!schema_check(&db_path).passed);
+
+        assert!(integrity_check(&db_path).passed);
+
+        let conn = Connection::open(&db_path).unwrap();
+        crate::db::apply_migrations(&conn, true).unwrap();
+        assert!(schema_check(&db_path).passed);
+        assert!(integrity_check(&db_path).passed);
+
+        conn.execute("DELETE FROM schema_version WHERE version = ?1", [latest_version()]).unwrap();
+        let stale = schema_check(&db_path);
+        assert!(!stale.passed);
+        assert!(stale.hint.unwrap().contains("migrate"));
+    }
+
+    #[test]
+    fn test_render_doctor() {
+        let output = DoctorOutput {
+            ok: false,
+            checks: vec![
+                check("git available", Severity::Error, Ok("git version 2.43.0".to_string())),
+                check("bd available", Severity::Warning, Err(("bd not found".to_string(), "Install bd".to_string()))),
+                check("beads database", Severity::Error, Err(("missing".to_string(), "Run init".to_string()))),
+            ],
+        };
+        assert_eq!(
+            render_doctor(&output),
+            "✓ git available: git version 2.43.0\n\
+             ! bd available: bd not found\n    hint: Install bd\n\
+             ✗ beads database: missing\n    hint: Run init\n\
+             Problems found"
+        );
+    }
+}
@@ -0,0 +1,166 @@
+//! Estimate tool - how much context a bead's work is likely to need
+//!
+//! Three methods, best first:
+//! - `symbols`: the line counts of the symbols linked in `bead_symbols`
+//! - `file_content`: the size of the tracked files matching the bead's file
+//!   hints (`register-file-hint`), at about four characters per token
+//! - `heuristic`: a flat per-bead guess when there is nothing to measure
+//!
+//! A bead over `TOKEN_THRESHOLD` is a candidate for `bacchus split`.
+
+use crate::db::with_db;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::process::Command;
+
+/// Estimates above this suggest splitting the bead
+pub const TOKEN_THRESHOLD: i64 = 100_000;
+/// Rough tokens per line of source code
+const TOKENS_PER_LINE: i64 = 10;
+const CHARS_PER_TOKEN: i64 = 4;
+/// Guess for a bead with no symbols or file hints
+const HEURISTIC_TOKENS: i64 = 20_000;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EstimateOutput {
+    pub bead_id: String,
+    pub estimated_tokens: i64,
+    pub file_count: usize,
+    pub symbol_count: usize,
+    pub exceeds_threshold: bool,
+    /// Beads of at most `TOKEN_THRESHOLD` tokens the work would split into
+    pub suggested_split_count: i64,
+    /// `symbols`, `file_content`, or `heuristic`
+    pub estimation_method: String,
+}
+
+pub fn estimate_task_size(bead_id: &str, workspace_root: &Path) -> rusqlite::Result<EstimateOutput> {
+    let (symbol_count, file_count, lines): (usize, usize, i64) = with_db(|conn| {
+        conn.query_row(
+            "SELECT COUNT(DISTINCT s.fq_name), COUNT(DISTINCT s.file), COALESCE(SUM(s.line_count), 0)
+             FROM (SELECT DISTINCT symbol_fq_name FROM bead_symbols WHERE bead_id = ?1) bs
+             JOIN symbols s ON s.fq_name = bs.symbol_fq_name",
+            [bead_id],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )
+    })?;
+
+    let (estimated_tokens, file_count, method) = if symbol_count > 0 {
+        (lines * TOKENS_PER_LINE, file_count, "symbols")
+    } else {
+        let files = hinted_files(bead_id, workspace_root)?;
+        if files.is_empty() {
+            (HEURISTIC_TOKENS, 0, "heuristic")
+        } else {
+            let chars: i64 = files
+                .iter()
+                .filter_map(|f| std::fs::read_to_string(workspace_root.join(f)).ok())
+                .map(|content| content.chars().count() as i64)
+                .sum();
+            (chars / CHARS_PER_TOKEN, files.len(), "file_content")
+        }
+    };
+
+    let exceeds_threshold = estimated_tokens > TOKEN_THRESHOLD;
+    Ok(EstimateOutput {
+        bead_id: bead_id.to_string(),
+        estimated_tokens,
+        file_count,
+        symbol_count,
+        exceeds_threshold,
+        suggested_split_count: if exceeds_threshold {
+            (estimated_tokens + TOKEN_THRESHOLD - 1) / TOKEN_THRESHOLD
+        } else {
+            1
+        },
+        estimation_method: method.to_string(),
+    })
+}
+
+/// Tracked files matching any of the bead's file hints
+fn hinted_files(bead_id: &str, workspace_root: &Path) -> rusqlite::Result<Vec<String>> {
+    let has_hints: bool = with_db(|conn| {
+        conn.query_row(
+            "SELECT EXISTS(SELECT 1 FROM bead_file_hints WHERE bead_id = ?1)",
+            [bead_id],
+            |row| row.get(0),
+        )
+    })?;
+    if !has_hints {
+        return Ok(Vec::new());
+    }
+
+    let tracked = Command::new("git")
+        .args(["ls-files"])
+        .current_dir(workspace_root)
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| String::from_utf8_lossy(&o.stdout).to_string())
+        .unwrap_or_default();
+
+    with_db(|conn| {
+        let mut stmt = conn.prepare(
+            "SELECT EXISTS(SELECT 1 FROM bead_file_hints WHERE bead_id = ?1 AND ?2 GLOB file_pattern)",
+        )?;
+        let mut files = Vec::new();
+        for file in tracked.lines() {
+            if stmt.query_row([bead_id, file], |row| row.get(0))? {
+                files.push(file.to_string());
+            }
+        }
+        Ok(files)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{close_db, init_db, test_lock};
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_estimation_methods() {
+        let _lock = test_lock();
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        init_db(Some(root.join("test.db").to_str().unwrap()), true).unwrap();
+
+        let estimate = estimate_task_size("bd-1", root).unwrap();
+        assert_eq!(estimate.estimation_method, "heuristic");
+        assert_eq!(estimate.estimated_tokens, HEURISTIC_TOKENS);
+
+        Command::new("git").arg("init").current_dir(root).output().unwrap();
+        std::fs::create_dir(root.join("src")).unwrap();
+        std::fs::write(root.join("src/a.rs"), "x".repeat(400)).unwrap();
+        std::fs::write(root.join("b.rs"), "x".repeat(4000)).unwrap();
+        Command::new("git").args(["add", "."]).current_dir(root).output().unwrap();
+        crate::tools::register_file_hint("bd-1", "src/*").unwrap();
+
+        let estimate = estimate_task_size("bd-1", root).unwrap();
+        assert_eq!(estimate.estimation_method, "file_content");
+        assert_eq!((estimate.file_count, estimate.estimated_tokens), (1, 100));
+
+        with_db(|conn| {
+            conn.execute_batch(
+                "INSERT INTO symbols (file, fq_name, kind, span_start_line, span_end_line, line_count, hash) VALUES
+                   ('src/a.rs', 'src/a.rs::f', 'function', 1, 9000, 9000, 'h1'),
+                   ('src/a.rs', 'src/a.rs::g', 'function', 1, 3000, 3000, 'h2');
+                 INSERT INTO bead_symbols (bead_id, symbol_fq_name, relation) VALUES
+                   ('bd-1', 'src/a.rs::f', 'modifies'),
+                   ('bd-1', 'src/a.rs::f', 'creates'),
+                   ('bd-1', 'src/a.rs::g', 'modifies');",
+            )
+        })
+        .unwrap();
+
+        let estimate = estimate_task_size("bd-1", root).unwrap();
+        assert_eq!(estimate.estimation_method, "symbols");
+        assert_eq!((estimate.symbol_count, estimate.file_count), (2, 1));
+        assert_eq!(estimate.estimated_tokens, 120_000);
+        assert!(estimate.exceeds_threshold);
+        assert_eq!(estimate.suggested_split_count, 2);
+
+        close_db();
+    }
+}
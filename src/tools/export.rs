@@ -0,0 +1,192 @@
+//! Export tool - dump the whole bacchus database for backup or external tooling
+//!
+//! `json` writes newline-delimited JSON to a single file: a manifest line
+//! first, then one object per row tagged with its `_table`. `csv` treats the
+//! path as a directory and writes `manifest.json` plus one `<table>.csv` per
+//! table. FTS shadow tables are skipped; they are rebuilt from `symbols`.
+
+use super::archive::rows_to_json;
+use super::export_beads::csv_field;
+use crate::db::{get_current_version, with_db};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExportManifest {
+    pub schema_version: i32,
+    pub exported_at: String,
+    pub bacchus_version: String,
+    /// Row count per exported table
+    pub tables: BTreeMap<String, usize>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExportOutput {
+    pub success: bool,
+    pub path: String,
+    pub format: String,
+    pub manifest: ExportManifest,
+    pub message: String,
+}
+
+struct TableDump {
+    name: String,
+    columns: Vec<String>,
+    rows: Vec<serde_json::Value>,
+}
+
+/// Export every table (minus `symbols` unless `include_symbols`) to `path`
+pub fn export_workspace(path: &Path, format: &str, include_symbols: bool) -> Result<String, String> {
+    if !matches!(format, "json" | "csv") {
+        return Err(format!("Unknown format: {}. Use json or csv", format));
+    }
+
+    let (schema_version, tables) = with_db(|conn| {
+        let mut stmt = conn.prepare(
+            "SELECT name FROM pragma_table_list
+             WHERE schema = 'main' AND type = 'table' AND name NOT LIKE 'sqlite_%'
+             ORDER BY name",
+        )?;
+        let names = stmt
+            .query_map([], |row| row.get::<_, String>(0))?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        let mut tables = Vec::new();
+        for name in names.into_iter().filter(|n| include_symbols || n != "symbols") {
+            let mut stmt = conn.prepare(&format!("SELECT name FROM pragma_table_info('{}') ORDER BY cid", name))?;
+            let columns = stmt
+                .query_map([], |row| row.get::<_, String>(0))?
+                .collect::<rusqlite::Result<Vec<_>>>()?;
+            let rows = rows_to_json(conn, &format!("SELECT * FROM \"{}\"", name), [])?;
+            tables.push(TableDump { name, columns, rows });
+        }
+        Ok((get_current_version(conn)?, tables))
+    })
+    .map_err(|e| e.to_string())?;
+
+    let manifest = ExportManifest {
+        schema_version,
+        exported_at: chrono::Utc::now().to_rfc3339(),
+        bacchus_version: env!("CARGO_PKG_VERSION").to_string(),
+        tables: tables.iter().map(|t| (t.name.clone(), t.rows.len())).collect(),
+    };
+
+    match format {
+        "csv" => write_csv(path, &manifest, &tables),
+        _ => write_ndjson(path, &manifest, &tables),
+    }
+    .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+
+    let total: usize = manifest.tables.values().sum();
+    let output = ExportOutput {
+        success: true,
+        path: path.to_string_lossy().to_string(),
+        format: format.to_string(),
+        message: format!(
+            "Exported {} row(s) from {} table(s) to {}",
+            total,
+            manifest.tables.len(),
+            path.display()
+        ),
+        manifest,
+    };
+    serde_json::to_string_pretty(&output).map_err(|e| e.to_string())
+}
+
+fn write_ndjson(path: &Path, manifest: &ExportManifest, tables: &[TableDump]) -> std::io::Result<()> {
+    let mut header = serde_json::to_value(manifest)?;
+    header["_table"] = "_manifest".into();
+    let mut lines = vec![header.to_string()];
+
+    for table in tables {
+        for row in &table.rows {
+            let mut row = row.clone();
+            row["_table"] = table.name.clone().into();
+            lines.push(row.to_string());
+        }
+    }
+
+    let mut content = lines.join("\n");
+    content.push('\n');
+    std::fs::write(path, content)
+}
+
+fn write_csv(dir: &Path, manifest: &ExportManifest, tables: &[TableDump]) -> std::io::Result<()> {
+    std::fs::create_dir_all(dir)?;
+    std::fs::write(dir.join("manifest.json"), serde_json::to_string_pretty(manifest)?)?;
+    for table in tables {
+        std::fs::write(dir.join(format!("{}.csv", table.name)), render_csv(table))?;
+    }
+    Ok(())
+}
+
+fn render_csv(table: &TableDump) -> String {
+    let mut out = table.columns.iter().map(|c| csv_field(c)).collect::<Vec<_>>().join(",");
+    out.push('\n');
+    for row in &table.rows {
+        let fields: Vec<String> = table
+            .columns
+            .iter()
+            .map(|c| match &row[c] {
+                serde_json::Value::Null => String::new(),
+                serde_json::Value::String(s) => csv_field(s),
+                other => csv_field(&other.to_string()),
+            })
+            .collect();
+        out.push_str(&fields.join(","));
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{close_db, init_db, test_lock};
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_export_formats() {
+        let _lock = test_lock();
+        let dir = tempdir().unwrap();
+        init_db(Some(dir.path().join("test.db").to_str().unwrap()), true).unwrap();
+
+        with_db(|conn| {
+            conn.execute_batch(
+                "INSERT INTO claims (bead_id, agent_id, worktree_path, branch_name, start_commit, claimed_at, title)
+                 VALUES ('BEAD-1', 'agent-a', '/wt', 'bacchus/BEAD-1', 'abc', 5, 'Fix, \"quoted\" bug');
+                 INSERT INTO symbols (file, fq_name, kind, span_start_line, span_end_line, line_count, hash)
+                 VALUES ('src/a.rs', 'src/a.rs::f', 'function', 1, 3, 3, 'h');",
+            )
+        })
+        .unwrap();
+
+        let ndjson = dir.path().join("export.ndjson");
+        export_workspace(&ndjson, "json", false).unwrap();
+        let lines: Vec<serde_json::Value> = std::fs::read_to_string(&ndjson)
+            .unwrap()
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+        assert_eq!(lines[0]["_table"], "_manifest");
+        assert_eq!(lines[0]["tables"]["claims"], 1);
+        assert!(lines[0]["tables"].get("symbols").is_none());
+        assert!(lines[0]["tables"].get("symbols_fts_data").is_none());
+        let claim = lines.iter().find(|l| l["_table"] == "claims").unwrap();
+        assert_eq!(claim["bead_id"], "BEAD-1");
+        assert_eq!(claim["claimed_at"], 5);
+
+        let csv_dir = dir.path().join("csv");
+        export_workspace(&csv_dir, "csv", true).unwrap();
+        let manifest: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(csv_dir.join("manifest.json")).unwrap()).unwrap();
+        assert_eq!(manifest["tables"]["symbols"], 1);
+        let claims = std::fs::read_to_string(csv_dir.join("claims.csv")).unwrap();
+        assert!(claims.starts_with("bead_id,agent_id,"));
+        assert!(claims.contains("\"Fix, \"\"quoted\"\" bug\""));
+
+        assert!(export_workspace(&ndjson, "xml", false).is_err());
+        close_db();
+    }
+}
@@ -0,0 +1,171 @@
+//! Export beads tool - dump every bead with its dependencies for backup or migration
+//!
+//! Read-only: beads come from `bd list --all` and `bd show`, nothing is written
+//! to either database.
+
+use crate::beads::{self, BeadInfo};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+pub(crate) const CSV_HEADER: [&str; 7] = ["id", "title", "status", "priority", "description", "labels", "depends_on"];
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExportedBead {
+    #[serde(flatten)]
+    pub bead: BeadInfo,
+    /// IDs of the beads this one depends on
+    #[serde(default)]
+    pub depends_on: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExportBeadsOutput {
+    pub success: bool,
+    pub count: usize,
+    pub output: String,
+    pub message: String,
+}
+
+/// Export beads as `json`, `csv`, or `markdown`, optionally writing to `output`
+pub fn export_beads(format: &str, output: Option<&PathBuf>, include_closed: bool) -> Result<String, String> {
+    let beads: Vec<ExportedBead> = beads::list_all_beads()
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .filter(|b| include_closed || b.status != "closed")
+        .map(|bead| {
+            let depends_on = beads::get_bead_dependencies(&bead.id)
+                .map(|deps| dependency_ids(&deps.depends_on))
+                .unwrap_or_default();
+            ExportedBead { bead, depends_on }
+        })
+        .collect();
+
+    let rendered = render(&beads, format)?;
+
+    let Some(path) = output else {
+        return Ok(rendered);
+    };
+
+    std::fs::write(path, rendered).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+
+    let result = ExportBeadsOutput {
+        success: true,
+        count: beads.len(),
+        output: path.to_string_lossy().to_string(),
+        message: format!("Exported {} beads to {}", beads.len(), path.display()),
+    };
+    serde_json::to_string_pretty(&result).map_err(|e| e.to_string())
+}
+
+pub(crate) fn render(beads: &[ExportedBead], format: &str) -> Result<String, String> {
+    match format {
+        "json" => serde_json::to_string_pretty(beads).map_err(|e| e.to_string()),
+        "csv" => Ok(render_csv(beads)),
+        "markdown" => Ok(render_markdown(beads)),
+        _ => Err(format!("Unknown format: {}. Use json, csv, or markdown", format)),
+    }
+}
+
+/// Dependency entries from `bd show` are objects; keep just their IDs
+pub(crate) fn dependency_ids(deps: &[serde_json::Value]) -> Vec<String> {
+    deps.iter()
+        .filter_map(|d| d["id"].as_str().or(d.as_str()))
+        .map(String::from)
+        .collect()
+}
+
+fn render_csv(beads: &[ExportedBead]) -> String {
+    let mut out = format!("{}\n", CSV_HEADER.join(","));
+    for b in beads {
+        let fields = [
+            b.bead.id.clone(),
+            b.bead.title.clone(),
+            b.bead.status.clone(),
+            b.bead.priority.to_string(),
+            b.bead.description.clone().unwrap_or_default(),
+            b.bead.labels.join(","),
+            b.depends_on.join(","),
+        ];
+        let row: Vec<String> = fields.iter().map(|f| csv_field(f)).collect();
+        out.push_str(&row.join(","));
+        out.push('\n');
+    }
+    out
+}
+
+/// Quote a CSV field when it contains a delimiter, quote, or newline
+pub(crate) fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+fn render_markdown(beads: &[ExportedBead]) -> String {
+    let mut out = String::from("# Beads\n");
+    for b in beads {
+        out.push_str(&format!("\n## {}: {}\n\n", b.bead.id, b.bead.title));
+        out.push_str(&format!("**Status:** {} · **Priority:** P{}", b.bead.status, b.bead.priority));
+        if !b.bead.labels.is_empty() {
+            out.push_str(&format!(" · **Labels:** {}", b.bead.labels.join(", ")));
+        }
+        out.push('\n');
+
+        if let Some(desc) = b.bead.description.as_deref().filter(|d| !d.is_empty()) {
+            out.push_str(&format!("\n{}\n", desc));
+        }
+
+        if !b.depends_on.is_empty() {
+            out.push_str("\n**Depends on:**\n");
+            for dep in &b.depends_on {
+                out.push_str(&format!("- {}\n", dep));
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Vec<ExportedBead> {
+        vec![ExportedBead {
+            bead: BeadInfo {
+                id: "bd-1".to_string(),
+                title: "Fix login, again".to_string(),
+                description: Some("Users see \"500\"".to_string()),
+                priority: 1,
+                status: "open".to_string(),
+                labels: vec!["auth".to_string()],
+            },
+            depends_on: vec!["bd-0".to_string(), "bd-2".to_string()],
+        }]
+    }
+
+    #[test]
+    fn test_render_formats() {
+        let csv = render(&sample(), "csv").unwrap();
+        assert_eq!(
+            csv.lines().nth(1).unwrap(),
+            r#"bd-1,"Fix login, again",open,1,"Users see ""500""",auth,"bd-0,bd-2""#
+        );
+
+        let md = render(&sample(), "markdown").unwrap();
+        assert!(md.contains("## bd-1: Fix login, again"));
+        assert!(md.contains("- bd-2\n"));
+
+        let json: serde_json::Value = serde_json::from_str(&render(&sample(), "json").unwrap()).unwrap();
+        assert_eq!(json[0]["id"], "bd-1");
+        assert_eq!(json[0]["depends_on"][1], "bd-2");
+
+        assert!(render(&sample(), "yaml").is_err());
+    }
+
+    #[test]
+    fn test_dependency_ids() {
+        let deps = vec![serde_json::json!({"id": "bd-3", "title": "x"}), serde_json::json!("bd-4")];
+        assert_eq!(dependency_ids(&deps), vec!["bd-3", "bd-4"]);
+    }
+}
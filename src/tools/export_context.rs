@@ -0,0 +1,126 @@
+//! Export context tool - bundles everything an agent would see for a bead
+//!
+//! Useful for reproducing agent failures offline.
+
+use super::context::generate_context;
+use super::list::{list_claims, ClaimInfo};
+use super::symbols::{find_symbols, FindSymbolsInput, SymbolInfo};
+use crate::beads::{self, BeadDependencies};
+use crate::db::with_db;
+use rusqlite::OptionalExtension;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ContextBundle {
+    pub bead_id: String,
+    pub context_text: String,
+    pub changed_files: Vec<String>,
+    pub symbols: Vec<SymbolInfo>,
+    pub claims: Vec<ClaimInfo>,
+    pub dependencies: BeadDependencies,
+    pub git_log: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExportContextOutput {
+    pub success: bool,
+    pub bead_id: String,
+    pub output: String,
+    pub message: String,
+}
+
+/// Build the context bundle for a bead
+pub fn build_context_bundle(bead_id: &str, workspace_root: &Path) -> Result<ContextBundle, String> {
+    let context_text = generate_context(Some(bead_id.to_string()), None, workspace_root)?;
+
+    let claim = with_db(|conn| {
+        conn.query_row(
+            "SELECT worktree_path, start_commit FROM claims WHERE bead_id = ?1",
+            [bead_id],
+            |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)),
+        )
+        .optional()
+    })
+    .map_err(|e| e.to_string())?;
+
+    // Changed files and history only exist for claimed beads with a worktree
+    let (changed_files, git_log) = match &claim {
+        Some((worktree_path, start_commit)) => {
+            let wt = Path::new(worktree_path);
+            (
+                git_lines(wt, &["diff", "--name-only", start_commit]),
+                git_lines(wt, &["log", "--oneline", &format!("{}..HEAD", start_commit)]),
+            )
+        }
+        None => (Vec::new(), Vec::new()),
+    };
+
+    let mut symbols = Vec::new();
+    for file in &changed_files {
+        let input = FindSymbolsInput {
+            file: Some(file.clone()),
+            limit: Some(1000),
+            ..Default::default()
+        };
+        symbols.extend(find_symbols(&input).map_err(|e| e.to_string())?.symbols);
+    }
+
+    let claims = list_claims().map_err(|e| e.to_string())?.claims;
+
+    // Missing bd or an unknown bead shouldn't block the rest of the export
+    let dependencies = beads::get_bead_dependencies(bead_id).unwrap_or_default();
+
+    Ok(ContextBundle {
+        bead_id: bead_id.to_string(),
+        context_text,
+        changed_files,
+        symbols,
+        claims,
+        dependencies,
+        git_log,
+    })
+}
+
+/// Export the context bundle to a file, or return it for stdout
+pub fn export_context(
+    bead_id: &str,
+    output: Option<&PathBuf>,
+    workspace_root: &Path,
+) -> Result<String, String> {
+    let bundle = build_context_bundle(bead_id, workspace_root)?;
+    let json = serde_json::to_string_pretty(&bundle).map_err(|e| e.to_string())?;
+
+    let Some(path) = output else {
+        return Ok(json);
+    };
+
+    std::fs::write(path, json).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+
+    let result = ExportContextOutput {
+        success: true,
+        bead_id: bead_id.to_string(),
+        output: path.to_string_lossy().to_string(),
+        message: format!("Exported context for {} to {}", bead_id, path.display()),
+    };
+    serde_json::to_string_pretty(&result).map_err(|e| e.to_string())
+}
+
+/// Run a git command in `dir` and return its non-empty output lines
+pub(crate) fn git_lines(dir: &Path, args: &[&str]) -> Vec<String> {
+    Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| {
+            String::from_utf8_lossy(&o.stdout)
+                .lines()
+                .filter(|l| !l.is_empty())
+                .map(String::from)
+                .collect()
+        })
+        .unwrap_or_default()
+}
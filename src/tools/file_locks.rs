@@ -0,0 +1,239 @@
+//! Advisory file locks - lets a claimed bead reserve a file it is about to edit
+//!
+//! Like symbol locks, nothing stops an edit; `register-file-hint` warns when a
+//! pattern covers a file another bead has locked. A lock belongs to the claim's
+//! agent and lasts until `unlock` or the bead is released.
+
+use crate::db::with_db;
+use rusqlite::Result;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FileLockOutput {
+    pub success: bool,
+    pub file: String,
+    pub bead_id: Option<String>,
+    pub agent_id: Option<String>,
+    /// Bead holding the lock when it belongs to another agent
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub locked_by: Option<String>,
+    pub message: String,
+}
+
+/// A file locked by a bead other than the one asking
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileLockConflict {
+    pub file_path: String,
+    pub locked_by: String,
+    pub agent_id: String,
+}
+
+struct Claim {
+    bead_id: String,
+    agent_id: String,
+    worktree_path: PathBuf,
+}
+
+/// Lock `file` for `bead_id`'s claim, or the claim whose worktree contains the current directory
+pub fn lock_file(file: &str, bead_id: Option<&str>, workspace_root: &Path) -> Result<FileLockOutput> {
+    let Some(claim) = find_claim(bead_id)? else {
+        return Ok(no_claim(file, bead_id));
+    };
+    let file_path = normalize(file, &claim.worktree_path, workspace_root);
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as i64)
+        .unwrap_or(0);
+
+    let inserted = with_db(|conn| {
+        conn.execute(
+            "INSERT OR FAIL INTO file_locks (file_path, bead_id, agent_id, locked_at) VALUES (?1, ?2, ?3, ?4)",
+            rusqlite::params![file_path, claim.bead_id, claim.agent_id, now],
+        )
+    });
+
+    let output = |success: bool, locked_by: Option<String>, message: String| FileLockOutput {
+        success,
+        file: file_path.clone(),
+        bead_id: Some(claim.bead_id.clone()),
+        agent_id: Some(claim.agent_id.clone()),
+        locked_by,
+        message,
+    };
+
+    match inserted {
+        Ok(_) => Ok(output(true, None, format!("Locked {} for {}", file_path, claim.bead_id))),
+        Err(rusqlite::Error::SqliteFailure(e, _)) if e.code == rusqlite::ErrorCode::ConstraintViolation => {
+            let (holder_bead, holder_agent): (String, String) = with_db(|conn| {
+                conn.query_row(
+                    "SELECT bead_id, agent_id FROM file_locks WHERE file_path = ?1",
+                    [&file_path],
+                    |row| Ok((row.get(0)?, row.get(1)?)),
+                )
+            })?;
+            if holder_agent == claim.agent_id {
+                Ok(output(true, None, format!("{} is already locked by {}", file_path, holder_bead)))
+            } else {
+                let message = format!("{} is locked by {} ({})", file_path, holder_bead, holder_agent);
+                Ok(output(false, Some(holder_bead), message))
+            }
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// Release the claim's agent's lock on `file`
+pub fn unlock_file(file: &str, bead_id: Option<&str>, workspace_root: &Path) -> Result<FileLockOutput> {
+    let Some(claim) = find_claim(bead_id)? else {
+        return Ok(no_claim(file, bead_id));
+    };
+    let file_path = normalize(file, &claim.worktree_path, workspace_root);
+
+    let removed = with_db(|conn| {
+        conn.execute(
+            "DELETE FROM file_locks WHERE file_path = ?1 AND agent_id = ?2",
+            [&file_path, &claim.agent_id],
+        )
+    })?;
+
+    Ok(FileLockOutput {
+        success: removed > 0,
+        message: if removed > 0 {
+            format!("Unlocked {}", file_path)
+        } else {
+            format!("{} is not locked by {}", file_path, claim.agent_id)
+        },
+        file: file_path,
+        bead_id: Some(claim.bead_id),
+        agent_id: Some(claim.agent_id),
+        locked_by: None,
+    })
+}
+
+/// Locked files matching a GLOB pattern that belong to a bead other than `bead_id`
+pub fn locked_files_matching(pattern: &str, bead_id: &str) -> Result<Vec<FileLockConflict>> {
+    with_db(|conn| {
+        let mut stmt = conn.prepare(
+            "SELECT file_path, bead_id, agent_id FROM file_locks
+             WHERE file_path GLOB ?1 AND bead_id != ?2 ORDER BY file_path",
+        )?;
+        let rows = stmt.query_map([pattern, bead_id], |row| {
+            Ok(FileLockConflict {
+                file_path: row.get(0)?,
+                locked_by: row.get(1)?,
+                agent_id: row.get(2)?,
+            })
+        })?;
+        rows.collect()
+    })
+}
+
+/// Drop every lock a bead holds
+///
+/// Deleting a claim already does this (a trigger on `claims`); this is for
+/// locks left behind regardless, e.g. by `reset --force`.
+pub fn release_file_locks(bead_id: &str) -> Result<usize> {
+    with_db(|conn| conn.execute("DELETE FROM file_locks WHERE bead_id = ?1", [bead_id]))
+}
+
+fn find_claim(bead_id: Option<&str>) -> Result<Option<Claim>> {
+    let claims: Vec<Claim> = with_db(|conn| {
+        let mut stmt = conn.prepare(
+            "SELECT bead_id, agent_id, worktree_path FROM claims WHERE ?1 IS NULL OR bead_id = ?1",
+        )?;
+        let rows = stmt.query_map([bead_id], |row| {
+            Ok(Claim {
+                bead_id: row.get(0)?,
+                agent_id: row.get(1)?,
+                worktree_path: PathBuf::from(row.get::<_, String>(2)?),
+            })
+        })?;
+        rows.collect()
+    })?;
+
+    if bead_id.is_some() {
+        return Ok(claims.into_iter().next());
+    }
+    let cwd = std::env::current_dir().ok().and_then(|d| d.canonicalize().ok());
+    Ok(cwd.and_then(|cwd| {
+        claims
+            .into_iter()
+            .find(|c| c.worktree_path.canonicalize().is_ok_and(|wt| cwd.starts_with(wt)))
+    }))
+}
+
+fn no_claim(file: &str, bead_id: Option<&str>) -> FileLockOutput {
+    FileLockOutput {
+        success: false,
+        file: file.to_string(),
+        bead_id: bead_id.map(String::from),
+        agent_id: None,
+        locked_by: None,
+        message: match bead_id {
+            Some(bead_id) => format!("No claim found for {}", bead_id),
+            None => "Not inside a claimed worktree; pass --bead".to_string(),
+        },
+    }
+}
+
+/// Repository-relative path, so a lock taken in one worktree matches the same file in another
+fn normalize(file: &str, worktree_path: &Path, workspace_root: &Path) -> String {
+    let path = Path::new(file);
+    let relative = [worktree_path, workspace_root]
+        .iter()
+        .find_map(|root| path.strip_prefix(root).ok())
+        .unwrap_or(path);
+    let relative = relative.to_string_lossy();
+    relative.strip_prefix("./").unwrap_or(&relative).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{close_db, init_db, test_lock};
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_file_locks() {
+        let _lock = test_lock();
+        let dir = tempdir().unwrap();
+        init_db(Some(dir.path().join("test.db").to_str().unwrap()), true).unwrap();
+        let root = Path::new("/repo");
+
+        with_db(|conn| {
+            conn.execute_batch(
+                "INSERT INTO claims (bead_id, agent_id, worktree_path, branch_name, start_commit, claimed_at)
+                 VALUES ('bead-a', 'agent-a', '/repo/.bacchus/worktrees/bead-a', 'bacchus/bead-a', 'abc', 1),
+                        ('bead-b', 'agent-b', '/repo/.bacchus/worktrees/bead-b', 'bacchus/bead-b', 'abc', 1);",
+            )
+        })
+        .unwrap();
+
+        let locked = lock_file("/repo/.bacchus/worktrees/bead-a/Cargo.toml", Some("bead-a"), root).unwrap();
+        assert!(locked.success);
+        assert_eq!(locked.file, "Cargo.toml");
+        assert!(lock_file("./Cargo.toml", Some("bead-a"), root).unwrap().success);
+
+        let conflict = lock_file("Cargo.toml", Some("bead-b"), root).unwrap();
+        assert!(!conflict.success);
+        assert_eq!(conflict.locked_by.as_deref(), Some("bead-a"));
+        assert!(!unlock_file("Cargo.toml", Some("bead-b"), root).unwrap().success);
+
+        assert_eq!(locked_files_matching("*.toml", "bead-b").unwrap()[0].locked_by, "bead-a");
+        assert!(locked_files_matching("*.toml", "bead-a").unwrap().is_empty());
+        assert!(!lock_file("Cargo.toml", Some("bead-z"), root).unwrap().success);
+
+        assert!(unlock_file("Cargo.toml", Some("bead-a"), root).unwrap().success);
+        assert!(lock_file("Cargo.toml", Some("bead-b"), root).unwrap().success);
+        assert_eq!(release_file_locks("bead-b").unwrap(), 1);
+
+        // Dropping a claim, by whichever path, frees its locks
+        assert!(lock_file("Cargo.toml", Some("bead-a"), root).unwrap().success);
+        with_db(|conn| conn.execute("DELETE FROM claims WHERE bead_id = 'bead-a'", [])).unwrap();
+        assert_eq!(release_file_locks("bead-a").unwrap(), 0);
+
+        close_db();
+    }
+}
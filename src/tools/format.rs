@@ -0,0 +1,224 @@
+//! Format tool - auto-format a bead's changed files in its worktree
+//!
+//! Each language among the changed files gets its formatter: `cargo fmt`,
+//! `prettier`, `black`, or `gofmt`. Files are compared before and after so
+//! only what the formatters actually touched is reported (and committed with
+//! `--commit`); earlier uncommitted edits elsewhere stay out of the commit.
+
+use super::test::{bead_worktree, changed_files};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::process::Command;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FormatOutput {
+    pub success: bool,
+    pub bead_id: String,
+    pub check: bool,
+    /// Formatters that ran
+    pub formatters: Vec<String>,
+    /// With `--check`: formatters that found files needing formatting
+    pub needs_formatting: Vec<String>,
+    /// Files the formatters rewrote
+    pub formatted_files: Vec<String>,
+    pub committed: bool,
+    pub message: String,
+}
+
+/// Format a bead's changed files; `check` only reports, `commit` commits the result
+pub fn format_bead(
+    bead_id: &str,
+    commit: bool,
+    check: bool,
+    workspace_root: &Path,
+) -> Result<FormatOutput, Box<dyn std::error::Error>> {
+    let (worktree_path, base) = bead_worktree(bead_id, workspace_root)?;
+    let changed: Vec<String> = changed_files(&worktree_path, &base)?
+        .into_iter()
+        .filter(|f| worktree_path.join(f).is_file())
+        .collect();
+
+    let commands = format_commands(&changed, check);
+    let mut output = FormatOutput {
+        success: true,
+        bead_id: bead_id.to_string(),
+        check,
+        formatters: commands.iter().map(|(name, _, _)| name.to_string()).collect(),
+        needs_formatting: Vec::new(),
+        formatted_files: Vec::new(),
+        committed: false,
+        message: String::new(),
+    };
+    if commands.is_empty() {
+        output.message = format!("No changed files with a known formatter ({} changed)", changed.len());
+        return Ok(output);
+    }
+
+    let targets: Vec<&String> = commands.iter().flat_map(|(_, _, files)| files).collect();
+    let before: Vec<Option<Vec<u8>>> = targets.iter().map(|f| std::fs::read(worktree_path.join(f)).ok()).collect();
+
+    let mut failures = Vec::new();
+    for (name, args, _) in &commands {
+        let run = Command::new(&args[0])
+            .args(&args[1..])
+            .current_dir(&worktree_path)
+            .output()
+            .map_err(|e| format!("Failed to run {}: {}", args[0], e))?;
+        // gofmt -l exits 0 and lists the files instead
+        let differs = !run.status.success() || (*name == "gofmt" && check && !run.stdout.is_empty());
+        if check && differs {
+            output.needs_formatting.push(name.to_string());
+        } else if differs {
+            failures.push(format!("{}: {}", name, String::from_utf8_lossy(&run.stderr).trim()));
+        }
+    }
+
+    if check {
+        output.success = output.needs_formatting.is_empty();
+        output.message = if output.success {
+            format!("{} changed files already formatted", targets.len())
+        } else {
+            format!(
+                "Formatting needed ({}); run `bacchus format {}`",
+                output.needs_formatting.join(", "),
+                bead_id
+            )
+        };
+        return Ok(output);
+    }
+
+    output.formatted_files = targets
+        .iter()
+        .zip(&before)
+        .filter(|(f, old)| std::fs::read(worktree_path.join(f)).ok() != **old)
+        .map(|(f, _)| f.to_string())
+        .collect();
+
+    if !failures.is_empty() {
+        output.success = false;
+        output.message = format!("Formatter failed: {}", failures.join("; "));
+        return Ok(output);
+    }
+
+    if output.formatted_files.is_empty() {
+        output.message = format!("{} changed files already formatted", targets.len());
+    } else if commit {
+        commit_formatted(&worktree_path, bead_id, &output.formatted_files)?;
+        output.committed = true;
+        output.message = format!(
+            "Formatted and committed {} files as style: auto-format for {}",
+            output.formatted_files.len(),
+            bead_id
+        );
+    } else {
+        output.message = format!("Formatted {} files (uncommitted)", output.formatted_files.len());
+    }
+    Ok(output)
+}
+
+struct Formatter {
+    name: &'static str,
+    extensions: &'static [&'static str],
+    /// Program and arguments that rewrite the files passed after them
+    write: &'static [&'static str],
+    /// Program and arguments that only report whether files need formatting
+    check: &'static [&'static str],
+}
+
+const FORMATTERS: &[Formatter] = &[
+    Formatter {
+        name: "cargo fmt",
+        extensions: &["rs"],
+        write: &["cargo", "fmt", "--"],
+        check: &["cargo", "fmt", "--check", "--"],
+    },
+    Formatter {
+        name: "prettier",
+        extensions: &["ts", "tsx", "js", "jsx", "mjs", "cjs"],
+        write: &["npx", "prettier", "--write"],
+        check: &["npx", "prettier", "--check"],
+    },
+    Formatter {
+        name: "black",
+        extensions: &["py", "pyi"],
+        write: &["black"],
+        check: &["black", "--check"],
+    },
+    Formatter {
+        name: "gofmt",
+        extensions: &["go"],
+        write: &["gofmt", "-w"],
+        check: &["gofmt", "-l"],
+    },
+];
+
+/// (formatter, program and arguments, files) for each language among `changed`
+fn format_commands(changed: &[String], check: bool) -> Vec<(&'static str, Vec<String>, Vec<String>)> {
+    FORMATTERS
+        .iter()
+        .filter_map(|formatter| {
+            let files: Vec<String> = changed
+                .iter()
+                .filter(|f| {
+                    Path::new(f)
+                        .extension()
+                        .and_then(|e| e.to_str())
+                        .is_some_and(|e| formatter.extensions.contains(&e))
+                })
+                .cloned()
+                .collect();
+            if files.is_empty() {
+                return None;
+            }
+            let base = if check { formatter.check } else { formatter.write };
+            let args = base.iter().map(|a| a.to_string()).chain(files.iter().cloned()).collect();
+            Some((formatter.name, args, files))
+        })
+        .collect()
+}
+
+/// Commit just the formatted files, leaving anything else staged or dirty alone
+fn commit_formatted(worktree_path: &Path, bead_id: &str, files: &[String]) -> Result<(), Box<dyn std::error::Error>> {
+    let message = format!("style: auto-format for {}", bead_id);
+    for args in [
+        ["add", "--"].iter().map(|a| a.to_string()).chain(files.iter().cloned()).collect::<Vec<_>>(),
+        ["commit", "-m", &message, "--"]
+            .iter()
+            .map(|a| a.to_string())
+            .chain(files.iter().cloned())
+            .collect(),
+    ] {
+        let output = Command::new("git").args(&args).current_dir(worktree_path).output()?;
+        if !output.status.success() {
+            return Err(format!("git {} failed: {}", args[0], String::from_utf8_lossy(&output.stderr).trim()).into());
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_commands() {
+        let changed = vec![
+            "src/lib.rs".to_string(),
+            "web/app.tsx".to_string(),
+            "README.md".to_string(),
+            "main.go".to_string(),
+        ];
+
+        let commands = format_commands(&changed, false);
+        let names: Vec<&str> = commands.iter().map(|(name, _, _)| *name).collect();
+        assert_eq!(names, ["cargo fmt", "prettier", "gofmt"]);
+        assert_eq!(commands[0].1, ["cargo", "fmt", "--", "src/lib.rs"]);
+        assert_eq!(commands[2].1, ["gofmt", "-w", "main.go"]);
+
+        let commands = format_commands(&changed, true);
+        assert_eq!(commands[1].1, ["npx", "prettier", "--check", "web/app.tsx"]);
+        assert_eq!(commands[2].2, ["main.go"]);
+
+        assert!(format_commands(&["notes.txt".to_string()], false).is_empty());
+    }
+}
@@ -0,0 +1,207 @@
+//! Graph tool - render the bead dependency DAG
+//!
+//! Beads and their edges come from `bd list --all` and `bd show`, the same
+//! sources as `export-beads`. Edges point from a blocker to the bead it blocks.
+
+use super::export_beads::dependency_ids;
+use crate::beads::{self, BeadInfo};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashSet};
+
+#[derive(Debug, Serialize)]
+pub struct GraphNode {
+    pub id: String,
+    pub title: String,
+    pub status: String,
+    pub priority: i32,
+}
+
+/// Adjacency-list form: each bead with the beads it depends on
+#[derive(Debug, Serialize)]
+pub struct BeadGraph {
+    pub nodes: Vec<GraphNode>,
+    pub depends_on: BTreeMap<String, Vec<String>>,
+}
+
+pub fn bead_graph(format: &str, include_closed: bool) -> Result<String, String> {
+    let beads: Vec<(BeadInfo, Vec<String>)> = beads::list_all_beads()
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .filter(|b| include_closed || b.status != "closed")
+        .map(|bead| {
+            let deps = beads::get_bead_dependencies(&bead.id)
+                .map(|d| dependency_ids(&d.depends_on))
+                .unwrap_or_default();
+            (bead, deps)
+        })
+        .collect();
+
+    let graph = build_graph(beads);
+    match format {
+        "dot" => Ok(render_dot(&graph)),
+        "text" => Ok(render_text(&graph)),
+        "json" => serde_json::to_string_pretty(&graph).map_err(|e| e.to_string()),
+        _ => Err(format!("Unknown format: {}. Use dot, text, or json", format)),
+    }
+}
+
+/// Build the graph, dropping edges to beads that were filtered out
+fn build_graph(beads: Vec<(BeadInfo, Vec<String>)>) -> BeadGraph {
+    let ids: HashSet<String> = beads.iter().map(|(b, _)| b.id.clone()).collect();
+
+    let mut nodes = Vec::new();
+    let mut depends_on = BTreeMap::new();
+    for (bead, deps) in beads {
+        let deps: Vec<String> = deps.into_iter().filter(|d| ids.contains(d)).collect();
+        depends_on.insert(bead.id.clone(), deps);
+        nodes.push(GraphNode {
+            id: bead.id,
+            title: bead.title,
+            status: bead.status,
+            priority: bead.priority,
+        });
+    }
+    nodes.sort_by(|a, b| a.id.cmp(&b.id));
+
+    BeadGraph { nodes, depends_on }
+}
+
+fn status_color(status: &str) -> &'static str {
+    match status {
+        "open" => "white",
+        "in_progress" => "yellow",
+        "blocked" => "red",
+        "closed" => "green",
+        _ => "lightgray",
+    }
+}
+
+fn status_marker(status: &str) -> &'static str {
+    match status {
+        "open" => "[ ]",
+        "in_progress" => "[~]",
+        "blocked" => "[!]",
+        "closed" => "[x]",
+        _ => "[?]",
+    }
+}
+
+fn dot_escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn render_dot(graph: &BeadGraph) -> String {
+    let mut out = String::from("digraph beads {\n  rankdir=LR;\n  node [shape=box, style=filled];\n");
+    for node in &graph.nodes {
+        out.push_str(&format!(
+            "  \"{}\" [label=\"{}\\n{}\", fillcolor={}];\n",
+            dot_escape(&node.id),
+            dot_escape(&node.id),
+            dot_escape(&node.title),
+            status_color(&node.status)
+        ));
+    }
+    for (bead, deps) in &graph.depends_on {
+        for dep in deps {
+            out.push_str(&format!("  \"{}\" -> \"{}\";\n", dot_escape(dep), dot_escape(bead)));
+        }
+    }
+    out.push_str("}\n");
+    out
+}
+
+/// Blocking chains as an indented tree: each bead lists the beads it blocks
+///
+/// Roots are beads with no (remaining) dependencies. A bead blocked by several
+/// others appears under each of them.
+fn render_text(graph: &BeadGraph) -> String {
+    let by_id: BTreeMap<&str, &GraphNode> = graph.nodes.iter().map(|n| (n.id.as_str(), n)).collect();
+    let mut blocks: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+    for (bead, deps) in &graph.depends_on {
+        for dep in deps {
+            blocks.entry(dep.as_str()).or_default().push(bead.as_str());
+        }
+    }
+
+    fn walk<'a>(
+        id: &'a str,
+        depth: usize,
+        by_id: &BTreeMap<&str, &GraphNode>,
+        blocks: &BTreeMap<&'a str, Vec<&'a str>>,
+        path: &mut Vec<&'a str>,
+        out: &mut String,
+    ) {
+        let Some(node) = by_id.get(id) else {
+            return;
+        };
+        let indent = "  ".repeat(depth);
+        if path.contains(&id) {
+            out.push_str(&format!("{}{} {} (cycle)\n", indent, status_marker(&node.status), id));
+            return;
+        }
+        out.push_str(&format!("{}{} {} - {}\n", indent, status_marker(&node.status), id, node.title));
+
+        path.push(id);
+        for child in blocks.get(id).into_iter().flatten() {
+            walk(child, depth + 1, by_id, blocks, path, out);
+        }
+        path.pop();
+    }
+
+    let mut out = String::new();
+    let roots = graph
+        .nodes
+        .iter()
+        .filter(|n| graph.depends_on.get(&n.id).is_none_or(|d| d.is_empty()));
+    for root in roots {
+        walk(&root.id, 0, &by_id, &blocks, &mut Vec::new(), &mut out);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bead(id: &str, status: &str) -> BeadInfo {
+        BeadInfo {
+            id: id.to_string(),
+            title: format!("Task {}", id),
+            description: None,
+            priority: 2,
+            status: status.to_string(),
+            labels: Vec::new(),
+        }
+    }
+
+    fn sample() -> BeadGraph {
+        // A blocks B and C; B blocks C; D depends on a bead that was filtered out
+        build_graph(vec![
+            (bead("A", "closed"), vec![]),
+            (bead("B", "in_progress"), vec!["A".to_string()]),
+            (bead("C", "blocked"), vec!["A".to_string(), "B".to_string()]),
+            (bead("D", "open"), vec!["GONE".to_string()]),
+        ])
+    }
+
+    #[test]
+    fn test_render_dot() {
+        let dot = render_dot(&sample());
+        assert!(dot.starts_with("digraph beads {"));
+        assert!(dot.contains("\"A\" [label=\"A\\nTask A\", fillcolor=green];"));
+        assert!(dot.contains("\"B\" [label=\"B\\nTask B\", fillcolor=yellow];"));
+        assert!(dot.contains("\"C\" [label=\"C\\nTask C\", fillcolor=red];"));
+        assert!(dot.contains("\"D\" [label=\"D\\nTask D\", fillcolor=white];"));
+        assert!(dot.contains("\"A\" -> \"C\";"));
+        assert!(dot.contains("\"B\" -> \"C\";"));
+        assert!(!dot.contains("GONE"));
+    }
+
+    #[test]
+    fn test_render_text() {
+        assert_eq!(
+            render_text(&sample()),
+            "[x] A - Task A\n  [~] B - Task B\n    [!] C - Task C\n  [!] C - Task C\n[ ] D - Task D\n"
+        );
+    }
+}
@@ -0,0 +1,148 @@
+//! Bead file hints - which files a bead is expected to touch
+//!
+//! Lets specialized agents use `next --filter-file` to pick beads in their area.
+
+use super::file_locks::{locked_files_matching, FileLockConflict};
+use crate::beads::BeadInfo;
+use crate::db::with_db;
+use rusqlite::Result;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RegisterFileHintOutput {
+    pub success: bool,
+    pub bead_id: String,
+    pub file_pattern: String,
+    /// Files matching the pattern that another bead has locked
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub locked_files: Vec<FileLockConflict>,
+    pub message: String,
+}
+
+/// Associate a file pattern (GLOB syntax, e.g. `src/auth/*`) with a bead
+pub fn register_file_hint(bead_id: &str, file_pattern: &str) -> Result<RegisterFileHintOutput> {
+    let inserted = with_db(|conn| {
+        conn.execute(
+            "INSERT OR IGNORE INTO bead_file_hints (bead_id, file_pattern) VALUES (?1, ?2)",
+            [bead_id, file_pattern],
+        )
+    })?;
+
+    let mut message = if inserted > 0 {
+        format!("Registered file hint {} for {}", file_pattern, bead_id)
+    } else {
+        format!("File hint {} already registered for {}", file_pattern, bead_id)
+    };
+
+    let locked_files = locked_files_matching(file_pattern, bead_id)?;
+    if !locked_files.is_empty() {
+        let held: Vec<String> = locked_files
+            .iter()
+            .map(|l| format!("{} ({})", l.file_path, l.locked_by))
+            .collect();
+        message.push_str(&format!("; warning: locked by other beads: {}", held.join(", ")));
+    }
+
+    Ok(RegisterFileHintOutput {
+        success: inserted > 0,
+        bead_id: bead_id.to_string(),
+        file_pattern: file_pattern.to_string(),
+        locked_files,
+        message,
+    })
+}
+
+/// Narrow ready beads to those relevant to `filter_files`
+///
+/// Beads whose hints match a filter file come first, then beads with no hints
+/// at all. Beads whose hints match none of the files are dropped. Priority
+/// order is kept within each group. An empty filter returns `beads` unchanged.
+pub fn filter_by_file_hints(beads: Vec<BeadInfo>, filter_files: &[String]) -> Result<Vec<BeadInfo>> {
+    if filter_files.is_empty() {
+        return Ok(beads);
+    }
+
+    let files: Vec<&str> = filter_files
+        .iter()
+        .map(|f| f.strip_prefix("./").unwrap_or(f))
+        .collect();
+
+    let mut matched = Vec::new();
+    let mut unhinted = Vec::new();
+
+    for bead in beads {
+        let hint_count: i64 = with_db(|conn| {
+            conn.query_row(
+                "SELECT COUNT(*) FROM bead_file_hints WHERE bead_id = ?1",
+                [&bead.id],
+                |row| row.get(0),
+            )
+        })?;
+
+        if hint_count == 0 {
+            unhinted.push(bead);
+            continue;
+        }
+
+        let mut is_match = false;
+        for file in &files {
+            is_match = with_db(|conn| {
+                conn.query_row(
+                    "SELECT EXISTS(SELECT 1 FROM bead_file_hints WHERE bead_id = ?1 AND ?2 GLOB file_pattern)",
+                    [bead.id.as_str(), file],
+                    |row| row.get(0),
+                )
+            })?;
+            if is_match {
+                break;
+            }
+        }
+
+        if is_match {
+            matched.push(bead);
+        }
+    }
+
+    matched.extend(unhinted);
+    Ok(matched)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{close_db, init_db, test_lock};
+    use tempfile::tempdir;
+
+    fn bead(id: &str) -> BeadInfo {
+        BeadInfo {
+            id: id.to_string(),
+            title: id.to_string(),
+            description: None,
+            priority: 1,
+            status: "open".to_string(),
+            labels: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_filter_by_file_hints() {
+        let _lock = test_lock();
+        let dir = tempdir().unwrap();
+        init_db(Some(dir.path().join("test.db").to_str().unwrap()), true).unwrap();
+
+        register_file_hint("UI-1", "src/ui/*").unwrap();
+        register_file_hint("AUTH-1", "src/auth/*").unwrap();
+        assert!(!register_file_hint("AUTH-1", "src/auth/*").unwrap().success);
+
+        let ready = vec![bead("FREE-1"), bead("UI-1"), bead("AUTH-1")];
+
+        let ids = |beads: Vec<BeadInfo>| beads.into_iter().map(|b| b.id).collect::<Vec<_>>();
+
+        let filtered = filter_by_file_hints(ready.clone(), &["./src/auth/login.rs".to_string()]).unwrap();
+        assert_eq!(ids(filtered), vec!["AUTH-1", "FREE-1"]);
+
+        assert_eq!(ids(filter_by_file_hints(ready, &[]).unwrap()), vec!["FREE-1", "UI-1", "AUTH-1"]);
+
+        close_db();
+    }
+}
@@ -0,0 +1,187 @@
+//! Hooks tool - install git hooks that enforce the bacchus workflow
+//!
+//! - `pre-push` blocks the push when `bacchus verify` finds errors
+//! - `post-commit` refreshes the claim heartbeat when committing on a `bacchus/<bead_id>` branch
+//!
+//! Hooks go in the repository's shared hooks directory, so they fire in every
+//! worktree. Each carries a `# managed by bacchus` marker: managed hooks are
+//! overwritten on reinstall and deleted on remove, anything else is left alone.
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+pub const HOOK_TYPES: [&str; 2] = ["pre-push", "post-commit"];
+
+const MARKER: &str = "# managed by bacchus";
+
+const PRE_PUSH: &str = r#"
+if ! bacchus verify >/dev/null; then
+    echo "bacchus: workspace is inconsistent; run 'bacchus verify' for details" >&2
+    exit 1
+fi
+"#;
+
+const POST_COMMIT: &str = r#"
+branch=$(git symbolic-ref --quiet --short HEAD) || exit 0
+case "$branch" in
+    bacchus/*) bead_id=${branch#bacchus/} ;;
+    *) exit 0 ;;
+esac
+agent_id=$(bacchus worktree-info "$bead_id" 2>/dev/null | sed -n 's/.*"agent_id": *"\([^"]*\)".*/\1/p' | head -n 1)
+[ -n "$agent_id" ] && bacchus heartbeat "$agent_id" --bead-id "$bead_id" >/dev/null 2>&1
+exit 0
+"#;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HooksOutput {
+    pub success: bool,
+    pub action: String,
+    pub hooks_dir: String,
+    /// Hooks written or deleted
+    pub changed: Vec<String>,
+    /// Hooks left alone because they aren't managed by bacchus (or don't exist, on remove)
+    pub skipped: Vec<String>,
+    pub message: String,
+}
+
+/// `install` or `remove` the given hook types (all of them when empty)
+pub fn manage_hooks(
+    action: &str,
+    hook_types: &[String],
+    workspace_root: &Path,
+) -> Result<HooksOutput, Box<dyn std::error::Error>> {
+    let selected: Vec<&str> = if hook_types.is_empty() {
+        HOOK_TYPES.to_vec()
+    } else {
+        hook_types.iter().map(String::as_str).collect()
+    };
+    if let Some(unknown) = selected.iter().find(|h| !HOOK_TYPES.contains(h)) {
+        return Err(format!("Unknown hook type: {}. Use one of: {}", unknown, HOOK_TYPES.join(", ")).into());
+    }
+
+    let hooks_dir = hooks_dir(workspace_root)?;
+    let mut changed = Vec::new();
+    let mut skipped = Vec::new();
+
+    for hook in selected {
+        let path = hooks_dir.join(hook);
+        let managed = std::fs::read_to_string(&path).ok().map(|c| c.contains(MARKER));
+        match (action, managed) {
+            ("install", Some(false)) | ("remove", Some(false) | None) => skipped.push(hook.to_string()),
+            ("install", _) => {
+                std::fs::create_dir_all(&hooks_dir)?;
+                std::fs::write(&path, hook_script(hook))?;
+                make_executable(&path)?;
+                changed.push(hook.to_string());
+            }
+            ("remove", Some(true)) => {
+                std::fs::remove_file(&path)?;
+                changed.push(hook.to_string());
+            }
+            _ => return Err(format!("Unknown action: {}. Use install or remove", action).into()),
+        }
+    }
+
+    let verb = if action == "install" { "Installed" } else { "Removed" };
+    let mut message = format!("{} {} hook(s)", verb, changed.len());
+    if !skipped.is_empty() {
+        message.push_str(&format!(
+            "; skipped {} ({})",
+            skipped.join(", "),
+            if action == "install" { "existing hook not managed by bacchus" } else { "not managed by bacchus" }
+        ));
+    }
+
+    Ok(HooksOutput {
+        success: true,
+        action: action.to_string(),
+        hooks_dir: hooks_dir.to_string_lossy().to_string(),
+        changed,
+        skipped,
+        message,
+    })
+}
+
+fn hook_script(hook: &str) -> String {
+    let body = match hook {
+        "pre-push" => PRE_PUSH,
+        _ => POST_COMMIT,
+    };
+    format!("#!/bin/sh\n{} v{}\n{}", MARKER, env!("CARGO_PKG_VERSION"), body)
+}
+
+/// Shared hooks directory (honours core.hooksPath)
+fn hooks_dir(workspace_root: &Path) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--git-common-dir"])
+        .current_dir(workspace_root)
+        .output()?;
+    if !output.status.success() {
+        return Err(format!("{} is not a git repository", workspace_root.display()).into());
+    }
+    let common_dir = workspace_root.join(String::from_utf8_lossy(&output.stdout).trim());
+
+    let hooks_path = Command::new("git")
+        .args(["config", "core.hooksPath"])
+        .current_dir(workspace_root)
+        .output()?;
+    let custom = String::from_utf8_lossy(&hooks_path.stdout).trim().to_string();
+    Ok(if hooks_path.status.success() && !custom.is_empty() {
+        workspace_root.join(custom)
+    } else {
+        common_dir.join("hooks")
+    })
+}
+
+fn make_executable(path: &Path) -> std::io::Result<()> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mut perms = std::fs::metadata(path)?.permissions();
+        perms.set_mode(0o755);
+        std::fs::set_permissions(path, perms)?;
+    }
+    #[cfg(not(unix))]
+    let _ = path;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_install_and_remove() {
+        let temp = TempDir::new().unwrap();
+        Command::new("git").arg("init").current_dir(temp.path()).output().unwrap();
+        let hooks = temp.path().join(".git/hooks");
+        std::fs::create_dir_all(&hooks).unwrap();
+        std::fs::write(hooks.join("post-commit"), "#!/bin/sh\necho mine\n").unwrap();
+
+        let output = manage_hooks("install", &[], temp.path()).unwrap();
+        assert_eq!(output.changed, vec!["pre-push"]);
+        assert_eq!(output.skipped, vec!["post-commit"]);
+        let script = std::fs::read_to_string(hooks.join("pre-push")).unwrap();
+        assert!(script.starts_with(&format!("#!/bin/sh\n{} v", MARKER)));
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(hooks.join("pre-push")).unwrap().permissions().mode();
+            assert_eq!(mode & 0o111, 0o111);
+        }
+
+        // Reinstalling overwrites only managed hooks
+        std::fs::remove_file(hooks.join("post-commit")).unwrap();
+        let output = manage_hooks("install", &[], temp.path()).unwrap();
+        assert_eq!(output.changed, vec!["pre-push", "post-commit"]);
+
+        let output = manage_hooks("remove", &["post-commit".to_string()], temp.path()).unwrap();
+        assert_eq!(output.changed, vec!["post-commit"]);
+        assert!(!hooks.join("post-commit").exists());
+        assert!(hooks.join("pre-push").exists());
+
+        assert!(manage_hooks("install", &["pre-commit".to_string()], temp.path()).is_err());
+    }
+}
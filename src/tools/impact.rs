@@ -0,0 +1,124 @@
+//! Impact tool - blast radius of changing a symbol
+//!
+//! Callers come from the call graph walk in `callers`; a bead is affected when
+//! it has an active claim and has registered the symbol or any of its callers.
+
+use super::callers::find_callers;
+use crate::db::with_db;
+use rusqlite::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+/// Risk added per affected bead
+const BEAD_WEIGHT: f32 = 1.0;
+/// Risk added per direct or transitive caller
+const CALLER_WEIGHT: f32 = 0.1;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ImpactOutput {
+    pub symbol: String,
+    pub direct_callers: Vec<String>,
+    /// Callers reached at depth 2 or more (excluding direct callers)
+    pub transitive_callers: Vec<String>,
+    /// Claimed beads that registered the symbol or one of its callers
+    pub affected_beads: Vec<String>,
+    /// 1 per affected bead plus 0.1 per caller
+    pub risk_score: f32,
+}
+
+/// Callers of `fq_name` up to `depth` levels, and the active beads they touch
+pub fn impact_analysis(fq_name: &str, depth: u32) -> Result<ImpactOutput> {
+    let callers = find_callers(fq_name, depth)?;
+
+    let direct_callers: BTreeSet<String> = callers
+        .callers
+        .iter()
+        .filter(|c| c.depth == 1 && c.fq_name != fq_name)
+        .map(|c| c.fq_name.clone())
+        .collect();
+    let transitive_callers: BTreeSet<String> = callers
+        .callers
+        .iter()
+        .filter(|c| c.depth > 1 && c.fq_name != fq_name && !direct_callers.contains(&c.fq_name))
+        .map(|c| c.fq_name.clone())
+        .collect();
+
+    let affected_beads: BTreeSet<String> = with_db(|conn| {
+        let mut stmt = conn.prepare(
+            "SELECT DISTINCT bs.bead_id FROM bead_symbols bs
+             JOIN claims c ON c.bead_id = bs.bead_id
+             WHERE bs.symbol_fq_name = ?1",
+        )?;
+        let mut beads = BTreeSet::new();
+        for symbol in std::iter::once(fq_name).chain(direct_callers.iter().chain(&transitive_callers).map(String::as_str)) {
+            for bead in stmt.query_map([symbol], |row| row.get::<_, String>(0))? {
+                beads.insert(bead?);
+            }
+        }
+        Ok(beads)
+    })?;
+
+    let risk_score = affected_beads.len() as f32 * BEAD_WEIGHT
+        + (direct_callers.len() + transitive_callers.len()) as f32 * CALLER_WEIGHT;
+
+    Ok(ImpactOutput {
+        symbol: fq_name.to_string(),
+        direct_callers: direct_callers.into_iter().collect(),
+        transitive_callers: transitive_callers.into_iter().collect(),
+        affected_beads: affected_beads.into_iter().collect(),
+        risk_score,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{close_db, init_db, test_lock};
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_impact_analysis() {
+        let _lock = test_lock();
+        let dir = tempdir().unwrap();
+        init_db(Some(dir.path().join("test.db").to_str().unwrap()), true).unwrap();
+
+        with_db(|conn| {
+            for (id, fq_name) in [(1, "a.rs::main"), (2, "a.rs::run"), (3, "b.rs::save"), (4, "b.rs::retry")] {
+                conn.execute(
+                    "INSERT INTO symbols (id, file, fq_name, kind, span_start_line, span_end_line, line_count, hash, language)
+                     VALUES (?1, ?2, ?3, 'function', 1, 1, 1, 'h', 'rust')",
+                    rusqlite::params![id, fq_name.split("::").next().unwrap(), fq_name],
+                )?;
+            }
+            // main -> run -> save, retry -> save, retry -> retry
+            for (caller, callee) in [(1, "a.rs::run"), (2, "b.rs::save"), (4, "b.rs::save"), (4, "b.rs::retry")] {
+                conn.execute(
+                    "INSERT INTO symbol_calls (caller_symbol_id, callee_fq_name, call_site_file, call_site_line)
+                     VALUES (?1, ?2, 'x', 1)",
+                    rusqlite::params![caller, callee],
+                )?;
+            }
+            conn.execute_batch(
+                "INSERT INTO claims (bead_id, agent_id, worktree_path, branch_name, start_commit, claimed_at)
+                 VALUES ('bd-1', 'agent', '/w/bd-1', 'bacchus/bd-1', 'abc', 0);
+                 INSERT INTO bead_symbols (bead_id, symbol_fq_name, relation) VALUES
+                   ('bd-1', 'a.rs::main', 'modifies'),
+                   ('bd-done', 'a.rs::run', 'modifies');",
+            )
+        })
+        .unwrap();
+
+        let impact = impact_analysis("b.rs::save", 3).unwrap();
+        assert_eq!(impact.direct_callers, ["a.rs::run", "b.rs::retry"]);
+        assert_eq!(impact.transitive_callers, ["a.rs::main"]);
+        // bd-done has no active claim
+        assert_eq!(impact.affected_beads, ["bd-1"]);
+        assert!((impact.risk_score - 1.3).abs() < 1e-6);
+
+        let shallow = impact_analysis("b.rs::save", 1).unwrap();
+        assert!(shallow.transitive_callers.is_empty());
+        assert!(shallow.affected_beads.is_empty());
+
+        close_db();
+    }
+}
@@ -0,0 +1,241 @@
+//! Import tool - restore workspace state written by `bacchus export`
+//!
+//! Reads either export layout (a newline-delimited JSON file or a directory of
+//! CSV files), refuses exports from a newer schema, and upserts every row in
+//! one transaction. Rows whose key already exists are skipped unless
+//! `overwrite` is set. `bacchus verify` runs afterwards.
+
+use super::export::ExportManifest;
+use super::import_beads::parse_csv;
+use super::verify::{verify_workspace, VerifyOutput};
+use crate::db::{get_current_version, with_db};
+use rusqlite::types::Value;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::Path;
+
+type Row = serde_json::Map<String, serde_json::Value>;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ImportOutput {
+    pub success: bool,
+    pub schema_version: i32,
+    /// Rows written per table
+    pub imported: BTreeMap<String, usize>,
+    /// Rows left alone because their key already existed
+    pub skipped: BTreeMap<String, usize>,
+    /// Tables in the export that this database doesn't have
+    pub unknown_tables: Vec<String>,
+    pub verify: VerifyOutput,
+    pub message: String,
+}
+
+pub fn import_workspace(path: &Path, overwrite: bool, workspace_root: &Path) -> Result<String, String> {
+    let (manifest, rows) = if path.is_dir() { read_csv_dir(path)? } else { read_ndjson(path)? };
+
+    let current = with_db(get_current_version).map_err(|e| e.to_string())?;
+    if manifest.schema_version > current {
+        return Err(format!(
+            "Export has schema version {} but this database is at {}; upgrade bacchus before importing",
+            manifest.schema_version, current
+        ));
+    }
+
+    let mut imported: BTreeMap<String, usize> = BTreeMap::new();
+    let mut skipped: BTreeMap<String, usize> = BTreeMap::new();
+    let mut unknown_tables = BTreeSet::new();
+
+    with_db(|conn| {
+        let mut columns: HashMap<String, Vec<String>> = HashMap::new();
+        let mut stmt = conn.prepare(
+            "SELECT name FROM pragma_table_list WHERE schema = 'main' AND type = 'table' AND name NOT LIKE 'sqlite_%'",
+        )?;
+        for table in stmt.query_map([], |row| row.get::<_, String>(0))? {
+            let table = table?;
+            let mut info = conn.prepare(&format!("SELECT name FROM pragma_table_info('{}')", table))?;
+            let names = info.query_map([], |row| row.get(0))?.collect::<rusqlite::Result<Vec<String>>>()?;
+            columns.insert(table, names);
+        }
+
+        let conflict = if overwrite { "REPLACE" } else { "IGNORE" };
+        let tx = conn.unchecked_transaction()?;
+        for (table, row) in &rows {
+            // The version of the importing database wins
+            if table == "schema_version" {
+                continue;
+            }
+            let Some(known) = columns.get(table) else {
+                unknown_tables.insert(table.clone());
+                continue;
+            };
+
+            // Columns from a newer export are dropped; missing ones take their defaults
+            let (names, values): (Vec<&String>, Vec<Value>) =
+                row.iter().filter(|(k, _)| known.contains(k)).map(|(k, v)| (k, to_sql(v))).unzip();
+            if names.is_empty() {
+                continue;
+            }
+            let sql = format!(
+                "INSERT OR {} INTO \"{}\" ({}) VALUES ({})",
+                conflict,
+                table,
+                names.iter().map(|n| format!("\"{}\"", n)).collect::<Vec<_>>().join(", "),
+                (1..=names.len()).map(|i| format!("?{}", i)).collect::<Vec<_>>().join(", ")
+            );
+            let counter = if tx.execute(&sql, rusqlite::params_from_iter(values))? > 0 {
+                &mut imported
+            } else {
+                &mut skipped
+            };
+            *counter.entry(table.clone()).or_default() += 1;
+        }
+        tx.commit()
+    })
+    .map_err(|e| format!("Import failed, nothing was written: {}", e))?;
+
+    let verify = verify_workspace(false, workspace_root).map_err(|e| e.to_string())?;
+    let total: usize = imported.values().sum();
+    let output = ImportOutput {
+        success: verify.ok,
+        schema_version: manifest.schema_version,
+        message: if verify.ok {
+            format!("Imported {} row(s), skipped {} existing", total, skipped.values().sum::<usize>())
+        } else {
+            format!("Imported {} row(s) but verify failed: {}", total, verify.message)
+        },
+        imported,
+        skipped,
+        unknown_tables: unknown_tables.into_iter().collect(),
+        verify,
+    };
+    serde_json::to_string_pretty(&output).map_err(|e| e.to_string())
+}
+
+fn read_ndjson(path: &Path) -> Result<(ExportManifest, Vec<(String, Row)>), String> {
+    let content = std::fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+
+    let mut manifest = None;
+    let mut rows = Vec::new();
+    for (i, line) in content.lines().enumerate().filter(|(_, l)| !l.trim().is_empty()) {
+        let mut row: Row = serde_json::from_str(line).map_err(|e| format!("Line {}: {}", i + 1, e))?;
+        let table = match row.remove("_table") {
+            Some(serde_json::Value::String(t)) => t,
+            _ => return Err(format!("Line {}: missing _table field", i + 1)),
+        };
+        if table == "_manifest" {
+            manifest = Some(
+                serde_json::from_value(serde_json::Value::Object(row)).map_err(|e| format!("Invalid manifest: {}", e))?,
+            );
+        } else {
+            rows.push((table, row));
+        }
+    }
+
+    let manifest = manifest.ok_or_else(|| format!("{} has no manifest; is it a bacchus export?", path.display()))?;
+    Ok((manifest, rows))
+}
+
+fn read_csv_dir(dir: &Path) -> Result<(ExportManifest, Vec<(String, Row)>), String> {
+    let manifest_path = dir.join("manifest.json");
+    let manifest: ExportManifest = std::fs::read_to_string(&manifest_path)
+        .map_err(|e| format!("Failed to read {}: {}", manifest_path.display(), e))
+        .and_then(|c| serde_json::from_str(&c).map_err(|e| format!("Invalid manifest: {}", e)))?;
+
+    let mut rows = Vec::new();
+    for table in manifest.tables.keys() {
+        let file = dir.join(format!("{}.csv", table));
+        let content = std::fs::read_to_string(&file).map_err(|e| format!("Failed to read {}: {}", file.display(), e))?;
+        let mut records = parse_csv(&content).into_iter();
+        let header = records.next().unwrap_or_default();
+        for record in records {
+            // Export writes NULL as an empty field
+            let row: Row = header
+                .iter()
+                .zip(record)
+                .map(|(k, v)| (k.clone(), if v.is_empty() { serde_json::Value::Null } else { v.into() }))
+                .collect();
+            rows.push((table.clone(), row));
+        }
+    }
+    Ok((manifest, rows))
+}
+
+fn to_sql(value: &serde_json::Value) -> Value {
+    match value {
+        serde_json::Value::Null => Value::Null,
+        serde_json::Value::Bool(b) => Value::Integer(*b as i64),
+        serde_json::Value::Number(n) => n
+            .as_i64()
+            .map(Value::Integer)
+            .unwrap_or_else(|| Value::Real(n.as_f64().unwrap_or(0.0))),
+        serde_json::Value::String(s) => Value::Text(s.clone()),
+        other => Value::Text(other.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{close_db, init_db, test_lock};
+    use crate::tools::export::export_workspace;
+    use tempfile::tempdir;
+
+    fn claim_title() -> String {
+        with_db(|conn| conn.query_row("SELECT title FROM claims WHERE bead_id = 'BEAD-1'", [], |r| r.get(0))).unwrap()
+    }
+
+    #[test]
+    fn test_import_round_trip() {
+        let _lock = test_lock();
+        let dir = tempdir().unwrap();
+        // verify runs git against the workspace
+        std::process::Command::new("git").arg("init").current_dir(dir.path()).output().unwrap();
+        init_db(Some(dir.path().join("test.db").to_str().unwrap()), true).unwrap();
+
+        with_db(|conn| {
+            conn.execute_batch(
+                "INSERT INTO claims (bead_id, agent_id, worktree_path, branch_name, start_commit, claimed_at, title)
+                 VALUES ('BEAD-1', 'agent-a', '/wt', 'bacchus/BEAD-1', 'abc', 5, 'Original, \"title\"');",
+            )
+        })
+        .unwrap();
+
+        for (format, path) in [("json", dir.path().join("export.ndjson")), ("csv", dir.path().join("csv"))] {
+            export_workspace(&path, format, false).unwrap();
+            with_db(|conn| conn.execute("UPDATE claims SET title = 'Changed'", [])).unwrap();
+
+            let output: ImportOutput = serde_json::from_str(&import_workspace(&path, false, dir.path()).unwrap()).unwrap();
+            // The claim's worktree doesn't exist, which verify reports
+            assert!(!output.success);
+            assert!(output.verify.checks.iter().any(|c| c.name == "claim_worktrees_exist" && !c.passed));
+            assert_eq!(output.skipped["claims"], 1);
+            assert_eq!(claim_title(), "Changed");
+
+            let output: ImportOutput = serde_json::from_str(&import_workspace(&path, true, dir.path()).unwrap()).unwrap();
+            assert_eq!(output.imported["claims"], 1);
+            assert_eq!(claim_title(), "Original, \"title\"");
+
+            with_db(|conn| conn.execute("DELETE FROM claims", [])).unwrap();
+            import_workspace(&path, false, dir.path()).unwrap();
+            assert_eq!(claim_title(), "Original, \"title\"");
+        }
+
+        close_db();
+    }
+
+    #[test]
+    fn test_read_ndjson_requires_manifest() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("export.ndjson");
+
+        std::fs::write(&path, "{\"_table\":\"claims\",\"bead_id\":\"BEAD-1\"}\n").unwrap();
+        assert!(read_ndjson(&path).unwrap_err().contains("no manifest"));
+
+        std::fs::write(
+            &path,
+            "{\"_table\":\"_manifest\",\"schema_version\":3,\"exported_at\":\"x\",\"bacchus_version\":\"0\",\"tables\":{}}\n{\"bead_id\":\"BEAD-1\"}\n",
+        )
+        .unwrap();
+        assert!(read_ndjson(&path).unwrap_err().contains("missing _table"));
+    }
+}
@@ -0,0 +1,216 @@
+//! Import beads tool - bulk-create beads from an `export-beads` JSON or CSV file
+//!
+//! Beads whose IDs already exist are skipped rather than failing the import.
+//! Dependencies are added only after every bead exists, so their order in the
+//! file doesn't matter.
+
+use super::export_beads::{ExportedBead, CSV_HEADER};
+use crate::beads::{self, BeadInfo, BeadsError};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ImportBeadsOutput {
+    pub dry_run: bool,
+    pub imported: usize,
+    pub skipped: usize,
+    pub failed: usize,
+    pub errors: Vec<String>,
+    /// Beads that would be created (dry run only)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub would_import: Vec<String>,
+}
+
+/// Import beads from `file`; `format` defaults to the file extension
+pub fn import_beads(file: &Path, format: Option<&str>, dry_run: bool) -> Result<ImportBeadsOutput, String> {
+    let format = match format {
+        Some(f) => f.to_string(),
+        None => match file.extension().and_then(|e| e.to_str()) {
+            Some("csv") => "csv".to_string(),
+            Some("json") => "json".to_string(),
+            _ => return Err(format!("Can't infer format of {}; pass --format json or csv", file.display())),
+        },
+    };
+
+    let content = std::fs::read_to_string(file).map_err(|e| format!("Failed to read {}: {}", file.display(), e))?;
+    let beads = parse_beads(&content, &format)?;
+
+    let mut output = ImportBeadsOutput {
+        dry_run,
+        ..Default::default()
+    };
+    let mut created = Vec::new();
+
+    for b in &beads {
+        match beads::get_bead(&b.bead.id) {
+            Ok(_) => {
+                output.skipped += 1;
+                continue;
+            }
+            Err(BeadsError::BeadNotFound(_)) => {}
+            Err(e) => return Err(e.to_string()),
+        }
+
+        if dry_run {
+            output.imported += 1;
+            output.would_import.push(b.bead.id.clone());
+            continue;
+        }
+
+        let result = beads::create_bead(&b.bead).and_then(|_| {
+            // bd creates beads as open; carry over any other status
+            if b.bead.status != "open" {
+                beads::update_bead_status(&b.bead.id, &b.bead.status)?;
+            }
+            Ok(())
+        });
+        match result {
+            Ok(()) => {
+                output.imported += 1;
+                created.push(b);
+            }
+            Err(e) => {
+                output.failed += 1;
+                output.errors.push(format!("{}: {}", b.bead.id, e));
+            }
+        }
+    }
+
+    for b in created {
+        for dep in &b.depends_on {
+            if let Err(e) = beads::add_dependency(&b.bead.id, dep) {
+                output.errors.push(format!("{} -> {}: {}", b.bead.id, dep, e));
+            }
+        }
+    }
+
+    Ok(output)
+}
+
+fn parse_beads(content: &str, format: &str) -> Result<Vec<ExportedBead>, String> {
+    match format {
+        "json" => serde_json::from_str(content).map_err(|e| format!("Invalid JSON: {}", e)),
+        "csv" => parse_csv_beads(content),
+        _ => Err(format!("Unknown format: {}. Use json or csv", format)),
+    }
+}
+
+fn parse_csv_beads(content: &str) -> Result<Vec<ExportedBead>, String> {
+    let mut rows = parse_csv(content).into_iter();
+    let header = rows.next().ok_or("CSV file is empty")?;
+
+    let column = |name: &str| header.iter().position(|h| h == name);
+    let columns: Vec<Option<usize>> = CSV_HEADER.iter().map(|name| column(name)).collect();
+    if columns[0].is_none() || columns[1].is_none() {
+        return Err("CSV must have at least 'id' and 'title' columns".to_string());
+    }
+
+    rows.enumerate()
+        .filter(|(_, row)| row.iter().any(|f| !f.is_empty()))
+        .map(|(i, row)| {
+            let field = |idx: usize| columns[idx].and_then(|c| row.get(c)).cloned().unwrap_or_default();
+            let list = |idx: usize| -> Vec<String> {
+                field(idx).split(',').map(str::trim).filter(|s| !s.is_empty()).map(String::from).collect()
+            };
+
+            let priority = field(3);
+            let priority = if priority.is_empty() {
+                2
+            } else {
+                priority
+                    .parse()
+                    .map_err(|_| format!("Row {}: invalid priority '{}'", i + 2, priority))?
+            };
+            let status = field(2);
+            let description = field(4);
+
+            Ok(ExportedBead {
+                bead: BeadInfo {
+                    id: field(0),
+                    title: field(1),
+                    description: (!description.is_empty()).then_some(description),
+                    priority,
+                    status: if status.is_empty() { "open".to_string() } else { status },
+                    labels: list(5),
+                },
+                depends_on: list(6),
+            })
+        })
+        .collect()
+}
+
+/// Minimal RFC 4180 reader: quoted fields may contain commas, `""`, and newlines
+pub(crate) fn parse_csv(content: &str) -> Vec<Vec<String>> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = content.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match (c, in_quotes) {
+            ('"', true) if chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            ('"', _) => in_quotes = !in_quotes,
+            (',', false) => row.push(std::mem::take(&mut field)),
+            ('\r', false) => {}
+            ('\n', false) => {
+                row.push(std::mem::take(&mut field));
+                rows.push(std::mem::take(&mut row));
+            }
+            _ => field.push(c),
+        }
+    }
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::export_beads::render;
+
+    #[test]
+    fn test_csv_round_trip() {
+        let original = vec![ExportedBead {
+            bead: BeadInfo {
+                id: "bd-1".to_string(),
+                title: "Fix login, again".to_string(),
+                description: Some("Users see \"500\"\non submit".to_string()),
+                priority: 0,
+                status: "closed".to_string(),
+                labels: vec!["auth".to_string(), "p0".to_string()],
+            },
+            depends_on: vec!["bd-0".to_string()],
+        }];
+
+        let csv = render(&original, "csv").unwrap();
+        let parsed = parse_beads(&csv, "csv").unwrap();
+        assert_eq!(parsed.len(), 1);
+        assert_eq!(parsed[0].bead.title, "Fix login, again");
+        assert_eq!(parsed[0].bead.description.as_deref(), Some("Users see \"500\"\non submit"));
+        assert_eq!(parsed[0].bead.status, "closed");
+        assert_eq!(parsed[0].bead.labels, vec!["auth", "p0"]);
+        assert_eq!(parsed[0].depends_on, vec!["bd-0"]);
+
+        let json = render(&original, "json").unwrap();
+        assert_eq!(parse_beads(&json, "json").unwrap()[0].bead.priority, 0);
+    }
+
+    #[test]
+    fn test_csv_minimal_columns() {
+        let parsed = parse_beads("title,id\nWrite docs,bd-9\n\n", "csv").unwrap();
+        assert_eq!(parsed.len(), 1);
+        assert_eq!(parsed[0].bead.id, "bd-9");
+        assert_eq!(parsed[0].bead.status, "open");
+        assert_eq!(parsed[0].bead.priority, 2);
+
+        assert!(parse_beads("name\nx\n", "csv").is_err());
+        assert!(parse_beads("id,title,status,priority\nbd-1,x,open,high\n", "csv").is_err());
+    }
+}
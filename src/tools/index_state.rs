@@ -0,0 +1,24 @@
+//! Index state - key/value bookkeeping for the indexer
+
+use crate::db::with_db;
+use rusqlite::{OptionalExtension, Result};
+
+/// Commit HEAD pointed at when the index was last written
+pub const LAST_INDEXED_COMMIT: &str = "last_indexed_commit";
+
+pub fn get_index_state(key: &str) -> Result<Option<String>> {
+    with_db(|conn| {
+        conn.query_row("SELECT value FROM index_state WHERE key = ?1", [key], |row| row.get(0))
+            .optional()
+    })
+}
+
+pub fn set_index_state(key: &str, value: &str) -> Result<()> {
+    with_db(|conn| {
+        conn.execute(
+            "INSERT OR REPLACE INTO index_state (key, value) VALUES (?1, ?2)",
+            [key, value],
+        )
+    })?;
+    Ok(())
+}
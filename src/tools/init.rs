@@ -0,0 +1,182 @@
+//! Init tool - bootstrap a bacchus workspace in an existing git repo
+//!
+//! Creates `.bacchus/` with its worktrees directory, database, and a
+//! commented-out `config.toml`. Without `.beads/`, an empty beads database is
+//! created too so `bd` has something to open.
+
+use crate::config::config_path;
+use crate::db;
+use crate::worktree;
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::process::Command;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct InitOutput {
+    pub success: bool,
+    pub workspace_root: String,
+    /// Paths created by this run
+    pub created: Vec<String>,
+    pub message: String,
+}
+
+const DEFAULT_CONFIG: &str = r#"# Bacchus workspace configuration
+#
+# Every option is commented out; uncomment to override the default.
+
+# Merge commit message for `release --status done`; a fast-forward has no merge
+# commit to put it on, so it only applies with the "no-ff" merge_strategy.
+# Placeholders: {bead_id}, {title}, {agent_id}, {timestamp}
+# merge_commit_template = "feat: {title} (bacchus/{bead_id})"
+
+# Branch new claims merge into (overridden by `next`/`claim --target-branch`)
+# target_branch = "main"
+
+# Default `--minutes` for `stale` and `notify-stale-agents`
+# stale_threshold_minutes = 15
+
+# Worktrees directory, absolute or workspace-relative (BACCHUS_WORKTREES wins)
+# worktrees_dir = ".bacchus/worktrees"
+
+# `merge` (fast-forward when possible), `squash` to always release as if
+# given `--squash`, or `no-ff` to always create a merge commit
+# merge_strategy = "merge"
+
+# Refuse to release a bead whose target branch has diverged, and
+# fast-forward instead of creating merge commits
+# require_linear_history = false
+
+# Refuse to merge a worktree whose HEAD commit fails `git verify-commit`
+# require_signatures = false
+
+# Sign the commits bacchus creates (merges, squashes) with `-S`
+# signed_commits = false
+
+# Directory/file names or workspace-relative paths `index` skips
+# index_ignore = ["vendor"]
+
+# [hooks]
+# Shell commands run in the worktree before `release --status done` merges
+# pre_merge = ["cargo test"]
+"#;
+
+/// Minimal beads schema: the tables `bd` needs to list and link issues
+const BEADS_SCHEMA: &str = r#"
+CREATE TABLE IF NOT EXISTS issues (
+    id TEXT PRIMARY KEY,
+    title TEXT NOT NULL,
+    description TEXT NOT NULL DEFAULT '',
+    status TEXT NOT NULL DEFAULT 'open',
+    priority INTEGER NOT NULL DEFAULT 2,
+    issue_type TEXT NOT NULL DEFAULT 'task',
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    closed_at DATETIME
+);
+
+CREATE TABLE IF NOT EXISTS dependencies (
+    issue_id TEXT NOT NULL,
+    depends_on_id TEXT NOT NULL,
+    type TEXT NOT NULL DEFAULT 'blocks',
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (issue_id, depends_on_id),
+    FOREIGN KEY (issue_id) REFERENCES issues(id) ON DELETE CASCADE,
+    FOREIGN KEY (depends_on_id) REFERENCES issues(id) ON DELETE CASCADE
+);
+"#;
+
+pub fn init_workspace(
+    force: bool,
+    with_beads: bool,
+    db_path: &Path,
+    workspace_root: &Path,
+) -> Result<InitOutput, Box<dyn std::error::Error>> {
+    let root_str = workspace_root.to_string_lossy().to_string();
+
+    let is_git_repo = Command::new("git")
+        .args(["rev-parse", "--git-dir"])
+        .current_dir(workspace_root)
+        .output()
+        .map(|o| o.status.success())
+        .unwrap_or(false);
+    if !is_git_repo {
+        return Ok(InitOutput {
+            success: false,
+            workspace_root: root_str,
+            created: Vec::new(),
+            message: "Not a git repository; run `git init` first".to_string(),
+        });
+    }
+
+    let bacchus_dir = workspace_root.join(".bacchus");
+    if bacchus_dir.exists() {
+        if !force {
+            return Ok(InitOutput {
+                success: false,
+                workspace_root: root_str,
+                created: Vec::new(),
+                message: format!("{} already exists; use --force to overwrite it", bacchus_dir.display()),
+            });
+        }
+        std::fs::remove_dir_all(&bacchus_dir)?;
+        // Drop git's records of the worktrees that were just deleted
+        Command::new("git")
+            .args(["worktree", "prune"])
+            .current_dir(workspace_root)
+            .output()?;
+    }
+
+    let mut created = Vec::new();
+
+    let worktrees_dir = worktree::get_worktrees_dir(workspace_root);
+    std::fs::create_dir_all(&worktrees_dir)?;
+    created.push(bacchus_dir.to_string_lossy().to_string());
+    created.push(worktrees_dir.to_string_lossy().to_string());
+
+    db::init_db(Some(&db_path.to_string_lossy()), true)?;
+    created.push(db_path.to_string_lossy().to_string());
+
+    let config = config_path(workspace_root);
+    std::fs::write(&config, DEFAULT_CONFIG)?;
+    created.push(config.to_string_lossy().to_string());
+
+    let beads_dir = workspace_root.join(".beads");
+    if with_beads && !beads_dir.exists() {
+        std::fs::create_dir_all(&beads_dir)?;
+        let beads_db = beads_dir.join("beads.db");
+        Connection::open(&beads_db)?.execute_batch(BEADS_SCHEMA)?;
+        created.push(beads_db.to_string_lossy().to_string());
+    }
+
+    Ok(InitOutput {
+        success: true,
+        workspace_root: root_str,
+        message: format!("Initialized bacchus workspace in {}", workspace_root.display()),
+        created,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_config_parses() {
+        // Everything is commented out, so it must load as the defaults
+        let config: crate::config::Config = toml::from_str(DEFAULT_CONFIG).unwrap();
+        assert!(config.merge_commit_template.is_none());
+        assert!(config.hooks.pre_merge.is_empty());
+
+        let uncommented: String = DEFAULT_CONFIG
+            .lines()
+            .map(|l| l.strip_prefix("# ").filter(|l| l.contains(" = ") || l.starts_with('[')).unwrap_or(""))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let config: crate::config::Config = toml::from_str(&uncommented).unwrap();
+        assert_eq!(config.target_branch, "main");
+        assert_eq!(config.stale_threshold_minutes, 15);
+        assert_eq!(config.hooks.pre_merge, vec!["cargo test"]);
+        assert_eq!(config.index_ignore, vec!["vendor"]);
+    }
+}
@@ -0,0 +1,313 @@
+//! Lint tool - run the workspace's linter on a bead's changed files
+//!
+//! The linter is picked from the manifest in the workspace root and run inside
+//! the bead's worktree with JSON output. eslint and ruff are handed the changed
+//! files directly; clippy and golangci-lint check the whole project, so their
+//! issues are filtered down to the changed files afterwards.
+
+use super::test::{bead_worktree, changed_files};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::Path;
+use std::process::Command;
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct LintIssue {
+    /// Path relative to the worktree
+    pub file: String,
+    pub line: u64,
+    /// `error` or `warning`
+    pub severity: String,
+    pub rule: Option<String>,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LintOutput {
+    pub success: bool,
+    pub bead_id: String,
+    /// `clippy`, `ruff`, `eslint`, `golangci-lint`, or `none`
+    pub linter: String,
+    pub issues: Vec<LintIssue>,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Linter {
+    Clippy,
+    Ruff,
+    Eslint,
+    GolangciLint,
+}
+
+impl Linter {
+    fn name(self) -> &'static str {
+        match self {
+            Linter::Clippy => "clippy",
+            Linter::Ruff => "ruff",
+            Linter::Eslint => "eslint",
+            Linter::GolangciLint => "golangci-lint",
+        }
+    }
+
+    fn extensions(self) -> &'static [&'static str] {
+        match self {
+            Linter::Clippy => &["rs"],
+            Linter::Ruff => &["py", "pyi"],
+            Linter::Eslint => &["ts", "tsx", "js", "jsx", "mjs", "cjs"],
+            Linter::GolangciLint => &["go"],
+        }
+    }
+}
+
+/// Lint a bead's changed files inside its worktree
+pub fn lint_bead(bead_id: &str, workspace_root: &Path) -> Result<LintOutput, Box<dyn std::error::Error>> {
+    let (worktree_path, base) = bead_worktree(bead_id, workspace_root)?;
+
+    let Some(linter) = detect_linter(workspace_root) else {
+        return Ok(LintOutput {
+            success: false,
+            bead_id: bead_id.to_string(),
+            linter: "none".to_string(),
+            issues: Vec::new(),
+            message: "No linter detected (expected Cargo.toml, pyproject.toml, package.json, or go.mod)".to_string(),
+        });
+    };
+
+    let changed: Vec<String> = changed_files(&worktree_path, &base)?
+        .into_iter()
+        .filter(|f| {
+            Path::new(f)
+                .extension()
+                .and_then(|e| e.to_str())
+                .is_some_and(|e| linter.extensions().contains(&e))
+        })
+        .filter(|f| worktree_path.join(f).is_file())
+        .collect();
+    if changed.is_empty() {
+        return Ok(LintOutput {
+            success: true,
+            bead_id: bead_id.to_string(),
+            linter: linter.name().to_string(),
+            issues: Vec::new(),
+            message: format!("No changed files for {} to check", linter.name()),
+        });
+    }
+
+    let args = lint_command(linter, &changed);
+    let output = Command::new(&args[0])
+        .args(&args[1..])
+        .current_dir(&worktree_path)
+        .output()
+        .map_err(|e| format!("Failed to run {}: {}", args[0], e))?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    let root = worktree_path.canonicalize().unwrap_or_else(|_| worktree_path.clone());
+    let relative = |file: &str| {
+        let path = Path::new(file);
+        path.strip_prefix(&root)
+            .or_else(|_| path.strip_prefix(&worktree_path))
+            .unwrap_or(path)
+            .to_string_lossy()
+            .to_string()
+    };
+    let reported = parse_issues(linter, &stdout);
+    let any_reported = !reported.is_empty();
+    let issues: Vec<LintIssue> = reported
+        .into_iter()
+        .map(|issue| LintIssue { file: relative(&issue.file), ..issue })
+        .filter(|issue| changed.contains(&issue.file))
+        .collect();
+
+    // A linter that fails without reporting anything couldn't run (bad config, compile error elsewhere)
+    if !output.status.success() && !any_reported {
+        return Ok(LintOutput {
+            success: false,
+            bead_id: bead_id.to_string(),
+            linter: linter.name().to_string(),
+            issues,
+            message: format!(
+                "{} failed without reporting issues: {}",
+                linter.name(),
+                String::from_utf8_lossy(&output.stderr).trim()
+            ),
+        });
+    }
+
+    let errors = issues.iter().filter(|i| i.severity == "error").count();
+    Ok(LintOutput {
+        success: issues.is_empty(),
+        bead_id: bead_id.to_string(),
+        linter: linter.name().to_string(),
+        message: if issues.is_empty() {
+            format!("{} clean on {} changed files", linter.name(), changed.len())
+        } else {
+            format!(
+                "{} issues ({} errors, {} warnings) in changed files",
+                issues.len(),
+                errors,
+                issues.len() - errors
+            )
+        },
+        issues,
+    })
+}
+
+fn detect_linter(workspace_root: &Path) -> Option<Linter> {
+    [
+        ("Cargo.toml", Linter::Clippy),
+        ("pyproject.toml", Linter::Ruff),
+        ("package.json", Linter::Eslint),
+        ("go.mod", Linter::GolangciLint),
+    ]
+    .into_iter()
+    .find(|(manifest, _)| workspace_root.join(manifest).is_file())
+    .map(|(_, linter)| linter)
+}
+
+/// Program and arguments for a linter run over `changed`
+fn lint_command(linter: Linter, changed: &[String]) -> Vec<String> {
+    let mut args: Vec<String> = match linter {
+        Linter::Clippy => vec!["cargo", "clippy", "--message-format", "json", "--", "-D", "warnings"],
+        Linter::Ruff => vec!["ruff", "check", "--output-format", "json"],
+        Linter::Eslint => vec!["npx", "eslint", "--max-warnings", "0", "--format", "json"],
+        Linter::GolangciLint => vec!["golangci-lint", "run", "--out-format", "json", "./..."],
+    }
+    .into_iter()
+    .map(String::from)
+    .collect();
+    if matches!(linter, Linter::Ruff | Linter::Eslint) {
+        args.extend(changed.iter().cloned());
+    }
+    args
+}
+
+/// Issues from a linter's JSON output; unparseable output yields none
+fn parse_issues(linter: Linter, stdout: &str) -> Vec<LintIssue> {
+    match linter {
+        Linter::Clippy => stdout
+            .lines()
+            .filter_map(|line| serde_json::from_str::<Value>(line).ok())
+            .filter(|v| v["reason"] == "compiler-message")
+            .filter_map(|v| {
+                let message = &v["message"];
+                let severity = message["level"].as_str()?;
+                if severity != "error" && severity != "warning" {
+                    return None;
+                }
+                // Summary lines like "aborting due to 2 previous errors" have no span
+                let span = message["spans"]
+                    .as_array()?
+                    .iter()
+                    .find(|s| s["is_primary"] == true)?;
+                Some(LintIssue {
+                    file: span["file_name"].as_str()?.to_string(),
+                    line: span["line_start"].as_u64().unwrap_or(0),
+                    severity: severity.to_string(),
+                    rule: message["code"]["code"].as_str().map(String::from),
+                    message: message["message"].as_str().unwrap_or_default().to_string(),
+                })
+            })
+            .collect(),
+        Linter::Ruff => serde_json::from_str::<Vec<Value>>(stdout)
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|v| {
+                Some(LintIssue {
+                    file: v["filename"].as_str()?.to_string(),
+                    line: v["location"]["row"].as_u64().unwrap_or(0),
+                    severity: "error".to_string(),
+                    rule: v["code"].as_str().map(String::from),
+                    message: v["message"].as_str().unwrap_or_default().to_string(),
+                })
+            })
+            .collect(),
+        Linter::Eslint => serde_json::from_str::<Vec<Value>>(stdout)
+            .unwrap_or_default()
+            .iter()
+            .flat_map(|file| {
+                let path = file["filePath"].as_str().unwrap_or_default().to_string();
+                file["messages"]
+                    .as_array()
+                    .cloned()
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(move |m| LintIssue {
+                        file: path.clone(),
+                        line: m["line"].as_u64().unwrap_or(0),
+                        severity: if m["severity"] == 2 { "error" } else { "warning" }.to_string(),
+                        rule: m["ruleId"].as_str().map(String::from),
+                        message: m["message"].as_str().unwrap_or_default().to_string(),
+                    })
+            })
+            .collect(),
+        Linter::GolangciLint => serde_json::from_str::<Value>(stdout)
+            .ok()
+            .and_then(|v| v["Issues"].as_array().cloned())
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|v| {
+                Some(LintIssue {
+                    file: v["Pos"]["Filename"].as_str()?.to_string(),
+                    line: v["Pos"]["Line"].as_u64().unwrap_or(0),
+                    severity: v["Severity"]
+                        .as_str()
+                        .filter(|s| !s.is_empty())
+                        .unwrap_or("error")
+                        .to_string(),
+                    rule: v["FromLinter"].as_str().map(String::from),
+                    message: v["Text"].as_str().unwrap_or_default().to_string(),
+                })
+            })
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_issues() {
+        let clippy = [
+            r#"{"reason":"compiler-artifact","target":{}}"#,
+            r#"{"reason":"compiler-message","message":{"level":"warning","code":{"code":"clippy::needless_return"},"message":"unneeded `return` statement","spans":[{"file_name":"src/lib.rs","line_start":4,"is_primary":true}]}}"#,
+            r#"{"reason":"compiler-message","message":{"level":"error","code":null,"message":"aborting due to 1 previous error","spans":[]}}"#,
+        ]
+        .join("\n");
+        assert_eq!(
+            parse_issues(Linter::Clippy, &clippy),
+            vec![LintIssue {
+                file: "src/lib.rs".to_string(),
+                line: 4,
+                severity: "warning".to_string(),
+                rule: Some("clippy::needless_return".to_string()),
+                message: "unneeded `return` statement".to_string(),
+            }]
+        );
+
+        let eslint = r#"[{"filePath":"/w/src/a.ts","messages":[
+            {"ruleId":"no-unused-vars","severity":2,"message":"'x' is unused","line":3},
+            {"ruleId":"eqeqeq","severity":1,"message":"Expected '==='","line":9}]}]"#;
+        let issues = parse_issues(Linter::Eslint, eslint);
+        assert_eq!(issues.len(), 2);
+        assert_eq!((issues[0].severity.as_str(), issues[0].line), ("error", 3));
+        assert_eq!((issues[1].severity.as_str(), issues[1].rule.as_deref()), ("warning", Some("eqeqeq")));
+
+        let ruff = r#"[{"code":"F401","message":"`os` imported but unused","filename":"/w/app.py","location":{"row":1,"column":8}}]"#;
+        assert_eq!(parse_issues(Linter::Ruff, ruff)[0].rule.as_deref(), Some("F401"));
+
+        let go = r#"{"Issues":[{"FromLinter":"errcheck","Text":"unchecked error","Severity":"","Pos":{"Filename":"main.go","Line":12}}]}"#;
+        let issues = parse_issues(Linter::GolangciLint, go);
+        assert_eq!((issues[0].file.as_str(), issues[0].severity.as_str()), ("main.go", "error"));
+
+        assert!(parse_issues(Linter::Ruff, "not json").is_empty());
+    }
+
+    #[test]
+    fn test_lint_command() {
+        let changed = vec!["a.py".to_string()];
+        assert_eq!(lint_command(Linter::Ruff, &changed), ["ruff", "check", "--output-format", "json", "a.py"]);
+        assert!(!lint_command(Linter::Clippy, &["src/a.rs".to_string()]).contains(&"src/a.rs".to_string()));
+    }
+}
@@ -8,6 +8,15 @@ use serde::{Deserialize, Serialize};
 pub struct ListOutput {
     pub claims: Vec<ClaimInfo>,
     pub total: usize,
+    /// Beads reserved with `bacchus assign` that haven't been claimed yet
+    pub assigned: Vec<AssignmentInfo>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AssignmentInfo {
+    pub bead_id: String,
+    pub agent_id: String,
+    pub age_minutes: i64,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -17,6 +26,12 @@ pub struct ClaimInfo {
     pub worktree_path: String,
     pub branch_name: String,
     pub age_minutes: i64,
+    /// The worktree has changes shelved with `bacchus stash`
+    pub stashed: bool,
+    /// Agent-reported completion percentage (`bacchus progress`)
+    pub progress: u8,
+    /// Exempt from stale detection (`bacchus pin`)
+    pub pinned: bool,
 }
 
 /// List all active claims
@@ -24,7 +39,8 @@ pub fn list_claims() -> Result<ListOutput> {
     with_db(|conn| {
         let mut stmt = conn.prepare(
             "SELECT bead_id, agent_id, worktree_path, branch_name,
-                    (strftime('%s', 'now') * 1000 - claimed_at) / 60000 as age_minutes
+                    (strftime('%s', 'now') * 1000 - claimed_at) / 60000 as age_minutes,
+                    stashed_at, progress, pinned
              FROM claims
              ORDER BY claimed_at DESC",
         )?;
@@ -37,6 +53,27 @@ pub fn list_claims() -> Result<ListOutput> {
                     worktree_path: row.get(2)?,
                     branch_name: row.get(3)?,
                     age_minutes: row.get(4)?,
+                    stashed: row.get::<_, Option<i64>>(5)?.is_some(),
+                    progress: row.get(6)?,
+                    pinned: row.get(7)?,
+                })
+            })?
+            .filter_map(|r| r.ok())
+            .collect();
+
+        let mut stmt = conn.prepare(
+            "SELECT bead_id, agent_id,
+                    (strftime('%s', 'now') * 1000 - assigned_at) / 60000 as age_minutes
+             FROM assignments
+             WHERE bead_id NOT IN (SELECT bead_id FROM claims)
+             ORDER BY assigned_at",
+        )?;
+        let assigned: Vec<AssignmentInfo> = stmt
+            .query_map([], |row| {
+                Ok(AssignmentInfo {
+                    bead_id: row.get(0)?,
+                    agent_id: row.get(1)?,
+                    age_minutes: row.get(2)?,
                 })
             })?
             .filter_map(|r| r.ok())
@@ -45,6 +82,7 @@ pub fn list_claims() -> Result<ListOutput> {
         Ok(ListOutput {
             total: claims.len(),
             claims,
+            assigned,
         })
     })
 }
@@ -0,0 +1,166 @@
+//! Log tool - coordination history from the audit log's operation rows
+//!
+//! Claims, releases, aborts, resolves, and stale cleanups record an outcome
+//! and details as they happen; invocation rows are left to `bacchus audit`.
+
+use super::report::parse_since;
+use crate::db::with_db;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LogEntry {
+    pub id: i64,
+    pub timestamp_ms: i64,
+    pub operation: String,
+    pub bead_id: Option<String>,
+    pub agent_id: Option<String>,
+    /// success, failure (refused, e.g. no claim), or error
+    pub outcome: Option<String>,
+    pub details: serde_json::Value,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LogOutput {
+    pub entries: Vec<LogEntry>,
+    pub total: usize,
+}
+
+/// Operations newest first, rendered as JSON or an ASCII table
+pub fn operation_log(
+    bead_id: Option<&str>,
+    agent_id: Option<&str>,
+    limit: i32,
+    since: Option<&str>,
+    format: &str,
+) -> Result<String, String> {
+    let now = chrono::Utc::now().timestamp_millis();
+    let since_ms = since.map(|s| parse_since(s, now)).transpose()?;
+
+    let entries = query_operations(bead_id, agent_id, limit, since_ms).map_err(|e| e.to_string())?;
+
+    match format {
+        "table" => Ok(render_table(&entries)),
+        _ => serde_json::to_string_pretty(&LogOutput {
+            total: entries.len(),
+            entries,
+        })
+        .map_err(|e| e.to_string()),
+    }
+}
+
+fn query_operations(
+    bead_id: Option<&str>,
+    agent_id: Option<&str>,
+    limit: i32,
+    since_ms: Option<i64>,
+) -> rusqlite::Result<Vec<LogEntry>> {
+    with_db(|conn| {
+        let mut stmt = conn.prepare(
+            "SELECT id, timestamp_ms, operation, bead_id, agent_id, outcome, details
+             FROM audit_log
+             WHERE operation IS NOT NULL
+               AND (?1 IS NULL OR bead_id = ?1)
+               AND (?2 IS NULL OR agent_id = ?2)
+               AND (?3 IS NULL OR timestamp_ms >= ?3)
+             ORDER BY timestamp_ms DESC, id DESC
+             LIMIT ?4",
+        )?;
+
+        let entries = stmt
+            .query_map(rusqlite::params![bead_id, agent_id, since_ms, limit], |row| {
+                let details: Option<String> = row.get(6)?;
+                Ok(LogEntry {
+                    id: row.get(0)?,
+                    timestamp_ms: row.get(1)?,
+                    operation: row.get(2)?,
+                    bead_id: row.get(3)?,
+                    agent_id: row.get(4)?,
+                    outcome: row.get(5)?,
+                    details: details
+                        .and_then(|d| serde_json::from_str(&d).ok())
+                        .unwrap_or(serde_json::Value::Null),
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(entries)
+    })
+}
+
+fn render_table(entries: &[LogEntry]) -> String {
+    let headers = ["TIME", "OPERATION", "BEAD", "AGENT", "OUTCOME"];
+    let rows: Vec<[String; 5]> = entries
+        .iter()
+        .map(|e| {
+            [
+                chrono::DateTime::from_timestamp_millis(e.timestamp_ms)
+                    .map(|d| d.format("%Y-%m-%d %H:%M:%S").to_string())
+                    .unwrap_or_default(),
+                e.operation.clone(),
+                e.bead_id.clone().unwrap_or_else(|| "-".to_string()),
+                e.agent_id.clone().unwrap_or_else(|| "-".to_string()),
+                e.outcome.clone().unwrap_or_else(|| "-".to_string()),
+            ]
+        })
+        .collect();
+
+    let widths: Vec<usize> = (0..headers.len())
+        .map(|i| rows.iter().map(|r| r[i].chars().count()).chain([headers[i].len()]).max().unwrap_or(0))
+        .collect();
+    let line = |cells: &[&str]| {
+        cells
+            .iter()
+            .zip(&widths)
+            .map(|(c, w)| format!("{:<width$}", c, width = w))
+            .collect::<Vec<_>>()
+            .join("  ")
+            .trim_end()
+            .to_string()
+    };
+
+    let mut out = vec![line(&headers)];
+    for row in &rows {
+        out.push(line(&row.iter().map(String::as_str).collect::<Vec<_>>()));
+    }
+    out.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{close_db, init_db, test_lock};
+    use crate::tools::audit::{log_operation, record_operation, start_audit};
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_operation_log() {
+        let _lock = test_lock();
+        let dir = tempdir().unwrap();
+        init_db(Some(dir.path().join("test.db").to_str().unwrap()), true).unwrap();
+
+        start_audit(&serde_json::json!("status")).unwrap();
+        record_operation("claim", Some("BEAD-1"), Some("agent-a"), "success", None).unwrap();
+        record_operation("claim", Some("BEAD-2"), Some("agent-b"), "success", None).unwrap();
+        let refused: Result<&str, String> = Ok("No claim found for BEAD-1");
+        log_operation("release", "BEAD-1", None, &refused, |m| (false, serde_json::json!({ "message": m })));
+
+        let all = query_operations(None, None, 50, None).unwrap();
+        assert_eq!(all.len(), 3);
+        assert_eq!(all[0].operation, "release");
+        assert_eq!(all[0].outcome.as_deref(), Some("failure"));
+        assert_eq!(all[0].details["message"], "No claim found for BEAD-1");
+
+        assert_eq!(query_operations(Some("BEAD-1"), None, 50, None).unwrap().len(), 2);
+        assert_eq!(query_operations(None, Some("agent-b"), 50, None).unwrap().len(), 1);
+        assert_eq!(query_operations(None, None, 1, None).unwrap().len(), 1);
+        assert!(query_operations(None, None, 50, Some(i64::MAX)).unwrap().is_empty());
+
+        let table = operation_log(None, Some("agent-a"), 50, Some("1d"), "table").unwrap();
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with("TIME"));
+        assert!(lines[1].ends_with("claim      BEAD-1  agent-a  success"));
+        assert!(operation_log(None, None, 50, Some("yesterday"), "json").is_err());
+
+        close_db();
+    }
+}
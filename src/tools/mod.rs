@@ -2,25 +2,157 @@
 //!
 //! Each tool corresponds to a CLI command.
 
+pub mod bead_symbols;
+pub mod blame;
+pub mod callers;
 pub mod context;
 pub mod claim;
+pub mod claim_events;
+pub mod clean;
+pub mod config;
+pub mod conflicts;
+pub mod create_worktree;
+pub mod db_integrity;
+pub mod db_stats;
+pub mod depends_on;
+pub mod diff;
+pub mod diff_symbols;
+pub mod doctor;
+pub mod estimate;
+pub mod export;
+pub mod export_beads;
+pub mod export_context;
+pub mod file_locks;
+pub mod format;
+pub mod graph;
+pub mod hints;
+pub mod hooks;
+pub mod impact;
+pub mod import;
+pub mod import_beads;
+pub mod index_state;
+pub mod init;
+pub mod lint;
 pub mod list;
+pub mod log;
 pub mod next;
+pub mod notifications;
+pub mod notify_stale;
+pub mod orchestrate;
+pub mod pause;
+pub mod pin;
+pub mod progress;
+pub mod push;
+pub mod queue;
+pub mod rebase;
+pub mod recover;
 pub mod release;
+pub mod related;
+pub mod remove_worktree;
+pub mod report;
+pub mod reset;
+pub mod revert;
+pub mod search_beads;
+pub mod search_code;
 pub mod resolve;
 pub mod abort;
+pub mod annotate;
+pub mod archive;
+pub mod assign;
+pub mod audit;
+pub mod balance;
 pub mod session;
+pub mod snapshot;
+pub mod split;
 pub mod stale;
+pub mod stash;
+pub mod stats;
+pub mod symbol_locks;
+pub mod symbol_template;
 pub mod symbols;
+pub mod sync;
+pub mod test;
+pub mod todos;
+pub mod verify;
+pub mod version;
+pub mod watch;
+pub mod workflow;
+pub mod worktree_info;
+pub mod worktree_list;
 
+pub use bead_symbols::register_bead_symbol;
+pub use blame::blame_symbol;
+pub use callers::find_callers;
 pub use context::generate_context;
-pub use claim::claim_task;
+pub use claim::{claim_from_file, claim_task};
+pub use clean::clean_workspace;
+pub use config::config_command;
+pub use conflicts::predict_conflicts;
+pub use create_worktree::create_worktree;
+pub use db_integrity::check_integrity;
+pub use db_stats::db_stats;
+pub use depends_on::{bead_blockers, render_blocker_tree};
+pub use diff::diff_bead;
+pub use diff_symbols::diff_symbols;
+pub use doctor::{render_doctor, run_doctor};
+pub use estimate::estimate_task_size;
+pub use export::export_workspace;
+pub use export_beads::export_beads;
+pub use export_context::export_context;
+pub use file_locks::{lock_file, unlock_file};
+pub use format::format_bead;
+pub use graph::bead_graph;
+pub use hints::register_file_hint;
+pub use hooks::manage_hooks;
+pub use impact::impact_analysis;
+pub use import::import_workspace;
+pub use import_beads::import_beads;
+pub use init::init_workspace;
+pub use lint::lint_bead;
 pub use list::list_claims;
-pub use next::next_task;
+pub use log::operation_log;
+pub use next::{next_task, next_task_wait};
+pub use notifications::{acknowledge_notification, heartbeat, list_notifications, render_notifications};
+pub use notify_stale::notify_stale_agents;
+pub use orchestrate::orchestrate;
+pub use pause::{pause_bead, resume_bead};
+pub use pin::{pin_claim, unpin_claim};
+pub use progress::set_progress;
+pub use push::push_bead;
+pub use queue::bead_queue;
+pub use rebase::rebase_bead;
+pub use recover::recover;
+pub use related::find_related_beads;
 pub use release::release_bead;
+pub use remove_worktree::remove_worktree;
+pub use report::generate_report;
+pub use reset::reset_bead;
+pub use revert::revert_bead;
+pub use search_beads::search_beads;
+pub use search_code::search_code;
 pub use resolve::resolve_merge;
 pub use abort::abort_merge;
+pub use annotate::annotate_file;
+pub use archive::{archive_bead, unarchive_bead};
+pub use assign::assign_bead;
+pub use audit::query_audit_log;
+pub use balance::balance;
 pub use session::{start_session, stop_session, session_status, check_session};
+pub use snapshot::{list_snapshots, restore_worktree_snapshot, snapshot_worktree};
+pub use split::split_bead;
 pub use stale::find_stale;
-pub use symbols::{find_symbols, FindSymbolsInput};
+pub use stash::{list_stashes, stash_bead, unstash_bead};
+pub use stats::compute_stats;
+pub use symbol_locks::{lock_symbol, unlock_symbol};
+pub use symbol_template::render_symbols;
+pub use symbols::{find_changed_symbols, find_cross_references, find_duplicate_symbols, find_symbols, register_virtual_symbol, FindSymbolsInput};
+pub use sync::sync_bead;
+pub use test::test_bead;
+pub use todos::list_todos;
+pub use verify::verify_workspace;
+pub use version::version_info;
+pub use watch::watch;
+pub use workflow::workflow_doc;
+pub use worktree_info::worktree_info;
+pub use worktree_list::worktree_list;
 
@@ -2,13 +2,26 @@
 //!
 //! Combines beads querying, worktree creation, and claiming in one operation.
 
+use super::assign::{assigned_beads, clear_assignment};
+use super::audit::log_operation;
+use super::claim_events::log_claim_event;
+use super::hints::filter_by_file_hints;
+use super::symbol_locks::{locked_by_others, SymbolLockConflict};
 use crate::beads;
+use crate::config::resolve_target_branch;
 use crate::db::with_db;
 use crate::worktree;
 use rusqlite::Result;
 use serde::{Deserialize, Serialize};
 use std::path::Path;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// How often `--wait` re-checks for ready beads
+const WAIT_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Set by the SIGINT handler to end a `--wait` loop early
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct NextOutput {
@@ -18,17 +31,24 @@ pub struct NextOutput {
     pub description: Option<String>,
     pub worktree_path: Option<String>,
     pub branch: Option<String>,
+    /// The bead's registered symbols that other beads hold advisory locks on
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub locked_symbols: Vec<SymbolLockConflict>,
     pub message: String,
 }
 
-pub fn next_task(agent_id: &str, workspace_root: &Path) -> Result<NextOutput> {
-    // 1. Get ready beads from beads DB
-    let ready = beads::get_ready_beads().map_err(|e| {
-        rusqlite::Error::SqliteFailure(
-            rusqlite::ffi::Error::new(1),
-            Some(format!("Failed to get ready beads: {}", e)),
-        )
-    })?;
+/// Claim the highest-priority ready bead
+///
+/// With `filter_files`, beads hinted at those files are preferred and beads
+/// hinted only at other files are skipped (see `hints::filter_by_file_hints`).
+pub fn next_task(
+    agent_id: &str,
+    filter_files: &[String],
+    target_branch: Option<&str>,
+    workspace_root: &Path,
+) -> Result<NextOutput> {
+    // 1. Get this agent's assigned beads, then ready beads from beads DB
+    let ready = candidate_beads(agent_id, filter_files)?;
 
     if ready.is_empty() {
         return Ok(NextOutput {
@@ -38,11 +58,12 @@ pub fn next_task(agent_id: &str, workspace_root: &Path) -> Result<NextOutput> {
             description: None,
             worktree_path: None,
             branch: None,
+            locked_symbols: Vec::new(),
             message: "No ready beads available".to_string(),
         });
     }
 
-    // 2. Pick first ready bead (already sorted by priority)
+    // 2. Pick first candidate (assignments, then ready beads by priority)
     let bead = &ready[0];
 
     // 3. Check if already claimed in bacchus DB
@@ -64,6 +85,7 @@ pub fn next_task(agent_id: &str, workspace_root: &Path) -> Result<NextOutput> {
             description: bead.description.clone(),
             worktree_path: None,
             branch: None,
+            locked_symbols: Vec::new(),
             message: format!("Bead {} is already claimed", bead.id),
         });
     }
@@ -82,16 +104,19 @@ pub fn next_task(agent_id: &str, workspace_root: &Path) -> Result<NextOutput> {
         .map(|d| d.as_millis() as i64)
         .unwrap_or(0);
 
+    let target_branch = resolve_target_branch(target_branch, workspace_root);
     let claim_result = with_db(|conn| {
         conn.execute(
-            "INSERT INTO claims (bead_id, agent_id, worktree_path, branch_name, start_commit, claimed_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            "INSERT INTO claims (bead_id, agent_id, worktree_path, branch_name, start_commit, claimed_at, target_branch, title) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
             rusqlite::params![
                 &bead.id,
                 agent_id,
                 wt.path.to_string_lossy().to_string(),
                 &wt.branch,
                 &wt.head_commit,
-                now
+                now,
+                target_branch,
+                &bead.title
             ],
         )
     });
@@ -115,6 +140,27 @@ pub fn next_task(agent_id: &str, workspace_root: &Path) -> Result<NextOutput> {
         ));
     }
 
+    log_claim_event(&bead.id, Some(agent_id), "claimed", None);
+    if let Err(e) = clear_assignment(&bead.id) {
+        eprintln!("Warning: failed to clear assignment of {}: {}", bead.id, e);
+    }
+
+    // 7. Warn (but don't refuse) if another bead has locked this bead's symbols
+    let locked_symbols = locked_by_others(&bead.id)?;
+    let mut message = format!("Claimed {} - work in {}", bead.id, wt.path.display());
+    if !locked_symbols.is_empty() {
+        let held: Vec<String> = locked_symbols
+            .iter()
+            .map(|l| format!("{} (locked by {})", l.fq_name, l.locked_by))
+            .collect();
+        eprintln!("Warning: {} has locked symbols: {}", bead.id, held.join(", "));
+        message.push_str(&format!(". Warning: locked symbols: {}", held.join(", ")));
+    }
+    let claimed: Result<&str> = Ok(&message);
+    log_operation("claim", &bead.id, Some(agent_id), &claimed, |m| {
+        (true, serde_json::json!({ "message": m }))
+    });
+
     Ok(NextOutput {
         success: true,
         bead_id: Some(bead.id.clone()),
@@ -122,6 +168,61 @@ pub fn next_task(agent_id: &str, workspace_root: &Path) -> Result<NextOutput> {
         description: bead.description.clone(),
         worktree_path: Some(wt.path.to_string_lossy().to_string()),
         branch: Some(wt.branch),
-        message: format!("Claimed {} - work in {}", bead.id, wt.path.display()),
+        locked_symbols,
+        message,
     })
 }
+
+/// Like `next_task`, but polls until a bead is ready, the timeout passes, or SIGINT
+///
+/// On timeout or interrupt this returns the usual "no ready beads" response.
+pub fn next_task_wait(
+    agent_id: &str,
+    filter_files: &[String],
+    timeout_secs: Option<u64>,
+    target_branch: Option<&str>,
+    workspace_root: &Path,
+) -> Result<NextOutput> {
+    // Only one handler per process; if one is already set we just can't interrupt early
+    let _ = ctrlc::set_handler(|| INTERRUPTED.store(true, Ordering::SeqCst));
+
+    let deadline = timeout_secs.map(|t| Instant::now() + Duration::from_secs(t));
+    let expired = || deadline.is_some_and(|d| Instant::now() >= d);
+
+    loop {
+        let ready = candidate_beads(agent_id, filter_files)?;
+
+        if !ready.is_empty() || expired() || INTERRUPTED.load(Ordering::SeqCst) {
+            break;
+        }
+
+        eprintln!("waiting for ready beads...");
+
+        // Sleep in short steps so SIGINT and the deadline are noticed promptly
+        let wake = Instant::now() + WAIT_POLL_INTERVAL;
+        while Instant::now() < wake && !expired() && !INTERRUPTED.load(Ordering::SeqCst) {
+            std::thread::sleep(Duration::from_millis(100));
+        }
+    }
+
+    next_task(agent_id, filter_files, target_branch, workspace_root)
+}
+
+/// Beads `agent_id` could take next: its assignments (oldest first), then
+/// ready beads from the beads DB, each narrowed by file hints
+fn candidate_beads(agent_id: &str, filter_files: &[String]) -> Result<Vec<beads::BeadInfo>> {
+    let mut candidates = filter_by_file_hints(assigned_beads(agent_id)?, filter_files)?;
+
+    let ready = beads::get_ready_beads().map_err(|e| {
+        rusqlite::Error::SqliteFailure(
+            rusqlite::ffi::Error::new(1),
+            Some(format!("Failed to get ready beads: {}", e)),
+        )
+    })?;
+    let ready: Vec<beads::BeadInfo> = ready
+        .into_iter()
+        .filter(|b| !candidates.iter().any(|c| c.id == b.id))
+        .collect();
+    candidates.extend(filter_by_file_hints(ready, filter_files)?);
+    Ok(candidates)
+}
@@ -0,0 +1,278 @@
+//! Agent notifications - queued messages delivered on the agent's next heartbeat
+//!
+//! A heartbeat also marks the agent's claims as alive, which resets their
+//! stale timer. `bacchus notifications` lists the queue without delivering it.
+
+use crate::db::with_db;
+use rusqlite::{OptionalExtension, Result};
+use serde::{Deserialize, Serialize};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Notification {
+    pub id: i64,
+    pub notification_type: String,
+    pub target_bead: Option<String>,
+    pub message: String,
+    pub created_at: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HeartbeatOutput {
+    pub agent_id: String,
+    /// `alive` if at least one claim was refreshed, else `no_claims`
+    pub status: String,
+    /// Claims refreshed by this heartbeat
+    pub beads: Vec<String>,
+    /// RFC 3339 time recorded on the refreshed claims
+    pub last_heartbeat: Option<String>,
+    pub notifications: Vec<Notification>,
+}
+
+fn now_ms() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as i64)
+        .unwrap_or(0)
+}
+
+/// Queue a notification for `agent_id`
+///
+/// Returns false without queueing if an identical type/bead notification is
+/// still pending for that agent, so repeated broadcasts don't pile up.
+pub fn queue_notification(
+    notification_type: &str,
+    agent_id: &str,
+    bead_id: Option<&str>,
+    message: &str,
+) -> Result<bool> {
+    with_db(|conn| {
+        let pending: Option<i64> = conn
+            .query_row(
+                "SELECT id FROM notifications
+                 WHERE notification_type = ?1 AND target_agent = ?2 AND target_bead IS ?3 AND status = 'pending'",
+                rusqlite::params![notification_type, agent_id, bead_id],
+                |row| row.get(0),
+            )
+            .optional()?;
+        if pending.is_some() {
+            return Ok(false);
+        }
+
+        conn.execute(
+            "INSERT INTO notifications (notification_type, target_agent, target_bead, change_description, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            rusqlite::params![notification_type, agent_id, bead_id, message, now_ms()],
+        )?;
+        Ok(true)
+    })
+}
+
+/// Refresh an agent's claims (or just `bead_id`'s) and deliver and acknowledge
+/// all of its pending notifications
+pub fn heartbeat(agent_id: &str, bead_id: Option<&str>) -> Result<HeartbeatOutput> {
+    let now = now_ms();
+    with_db(|conn| {
+        let tx = conn.unchecked_transaction()?;
+        let beads = {
+            let mut stmt = tx.prepare(
+                "UPDATE claims SET last_heartbeat = ?1
+                 WHERE agent_id = ?2 AND (?3 IS NULL OR bead_id = ?3)
+                 RETURNING bead_id",
+            )?;
+            let rows = stmt.query_map(rusqlite::params![now, agent_id, bead_id], |row| row.get(0))?;
+            let mut beads = rows.collect::<Result<Vec<String>>>()?;
+            beads.sort();
+            beads
+        };
+        let notifications = {
+            let mut stmt = tx.prepare(
+                "SELECT id, notification_type, target_bead, change_description, created_at FROM notifications
+                 WHERE target_agent = ?1 AND status = 'pending' ORDER BY created_at, id",
+            )?;
+            let rows = stmt.query_map([agent_id], |row| {
+                Ok(Notification {
+                    id: row.get(0)?,
+                    notification_type: row.get(1)?,
+                    target_bead: row.get(2)?,
+                    message: row.get(3)?,
+                    created_at: row.get(4)?,
+                })
+            })?;
+            rows.collect::<Result<Vec<_>>>()?
+        };
+        tx.execute(
+            "UPDATE notifications SET status = 'acknowledged', acknowledged_at = ?1
+             WHERE target_agent = ?2 AND status = 'pending'",
+            rusqlite::params![now, agent_id],
+        )?;
+        tx.commit()?;
+
+        let alive = !beads.is_empty();
+        Ok(HeartbeatOutput {
+            agent_id: agent_id.to_string(),
+            status: if alive { "alive" } else { "no_claims" }.to_string(),
+            beads,
+            last_heartbeat: alive
+                .then(|| chrono::DateTime::from_timestamp_millis(now).map(|d| d.to_rfc3339()))
+                .flatten(),
+            notifications,
+        })
+    })
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct NotificationEntry {
+    pub id: i64,
+    pub notification_type: String,
+    pub target_agent: String,
+    pub target_bead: Option<String>,
+    pub message: String,
+    /// `pending` or `acknowledged`
+    pub status: String,
+    pub created_at: i64,
+    pub age_minutes: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct NotificationsOutput {
+    pub notifications: Vec<NotificationEntry>,
+    pub total: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AcknowledgeOutput {
+    pub success: bool,
+    pub id: i64,
+    pub message: String,
+}
+
+/// Newest notifications first, optionally for one agent and only pending ones
+pub fn list_notifications(agent_id: Option<&str>, pending_only: bool, limit: i32) -> Result<Vec<NotificationEntry>> {
+    let now = now_ms();
+    with_db(|conn| {
+        let mut stmt = conn.prepare(
+            "SELECT id, notification_type, target_agent, target_bead, change_description, status, created_at
+             FROM notifications
+             WHERE (?1 IS NULL OR target_agent = ?1) AND (?2 = 0 OR status = 'pending')
+             ORDER BY created_at DESC, id DESC LIMIT ?3",
+        )?;
+        let rows = stmt.query_map(rusqlite::params![agent_id, pending_only, limit], |row| {
+            let created_at: i64 = row.get(6)?;
+            Ok(NotificationEntry {
+                id: row.get(0)?,
+                notification_type: row.get(1)?,
+                target_agent: row.get(2)?,
+                target_bead: row.get(3)?,
+                message: row.get(4)?,
+                status: row.get(5)?,
+                created_at,
+                age_minutes: (now - created_at) / 60_000,
+            })
+        })?;
+        rows.collect()
+    })
+}
+
+/// Mark one pending notification as acknowledged without a heartbeat
+pub fn acknowledge_notification(id: i64) -> Result<AcknowledgeOutput> {
+    let updated = with_db(|conn| {
+        conn.execute(
+            "UPDATE notifications SET status = 'acknowledged', acknowledged_at = ?1
+             WHERE id = ?2 AND status = 'pending'",
+            rusqlite::params![now_ms(), id],
+        )
+    })?;
+
+    Ok(AcknowledgeOutput {
+        success: updated > 0,
+        id,
+        message: if updated > 0 {
+            format!("Acknowledged notification {}", id)
+        } else {
+            format!("No pending notification with id {}", id)
+        },
+    })
+}
+
+/// Render notifications as JSON or an ASCII table
+pub fn render_notifications(notifications: Vec<NotificationEntry>, format: &str) -> String {
+    if format != "table" {
+        let output = NotificationsOutput {
+            total: notifications.len(),
+            notifications,
+        };
+        return serde_json::to_string_pretty(&output).unwrap_or_default();
+    }
+
+    let headers = ["ID", "TYPE", "AGENT", "BEAD", "STATUS", "AGE", "MESSAGE"];
+    let rows: Vec<[String; 7]> = notifications
+        .iter()
+        .map(|n| {
+            [
+                n.id.to_string(),
+                n.notification_type.clone(),
+                n.target_agent.clone(),
+                n.target_bead.clone().unwrap_or_else(|| "-".to_string()),
+                n.status.clone(),
+                format!("{}m", n.age_minutes),
+                n.message.clone(),
+            ]
+        })
+        .collect();
+
+    let widths: Vec<usize> = (0..headers.len())
+        .map(|i| rows.iter().map(|r| r[i].chars().count()).chain([headers[i].len()]).max().unwrap_or(0))
+        .collect();
+    let line = |cells: &[&str]| {
+        cells
+            .iter()
+            .zip(&widths)
+            .map(|(c, w)| format!("{:<width$}", c, width = w))
+            .collect::<Vec<_>>()
+            .join("  ")
+            .trim_end()
+            .to_string()
+    };
+
+    let mut out = vec![line(&headers)];
+    for row in &rows {
+        out.push(line(&row.iter().map(String::as_str).collect::<Vec<_>>()));
+    }
+    out.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{close_db, init_db, test_lock};
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_list_and_acknowledge() {
+        let _lock = test_lock();
+        let dir = tempdir().unwrap();
+        init_db(Some(dir.path().join("test.db").to_str().unwrap()), true).unwrap();
+
+        assert!(queue_notification("stale_claim", "agent-a", Some("BEAD-1"), "BEAD-1 looks stale").unwrap());
+        assert!(queue_notification("symbol_changed", "agent-b", None, "foo changed").unwrap());
+
+        let all = list_notifications(None, true, 50).unwrap();
+        assert_eq!(all.len(), 2);
+        let mine = list_notifications(Some("agent-a"), true, 50).unwrap();
+        assert_eq!(mine.len(), 1);
+        assert_eq!(mine[0].target_bead.as_deref(), Some("BEAD-1"));
+
+        assert!(acknowledge_notification(mine[0].id).unwrap().success);
+        assert!(!acknowledge_notification(mine[0].id).unwrap().success);
+        assert!(list_notifications(Some("agent-a"), true, 50).unwrap().is_empty());
+        assert_eq!(list_notifications(Some("agent-a"), false, 50).unwrap()[0].status, "acknowledged");
+        assert_eq!(list_notifications(None, false, 1).unwrap().len(), 1);
+
+        let table = render_notifications(list_notifications(Some("agent-b"), true, 50).unwrap(), "table");
+        assert!(table.lines().next().unwrap().starts_with("ID  TYPE"));
+        assert!(table.contains("symbol_changed  agent-b  -     pending  0m   foo changed"));
+
+        close_db();
+    }
+}
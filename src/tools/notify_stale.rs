@@ -0,0 +1,95 @@
+//! Notify stale agents tool - warn agents whose claims have gone stale
+//!
+//! Warnings are queued as `stale_warning` notifications and delivered on the
+//! agent's next `bacchus heartbeat`.
+
+use super::notifications::queue_notification;
+use super::stale::{find_stale, StaleClaim};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct NotifyStaleOutput {
+    pub notified: Vec<StaleClaim>,
+    /// Beads whose agent already has a pending warning
+    pub already_pending: Vec<String>,
+    pub message: String,
+}
+
+/// Queue a stale warning for the agent of every claim older than `minutes`
+pub fn notify_stale_agents(
+    minutes: i64,
+    workspace_root: &Path,
+) -> Result<NotifyStaleOutput, Box<dyn std::error::Error>> {
+    let stale = find_stale(minutes, false, None, workspace_root)?;
+
+    let mut notified = Vec::new();
+    let mut already_pending = Vec::new();
+    for claim in stale.stale_claims {
+        let message = format!(
+            "Your claim for {} has been stale for {} minutes. Please heartbeat or release.",
+            claim.bead_id, claim.age_minutes
+        );
+        if queue_notification("stale_warning", &claim.agent_id, Some(&claim.bead_id), &message)? {
+            notified.push(claim);
+        } else {
+            already_pending.push(claim.bead_id);
+        }
+    }
+
+    let message = format!("Notified {} agent claim(s)", notified.len());
+    Ok(NotifyStaleOutput {
+        notified,
+        already_pending,
+        message,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{close_db, init_db, test_lock, with_db};
+    use crate::tools::notifications::heartbeat;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_notify_stale_agents() {
+        let _lock = test_lock();
+        let dir = tempdir().unwrap();
+        init_db(Some(dir.path().join("test.db").to_str().unwrap()), true).unwrap();
+
+        with_db(|conn| {
+            conn.execute(
+                "INSERT INTO claims (bead_id, agent_id, worktree_path, branch_name, start_commit, claimed_at)
+                 VALUES ('old-bead', 'agent-1', '/tmp/wt', 'bacchus/old-bead', 'abc', 0)",
+                [],
+            )
+        })
+        .unwrap();
+
+        let output = notify_stale_agents(30, dir.path()).unwrap();
+        assert_eq!(output.notified.len(), 1);
+
+        // A second broadcast doesn't duplicate the pending warning
+        let output = notify_stale_agents(30, dir.path()).unwrap();
+        assert!(output.notified.is_empty());
+        assert_eq!(output.already_pending, vec!["old-bead"]);
+
+        let beat = heartbeat("agent-1", None).unwrap();
+        assert_eq!(beat.status, "alive");
+        assert_eq!(beat.beads, vec!["old-bead"]);
+        assert_eq!(beat.notifications.len(), 1);
+        assert_eq!(beat.notifications[0].notification_type, "stale_warning");
+        assert!(beat.notifications[0].message.starts_with("Your claim for old-bead has been stale for"));
+
+        assert!(heartbeat("agent-1", Some("old-bead")).unwrap().notifications.is_empty());
+        let beat = heartbeat("agent-2", None).unwrap();
+        assert_eq!(beat.status, "no_claims");
+        assert!(beat.notifications.is_empty());
+
+        // The heartbeat made the claim fresh again
+        assert!(find_stale(30, false, None, dir.path()).unwrap().stale_claims.is_empty());
+
+        close_db();
+    }
+}
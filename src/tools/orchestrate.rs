@@ -0,0 +1,127 @@
+//! Orchestrate tool - autonomous claim loop
+//!
+//! Each cycle cleans up stale claims, then claims ready beads for free
+//! `orchestrator-N` slots until `max_agents` claims are active. The loop ends
+//! once nothing is ready and nothing is claimed, or on SIGINT.
+
+use super::list::list_claims;
+use super::next::next_task;
+use super::stale::find_stale;
+use crate::beads;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+/// Set by the SIGINT handler to stop after the current cycle
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+
+const AGENT_PREFIX: &str = "orchestrator-";
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OrchestrateOutput {
+    pub cycles: u64,
+    pub claimed: Vec<String>,
+    pub cleaned_up: Vec<String>,
+    pub interrupted: bool,
+    pub message: String,
+}
+
+/// Run the orchestration loop; with `dry_run`, report one cycle without changing anything
+pub fn orchestrate(
+    max_agents: u32,
+    stale_minutes: i64,
+    poll_interval_seconds: u64,
+    dry_run: bool,
+    workspace_root: &Path,
+) -> Result<OrchestrateOutput, Box<dyn std::error::Error>> {
+    // Only one handler per process; if one is already set we just can't interrupt early
+    let _ = ctrlc::set_handler(|| INTERRUPTED.store(true, Ordering::SeqCst));
+
+    let mut output = OrchestrateOutput {
+        cycles: 0,
+        claimed: Vec::new(),
+        cleaned_up: Vec::new(),
+        interrupted: false,
+        message: String::new(),
+    };
+
+    loop {
+        output.cycles += 1;
+
+        let stale = find_stale(stale_minutes, !dry_run, None, workspace_root)?;
+        for claim in &stale.stale_claims {
+            let action = if dry_run { "would clean up" } else { "cleaned up" };
+            eprintln!("[orchestrate] {} stale claim {} ({} min)", action, claim.bead_id, claim.age_minutes);
+        }
+        output.cleaned_up.extend(stale.cleaned_up);
+
+        let ready = beads::get_ready_beads()?.len();
+        let claims = list_claims()?.claims;
+        let active: Vec<&str> = claims.iter().map(|c| c.agent_id.as_str()).collect();
+        eprintln!("[orchestrate] cycle {}: {} ready, {} active", output.cycles, ready, active.len());
+
+        if ready == 0 && active.is_empty() {
+            output.message = "No ready beads and no active claims; done".to_string();
+            break;
+        }
+
+        let slots = (max_agents as usize).saturating_sub(active.len()).min(ready);
+        for agent_id in free_agent_ids(&active, max_agents).into_iter().take(slots) {
+            if dry_run {
+                eprintln!("[orchestrate] would claim next ready bead as {}", agent_id);
+                continue;
+            }
+            let next = next_task(&agent_id, &[], None, workspace_root)?;
+            match (next.success, next.bead_id) {
+                (true, Some(bead_id)) => {
+                    eprintln!("[orchestrate] {} claimed {}", agent_id, bead_id);
+                    output.claimed.push(bead_id);
+                }
+                _ => eprintln!("[orchestrate] {}: {}", agent_id, next.message),
+            }
+        }
+
+        if dry_run {
+            output.message = "Dry run: reported one cycle without changes".to_string();
+            break;
+        }
+
+        // Sleep in short steps so SIGINT is noticed promptly
+        let wake = Instant::now() + Duration::from_secs(poll_interval_seconds);
+        while Instant::now() < wake && !INTERRUPTED.load(Ordering::SeqCst) {
+            std::thread::sleep(Duration::from_millis(100));
+        }
+        if INTERRUPTED.load(Ordering::SeqCst) {
+            eprintln!("[orchestrate] interrupted, shutting down");
+            output.interrupted = true;
+            output.message = "Interrupted".to_string();
+            break;
+        }
+    }
+
+    Ok(output)
+}
+
+/// `orchestrator-1..=max` IDs that don't currently hold a claim
+fn free_agent_ids(active: &[&str], max_agents: u32) -> Vec<String> {
+    (1..=max_agents)
+        .map(|n| format!("{}{}", AGENT_PREFIX, n))
+        .filter(|id| !active.contains(&id.as_str()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_free_agent_ids() {
+        assert_eq!(free_agent_ids(&[], 2), vec!["orchestrator-1", "orchestrator-2"]);
+        assert_eq!(
+            free_agent_ids(&["orchestrator-1", "someone-else"], 3),
+            vec!["orchestrator-2", "orchestrator-3"]
+        );
+        assert!(free_agent_ids(&[], 0).is_empty());
+    }
+}
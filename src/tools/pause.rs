@@ -0,0 +1,238 @@
+//! Pause tool - suspend work on a bead without blocking its dependents
+//!
+//! `pause` moves the claim to `paused_claims` and sets the bead to `paused`,
+//! leaving the worktree and branch in place. `resume` puts the claim back for
+//! an agent with a fresh `claimed_at`, so stale detection starts over.
+
+use super::audit::{claim_agent, log_operation};
+use super::claim_events::log_claim_event;
+use crate::beads;
+use crate::db::with_db;
+use rusqlite::{Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Claim columns kept while a bead is paused
+const PAUSED_COLUMNS: &str = "bead_id, agent_id, worktree_path, branch_name, start_commit, target_branch, title, progress";
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PauseOutput {
+    pub success: bool,
+    pub bead_id: String,
+    pub worktree_path: Option<String>,
+    pub message: String,
+}
+
+fn output(bead_id: &str, success: bool, worktree_path: Option<String>, message: String) -> PauseOutput {
+    PauseOutput {
+        success,
+        bead_id: bead_id.to_string(),
+        worktree_path,
+        message,
+    }
+}
+
+fn now_ms() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as i64)
+        .unwrap_or(0)
+}
+
+pub fn pause_bead(bead_id: &str) -> Result<PauseOutput, Box<dyn std::error::Error>> {
+    let agent_id = claim_agent(bead_id);
+    let result = try_pause(bead_id);
+    log_operation("pause", bead_id, agent_id.as_deref(), &result, |r| {
+        (r.success, serde_json::json!({ "message": r.message }))
+    });
+    result
+}
+
+pub fn resume_bead(bead_id: &str, agent_id: &str) -> Result<PauseOutput, Box<dyn std::error::Error>> {
+    let result = try_resume(bead_id, agent_id);
+    log_operation("resume", bead_id, Some(agent_id), &result, |r| {
+        (r.success, serde_json::json!({ "message": r.message }))
+    });
+    result
+}
+
+fn try_pause(bead_id: &str) -> Result<PauseOutput, Box<dyn std::error::Error>> {
+    let claim: Option<(String, String)> = with_db(|conn| {
+        conn.query_row(
+            "SELECT agent_id, worktree_path FROM claims WHERE bead_id = ?1",
+            [bead_id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .optional()
+    })?;
+    let Some((agent_id, worktree_path)) = claim else {
+        return Ok(output(bead_id, false, None, format!("No claim found for {}", bead_id)));
+    };
+
+    // Status first: if bd fails, the claim is still intact
+    match beads::update_bead_status(bead_id, "paused") {
+        Err(beads::BeadsError::CommandFailed(stderr)) => {
+            let message = beads::custom_status_error(bead_id, "paused", &stderr);
+            return Ok(output(bead_id, false, Some(worktree_path), message));
+        }
+        result => result?,
+    }
+
+    with_db(|conn| {
+        let tx = conn.unchecked_transaction()?;
+        tx.execute(
+            &format!(
+                "INSERT OR REPLACE INTO paused_claims ({cols}, paused_at) SELECT {cols}, ?2 FROM claims WHERE bead_id = ?1",
+                cols = PAUSED_COLUMNS
+            ),
+            rusqlite::params![bead_id, now_ms()],
+        )?;
+        tx.execute("DELETE FROM claims WHERE bead_id = ?1", [bead_id])?;
+        tx.commit()
+    })?;
+    log_claim_event(bead_id, Some(&agent_id), "paused", None);
+
+    Ok(output(
+        bead_id,
+        true,
+        Some(worktree_path),
+        format!("Paused {}; resume with: bacchus resume {} <agent_id>", bead_id, bead_id),
+    ))
+}
+
+fn try_resume(bead_id: &str, agent_id: &str) -> Result<PauseOutput, Box<dyn std::error::Error>> {
+    let paused: Option<String> = with_db(|conn| {
+        conn.query_row("SELECT worktree_path FROM paused_claims WHERE bead_id = ?1", [bead_id], |row| row.get(0))
+            .optional()
+    })?;
+    let Some(worktree_path) = paused else {
+        return Ok(output(bead_id, false, None, format!("{} is not paused", bead_id)));
+    };
+
+    if !Path::new(&worktree_path).is_dir() {
+        return Ok(output(
+            bead_id,
+            false,
+            Some(worktree_path.clone()),
+            format!(
+                "Worktree {} no longer exists (recreate it with: bacchus create-worktree {}, then bacchus recover {})",
+                worktree_path, bead_id, bead_id
+            ),
+        ));
+    }
+
+    let claimed_by: Option<String> = with_db(|conn| {
+        conn.query_row("SELECT agent_id FROM claims WHERE bead_id = ?1", [bead_id], |row| row.get(0))
+            .optional()
+    })?;
+    if let Some(claimed_by) = claimed_by {
+        return Ok(output(
+            bead_id,
+            false,
+            Some(worktree_path),
+            format!("{} is already claimed by {}", bead_id, claimed_by),
+        ));
+    }
+
+    let status = beads::get_bead(bead_id)?.status;
+    if status != "paused" {
+        return Ok(output(
+            bead_id,
+            false,
+            Some(worktree_path),
+            format!("{} has status {}, not paused", bead_id, status),
+        ));
+    }
+
+    with_db(|conn| {
+        let tx = conn.unchecked_transaction()?;
+        tx.execute(
+            "INSERT INTO claims (bead_id, agent_id, worktree_path, branch_name, start_commit, claimed_at, target_branch, title, progress)
+             SELECT bead_id, ?2, worktree_path, branch_name, start_commit, ?3, target_branch, title, progress
+             FROM paused_claims WHERE bead_id = ?1",
+            rusqlite::params![bead_id, agent_id, now_ms()],
+        )?;
+        tx.execute("DELETE FROM paused_claims WHERE bead_id = ?1", [bead_id])?;
+        tx.commit()
+    })?;
+
+    if let Err(e) = beads::update_bead_status(bead_id, "in_progress") {
+        // Put it back the way it was rather than leave a claim on a paused bead
+        let _ = with_db(|conn| {
+            let tx = conn.unchecked_transaction()?;
+            tx.execute(
+                &format!(
+                    "INSERT INTO paused_claims ({cols}, paused_at) SELECT {cols}, ?2 FROM claims WHERE bead_id = ?1",
+                    cols = PAUSED_COLUMNS
+                ),
+                rusqlite::params![bead_id, now_ms()],
+            )?;
+            tx.execute("DELETE FROM claims WHERE bead_id = ?1", [bead_id])?;
+            tx.commit()
+        });
+        return Err(e.into());
+    }
+    log_claim_event(bead_id, Some(agent_id), "resumed", None);
+
+    Ok(output(
+        bead_id,
+        true,
+        Some(worktree_path.clone()),
+        format!("Resumed {} - work in {}", bead_id, worktree_path),
+    ))
+}
+
+/// (bead_id, worktree_path) of every paused bead, oldest first
+pub fn paused_claims(conn: &Connection) -> rusqlite::Result<Vec<(String, String)>> {
+    let mut stmt = conn.prepare("SELECT bead_id, worktree_path FROM paused_claims ORDER BY paused_at, bead_id")?;
+    let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+    rows.collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{close_db, init_db, test_lock};
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_resume_preconditions() {
+        let _lock = test_lock();
+        let dir = tempdir().unwrap();
+        init_db(Some(dir.path().join("test.db").to_str().unwrap()), true).unwrap();
+
+        assert!(!resume_bead("bd-1", "agent-b").unwrap().success);
+
+        let worktree = dir.path().join("bd-1");
+        with_db(|conn| {
+            conn.execute(
+                "INSERT INTO paused_claims (bead_id, agent_id, worktree_path, branch_name, start_commit, paused_at)
+                 VALUES ('bd-1', 'agent-a', ?1, 'bacchus/bd-1', 'abc', 1000)",
+                [worktree.to_string_lossy()],
+            )
+        })
+        .unwrap();
+        let missing = resume_bead("bd-1", "agent-b").unwrap();
+        assert!(!missing.success);
+        assert!(missing.message.contains("no longer exists"));
+
+        std::fs::create_dir(&worktree).unwrap();
+        with_db(|conn| {
+            conn.execute(
+                "INSERT INTO claims (bead_id, agent_id, worktree_path, branch_name, start_commit, claimed_at)
+                 VALUES ('bd-1', 'agent-c', ?1, 'bacchus/bd-1', 'abc', 1000)",
+                [worktree.to_string_lossy()],
+            )
+        })
+        .unwrap();
+        let claimed = resume_bead("bd-1", "agent-b").unwrap();
+        assert!(!claimed.success);
+        assert!(claimed.message.contains("already claimed by agent-c"));
+
+        let paused = with_db(paused_claims).unwrap();
+        assert_eq!(paused, vec![("bd-1".to_string(), worktree.to_string_lossy().to_string())]);
+
+        close_db();
+    }
+}
@@ -0,0 +1,129 @@
+//! Pin tool - exempt long-running claims from stale detection
+//!
+//! A pinned claim is never reported by `stale` (or warned by
+//! `notify-stale-agents`, which uses it) until it is unpinned.
+
+use super::audit::{claim_agent, log_operation};
+use crate::db::with_db;
+use rusqlite::{OptionalExtension, Result};
+use serde::{Deserialize, Serialize};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PinOutput {
+    pub success: bool,
+    pub bead_id: String,
+    pub pinned: bool,
+    /// Set when pinning a claim that is already past the stale threshold
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub warning: Option<String>,
+    pub message: String,
+}
+
+/// Pin a claim, warning if it has already gone `stale_minutes` without a heartbeat
+pub fn pin_claim(bead_id: &str, stale_minutes: i64) -> Result<PinOutput> {
+    set_pinned(bead_id, true, stale_minutes)
+}
+
+pub fn unpin_claim(bead_id: &str) -> Result<PinOutput> {
+    set_pinned(bead_id, false, 0)
+}
+
+fn set_pinned(bead_id: &str, pinned: bool, stale_minutes: i64) -> Result<PinOutput> {
+    let agent_id = claim_agent(bead_id);
+    let result = try_set_pinned(bead_id, pinned, stale_minutes);
+    let operation = if pinned { "pin" } else { "unpin" };
+    log_operation(operation, bead_id, agent_id.as_deref(), &result, |r| {
+        (r.success, serde_json::json!({ "message": r.message }))
+    });
+    result
+}
+
+fn try_set_pinned(bead_id: &str, pinned: bool, stale_minutes: i64) -> Result<PinOutput> {
+    let output = |success: bool, warning: Option<String>, message: String| PinOutput {
+        success,
+        bead_id: bead_id.to_string(),
+        pinned: success && pinned,
+        warning,
+        message,
+    };
+
+    let last_seen: Option<i64> = with_db(|conn| {
+        conn.query_row(
+            "SELECT COALESCE(last_heartbeat, claimed_at) FROM claims WHERE bead_id = ?1",
+            [bead_id],
+            |row| row.get(0),
+        )
+        .optional()
+    })?;
+    let Some(last_seen) = last_seen else {
+        return Ok(output(false, None, format!("No claim found for {}", bead_id)));
+    };
+
+    with_db(|conn| {
+        conn.execute(
+            "UPDATE claims SET pinned = ?1 WHERE bead_id = ?2",
+            rusqlite::params![pinned, bead_id],
+        )
+    })?;
+
+    if !pinned {
+        return Ok(output(true, None, format!("Unpinned {}", bead_id)));
+    }
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as i64)
+        .unwrap_or(0);
+    let age_minutes = (now - last_seen) / 60_000;
+    let warning = (age_minutes >= stale_minutes).then(|| {
+        format!(
+            "{} was already stale ({} minutes without a heartbeat, threshold {}); make sure its agent is still working",
+            bead_id, age_minutes, stale_minutes
+        )
+    });
+    if let Some(warning) = &warning {
+        eprintln!("Warning: {}", warning);
+    }
+
+    Ok(output(true, warning, format!("Pinned {}; stale detection will skip it", bead_id)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{close_db, init_db, test_lock};
+    use crate::tools::stale::find_stale;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_pinned_claims_are_not_stale() {
+        let _lock = test_lock();
+        let dir = tempdir().unwrap();
+        init_db(Some(dir.path().join("test.db").to_str().unwrap()), true).unwrap();
+
+        with_db(|conn| {
+            conn.execute(
+                "INSERT INTO claims (bead_id, agent_id, worktree_path, branch_name, start_commit, claimed_at)
+                 VALUES ('bd-1', 'agent-a', '/tmp/bd-1', 'bacchus/bd-1', 'abc', 1000)",
+                [],
+            )
+        })
+        .unwrap();
+        assert_eq!(find_stale(15, false, None, dir.path()).unwrap().stale_claims.len(), 1);
+
+        let pinned = pin_claim("bd-1", 15).unwrap();
+        assert!(pinned.success && pinned.pinned);
+        assert!(pinned.warning.is_some());
+        assert!(find_stale(15, false, None, dir.path()).unwrap().stale_claims.is_empty());
+        assert!(crate::tools::list_claims().unwrap().claims[0].pinned);
+
+        let unpinned = unpin_claim("bd-1").unwrap();
+        assert!(unpinned.success && !unpinned.pinned);
+        assert_eq!(find_stale(15, false, None, dir.path()).unwrap().stale_claims.len(), 1);
+
+        assert!(!pin_claim("bd-2", 15).unwrap().success);
+
+        close_db();
+    }
+}
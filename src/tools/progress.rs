@@ -0,0 +1,94 @@
+//! Progress tool - agent-reported completion percentage of a claim
+//!
+//! Coarse-grained and self-reported; it lives on the claim row, so a bead
+//! claimed again starts back at 0.
+
+use super::audit::{claim_agent, log_operation};
+use crate::db::with_db;
+use rusqlite::Result;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ProgressOutput {
+    pub success: bool,
+    pub bead_id: String,
+    pub progress: u8,
+    pub message: String,
+}
+
+pub fn set_progress(bead_id: &str, percent: u8) -> Result<ProgressOutput> {
+    let agent_id = claim_agent(bead_id);
+    let result = try_set_progress(bead_id, percent);
+    log_operation("progress", bead_id, agent_id.as_deref(), &result, |r| {
+        (r.success, serde_json::json!({ "message": r.message, "progress": r.progress }))
+    });
+    result
+}
+
+fn try_set_progress(bead_id: &str, percent: u8) -> Result<ProgressOutput> {
+    if percent > 100 {
+        return Ok(ProgressOutput {
+            success: false,
+            bead_id: bead_id.to_string(),
+            progress: percent,
+            message: format!("Progress must be 0-100, got {}", percent),
+        });
+    }
+
+    let updated = with_db(|conn| {
+        conn.execute(
+            "UPDATE claims SET progress = ?1 WHERE bead_id = ?2",
+            rusqlite::params![percent, bead_id],
+        )
+    })?;
+
+    if updated == 0 {
+        return Ok(ProgressOutput {
+            success: false,
+            bead_id: bead_id.to_string(),
+            progress: percent,
+            message: format!("No claim found for {}", bead_id),
+        });
+    }
+
+    Ok(ProgressOutput {
+        success: true,
+        bead_id: bead_id.to_string(),
+        progress: percent,
+        message: format!("{} is {}% done", bead_id, percent),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{close_db, init_db, test_lock};
+    use crate::tools::list::list_claims;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_set_progress() {
+        let _lock = test_lock();
+        let dir = tempdir().unwrap();
+        init_db(Some(dir.path().join("test.db").to_str().unwrap()), true).unwrap();
+
+        with_db(|conn| {
+            conn.execute(
+                "INSERT INTO claims (bead_id, agent_id, worktree_path, branch_name, start_commit, claimed_at)
+                 VALUES ('BEAD-1', 'agent-a', '/tmp/wt', 'bacchus/BEAD-1', 'abc', 0)",
+                [],
+            )
+        })
+        .unwrap();
+        assert_eq!(list_claims().unwrap().claims[0].progress, 0);
+
+        assert!(set_progress("BEAD-1", 40).unwrap().success);
+        assert_eq!(list_claims().unwrap().claims[0].progress, 40);
+
+        assert!(!set_progress("BEAD-1", 101).unwrap().success);
+        assert!(!set_progress("BEAD-2", 10).unwrap().success);
+        assert_eq!(list_claims().unwrap().claims[0].progress, 40);
+
+        close_db();
+    }
+}
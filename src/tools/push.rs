@@ -0,0 +1,56 @@
+//! Push tool - publish a bead's worktree branch for CI or review
+
+use crate::db::with_db;
+use crate::worktree;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PushOutput {
+    pub success: bool,
+    pub bead_id: String,
+    pub remote_url: Option<String>,
+    pub branch: String,
+    pub tracking_created: bool,
+    pub message: String,
+}
+
+pub fn push_bead(
+    bead_id: &str,
+    remote: &str,
+    force: bool,
+    workspace_root: &Path,
+) -> Result<PushOutput, Box<dyn std::error::Error>> {
+    // Only branches of active claims may be pushed
+    let claim_exists = with_db(|conn| {
+        Ok(conn
+            .query_row(
+                "SELECT 1 FROM claims WHERE bead_id = ?1",
+                [bead_id],
+                |_| Ok(true),
+            )
+            .unwrap_or(false))
+    })?;
+
+    if !claim_exists {
+        return Ok(PushOutput {
+            success: false,
+            bead_id: bead_id.to_string(),
+            remote_url: None,
+            branch: worktree::branch_name(bead_id),
+            tracking_created: false,
+            message: format!("No claim found for {}", bead_id),
+        });
+    }
+
+    let info = worktree::push_worktree(workspace_root, bead_id, remote, force)?;
+
+    Ok(PushOutput {
+        success: true,
+        bead_id: bead_id.to_string(),
+        message: format!("Pushed {} to {} ({})", info.branch, remote, info.remote_url),
+        remote_url: Some(info.remote_url),
+        branch: info.branch,
+        tracking_created: info.tracking_created,
+    })
+}
@@ -0,0 +1,202 @@
+//! Queue tool - the ready beads by priority (then ID), with ETAs
+//!
+//! Estimates assume the agents with active claims keep working one bead at a
+//! time (at least one agent), each bead taking the average claim duration from
+//! the audit log. Without any finished claims in the log there is nothing to
+//! estimate from, and the estimates are null.
+
+use super::stats::avg_claim_duration_minutes;
+use crate::beads::{self, BeadInfo};
+use crate::db::with_db;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct QueueEntry {
+    /// 1-based
+    pub position_in_queue: usize,
+    pub bead_id: String,
+    pub title: String,
+    pub priority: i32,
+    /// Beads that depend on this one
+    pub blocks_count: usize,
+    pub estimated_start_minutes: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct QueueSummary {
+    pub total_ready: usize,
+    /// Open beads that aren't ready (blocked by status or dependencies)
+    pub total_blocked: usize,
+    /// Active claims
+    pub total_in_progress: usize,
+    pub avg_claim_duration_minutes: Option<f64>,
+    /// Until the last ready bead would be done
+    pub estimated_completion_minutes: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct QueueOutput {
+    pub queue: Vec<QueueEntry>,
+    pub summary: QueueSummary,
+}
+
+/// The ready queue, rendered as JSON or an ASCII table; `limit` caps the entries shown
+pub fn bead_queue(format: &str, limit: usize) -> Result<String, Box<dyn std::error::Error>> {
+    let ready = beads::get_ready_beads()?;
+    let ready_ids: Vec<&str> = ready.iter().map(|b| b.id.as_str()).collect();
+    let total_blocked = beads::list_all_beads()?
+        .iter()
+        .filter(|b| matches!(b.status.as_str(), "open" | "blocked") && !ready_ids.contains(&b.id.as_str()))
+        .count();
+
+    let (active_claims, avg_minutes) = with_db(|conn| {
+        let active: usize = conn.query_row("SELECT COUNT(*) FROM claims", [], |r| r.get(0))?;
+        Ok((active, avg_claim_duration_minutes(conn, None)?))
+    })?;
+
+    let output = build_queue(ready, total_blocked, active_claims, avg_minutes, limit, |id| {
+        beads::get_bead_dependencies(id).map(|d| d.dependents.len()).unwrap_or(0)
+    });
+
+    match format {
+        "table" => Ok(render_table(&output)),
+        _ => Ok(serde_json::to_string_pretty(&output)?),
+    }
+}
+
+/// Order the ready beads and attach estimates; `blocks_count` is only asked
+/// about the entries that make the cut
+fn build_queue(
+    mut ready: Vec<BeadInfo>,
+    total_blocked: usize,
+    active_claims: usize,
+    avg_minutes: Option<f64>,
+    limit: usize,
+    blocks_count: impl Fn(&str) -> usize,
+) -> QueueOutput {
+    ready.sort_by(|a, b| a.priority.cmp(&b.priority).then_with(|| a.id.cmp(&b.id)));
+
+    let agents = active_claims.max(1);
+    // Beads ahead in line (including claimed ones) are worked off `agents` at a time
+    let rounds_before = |position: usize| (active_claims + position - 1) / agents;
+    let minutes = |rounds: usize| avg_minutes.map(|avg| (rounds as f64 * avg).round() as i64);
+
+    let total_ready = ready.len();
+    let queue = ready
+        .into_iter()
+        .take(limit)
+        .enumerate()
+        .map(|(i, bead)| QueueEntry {
+            position_in_queue: i + 1,
+            blocks_count: blocks_count(&bead.id),
+            estimated_start_minutes: minutes(rounds_before(i + 1)),
+            bead_id: bead.id,
+            title: bead.title,
+            priority: bead.priority,
+        })
+        .collect();
+
+    QueueOutput {
+        queue,
+        summary: QueueSummary {
+            total_ready,
+            total_blocked,
+            total_in_progress: active_claims,
+            avg_claim_duration_minutes: avg_minutes,
+            estimated_completion_minutes: minutes((active_claims + total_ready).div_ceil(agents)),
+        },
+    }
+}
+
+fn render_table(output: &QueueOutput) -> String {
+    let headers = ["#", "BEAD", "PRIORITY", "BLOCKS", "START", "TITLE"];
+    let rows: Vec<[String; 6]> = output
+        .queue
+        .iter()
+        .map(|e| {
+            [
+                e.position_in_queue.to_string(),
+                e.bead_id.clone(),
+                e.priority.to_string(),
+                e.blocks_count.to_string(),
+                e.estimated_start_minutes.map(|m| format!("~{}m", m)).unwrap_or_else(|| "-".to_string()),
+                e.title.clone(),
+            ]
+        })
+        .collect();
+
+    let widths: Vec<usize> = (0..headers.len())
+        .map(|i| rows.iter().map(|r| r[i].chars().count()).chain([headers[i].len()]).max().unwrap_or(0))
+        .collect();
+    let line = |cells: &[&str]| {
+        cells
+            .iter()
+            .zip(&widths)
+            .map(|(c, w)| format!("{:<width$}", c, width = w))
+            .collect::<Vec<_>>()
+            .join("  ")
+            .trim_end()
+            .to_string()
+    };
+
+    let mut out = vec![line(&headers)];
+    for row in &rows {
+        out.push(line(&row.iter().map(String::as_str).collect::<Vec<_>>()));
+    }
+
+    let s = &output.summary;
+    out.push(String::new());
+    out.push(format!(
+        "{} ready, {} blocked, {} in progress; all ready done in {}",
+        s.total_ready,
+        s.total_blocked,
+        s.total_in_progress,
+        s.estimated_completion_minutes.map(|m| format!("~{}m", m)).unwrap_or_else(|| "unknown".to_string())
+    ));
+    out.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bead(id: &str, priority: i32) -> BeadInfo {
+        BeadInfo {
+            id: id.to_string(),
+            title: format!("Bead {}", id),
+            description: None,
+            priority,
+            status: "open".to_string(),
+            labels: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_build_queue() {
+        let ready = vec![bead("b", 2), bead("c", 1), bead("a", 2)];
+        let output = build_queue(ready, 4, 2, Some(30.0), 10, |id| if id == "c" { 3 } else { 0 });
+
+        let order: Vec<&str> = output.queue.iter().map(|e| e.bead_id.as_str()).collect();
+        assert_eq!(order, ["c", "a", "b"]);
+        assert_eq!(output.queue[0].blocks_count, 3);
+        // Two agents busy: the first two wait one round, the third two
+        let starts: Vec<Option<i64>> = output.queue.iter().map(|e| e.estimated_start_minutes).collect();
+        assert_eq!(starts, [Some(30), Some(30), Some(60)]);
+        assert_eq!(output.summary.total_ready, 3);
+        assert_eq!(output.summary.total_blocked, 4);
+        assert_eq!(output.summary.estimated_completion_minutes, Some(90));
+
+        // Idle workspace, no history, and a limit
+        let output = build_queue(vec![bead("a", 1), bead("b", 1)], 0, 0, None, 1, |_| 0);
+        assert_eq!(output.queue.len(), 1);
+        assert_eq!(output.queue[0].estimated_start_minutes, None);
+        assert_eq!(output.summary.total_ready, 2);
+        assert_eq!(output.summary.estimated_completion_minutes, None);
+
+        let table = render_table(&build_queue(vec![bead("a", 1)], 0, 0, Some(10.0), 5, |_| 1));
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines[0], "#  BEAD  PRIORITY  BLOCKS  START  TITLE");
+        assert_eq!(lines[1], "1  a     1         1       ~0m    Bead a");
+        assert_eq!(lines[3], "1 ready, 0 blocked, 0 in progress; all ready done in ~10m");
+    }
+}
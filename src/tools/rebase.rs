@@ -0,0 +1,75 @@
+//! Rebase tool - bring a bead's worktree branch up to date with the target branch
+
+use crate::config::DEFAULT_TARGET_BRANCH;
+use crate::db::with_db;
+use crate::worktree::{self, RebaseOutcome};
+use rusqlite::OptionalExtension;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RebaseOutput {
+    pub success: bool,
+    pub bead_id: String,
+    pub commits_rebased: usize,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub conflicts: Vec<String>,
+    pub message: String,
+}
+
+/// Rebase onto `target_branch`, or the claim's target branch if not given
+pub fn rebase_bead(
+    bead_id: &str,
+    target_branch: Option<&str>,
+    workspace_root: &Path,
+) -> Result<RebaseOutput, Box<dyn std::error::Error>> {
+    let claim_target: Option<Option<String>> = with_db(|conn| {
+        conn.query_row(
+            "SELECT target_branch FROM claims WHERE bead_id = ?1",
+            [bead_id],
+            |row| row.get(0),
+        )
+        .optional()
+    })?;
+
+    let Some(claim_target) = claim_target else {
+        return Ok(RebaseOutput {
+            success: false,
+            bead_id: bead_id.to_string(),
+            commits_rebased: 0,
+            conflicts: Vec::new(),
+            message: format!("No claim found for {}", bead_id),
+        });
+    };
+
+    let target_branch = target_branch
+        .map(String::from)
+        .or(claim_target)
+        .unwrap_or_else(|| DEFAULT_TARGET_BRANCH.to_string());
+    let target_branch = target_branch.as_str();
+
+    let output = match worktree::rebase_worktree(workspace_root, bead_id, target_branch)? {
+        RebaseOutcome::Rebased(commits) => RebaseOutput {
+            success: true,
+            bead_id: bead_id.to_string(),
+            commits_rebased: commits,
+            conflicts: Vec::new(),
+            message: format!("Rebased {} commit(s) of {} onto {}", commits, bead_id, target_branch),
+        },
+        RebaseOutcome::Conflicts(files) => RebaseOutput {
+            success: false,
+            bead_id: bead_id.to_string(),
+            commits_rebased: 0,
+            message: format!(
+                "Rebase onto {} stopped on conflicts in: {}. Resolve them in .bacchus/worktrees/{}, `git add` the files and run `git rebase --continue`, or run `bacchus abort {}` to roll back.",
+                target_branch,
+                files.join(", "),
+                bead_id,
+                bead_id
+            ),
+            conflicts: files,
+        },
+    };
+
+    Ok(output)
+}
@@ -0,0 +1,188 @@
+//! Recover tool - repairs a bead whose worktree, claim, and bead status disagree
+//!
+//! Checks run in order, each reporting ok, fixed, would_fix (dry run), or failed:
+//! 1. Worktree exists and git works inside it
+//! 2. Claim exists (re-created from the worktree if missing)
+//! 3. Bead status is in_progress
+//! 4. Claim's start_commit is a valid commit (re-detected from history if not)
+
+use crate::beads;
+use crate::config::resolve_target_branch;
+use crate::db::with_db;
+use crate::worktree;
+use rusqlite::OptionalExtension;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Agent ID recorded on claims recreated by recovery
+const RECOVERED_AGENT_ID: &str = "recovered";
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RecoveryStep {
+    pub check: String,
+    /// ok, fixed, would_fix, or failed
+    pub status: String,
+    pub detail: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RecoverOutput {
+    pub success: bool,
+    pub bead_id: String,
+    pub dry_run: bool,
+    pub steps: Vec<RecoveryStep>,
+    pub message: String,
+}
+
+fn step(check: &str, status: &str, detail: impl Into<String>) -> RecoveryStep {
+    RecoveryStep {
+        check: check.to_string(),
+        status: status.to_string(),
+        detail: detail.into(),
+    }
+}
+
+pub fn recover(
+    bead_id: &str,
+    dry_run: bool,
+    workspace_root: &Path,
+) -> Result<RecoverOutput, Box<dyn std::error::Error>> {
+    let fix_status = if dry_run { "would_fix" } else { "fixed" };
+    let mut steps = Vec::new();
+
+    // 1. Worktree exists and is healthy; nothing else can be rebuilt without it
+    let wt_path = worktree::get_worktrees_dir(workspace_root).join(bead_id);
+    if !wt_path.exists() {
+        steps.push(step(
+            "worktree",
+            "failed",
+            format!("No worktree at {} (recreate it with: bacchus create-worktree {})", wt_path.display(), bead_id),
+        ));
+        return Ok(finish(bead_id, dry_run, steps));
+    }
+    let Some(head) = worktree::git_output(&wt_path, &["rev-parse", "--verify", "HEAD"]).ok() else {
+        steps.push(step("worktree", "failed", format!("git is not healthy in {}", wt_path.display())));
+        return Ok(finish(bead_id, dry_run, steps));
+    };
+    steps.push(step("worktree", "ok", format!("{} at {}", wt_path.display(), worktree::short_commit(&head))));
+
+    // 2. Claim exists, else recreate it from the worktree's branch
+    let claim = with_db(|conn| {
+        conn.query_row(
+            "SELECT start_commit FROM claims WHERE bead_id = ?1",
+            [bead_id],
+            |row| row.get::<_, String>(0),
+        )
+        .optional()
+    })?;
+
+    let detected_start = detect_start_commit(&wt_path, workspace_root).unwrap_or_else(|| head.clone());
+
+    let start_commit = match claim {
+        Some(start_commit) => {
+            steps.push(step("claim", "ok", "Claim exists"));
+            Some(start_commit)
+        }
+        None => {
+            let branch = worktree::git_output(&wt_path, &["rev-parse", "--abbrev-ref", "HEAD"])
+                .ok()
+                .unwrap_or_else(|| worktree::branch_name(bead_id));
+            if !dry_run {
+                let now = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map(|d| d.as_millis() as i64)
+                    .unwrap_or(0);
+                with_db(|conn| {
+                    conn.execute(
+                        "INSERT INTO claims (bead_id, agent_id, worktree_path, branch_name, start_commit, claimed_at, target_branch) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                        rusqlite::params![
+                            bead_id,
+                            RECOVERED_AGENT_ID,
+                            wt_path.to_string_lossy().to_string(),
+                            &branch,
+                            &detected_start,
+                            now,
+                            resolve_target_branch(None, workspace_root)
+                        ],
+                    )
+                })?;
+            }
+            steps.push(step(
+                "claim",
+                fix_status,
+                format!("Recreate claim on {} for agent '{}'", branch, RECOVERED_AGENT_ID),
+            ));
+            // The recreated claim already uses the detected start commit
+            None
+        }
+    };
+
+    // 3. Bead is marked in_progress
+    match beads::get_bead(bead_id) {
+        Ok(bead) if bead.status == "in_progress" => steps.push(step("bead_status", "ok", "Bead is in_progress")),
+        Ok(bead) => {
+            let result = if dry_run { Ok(()) } else { beads::update_bead_status(bead_id, "in_progress") };
+            match result {
+                Ok(()) => steps.push(step(
+                    "bead_status",
+                    fix_status,
+                    format!("Set status from {} to in_progress", bead.status),
+                )),
+                Err(e) => steps.push(step("bead_status", "failed", format!("Failed to update bead: {}", e))),
+            }
+        }
+        Err(e) => steps.push(step("bead_status", "failed", format!("Failed to get bead: {}", e))),
+    }
+
+    // 4. start_commit points at a real commit
+    match start_commit {
+        None => steps.push(step("start_commit", "ok", format!("Using {}", worktree::short_commit(&detected_start)))),
+        Some(commit) if worktree::git_output(workspace_root, &["cat-file", "-e", &format!("{}^{{commit}}", commit)]).is_ok() => {
+            steps.push(step("start_commit", "ok", format!("{} is valid", worktree::short_commit(&commit))))
+        }
+        Some(commit) => {
+            if !dry_run {
+                with_db(|conn| {
+                    conn.execute(
+                        "UPDATE claims SET start_commit = ?1 WHERE bead_id = ?2",
+                        [&detected_start, bead_id],
+                    )
+                })?;
+            }
+            steps.push(step(
+                "start_commit",
+                fix_status,
+                format!("Replace invalid '{}' with {}", commit, worktree::short_commit(&detected_start)),
+            ));
+        }
+    }
+
+    Ok(finish(bead_id, dry_run, steps))
+}
+
+fn finish(bead_id: &str, dry_run: bool, steps: Vec<RecoveryStep>) -> RecoverOutput {
+    let failed = steps.iter().filter(|s| s.status == "failed").count();
+    let fixes = steps.iter().filter(|s| s.status == "fixed" || s.status == "would_fix").count();
+
+    let message = match (failed, fixes, dry_run) {
+        (0, 0, _) => format!("{} is healthy; nothing to recover", bead_id),
+        (0, n, true) => format!("Would apply {} fix(es) to {}", n, bead_id),
+        (0, n, false) => format!("Recovered {} ({} fix(es))", bead_id, n),
+        (f, _, _) => format!("{} check(s) failed for {}; see steps", f, bead_id),
+    };
+
+    RecoverOutput {
+        success: failed == 0,
+        bead_id: bead_id.to_string(),
+        dry_run,
+        steps,
+        message,
+    }
+}
+
+/// Where the worktree branch left the workspace's current branch
+fn detect_start_commit(wt_path: &Path, workspace_root: &Path) -> Option<String> {
+    let base_head = worktree::git_output(workspace_root, &["rev-parse", "HEAD"]).ok()?;
+    worktree::git_output(wt_path, &["merge-base", &base_head, "HEAD"]).ok()
+}
@@ -0,0 +1,182 @@
+//! Find related tool - open beads that touch the same files as a given bead
+//!
+//! A bead's files are those of its registered symbols, its file hints, and any
+//! paths its description (the plan bd stores) mentions. Beads are ranked by
+//! the Jaccard similarity of those sets. When the source bead has no
+//! registered symbols, its files are too sparse to match exactly, so both sides
+//! are compared by directory instead.
+
+use crate::beads::{self, BeadInfo};
+use crate::db::with_db;
+use crate::indexer::Language;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::path::Path;
+
+/// How many related beads to return
+const MAX_RELATED: usize = 5;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RelatedBeadsOutput {
+    pub source_bead: String,
+    /// `files` (exact paths) or `prefixes` (directories; no registered symbols)
+    pub method: String,
+    /// Best match first, as [bead, similarity]
+    pub related: Vec<(BeadInfo, f32)>,
+}
+
+/// The open beads most similar to `bead_id` by the files they reference
+pub fn find_related_beads(bead_id: &str) -> Result<RelatedBeadsOutput, Box<dyn std::error::Error>> {
+    let all = beads::list_all_beads()?;
+    let source = all.iter().find(|b| b.id == bead_id);
+    let mut source_files = symbol_files(bead_id)?;
+    let by_prefix = source_files.is_empty();
+
+    source_files.extend(hint_files(bead_id)?);
+    if let Some(description) = source.and_then(|b| b.description.as_deref()) {
+        source_files.extend(referenced_paths(description));
+    }
+
+    let mut candidates = Vec::new();
+    for bead in all.into_iter().filter(|b| b.status == "open" && b.id != bead_id) {
+        let mut files = symbol_files(&bead.id)?;
+        files.extend(hint_files(&bead.id)?);
+        files.extend(bead.description.as_deref().map(referenced_paths).unwrap_or_default());
+        candidates.push((bead, files));
+    }
+
+    Ok(RelatedBeadsOutput {
+        source_bead: bead_id.to_string(),
+        method: if by_prefix { "prefixes" } else { "files" }.to_string(),
+        related: rank_related(&source_files, candidates, by_prefix),
+    })
+}
+
+/// Files of the symbols registered to a bead (the path part of each fq_name)
+fn symbol_files(bead_id: &str) -> rusqlite::Result<BTreeSet<String>> {
+    with_db(|conn| {
+        let mut stmt = conn.prepare("SELECT symbol_fq_name FROM bead_symbols WHERE bead_id = ?1")?;
+        let rows = stmt.query_map([bead_id], |row| row.get::<_, String>(0))?;
+        rows.map(|r| r.map(|fq_name| fq_name.split_once("::").map_or(fq_name.clone(), |(file, _)| file.to_string())))
+            .collect()
+    })
+}
+
+fn hint_files(bead_id: &str) -> rusqlite::Result<BTreeSet<String>> {
+    with_db(|conn| {
+        let mut stmt = conn.prepare("SELECT file_pattern FROM bead_file_hints WHERE bead_id = ?1")?;
+        let rows = stmt.query_map([bead_id], |row| row.get::<_, String>(0))?;
+        rows.collect()
+    })
+}
+
+/// File paths mentioned in free text: tokens with a `/`, or a known source extension
+fn referenced_paths(text: &str) -> BTreeSet<String> {
+    text.split(|c: char| c.is_whitespace() || matches!(c, '`' | '\'' | '"' | ',' | '(' | ')' | '[' | ']'))
+        .map(|t| t.trim_end_matches(['.', ':', ';']))
+        .filter(|t| !t.contains("://"))
+        .filter(|t| {
+            let known_ext = Path::new(t)
+                .extension()
+                .and_then(|e| e.to_str())
+                .and_then(Language::from_extension)
+                .is_some();
+            known_ext || (t.contains('/') && t.chars().any(|c| c.is_alphanumeric()))
+        })
+        .map(String::from)
+        .collect()
+}
+
+/// Directory part of a path or glob, e.g. `src/auth/*.rs` -> `src/auth`
+fn prefix(path: &str) -> String {
+    let literal = path.split(['*', '?', '[']).next().unwrap_or_default();
+    match literal.rsplit_once('/') {
+        Some((dir, _)) => dir.to_string(),
+        None => ".".to_string(),
+    }
+}
+
+fn jaccard(a: &BTreeSet<String>, b: &BTreeSet<String>) -> f32 {
+    let union = a.union(b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(b).count() as f32 / union as f32
+}
+
+/// Top matches with a nonzero similarity, best first (ties by bead ID)
+fn rank_related(
+    source: &BTreeSet<String>,
+    candidates: Vec<(BeadInfo, BTreeSet<String>)>,
+    by_prefix: bool,
+) -> Vec<(BeadInfo, f32)> {
+    let key = |files: &BTreeSet<String>| -> BTreeSet<String> {
+        if by_prefix {
+            files.iter().map(|f| prefix(f)).collect()
+        } else {
+            files.clone()
+        }
+    };
+    let source = key(source);
+
+    let mut scored: Vec<(BeadInfo, f32)> = candidates
+        .into_iter()
+        .map(|(bead, files)| {
+            let score = jaccard(&source, &key(&files));
+            (bead, score)
+        })
+        .filter(|(_, score)| *score > 0.0)
+        .collect();
+    scored.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.id.cmp(&b.0.id)));
+    scored.truncate(MAX_RELATED);
+    scored
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bead(id: &str) -> BeadInfo {
+        BeadInfo {
+            id: id.to_string(),
+            title: id.to_string(),
+            description: None,
+            priority: 2,
+            status: "open".to_string(),
+            labels: Vec::new(),
+        }
+    }
+
+    fn set(files: &[&str]) -> BTreeSet<String> {
+        files.iter().map(|f| f.to_string()).collect()
+    }
+
+    #[test]
+    fn test_referenced_paths() {
+        let paths = referenced_paths("Refactor `src/auth/login.rs` and main.py (see https://x.io/a/b). Touch web/components/.");
+        assert_eq!(paths, set(&["main.py", "src/auth/login.rs", "web/components/"]));
+    }
+
+    #[test]
+    fn test_rank_related() {
+        let source = set(&["src/auth/login.rs", "src/auth/session.rs"]);
+        let candidates = vec![
+            (bead("bd-a"), set(&["src/auth/login.rs"])),
+            (bead("bd-b"), set(&["src/auth/login.rs", "src/auth/session.rs", "src/db.rs"])),
+            (bead("bd-c"), set(&["docs/readme.md"])),
+            (bead("bd-d"), set(&["src/auth/token.rs"])),
+        ];
+
+        let ranked = rank_related(&source, candidates.clone(), false);
+        let ids: Vec<&str> = ranked.iter().map(|(b, _)| b.id.as_str()).collect();
+        assert_eq!(ids, ["bd-b", "bd-a"]);
+        assert!((ranked[0].1 - 2.0 / 3.0).abs() < 1e-6);
+
+        // By directory, bd-d's token.rs counts too
+        let ranked = rank_related(&source, candidates, true);
+        let ids: Vec<&str> = ranked.iter().map(|(b, _)| b.id.as_str()).collect();
+        assert_eq!(ids, ["bd-a", "bd-d", "bd-b"]);
+        assert_eq!(prefix("src/auth/*.rs"), "src/auth");
+        assert_eq!(prefix("main.py"), ".");
+    }
+}
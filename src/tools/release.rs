@@ -2,11 +2,22 @@
 //!
 //! Handles completing, blocking, or failing a claimed bead.
 
+use super::audit::{claim_agent, log_operation};
+use super::claim_events::log_claim_event;
+use super::test::test_bead;
 use crate::beads;
+use crate::config::{load_config, DEFAULT_TARGET_BRANCH};
 use crate::db::with_db;
 use crate::worktree;
+use rusqlite::OptionalExtension;
 use serde::{Deserialize, Serialize};
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+pub const DEFAULT_MERGE_COMMIT_TEMPLATE: &str = "feat: {title} (bacchus/{bead_id})";
+
+/// Longest merge commit subject line that doesn't draw a warning
+const MAX_SUBJECT_LEN: usize = 72;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ReleaseOutput {
@@ -14,65 +25,226 @@ pub struct ReleaseOutput {
     pub bead_id: String,
     pub status: String,
     pub merged: bool,
+    /// Exit code of the post-merge script, if one ran
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub post_merge_exit_code: Option<i32>,
+    /// Annotated tag created on the merge commit (`--tag`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
+    /// Commit the bead was merged as (`revert` undoes it)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub merge_commit: Option<String>,
+    /// Target branch head before the merge, which bounds a fast-forward for `revert`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_commit: Option<String>,
     pub message: String,
 }
 
+impl ReleaseOutput {
+    /// A release that stopped short, leaving the claim in place
+    fn failed(bead_id: &str, status: &str, message: String) -> Self {
+        ReleaseOutput {
+            success: false,
+            bead_id: bead_id.to_string(),
+            status: status.to_string(),
+            merged: false,
+            post_merge_exit_code: None,
+            tag: None,
+            merge_commit: None,
+            base_commit: None,
+            message,
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn release_bead(
     bead_id: &str,
     status: &str,
+    commit_message: Option<&str>,
+    post_merge_script: Option<&str>,
+    squash: bool,
+    tag: bool,
+    run_tests: bool,
+    workspace_root: &Path,
+) -> Result<ReleaseOutput, Box<dyn std::error::Error>> {
+    let agent_id = claim_agent(bead_id);
+    let result = try_release_bead(bead_id, status, commit_message, post_merge_script, squash, tag, run_tests, workspace_root);
+    log_operation("release", bead_id, agent_id.as_deref(), &result, |r| {
+        (
+            r.success,
+            serde_json::json!({
+                "message": r.message,
+                "status": r.status,
+                "merged": r.merged,
+                "merge_commit": r.merge_commit,
+                "base_commit": r.base_commit,
+            }),
+        )
+    });
+    result
+}
+
+#[allow(clippy::too_many_arguments)]
+fn try_release_bead(
+    bead_id: &str,
+    status: &str,
+    commit_message: Option<&str>,
+    post_merge_script: Option<&str>,
+    squash: bool,
+    tag: bool,
+    run_tests: bool,
     workspace_root: &Path,
 ) -> Result<ReleaseOutput, Box<dyn std::error::Error>> {
     // 1. Check claim exists
-    let claim_exists = with_db(|conn| {
-        Ok(conn
-            .query_row(
-                "SELECT 1 FROM claims WHERE bead_id = ?1",
-                [bead_id],
-                |_| Ok(true),
-            )
-            .unwrap_or(false))
+    let claim = with_db(|conn| {
+        conn.query_row(
+            "SELECT agent_id, start_commit, target_branch, title FROM claims WHERE bead_id = ?1",
+            [bead_id],
+            |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, Option<String>>(2)?,
+                    row.get::<_, Option<String>>(3)?,
+                ))
+            },
+        )
+        .optional()
     })?;
 
-    if !claim_exists {
-        return Ok(ReleaseOutput {
-            success: false,
-            bead_id: bead_id.to_string(),
-            status: status.to_string(),
-            merged: false,
-            message: format!("No claim found for {}", bead_id),
-        });
-    }
+    let Some((agent_id, start_commit, target_branch, claim_title)) = claim else {
+        return Ok(ReleaseOutput::failed(bead_id, status, format!("No claim found for {}", bead_id)));
+    };
 
+    let target_branch = target_branch.unwrap_or_else(|| DEFAULT_TARGET_BRANCH.to_string());
     let mut merged = false;
+    let mut merge_commit_hash = None;
+    let mut base_commit = None;
+    let mut post_merge_exit_code = None;
+    let mut created_tag = None;
+    let mut diff_stat = DiffStat::default();
+    let mut skipped_message = None;
 
     match status {
         "done" => {
-            // Merge worktree branch to main, then cleanup
-            if let Err(e) = worktree::merge_worktree(workspace_root, bead_id, "main") {
-                // Check if this is a merge conflict
-                let is_conflict = worktree::is_in_merge_conflict(workspace_root).unwrap_or(false);
-
-                let message = if is_conflict {
-                    format!(
-                        "Merge conflict detected. Options:\n\
-                         1. Resolve conflicts manually, then: bacchus resolve {}\n\
-                         2. Abort merge, keep working: bacchus abort {}\n\
-                         3. Discard all work: bacchus release {} --status failed",
-                        bead_id, bead_id, bead_id
-                    )
+            // Merge worktree branch to the claim's target branch, then cleanup
+            let config = load_config(workspace_root);
+
+            // A failing check leaves the worktree and claim in place for inspection
+            let worktree_path = worktree::get_worktrees_dir(workspace_root).join(bead_id);
+            if let Err(message) = run_pre_merge_hooks(&config.hooks.pre_merge, &worktree_path) {
+                return Ok(ReleaseOutput::failed(bead_id, status, message));
+            }
+
+            if run_tests {
+                let tests = test_bead(bead_id, workspace_root)?;
+                if !tests.success {
+                    let message = format!("{}; run `bacchus test {}` for the output", tests.message, bead_id);
+                    return Ok(ReleaseOutput::failed(bead_id, status, message));
+                }
+            }
+
+            // A merge commit would break linear history, so only fast-forwards are allowed
+            if config.require_linear_history {
+                if let Err(e) = worktree::check_branch_protection(workspace_root, bead_id, &target_branch) {
+                    return Ok(ReleaseOutput::failed(bead_id, status, e.to_string()));
+                }
+            }
+
+            let template_option = if commit_message.is_some() {
+                Some("--commit-message")
+            } else {
+                config.merge_commit_template.as_ref().map(|_| "merge_commit_template")
+            };
+            let template = commit_message
+                .map(String::from)
+                .or(config.merge_commit_template)
+                .unwrap_or_else(|| DEFAULT_MERGE_COMMIT_TEMPLATE.to_string());
+            let message = merge_commit_message(
+                &template,
+                bead_id,
+                &agent_id,
+                claim_title.as_deref(),
+                &target_branch,
+                workspace_root,
+            );
+
+            if squash || config.merge_strategy == "squash" {
+                // Fall back to the ID so squashing still works without bd
+                let title = beads::get_bead(bead_id)
+                    .map(|b| b.title)
+                    .unwrap_or_else(|_| bead_id.to_string());
+                worktree::squash_worktree(workspace_root, bead_id, &target_branch, &title)?;
+            }
+
+            // Measure the branch before the merge and worktree removal erase the distinction
+            diff_stat = branch_diff_stat(workspace_root, &start_commit, &worktree::branch_name(bead_id));
+            base_commit = worktree::resolve_commit(workspace_root, &target_branch).ok();
+            let branch_tip = worktree::resolve_commit(workspace_root, &worktree::branch_name(bead_id)).ok();
+
+            // An explicit --commit-message needs a merge commit to carry it
+            let merge_message = (!config.require_linear_history).then_some(message.as_str());
+            let no_ff = !config.require_linear_history && (config.merge_strategy == "no-ff" || commit_message.is_some());
+            let merge_commit = match worktree::merge_worktree(workspace_root, bead_id, &target_branch, merge_message, no_ff) {
+                Ok(commit) => commit,
+                Err(e) => {
+                    // Check if this is a merge conflict
+                    let is_conflict = worktree::is_in_merge_conflict(workspace_root).unwrap_or(false);
+
+                    let message = if is_conflict {
+                        format!(
+                            "Merge conflict detected. Options:\n\
+                             1. Resolve conflicts manually, then: bacchus resolve {}\n\
+                             2. Abort merge, keep working: bacchus abort {}\n\
+                             3. Discard all work: bacchus release {} --status failed",
+                            bead_id, bead_id, bead_id
+                        )
+                    } else {
+                        format!("Failed to merge: {}", e)
+                    };
+
+                    return Ok(ReleaseOutput::failed(bead_id, status, message));
+                }
+            };
+            merged = true;
+            merge_commit_hash = Some(merge_commit.clone());
+
+            // git only uses the message when it creates a merge commit
+            let up_to_date = Some(&merge_commit) == base_commit.as_ref();
+            let fast_forwarded = Some(&merge_commit) == branch_tip.as_ref();
+            if let Some(option) = template_option.filter(|_| up_to_date || fast_forwarded) {
+                let reason = if config.require_linear_history {
+                    "require_linear_history only allows fast-forwards"
+                } else if up_to_date {
+                    "the target branch already had these commits"
                 } else {
-                    format!("Failed to merge: {}", e)
+                    "the merge fast-forwarded (merge_strategy = \"no-ff\" always creates a merge commit)"
                 };
+                skipped_message = Some(format!("{} not applied: {}", option, reason));
+            }
 
-                return Ok(ReleaseOutput {
-                    success: false,
-                    bead_id: bead_id.to_string(),
-                    status: status.to_string(),
-                    merged: false,
-                    message,
-                });
+            if tag {
+                // The merge already happened, so a tagging failure only warns
+                let tag_name = format!("bacchus/{}/{}", bead_id, chrono::Utc::now().timestamp());
+                let title = beads::get_bead(bead_id)
+                    .map(|b| b.title)
+                    .unwrap_or_else(|_| bead_id.to_string());
+                let tag_message = format!("Release bead {}: {}", bead_id, title);
+                match worktree::create_release_tag(workspace_root, &tag_name, &merge_commit, &tag_message) {
+                    Ok(()) => created_tag = Some(tag_name),
+                    Err(e) => eprintln!("Warning: could not create tag {}: {}", tag_name, e),
+                }
+            }
+
+            let script = post_merge_script
+                .map(String::from)
+                .or_else(|| std::env::var("BACCHUS_POST_MERGE_SCRIPT").ok())
+                .filter(|s| !s.is_empty());
+            if let Some(script) = script {
+                post_merge_exit_code =
+                    run_post_merge_script(&script, bead_id, &agent_id, &merge_commit, workspace_root);
             }
-            merged = true;
 
             // Remove worktree (non-force since we merged)
             worktree::remove_worktree(workspace_root, bead_id, false)?;
@@ -91,24 +263,332 @@ pub fn release_bead(
             beads::update_bead_status(bead_id, "open")?;
         }
         _ => {
-            return Ok(ReleaseOutput {
-                success: false,
-                bead_id: bead_id.to_string(),
-                status: status.to_string(),
-                merged: false,
-                message: format!("Invalid status: {}. Use done, blocked, or failed", status),
-            });
+            let message = format!("Invalid status: {}. Use done, blocked, or failed", status);
+            return Ok(ReleaseOutput::failed(bead_id, status, message));
         }
     }
 
-    // Remove claim from DB
+    // Remove claim from DB (its file locks go with it)
     with_db(|conn| conn.execute("DELETE FROM claims WHERE bead_id = ?1", [bead_id]))?;
 
+    let details = serde_json::json!({
+        "status": status,
+        "merged": merged,
+        "merge_commit": &merge_commit_hash,
+        "files_changed": diff_stat.files_changed,
+        "insertions": diff_stat.insertions,
+        "deletions": diff_stat.deletions,
+    });
+    log_claim_event(bead_id, Some(&agent_id), "released", Some(&details));
+
     Ok(ReleaseOutput {
         success: true,
         bead_id: bead_id.to_string(),
         status: status.to_string(),
         merged,
-        message: format!("Released {} with status {}", bead_id, status),
+        post_merge_exit_code,
+        tag: created_tag,
+        merge_commit: merge_commit_hash,
+        base_commit,
+        message: match skipped_message {
+            Some(note) => format!("Released {} with status {}; {}", bead_id, status, note),
+            None => format!("Released {} with status {}", bead_id, status),
+        },
     })
 }
+
+/// Run each `hooks.pre_merge` command in the worktree, stopping at the first failure
+///
+/// The error is the release message: the failed command and its stderr (or
+/// stdout, if it wrote nothing to stderr).
+fn run_pre_merge_hooks(hooks: &[String], worktree_path: &Path) -> Result<(), String> {
+    for hook in hooks {
+        let output = Command::new("sh")
+            .arg("-c")
+            .arg(hook)
+            .current_dir(worktree_path)
+            .output()
+            .map_err(|e| format!("Pre-merge hook failed: {}\n{}", hook, e))?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let captured = if stderr.trim().is_empty() {
+                String::from_utf8_lossy(&output.stdout)
+            } else {
+                stderr
+            };
+            return Err(format!("Pre-merge hook failed: {}\n{}", hook, captured.trim_end()));
+        }
+    }
+    Ok(())
+}
+
+/// Run the post-merge hook, recording its exit code as a `post_merge_hook` event
+///
+/// Failures only warn: the merge has already happened, so the release goes on.
+/// The script's stdout goes to stderr to keep our JSON output clean.
+fn run_post_merge_script(
+    script: &str,
+    bead_id: &str,
+    agent_id: &str,
+    merge_commit: &str,
+    workspace_root: &Path,
+) -> Option<i32> {
+    let path = PathBuf::from(script);
+    let path = if path.is_absolute() { path } else { workspace_root.join(path) };
+
+    let exit_code = match Command::new(&path)
+        .current_dir(workspace_root)
+        .env("BACCHUS_BEAD_ID", bead_id)
+        .env("BACCHUS_BRANCH", worktree::branch_name(bead_id))
+        .env("BACCHUS_MERGE_COMMIT", merge_commit)
+        .env("BACCHUS_AGENT_ID", agent_id)
+        .stdout(Stdio::from(std::io::stderr()))
+        .status()
+    {
+        Ok(status) => {
+            if !status.success() {
+                eprintln!("Warning: post-merge script {} failed ({})", path.display(), status);
+            }
+            status.code()
+        }
+        Err(e) => {
+            eprintln!("Warning: could not run post-merge script {}: {}", path.display(), e);
+            None
+        }
+    };
+
+    let details = serde_json::json!({
+        "script": path.to_string_lossy(),
+        "merge_commit": merge_commit,
+        "exit_code": exit_code,
+    });
+    log_claim_event(bead_id, Some(agent_id), "post_merge_hook", Some(&details));
+
+    exit_code
+}
+
+/// Size of a branch's changes, from `git diff --shortstat`
+#[derive(Debug, Default, PartialEq)]
+struct DiffStat {
+    files_changed: u64,
+    insertions: u64,
+    deletions: u64,
+}
+
+fn branch_diff_stat(workspace_root: &Path, start_commit: &str, branch: &str) -> DiffStat {
+    Command::new("git")
+        .args(["diff", "--shortstat", start_commit, branch])
+        .current_dir(workspace_root)
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| parse_shortstat(&String::from_utf8_lossy(&o.stdout)))
+        .unwrap_or_default()
+}
+
+/// Parse " 3 files changed, 10 insertions(+), 2 deletions(-)" (parts may be missing)
+fn parse_shortstat(line: &str) -> DiffStat {
+    let mut stat = DiffStat::default();
+    for part in line.split(',') {
+        let mut words = part.split_whitespace();
+        let (Some(count), Some(label)) = (words.next(), words.next()) else {
+            continue;
+        };
+        let Ok(count) = count.parse::<u64>() else {
+            continue;
+        };
+        if label.starts_with("file") {
+            stat.files_changed = count;
+        } else if label.starts_with("insertion") {
+            stat.insertions = count;
+        } else if label.starts_with("deletion") {
+            stat.deletions = count;
+        }
+    }
+    stat
+}
+
+/// Fill a merge commit template with bead metadata
+///
+/// The title comes from the claim (stored at claim time), then bd, then the ID.
+fn merge_commit_message(
+    template: &str,
+    bead_id: &str,
+    agent_id: &str,
+    claim_title: Option<&str>,
+    target_branch: &str,
+    workspace_root: &Path,
+) -> String {
+    let branch = worktree::branch_name(bead_id);
+    // Fall back to the ID so the default template still reads well without bd
+    let title = claim_title.map(String::from).unwrap_or_else(|| {
+        beads::get_bead(bead_id)
+            .map(|b| b.title)
+            .unwrap_or_else(|_| bead_id.to_string())
+    });
+    let files_changed = count_changed_files(workspace_root, target_branch, &branch);
+    let date = chrono::Local::now().format("%Y-%m-%d").to_string();
+    let timestamp = chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+
+    let message = format_commit_message(
+        template,
+        &[
+            ("bead_id", bead_id),
+            ("title", &title),
+            ("agent_id", agent_id),
+            ("branch", &branch),
+            ("files_changed", &files_changed.to_string()),
+            ("date", &date),
+            ("timestamp", &timestamp),
+        ],
+    );
+
+    if let Some(warning) = subject_length_warning(&message) {
+        eprintln!("Warning: {}", warning);
+    }
+    message
+}
+
+/// Warn about merge commit subjects too long for `git log --oneline` and friends
+fn subject_length_warning(message: &str) -> Option<String> {
+    let subject_len = message.lines().next().unwrap_or("").chars().count();
+    (subject_len > MAX_SUBJECT_LEN).then(|| {
+        format!(
+            "merge commit subject is {} characters (recommended: at most {})",
+            subject_len, MAX_SUBJECT_LEN
+        )
+    })
+}
+
+/// Replace each `{key}` in the template; unknown placeholders are left as-is
+fn format_commit_message(template: &str, vars: &[(&str, &str)]) -> String {
+    vars.iter().fold(template.to_string(), |acc, (key, value)| {
+        acc.replace(&format!("{{{}}}", key), value)
+    })
+}
+
+/// Number of files the branch changes relative to its merge base with the target
+fn count_changed_files(workspace_root: &Path, target_branch: &str, branch: &str) -> usize {
+    Command::new("git")
+        .args(["diff", "--name-only", &format!("{}...{}", target_branch, branch)])
+        .current_dir(workspace_root)
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| {
+            String::from_utf8_lossy(&o.stdout)
+                .lines()
+                .filter(|l| !l.is_empty())
+                .count()
+        })
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{close_db, init_db, test_lock};
+
+    #[test]
+    fn test_format_commit_message() {
+        let vars = [
+            ("bead_id", "bd-12"),
+            ("title", "Fix login"),
+            ("files_changed", "3"),
+        ];
+        assert_eq!(
+            format_commit_message(DEFAULT_MERGE_COMMIT_TEMPLATE, &vars),
+            "feat: Fix login (bacchus/bd-12)"
+        );
+        assert_eq!(
+            format_commit_message("{bead_id} ({files_changed} files) {pr_url}", &vars),
+            "bd-12 (3 files) {pr_url}"
+        );
+    }
+
+    #[test]
+    fn test_subject_length_warning() {
+        assert!(subject_length_warning("feat: Fix login (bacchus/bd-12)").is_none());
+        assert!(subject_length_warning(&format!("{}\n\n{}", "a".repeat(72), "b".repeat(200))).is_none());
+        assert_eq!(
+            subject_length_warning(&"a".repeat(73)).as_deref(),
+            Some("merge commit subject is 73 characters (recommended: at most 72)")
+        );
+    }
+
+    #[test]
+    fn test_parse_shortstat() {
+        assert_eq!(
+            parse_shortstat(" 3 files changed, 10 insertions(+), 2 deletions(-)\n"),
+            DiffStat { files_changed: 3, insertions: 10, deletions: 2 }
+        );
+        assert_eq!(
+            parse_shortstat(" 1 file changed, 1 deletion(-)"),
+            DiffStat { files_changed: 1, insertions: 0, deletions: 1 }
+        );
+        assert_eq!(parse_shortstat(""), DiffStat::default());
+    }
+
+    #[test]
+    fn test_pre_merge_hooks() {
+        let dir = tempfile::tempdir().unwrap();
+        let hooks = |cmds: &[&str]| cmds.iter().map(|c| c.to_string()).collect::<Vec<_>>();
+
+        assert!(run_pre_merge_hooks(&[], dir.path()).is_ok());
+        assert!(run_pre_merge_hooks(&hooks(&["touch ran", "true"]), dir.path()).is_ok());
+        // Hooks run inside the worktree
+        assert!(dir.path().join("ran").exists());
+
+        let err = run_pre_merge_hooks(
+            &hooks(&["true", "echo 'lint failed' >&2; exit 2", "touch never"]),
+            dir.path(),
+        )
+        .unwrap_err();
+        assert_eq!(err, "Pre-merge hook failed: echo 'lint failed' >&2; exit 2\nlint failed");
+        assert!(!dir.path().join("never").exists());
+
+        let err = run_pre_merge_hooks(&hooks(&["echo 'tests failed'; false"]), dir.path()).unwrap_err();
+        assert!(err.ends_with("\ntests failed"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_post_merge_script() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let _lock = test_lock();
+        let dir = tempfile::tempdir().unwrap();
+        init_db(Some(dir.path().join("test.db").to_str().unwrap()), true).unwrap();
+
+        let script = dir.path().join("hook.sh");
+        std::fs::write(
+            &script,
+            "#!/bin/sh\necho \"$BACCHUS_BEAD_ID $BACCHUS_BRANCH $BACCHUS_AGENT_ID $BACCHUS_MERGE_COMMIT\" > hook.out\nexit 3\n",
+        )
+        .unwrap();
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        // Relative paths resolve against the workspace root
+        let code = run_post_merge_script("hook.sh", "BEAD-1", "agent-a", "abc123", dir.path());
+        assert_eq!(code, Some(3));
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("hook.out")).unwrap().trim(),
+            "BEAD-1 bacchus/BEAD-1 agent-a abc123"
+        );
+
+        let (event_type, details): (String, String) = with_db(|conn| {
+            conn.query_row(
+                "SELECT event_type, details FROM claim_events WHERE bead_id = 'BEAD-1'",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+        })
+        .unwrap();
+        assert_eq!(event_type, "post_merge_hook");
+        let details: serde_json::Value = serde_json::from_str(&details).unwrap();
+        assert_eq!(details["exit_code"], 3);
+
+        close_db();
+    }
+}
@@ -0,0 +1,73 @@
+//! Remove worktree tool - explicit worktree removal without the release flow
+//!
+//! Removes the worktree (and its branch unless `--keep-branch`), drops the claim,
+//! and puts the bead back to `open`.
+
+use crate::beads;
+use crate::db::with_db;
+use crate::worktree::{self, WorktreeError};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RemoveWorktreeOutput {
+    pub success: bool,
+    pub bead_id: String,
+    pub removed: bool,
+    pub branch_deleted: bool,
+    pub claim_removed: bool,
+    pub message: String,
+}
+
+pub fn remove_worktree(
+    bead_id: &str,
+    keep_branch: bool,
+    force: bool,
+    workspace_root: &Path,
+) -> Result<RemoveWorktreeOutput, Box<dyn std::error::Error>> {
+    let mut notes = Vec::new();
+
+    // A missing worktree is fine; we still clean up the branch and claim
+    let removed = match worktree::remove_worktree_dir(workspace_root, bead_id, force) {
+        Ok(()) => true,
+        Err(WorktreeError::NotFound(_)) => {
+            notes.push("no worktree found".to_string());
+            false
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    let branch_deleted = if keep_branch {
+        false
+    } else {
+        match worktree::delete_branch(workspace_root, &worktree::branch_name(bead_id), force) {
+            Ok(()) => true,
+            Err(e) => {
+                notes.push(e.to_string());
+                false
+            }
+        }
+    };
+
+    let claim_removed =
+        with_db(|conn| conn.execute("DELETE FROM claims WHERE bead_id = ?1", [bead_id]))? > 0;
+
+    // Beads may not know about this ID (e.g. a --no-claim worktree)
+    if let Err(e) = beads::update_bead_status(bead_id, "open") {
+        notes.push(format!("bead status not reset: {}", e));
+    }
+
+    let mut message = format!("Removed worktree for {}", bead_id);
+    if !notes.is_empty() {
+        message = format!("{} ({})", message, notes.join("; "));
+    }
+
+    Ok(RemoveWorktreeOutput {
+        success: removed || branch_deleted || claim_removed,
+        bead_id: bead_id.to_string(),
+        removed,
+        branch_deleted,
+        claim_removed,
+        message,
+    })
+}
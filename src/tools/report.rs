@@ -0,0 +1,313 @@
+//! Report tool - sprint/milestone summary built from claim events
+//!
+//! Completed beads are `released` events with status `done`; lines changed
+//! come from the diff stats recorded at release time. Symbol churn is the
+//! diff from the most recent index run.
+
+use super::symbols::find_changed_symbols;
+use crate::beads;
+use crate::db::with_db;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+const DAY_MS: i64 = 24 * 60 * 60 * 1000;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReportBead {
+    pub bead_id: String,
+    pub title: Option<String>,
+    pub agent_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AgentThroughput {
+    pub agent_id: String,
+    pub started: usize,
+    pub completed: usize,
+    pub beads_per_day: f64,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct SymbolChurn {
+    pub added: usize,
+    pub modified: usize,
+    pub deleted: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Report {
+    /// Start of the reporting window (Unix ms); None means all history
+    pub since: Option<i64>,
+    pub generated_at: String,
+    pub completed: Vec<ReportBead>,
+    pub not_completed: Vec<ReportBead>,
+    pub agents: Vec<AgentThroughput>,
+    pub files_changed: u64,
+    pub lines_added: u64,
+    pub lines_deleted: u64,
+    pub symbol_churn: SymbolChurn,
+}
+
+struct ClaimEvent {
+    bead_id: String,
+    agent_id: Option<String>,
+    event_type: String,
+    details: serde_json::Value,
+    created_at: i64,
+}
+
+/// Generate the report as `markdown` or `json`, optionally writing it to `output`
+pub fn generate_report(format: &str, since: Option<&str>, output: Option<&PathBuf>) -> Result<String, String> {
+    let now = chrono::Utc::now().timestamp_millis();
+    let since_ms = since.map(|s| parse_since(s, now)).transpose()?;
+
+    let report = build_report(since_ms, now)?;
+    let rendered = match format {
+        "json" => serde_json::to_string_pretty(&report).map_err(|e| e.to_string())?,
+        "markdown" => render_markdown(&report),
+        _ => return Err(format!("Unknown format: {}. Use markdown or json", format)),
+    };
+
+    let Some(path) = output else {
+        return Ok(rendered);
+    };
+
+    std::fs::write(path, rendered).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    let result = serde_json::json!({
+        "success": true,
+        "output": path.to_string_lossy(),
+        "message": format!("Wrote report to {}", path.display()),
+    });
+    serde_json::to_string_pretty(&result).map_err(|e| e.to_string())
+}
+
+pub fn build_report(since_ms: Option<i64>, now: i64) -> Result<Report, String> {
+    let events = with_db(|conn| {
+        let mut stmt = conn.prepare(
+            "SELECT bead_id, agent_id, event_type, details, created_at FROM claim_events
+             WHERE created_at >= ?1 ORDER BY created_at, id",
+        )?;
+        let rows = stmt.query_map([since_ms.unwrap_or(0)], |row| {
+            let details: Option<String> = row.get(3)?;
+            Ok(ClaimEvent {
+                bead_id: row.get(0)?,
+                agent_id: row.get(1)?,
+                event_type: row.get(2)?,
+                details: details
+                    .and_then(|d| serde_json::from_str(&d).ok())
+                    .unwrap_or(serde_json::Value::Null),
+                created_at: row.get(4)?,
+            })
+        })?;
+        rows.collect::<rusqlite::Result<Vec<_>>>()
+    })
+    .map_err(|e| e.to_string())?;
+
+    // Latest state per bead within the window: who claimed it, and whether it finished
+    let mut claimed: BTreeMap<String, Option<String>> = BTreeMap::new();
+    let mut done: BTreeMap<String, Option<String>> = BTreeMap::new();
+    let mut agents: BTreeMap<String, (usize, usize)> = BTreeMap::new();
+    let (mut files_changed, mut lines_added, mut lines_deleted) = (0, 0, 0);
+
+    for event in &events {
+        match event.event_type.as_str() {
+            "claimed" => {
+                claimed.insert(event.bead_id.clone(), event.agent_id.clone());
+                if let Some(agent) = &event.agent_id {
+                    agents.entry(agent.clone()).or_default().0 += 1;
+                }
+            }
+            "released" if event.details["status"] == "done" => {
+                done.insert(event.bead_id.clone(), event.agent_id.clone());
+                if let Some(agent) = &event.agent_id {
+                    agents.entry(agent.clone()).or_default().1 += 1;
+                }
+                files_changed += event.details["files_changed"].as_u64().unwrap_or(0);
+                lines_added += event.details["insertions"].as_u64().unwrap_or(0);
+                lines_deleted += event.details["deletions"].as_u64().unwrap_or(0);
+            }
+            _ => {}
+        }
+    }
+
+    let window_start = since_ms
+        .or_else(|| events.first().map(|e| e.created_at))
+        .unwrap_or(now);
+    let days = ((now - window_start) as f64 / DAY_MS as f64).max(1.0);
+
+    let report_bead = |bead_id: &str, agent_id: &Option<String>| ReportBead {
+        bead_id: bead_id.to_string(),
+        title: beads::get_bead(bead_id).ok().map(|b| b.title),
+        agent_id: agent_id.clone(),
+    };
+
+    let completed = done.iter().map(|(id, agent)| report_bead(id, agent)).collect();
+    let not_completed = claimed
+        .iter()
+        .filter(|(id, _)| !done.contains_key(*id))
+        .map(|(id, agent)| report_bead(id, agent))
+        .collect();
+
+    let agents = agents
+        .into_iter()
+        .map(|(agent_id, (started, completed))| AgentThroughput {
+            agent_id,
+            started,
+            completed,
+            beads_per_day: (completed as f64 / days * 100.0).round() / 100.0,
+        })
+        .collect();
+
+    let mut symbol_churn = SymbolChurn::default();
+    for change in find_changed_symbols().map_err(|e| e.to_string())?.changes {
+        match change.change_type.as_str() {
+            "added" => symbol_churn.added += 1,
+            "modified" => symbol_churn.modified += 1,
+            "deleted" => symbol_churn.deleted += 1,
+            _ => {}
+        }
+    }
+
+    Ok(Report {
+        since: since_ms,
+        generated_at: chrono::Utc::now().to_rfc3339(),
+        completed,
+        not_completed,
+        agents,
+        files_changed,
+        lines_added,
+        lines_deleted,
+        symbol_churn,
+    })
+}
+
+/// Accept a relative window (`7d`, `12h`, `30m`), a date (`2024-06-01`), or RFC 3339
+pub(crate) fn parse_since(since: &str, now: i64) -> Result<i64, String> {
+    let since = since.trim();
+    let unit_ms = match since.chars().last() {
+        Some('d') => Some(DAY_MS),
+        Some('h') => Some(60 * 60 * 1000),
+        Some('m') => Some(60 * 1000),
+        _ => None,
+    };
+    if let Some(unit_ms) = unit_ms {
+        if let Ok(n) = since[..since.len() - 1].parse::<i64>() {
+            return Ok(now - n * unit_ms);
+        }
+    }
+
+    if let Ok(date) = chrono::NaiveDate::parse_from_str(since, "%Y-%m-%d") {
+        return Ok(date.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc().timestamp_millis());
+    }
+
+    chrono::DateTime::parse_from_rfc3339(since)
+        .map(|dt| dt.timestamp_millis())
+        .map_err(|_| format!("Invalid --since '{}': use 7d, 12h, 30m, YYYY-MM-DD, or RFC 3339", since))
+}
+
+fn render_markdown(report: &Report) -> String {
+    let mut out = String::from("# Bacchus Report\n\n");
+
+    let since = report
+        .since
+        .and_then(chrono::DateTime::from_timestamp_millis)
+        .map(|dt| dt.format("%Y-%m-%d").to_string())
+        .unwrap_or_else(|| "the beginning".to_string());
+    out.push_str(&format!("_Since {} · generated {}_\n\n", since, &report.generated_at[..10]));
+
+    out.push_str("## Summary\n\n");
+    out.push_str(&format!("- **Completed**: {}\n", report.completed.len()));
+    out.push_str(&format!("- **Started, not completed**: {}\n", report.not_completed.len()));
+    out.push_str(&format!(
+        "- **Lines changed**: +{} / -{} across {} files\n",
+        report.lines_added, report.lines_deleted, report.files_changed
+    ));
+    out.push_str(&format!(
+        "- **Symbol churn (last index)**: {} added, {} modified, {} deleted\n",
+        report.symbol_churn.added, report.symbol_churn.modified, report.symbol_churn.deleted
+    ));
+
+    push_bead_table(&mut out, "Completed", &report.completed);
+    push_bead_table(&mut out, "Started, Not Completed", &report.not_completed);
+
+    out.push_str("\n## Agents\n\n");
+    if report.agents.is_empty() {
+        out.push_str("_No agent activity._\n");
+    } else {
+        out.push_str("| Agent | Started | Completed | Beads/day |\n|---|---|---|---|\n");
+        for agent in &report.agents {
+            out.push_str(&format!(
+                "| {} | {} | {} | {:.2} |\n",
+                agent.agent_id, agent.started, agent.completed, agent.beads_per_day
+            ));
+        }
+    }
+
+    out
+}
+
+fn push_bead_table(out: &mut String, heading: &str, beads: &[ReportBead]) {
+    out.push_str(&format!("\n## {}\n\n", heading));
+    if beads.is_empty() {
+        out.push_str("_None._\n");
+        return;
+    }
+    out.push_str("| Bead | Title | Agent |\n|---|---|---|\n");
+    for bead in beads {
+        out.push_str(&format!(
+            "| {} | {} | {} |\n",
+            bead.bead_id,
+            bead.title.as_deref().unwrap_or("-"),
+            bead.agent_id.as_deref().unwrap_or("-")
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{close_db, init_db, test_lock};
+    use crate::tools::claim_events::record_claim_event;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_build_report() {
+        let _lock = test_lock();
+        let dir = tempdir().unwrap();
+        init_db(Some(dir.path().join("test.db").to_str().unwrap()), true).unwrap();
+
+        let released = serde_json::json!({"status": "done", "merged": true, "files_changed": 2, "insertions": 30, "deletions": 5});
+        record_claim_event("report-a", Some("agent-1"), "claimed", None).unwrap();
+        record_claim_event("report-a", Some("agent-1"), "released", Some(&released)).unwrap();
+        record_claim_event("report-b", Some("agent-2"), "claimed", None).unwrap();
+
+        let now = chrono::Utc::now().timestamp_millis();
+        let report = build_report(Some(now - DAY_MS), now).unwrap();
+        assert_eq!(report.completed.len(), 1);
+        assert_eq!(report.completed[0].bead_id, "report-a");
+        assert_eq!(report.not_completed[0].bead_id, "report-b");
+        assert_eq!(report.lines_added, 30);
+        assert_eq!(report.agents.len(), 2);
+        assert_eq!(report.agents[0].completed, 1);
+        assert_eq!(report.agents[0].beads_per_day, 1.0);
+
+        let md = render_markdown(&report);
+        assert!(md.contains("- **Lines changed**: +30 / -5 across 2 files"));
+        assert!(md.contains("| agent-2 | 1 | 0 | 0.00 |"));
+
+        // Nothing in a window that starts in the future
+        assert!(build_report(Some(now + DAY_MS), now).unwrap().completed.is_empty());
+
+        close_db();
+    }
+
+    #[test]
+    fn test_parse_since() {
+        let now = 10 * DAY_MS;
+        assert_eq!(parse_since("7d", now).unwrap(), 3 * DAY_MS);
+        assert_eq!(parse_since("12h", now).unwrap(), now - DAY_MS / 2);
+        assert_eq!(parse_since("1970-01-02", now).unwrap(), DAY_MS);
+        assert!(parse_since("last week", now).is_err());
+    }
+}
@@ -0,0 +1,104 @@
+//! Reset tool - forcibly return a bead to open, whatever state it's in
+//!
+//! For when a claim or worktree is beyond repair. Every step is best effort:
+//! the claim and any assignment are dropped, the worktree (even a corrupt or
+//! unregistered one) and its branch are deleted, and the bead is reopened.
+
+use super::assign::clear_assignment;
+use super::audit::{claim_agent, log_operation};
+use super::file_locks::release_file_locks;
+use crate::beads;
+use crate::db::with_db;
+use crate::worktree::{self, WorktreeError};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ResetOutput {
+    pub success: bool,
+    pub bead_id: String,
+    pub claim_removed: bool,
+    pub worktree_removed: bool,
+    pub branch_deleted: bool,
+    pub bead_reopened: bool,
+    pub message: String,
+}
+
+pub fn reset_bead(
+    bead_id: &str,
+    confirm: bool,
+    workspace_root: &Path,
+) -> Result<ResetOutput, Box<dyn std::error::Error>> {
+    if !confirm {
+        return Ok(ResetOutput {
+            success: false,
+            bead_id: bead_id.to_string(),
+            claim_removed: false,
+            worktree_removed: false,
+            branch_deleted: false,
+            bead_reopened: false,
+            message: format!(
+                "Reset discards {}'s worktree and claim; re-run with --confirm to proceed",
+                bead_id
+            ),
+        });
+    }
+
+    let agent_id = claim_agent(bead_id);
+    let result = force_reset(bead_id, workspace_root);
+    log_operation("reset", bead_id, agent_id.as_deref(), &result, |r| {
+        (r.success, serde_json::json!({ "message": r.message }))
+    });
+    result
+}
+
+fn force_reset(bead_id: &str, workspace_root: &Path) -> Result<ResetOutput, Box<dyn std::error::Error>> {
+    let mut notes = Vec::new();
+
+    let claim_removed =
+        with_db(|conn| conn.execute("DELETE FROM claims WHERE bead_id = ?1", [bead_id]))? > 0;
+    with_db(|conn| conn.execute("DELETE FROM paused_claims WHERE bead_id = ?1", [bead_id]))?;
+    // The stash commits stay in git's stash list; only bacchus forgets them
+    with_db(|conn| conn.execute("DELETE FROM snapshots WHERE bead_id = ?1", [bead_id]))?;
+    clear_assignment(bead_id)?;
+    release_file_locks(bead_id)?;
+
+    let worktree_path = worktree::get_worktrees_dir(workspace_root).join(bead_id);
+    let worktree_removed = match worktree::remove_worktree_dir(workspace_root, bead_id, true) {
+        Ok(()) => true,
+        Err(WorktreeError::NotFound(_)) => false,
+        // Not a working git worktree any more; delete what's left on disk
+        Err(_) => {
+            std::fs::remove_dir_all(&worktree_path)?;
+            true
+        }
+    };
+    if let Err(e) = worktree::prune_worktrees(workspace_root, false) {
+        notes.push(e.to_string());
+    }
+
+    let branch_deleted = worktree::delete_branch(workspace_root, &worktree::branch_name(bead_id), true).is_ok();
+
+    let bead_reopened = match beads::update_bead_status(bead_id, "open") {
+        Ok(()) => true,
+        Err(e) => {
+            notes.push(format!("bead status not reset: {}", e));
+            false
+        }
+    };
+
+    let mut message = format!("Reset {}", bead_id);
+    if !notes.is_empty() {
+        message = format!("{} ({})", message, notes.join("; "));
+    }
+
+    Ok(ResetOutput {
+        success: true,
+        bead_id: bead_id.to_string(),
+        claim_removed,
+        worktree_removed,
+        branch_deleted,
+        bead_reopened,
+        message,
+    })
+}
@@ -2,9 +2,12 @@
 //!
 //! Finishes the merge, removes worktree, and updates bead status.
 
+use super::audit::{claim_agent, log_operation};
 use crate::beads;
+use crate::config::DEFAULT_TARGET_BRANCH;
 use crate::db::with_db;
 use crate::worktree;
+use rusqlite::OptionalExtension;
 use serde::{Deserialize, Serialize};
 use std::path::Path;
 
@@ -13,32 +16,48 @@ pub struct ResolveOutput {
     pub success: bool,
     pub bead_id: String,
     pub merged: bool,
+    /// Commit the completed merge created (`revert` undoes it)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub merge_commit: Option<String>,
     pub message: String,
 }
 
 pub fn resolve_merge(
     bead_id: &str,
     workspace_root: &Path,
+) -> Result<ResolveOutput, Box<dyn std::error::Error>> {
+    let agent_id = claim_agent(bead_id);
+    let result = try_resolve_merge(bead_id, workspace_root);
+    log_operation("resolve", bead_id, agent_id.as_deref(), &result, |r| {
+        (r.success, serde_json::json!({ "message": r.message, "merge_commit": r.merge_commit }))
+    });
+    result
+}
+
+fn try_resolve_merge(
+    bead_id: &str,
+    workspace_root: &Path,
 ) -> Result<ResolveOutput, Box<dyn std::error::Error>> {
     // 1. Check claim exists
-    let claim_exists = with_db(|conn| {
-        Ok(conn
-            .query_row(
-                "SELECT 1 FROM claims WHERE bead_id = ?1",
-                [bead_id],
-                |_| Ok(true),
-            )
-            .unwrap_or(false))
+    let claim_target: Option<Option<String>> = with_db(|conn| {
+        conn.query_row(
+            "SELECT target_branch FROM claims WHERE bead_id = ?1",
+            [bead_id],
+            |row| row.get(0),
+        )
+        .optional()
     })?;
 
-    if !claim_exists {
+    let Some(claim_target) = claim_target else {
         return Ok(ResolveOutput {
             success: false,
             bead_id: bead_id.to_string(),
             merged: false,
+            merge_commit: None,
             message: format!("No claim found for {}", bead_id),
         });
-    }
+    };
+    let target_branch = claim_target.unwrap_or_else(|| DEFAULT_TARGET_BRANCH.to_string());
 
     // 2. Check we're in a merge state
     if !worktree::is_in_merge_conflict(workspace_root)? {
@@ -46,20 +65,37 @@ pub fn resolve_merge(
             success: false,
             bead_id: bead_id.to_string(),
             merged: false,
+            merge_commit: None,
             message: "Not in a merge state. Use 'bacchus release --status done' instead.".to_string(),
         });
     }
 
-    // 3. Verify the merge is for this bead's branch
+    // 3. Verify the merge is of this bead's branch into its target branch
     let merge_branch = worktree::get_merge_branch(workspace_root)?;
     let expected = format!("bacchus/{}", bead_id);
 
+    if let Some(current) = worktree::current_branch(workspace_root)? {
+        if current != target_branch {
+            return Ok(ResolveOutput {
+                success: false,
+                bead_id: bead_id.to_string(),
+                merged: false,
+                merge_commit: None,
+                message: format!(
+                    "Current merge is into '{}', but {} targets '{}'. Resolve the correct bead.",
+                    current, bead_id, target_branch
+                ),
+            });
+        }
+    }
+
     if let Some(ref branch) = merge_branch {
         if branch != &expected {
             return Ok(ResolveOutput {
                 success: false,
                 bead_id: bead_id.to_string(),
                 merged: false,
+                merge_commit: None,
                 message: format!(
                     "Current merge is for '{}', not '{}'. Resolve the correct bead.",
                     branch, expected
@@ -74,12 +110,14 @@ pub fn resolve_merge(
             success: false,
             bead_id: bead_id.to_string(),
             merged: false,
+            merge_commit: None,
             message: "Unresolved conflicts remain. Fix all conflicts and stage changes with 'git add'.".to_string(),
         });
     }
 
     // 5. Complete the merge
     worktree::complete_merge(workspace_root)?;
+    let merge_commit = worktree::get_head_commit(workspace_root).ok();
 
     // 6. Remove worktree (non-force since we merged)
     worktree::remove_worktree(workspace_root, bead_id, false)?;
@@ -94,6 +132,7 @@ pub fn resolve_merge(
         success: true,
         bead_id: bead_id.to_string(),
         merged: true,
+        merge_commit,
         message: format!("Merge completed for {}. Worktree removed, bead closed.", bead_id),
     })
 }
@@ -0,0 +1,187 @@
+//! Revert tool - undo a merged bead on the current branch
+//!
+//! The merge commit comes from the bead's last successful `release` (or
+//! `resolve`) in the audit log. A merge commit is reverted against its first
+//! parent; a fast-forward is reverted as the range from the pre-merge head.
+
+use super::audit::log_operation;
+use super::claim_events::log_claim_event;
+use crate::beads;
+use crate::db::with_db;
+use crate::worktree;
+use rusqlite::OptionalExtension;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RevertOutput {
+    pub success: bool,
+    pub bead_id: String,
+    pub merge_commit: String,
+    /// What was passed to `git revert`: the merge commit or a `base..tip` range
+    pub reverted: Option<String>,
+    /// False with `--no-commit`: the inverse patch is left staged
+    pub committed: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub warning: Option<String>,
+    pub message: String,
+}
+
+pub fn revert_bead(
+    bead_id: &str,
+    no_commit: bool,
+    workspace_root: &Path,
+) -> Result<RevertOutput, Box<dyn std::error::Error>> {
+    let result = try_revert_bead(bead_id, no_commit, workspace_root);
+    log_operation("revert", bead_id, None, &result, |r| {
+        (
+            r.success,
+            serde_json::json!({ "message": r.message, "merge_commit": r.merge_commit, "committed": r.committed }),
+        )
+    });
+    result
+}
+
+fn try_revert_bead(
+    bead_id: &str,
+    no_commit: bool,
+    workspace_root: &Path,
+) -> Result<RevertOutput, Box<dyn std::error::Error>> {
+    let Some((merge_commit, base_commit)) = merge_record(bead_id)? else {
+        return Err(format!(
+            "No merge commit recorded for {} in the audit log; revert needs the release to have been \
+             audit-logged with its merge commit (use git revert directly for older merges)",
+            bead_id
+        )
+        .into());
+    };
+
+    if let Some(revert) = reverted_by(workspace_root, &merge_commit) {
+        let warning = format!("{} ({}) was already reverted by {}", bead_id, worktree::short_commit(&merge_commit), worktree::short_commit(&revert));
+        eprintln!("Warning: {}", warning);
+        return Ok(RevertOutput {
+            success: false,
+            bead_id: bead_id.to_string(),
+            merge_commit,
+            reverted: None,
+            committed: false,
+            message: format!("Nothing to do: {}", warning),
+            warning: Some(warning),
+        });
+    }
+
+    let mainline = parent_count(workspace_root, &merge_commit) > 1;
+    let revision = match base_commit {
+        Some(base) if !mainline && base != merge_commit => format!("{}..{}", base, merge_commit),
+        _ => merge_commit.clone(),
+    };
+    let message = format!("revert: {}\n\nThis reverts commit {}.", bead_id, merge_commit);
+    worktree::revert_commits(workspace_root, &revision, mainline, (!no_commit).then_some(message.as_str()))?;
+
+    // The revert already happened, so a bd failure only warns
+    if let Err(e) = beads::update_bead_status(bead_id, "open") {
+        eprintln!("Warning: failed to reopen {}: {}", bead_id, e);
+    }
+    let details = serde_json::json!({ "merge_commit": merge_commit, "committed": !no_commit });
+    log_claim_event(bead_id, None, "reverted", Some(&details));
+
+    Ok(RevertOutput {
+        success: true,
+        bead_id: bead_id.to_string(),
+        merge_commit,
+        reverted: Some(revision),
+        committed: !no_commit,
+        warning: None,
+        message: if no_commit {
+            format!("Staged the revert of {}; review and commit it", bead_id)
+        } else {
+            format!("Reverted {} and reopened the bead", bead_id)
+        },
+    })
+}
+
+/// (merge_commit, base_commit) of the bead's latest successful merge
+fn merge_record(bead_id: &str) -> rusqlite::Result<Option<(String, Option<String>)>> {
+    with_db(|conn| {
+        conn.query_row(
+            "SELECT json_extract(details, '$.merge_commit'), json_extract(details, '$.base_commit') FROM audit_log
+             WHERE bead_id = ?1 AND operation IN ('release', 'resolve') AND outcome = 'success'
+               AND json_extract(details, '$.merge_commit') IS NOT NULL
+             ORDER BY timestamp_ms DESC, id DESC
+             LIMIT 1",
+            [bead_id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .optional()
+    })
+}
+
+/// Commit on HEAD whose message says it reverts `commit`
+fn reverted_by(workspace_root: &Path, commit: &str) -> Option<String> {
+    worktree::git_output(
+        workspace_root,
+        &["log", "--format=%H", "-F", &format!("--grep=This reverts commit {}", commit), "HEAD"],
+    )
+    .ok()
+    .and_then(|out| out.lines().next().map(String::from))
+}
+
+fn parent_count(workspace_root: &Path, commit: &str) -> usize {
+    worktree::git_output(workspace_root, &["rev-list", "--parents", "-n", "1", commit])
+        .ok()
+        .map(|out| out.split_whitespace().count().saturating_sub(1))
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{close_db, init_db, test_lock};
+    use crate::worktree::test_git;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_revert_merged_bead() {
+        let _lock = test_lock();
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        let run = |args: &[&str]| test_git(root, args);
+        run(&["init"]);
+        run(&["config", "user.name", "Test"]);
+        run(&["config", "user.email", "test@test.com"]);
+        std::fs::write(root.join("a.txt"), "a").unwrap();
+        run(&["add", "."]);
+        run(&["commit", "-m", "init"]);
+        run(&["checkout", "-b", "bacchus/bd-1"]);
+        std::fs::write(root.join("b.txt"), "b").unwrap();
+        run(&["add", "."]);
+        run(&["commit", "-m", "feature"]);
+        run(&["checkout", "-"]);
+        run(&["merge", "--no-ff", "-m", "merge bd-1", "bacchus/bd-1"]);
+        let merge_commit = run(&["rev-parse", "HEAD"]);
+
+        init_db(Some(root.join("test.db").to_str().unwrap()), true).unwrap();
+        assert!(revert_bead("bd-1", false, root).unwrap_err().to_string().contains("audit log"));
+
+        let details = serde_json::json!({ "merged": true, "merge_commit": merge_commit });
+        with_db(|conn| {
+            conn.execute(
+                "INSERT INTO audit_log (timestamp_ms, command, bead_id, operation, outcome, details)
+                 VALUES (1000, 'release', 'bd-1', 'release', 'success', ?1)",
+                [details.to_string()],
+            )
+        })
+        .unwrap();
+
+        let reverted = revert_bead("bd-1", false, root).unwrap();
+        assert!(reverted.success && reverted.committed);
+        assert!(!root.join("b.txt").exists());
+        assert_eq!(run(&["log", "-1", "--format=%s"]), "revert: bd-1");
+
+        let again = revert_bead("bd-1", false, root).unwrap();
+        assert!(!again.success);
+        assert!(again.warning.unwrap().contains("already reverted"));
+
+        close_db();
+    }
+}
@@ -0,0 +1,115 @@
+//! Search beads tool - full-text search over bead titles and descriptions
+//!
+//! Queries bd's database directly through the `issues_fts` index (see
+//! `beads::ensure_issues_fts`), ranked by bm25. The query uses FTS5 syntax:
+//! plain terms, `"phrases"`, `prefix*`, `AND`/`OR`/`NOT`, `title:term`.
+
+use crate::beads::ensure_issues_fts;
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BeadSearchResult {
+    pub id: String,
+    pub title: String,
+    pub status: String,
+    pub priority: i32,
+    /// Matching excerpt with terms wrapped in `**`
+    pub snippet: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SearchBeadsOutput {
+    pub query: String,
+    pub results: Vec<BeadSearchResult>,
+    /// Matches before `--limit`
+    pub total: usize,
+}
+
+/// Best `limit` matches for `query`, optionally only beads with `status`
+pub fn search_beads(
+    beads_db: &Path,
+    query: &str,
+    limit: i32,
+    status: Option<&str>,
+) -> Result<SearchBeadsOutput, Box<dyn std::error::Error>> {
+    if !beads_db.exists() {
+        return Err(format!("Beads database not found at {} (run `bd init`)", beads_db.display()).into());
+    }
+    let conn = Connection::open(beads_db)?;
+    ensure_issues_fts(&conn)?;
+
+    // snippet() column -1 picks whichever of title/description matched best
+    let mut stmt = conn.prepare(
+        "SELECT i.id, i.title, i.status, i.priority,
+                snippet(issues_fts, -1, '**', '**', '…', 12)
+         FROM issues_fts JOIN issues i ON i.rowid = issues_fts.rowid
+         WHERE issues_fts MATCH ?1 AND (?2 IS NULL OR i.status = ?2)
+         ORDER BY bm25(issues_fts)",
+    )?;
+    let matches: Vec<BeadSearchResult> = stmt
+        .query_map(rusqlite::params![query, status], |row| {
+            Ok(BeadSearchResult {
+                id: row.get(0)?,
+                title: row.get(1)?,
+                status: row.get(2)?,
+                priority: row.get(3)?,
+                snippet: row.get(4)?,
+            })
+        })?
+        .collect::<rusqlite::Result<_>>()
+        .map_err(|e| format!("Search failed for '{}': {}", query, e))?;
+
+    let total = matches.len();
+    Ok(SearchBeadsOutput {
+        query: query.to_string(),
+        results: matches.into_iter().take(limit.max(0) as usize).collect(),
+        total,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_search_beads() {
+        let dir = tempdir().unwrap();
+        let db = dir.path().join("beads.db");
+        Connection::open(&db)
+            .unwrap()
+            .execute_batch(
+                "CREATE TABLE issues (
+                   id TEXT PRIMARY KEY, title TEXT NOT NULL, description TEXT NOT NULL DEFAULT '',
+                   status TEXT NOT NULL DEFAULT 'open', priority INTEGER NOT NULL DEFAULT 2
+                 );
+                 INSERT INTO issues (id, title, description, status, priority) VALUES
+                   ('bd-1', 'Fix login redirect', 'Users land on a blank page after login', 'open', 1),
+                   ('bd-2', 'Add audit log', 'Record every login attempt', 'closed', 2),
+                   ('bd-3', 'Speed up indexer', 'Parallel parsing', 'open', 3);",
+            )
+            .unwrap();
+
+        let found = search_beads(&db, "login", 10, None).unwrap();
+        assert_eq!(found.total, 2);
+        // Title hits rank first
+        assert_eq!(found.results[0].id, "bd-1");
+        assert!(found.results[0].snippet.contains("**login**"), "{}", found.results[0].snippet);
+
+        let open = search_beads(&db, "login", 10, Some("open")).unwrap();
+        assert_eq!(open.results.len(), 1);
+        assert_eq!(search_beads(&db, "login", 1, None).unwrap().results.len(), 1);
+
+        // Beads written after the index was created are still found
+        Connection::open(&db)
+            .unwrap()
+            .execute("INSERT INTO issues (id, title) VALUES ('bd-4', 'Login throttling')", [])
+            .unwrap();
+        assert_eq!(search_beads(&db, "login", 10, None).unwrap().total, 3);
+
+        assert!(search_beads(&db, "\"unterminated", 10, None).is_err());
+        assert!(search_beads(&dir.path().join("missing.db"), "x", 10, None).is_err());
+    }
+}
@@ -0,0 +1,140 @@
+//! Search code tool - grep across the worktrees of active claims
+
+use crate::db::with_db;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::process::Command;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CodeMatch {
+    pub bead_id: String,
+    pub file: String,
+    pub line: u64,
+    pub text: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SearchCodeOutput {
+    pub matches: Vec<CodeMatch>,
+    pub total: usize,
+    /// True when results were cut off at `max_matches`
+    pub truncated: bool,
+}
+
+/// Grep `pattern` (extended regex) in every active worktree, or only those of `bead_ids`
+pub fn search_code(
+    pattern: &str,
+    file_pattern: Option<&str>,
+    bead_ids: &[String],
+    max_matches: usize,
+) -> Result<SearchCodeOutput, String> {
+    let worktrees = with_db(|conn| {
+        let mut stmt = conn.prepare("SELECT bead_id, worktree_path FROM claims ORDER BY bead_id")?;
+        let rows = stmt.query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))?;
+        rows.collect::<rusqlite::Result<Vec<_>>>()
+    })
+    .map_err(|e| e.to_string())?;
+
+    let mut matches = Vec::new();
+    let mut truncated = false;
+    for (bead_id, worktree_path) in worktrees {
+        if !bead_ids.is_empty() && !bead_ids.contains(&bead_id) {
+            continue;
+        }
+        let path = Path::new(&worktree_path);
+        if !path.is_dir() {
+            eprintln!("Warning: worktree for {} not found at {}", bead_id, worktree_path);
+            continue;
+        }
+
+        let remaining = max_matches - matches.len();
+        let found = grep_worktree(&bead_id, path, pattern, file_pattern)?;
+        if found.len() > remaining {
+            truncated = true;
+        }
+        matches.extend(found.into_iter().take(remaining));
+        if truncated {
+            break;
+        }
+    }
+
+    Ok(SearchCodeOutput {
+        total: matches.len(),
+        matches,
+        truncated,
+    })
+}
+
+fn grep_worktree(
+    bead_id: &str,
+    dir: &Path,
+    pattern: &str,
+    file_pattern: Option<&str>,
+) -> Result<Vec<CodeMatch>, String> {
+    let mut cmd = Command::new("grep");
+    // -Z ends file names with NUL so paths containing ':' parse cleanly
+    cmd.args(["-r", "-n", "-E", "-I", "-Z", "--exclude-dir=.git"]);
+    if let Some(glob) = file_pattern {
+        cmd.arg(format!("--include={}", glob));
+    }
+    let output = cmd
+        .arg("-e")
+        .arg(pattern)
+        .arg(".")
+        .current_dir(dir)
+        .output()
+        .map_err(|e| format!("Failed to run grep: {}", e))?;
+
+    // grep exits 1 when nothing matched, 2 on errors such as a bad regex
+    if output.status.code() == Some(2) {
+        return Err(format!(
+            "grep failed in {}: {}",
+            dir.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|l| parse_grep_line(bead_id, l))
+        .collect())
+}
+
+/// Parse `./<file>\0<line>:<text>`
+fn parse_grep_line(bead_id: &str, line: &str) -> Option<CodeMatch> {
+    let (file, rest) = line.split_once('\0')?;
+    let (line_no, text) = rest.split_once(':')?;
+    Some(CodeMatch {
+        bead_id: bead_id.to_string(),
+        file: file.trim_start_matches("./").to_string(),
+        line: line_no.parse().ok()?,
+        text: text.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_grep_worktree() {
+        let dir = tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("src")).unwrap();
+        std::fs::write(dir.path().join("src/auth.rs"), "fn login() {}\nfn logout() {}\n").unwrap();
+        std::fs::write(dir.path().join("notes.md"), "login flow\n").unwrap();
+
+        let found = grep_worktree("bd-1", dir.path(), "log(in|out)\\(", None).unwrap();
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[0].file, "src/auth.rs");
+        assert_eq!(found[1].line, 2);
+        assert_eq!(found[1].text, "fn logout() {}");
+
+        let found = grep_worktree("bd-1", dir.path(), "login", Some("*.md")).unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].file, "notes.md");
+
+        assert!(grep_worktree("bd-1", dir.path(), "nothing here", None).unwrap().is_empty());
+        assert!(grep_worktree("bd-1", dir.path(), "(", None).is_err());
+    }
+}
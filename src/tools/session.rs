@@ -16,6 +16,9 @@ pub struct Session {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub max_concurrent: Option<i32>,
     pub started_at: String,
+    /// Last `context --agent-persona`, reused by later context calls
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub persona: Option<String>,
 }
 
 /// Output for hook check command
@@ -76,6 +79,7 @@ pub fn start_session(mode: &str, bead_id: Option<&str>, max_concurrent: i32) ->
                 bead_id: Some(bead_id.to_string()),
                 max_concurrent: None,
                 started_at: chrono::Utc::now().to_rfc3339(),
+                persona: None,
             }
         }
         "orchestrator" => Session {
@@ -83,6 +87,7 @@ pub fn start_session(mode: &str, bead_id: Option<&str>, max_concurrent: i32) ->
             bead_id: None,
             max_concurrent: Some(max_concurrent),
             started_at: chrono::Utc::now().to_rfc3339(),
+            persona: None,
         },
         _ => return Err(format!("Unknown mode: {}. Use 'agent' or 'orchestrator'", mode)),
     };
@@ -123,6 +128,31 @@ pub fn session_status() -> Result<serde_json::Value, String> {
     }))
 }
 
+fn read_session() -> Option<(std::path::PathBuf, Session)> {
+    let path = session_path()?;
+    let content = fs::read_to_string(&path).ok()?;
+    let session = serde_json::from_str(&content).ok()?;
+    Some((path, session))
+}
+
+/// Persona remembered in the active session, if any
+pub fn session_persona() -> Option<String> {
+    read_session().and_then(|(_, session)| session.persona)
+}
+
+/// Remember a persona in the active session
+///
+/// Returns false when there is no session to store it in.
+pub fn set_session_persona(persona: &str) -> Result<bool, String> {
+    let Some((path, mut session)) = read_session() else {
+        return Ok(false);
+    };
+    session.persona = Some(persona.to_string());
+    let json = serde_json::to_string_pretty(&session).map_err(|e| e.to_string())?;
+    fs::write(path, json).map_err(|e| e.to_string())?;
+    Ok(true)
+}
+
 /// Check if session should block exit (for stop hook)
 pub fn check_session() -> HookCheckOutput {
     // Read session file
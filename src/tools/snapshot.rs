@@ -0,0 +1,222 @@
+//! Snapshot tool - named restore points inside a bead's worktree
+//!
+//! A snapshot is a `git stash push --include-untracked` whose work is put
+//! straight back, so taking one doesn't disturb the worktree. The stash SHA is
+//! recorded in `snapshots`; restoring applies it (never pops), so the same
+//! snapshot can be restored again.
+
+use crate::db::with_db;
+use crate::worktree;
+use rusqlite::OptionalExtension;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SnapshotOutput {
+    pub success: bool,
+    pub bead_id: String,
+    pub name: String,
+    pub stash_sha: Option<String>,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SnapshotInfo {
+    pub name: String,
+    pub stash_sha: String,
+    pub created_at: Option<String>,
+    pub age_minutes: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SnapshotListOutput {
+    pub bead_id: String,
+    /// Oldest first
+    pub snapshots: Vec<SnapshotInfo>,
+    pub total: usize,
+}
+
+fn has_claim(bead_id: &str) -> rusqlite::Result<bool> {
+    with_db(|conn| {
+        conn.query_row("SELECT 1 FROM claims WHERE bead_id = ?1", [bead_id], |_| Ok(()))
+            .optional()
+            .map(|r| r.is_some())
+    })
+}
+
+fn snapshot_sha(bead_id: &str, name: &str) -> rusqlite::Result<Option<String>> {
+    with_db(|conn| {
+        conn.query_row(
+            "SELECT stash_sha FROM snapshots WHERE bead_id = ?1 AND name = ?2",
+            [bead_id, name],
+            |row| row.get(0),
+        )
+        .optional()
+    })
+}
+
+/// Record the worktree's uncommitted changes as snapshot `name` (default: a timestamp)
+pub fn snapshot_worktree(
+    bead_id: &str,
+    name: Option<&str>,
+    workspace_root: &Path,
+) -> Result<SnapshotOutput, Box<dyn std::error::Error>> {
+    let now = chrono::Utc::now();
+    let name = name
+        .map(String::from)
+        .unwrap_or_else(|| now.format("%Y%m%d-%H%M%S").to_string());
+    let output = |success, stash_sha, message| SnapshotOutput {
+        success,
+        bead_id: bead_id.to_string(),
+        name: name.clone(),
+        stash_sha,
+        message,
+    };
+
+    if !has_claim(bead_id)? {
+        return Ok(output(false, None, format!("No claim found for {}", bead_id)));
+    }
+    if let Some(sha) = snapshot_sha(bead_id, &name)? {
+        return Ok(output(
+            false,
+            Some(sha),
+            format!("Snapshot '{}' already exists for {}", name, bead_id),
+        ));
+    }
+
+    let message = format!("bacchus-snapshot-{}", name);
+    let Some(sha) = worktree::snapshot_worktree_changes(workspace_root, bead_id, &message)? else {
+        return Ok(output(false, None, format!("No local changes to snapshot in {}", bead_id)));
+    };
+
+    with_db(|conn| {
+        conn.execute(
+            "INSERT INTO snapshots (bead_id, name, stash_sha, created_at) VALUES (?1, ?2, ?3, ?4)",
+            rusqlite::params![bead_id, name, sha, now.timestamp_millis()],
+        )
+    })?;
+
+    let message = format!(
+        "Snapshot '{}' saved for {}. Run `bacchus restore {} {}` to return to it.",
+        name, bead_id, bead_id, name
+    );
+    Ok(output(true, Some(sha), message))
+}
+
+/// Apply snapshot `name` to the worktree, keeping the snapshot for later restores
+pub fn restore_worktree_snapshot(
+    bead_id: &str,
+    name: &str,
+    workspace_root: &Path,
+) -> Result<SnapshotOutput, Box<dyn std::error::Error>> {
+    let output = |success, stash_sha, message| SnapshotOutput {
+        success,
+        bead_id: bead_id.to_string(),
+        name: name.to_string(),
+        stash_sha,
+        message,
+    };
+
+    let Some(sha) = snapshot_sha(bead_id, name)? else {
+        return Ok(output(false, None, format!("No snapshot '{}' for {}", name, bead_id)));
+    };
+
+    // git refuses to apply over conflicting local changes, leaving the worktree untouched
+    if let Err(e) = worktree::apply_stash_commit(workspace_root, bead_id, &sha, false) {
+        return Ok(output(
+            false,
+            Some(sha),
+            format!("Failed to restore snapshot '{}' (commit or discard local changes first): {}", name, e),
+        ));
+    }
+
+    Ok(output(true, Some(sha), format!("Restored snapshot '{}' in {}", name, bead_id)))
+}
+
+/// Snapshots recorded for a bead, oldest first
+pub fn list_snapshots(bead_id: &str) -> rusqlite::Result<SnapshotListOutput> {
+    let now = chrono::Utc::now().timestamp_millis();
+    let snapshots: Vec<SnapshotInfo> = with_db(|conn| {
+        let mut stmt = conn.prepare(
+            "SELECT name, stash_sha, created_at FROM snapshots WHERE bead_id = ?1 ORDER BY created_at, id",
+        )?;
+        let rows = stmt.query_map([bead_id], |row| {
+            let created_at: i64 = row.get(2)?;
+            Ok(SnapshotInfo {
+                name: row.get(0)?,
+                stash_sha: row.get(1)?,
+                created_at: chrono::DateTime::from_timestamp_millis(created_at).map(|d| d.to_rfc3339()),
+                age_minutes: (now - created_at) / 60_000,
+            })
+        })?;
+        rows.collect()
+    })?;
+
+    Ok(SnapshotListOutput {
+        bead_id: bead_id.to_string(),
+        total: snapshots.len(),
+        snapshots,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{close_db, init_db, test_lock};
+    use crate::worktree::test_git;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_snapshot_and_restore() {
+        let _lock = test_lock();
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        test_git(root, &["init", "-q"]);
+        test_git(root, &["config", "user.email", "test@example.com"]);
+        test_git(root, &["config", "user.name", "Test"]);
+        std::fs::write(root.join("a.txt"), "base\n").unwrap();
+        test_git(root, &["add", "."]);
+        test_git(root, &["commit", "-qm", "init"]);
+
+        let worktree_path = worktree::get_worktrees_dir(root).join("bd-1");
+        test_git(root, &["worktree", "add", "-q", "-b", "bacchus/bd-1", worktree_path.to_str().unwrap()]);
+
+        init_db(Some(root.join("test.db").to_str().unwrap()), true).unwrap();
+        with_db(|conn| {
+            conn.execute(
+                "INSERT INTO claims (bead_id, agent_id, worktree_path, branch_name, start_commit, claimed_at)
+                 VALUES ('bd-1', 'agent', ?1, 'bacchus/bd-1', 'HEAD', 0)",
+                [worktree_path.to_str().unwrap()],
+            )
+        })
+        .unwrap();
+
+        // Nothing to record yet
+        assert!(!snapshot_worktree("bd-1", Some("empty"), root).unwrap().success);
+
+        std::fs::write(worktree_path.join("a.txt"), "good\n").unwrap();
+        std::fs::write(worktree_path.join("new.txt"), "untracked\n").unwrap();
+        let snap = snapshot_worktree("bd-1", Some("good"), root).unwrap();
+        assert!(snap.success, "{}", snap.message);
+        // Taking a snapshot leaves the work in place
+        assert_eq!(std::fs::read_to_string(worktree_path.join("a.txt")).unwrap(), "good\n");
+        assert!(worktree_path.join("new.txt").exists());
+        assert!(!snapshot_worktree("bd-1", Some("good"), root).unwrap().success);
+
+        // A risky change goes wrong; discard it and go back
+        std::fs::write(worktree_path.join("a.txt"), "broken\n").unwrap();
+        test_git(&worktree_path, &["checkout", "--", "a.txt"]);
+        std::fs::remove_file(worktree_path.join("new.txt")).unwrap();
+        let restored = restore_worktree_snapshot("bd-1", "good", root).unwrap();
+        assert!(restored.success, "{}", restored.message);
+        assert_eq!(std::fs::read_to_string(worktree_path.join("a.txt")).unwrap(), "good\n");
+        assert!(worktree_path.join("new.txt").exists());
+
+        assert!(!restore_worktree_snapshot("bd-1", "nope", root).unwrap().success);
+        let list = list_snapshots("bd-1").unwrap();
+        assert_eq!(list.total, 1);
+        assert_eq!(list.snapshots[0].name, "good");
+
+        close_db();
+    }
+}
@@ -0,0 +1,172 @@
+//! Split tool - decompose a claimed bead into new beads at agent-chosen boundaries
+//!
+//! Each new bead is created in beads with a reference to the original, and
+//! the original depends on all of them and is marked `split`. The original's
+//! claim and worktree are left in place for reference.
+
+use super::audit::log_operation;
+use crate::beads::{self, BeadInfo, BeadsError};
+use crate::db::with_db;
+use rusqlite::OptionalExtension;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SplitOutput {
+    pub success: bool,
+    pub bead_id: String,
+    pub created: Vec<String>,
+    /// `bead_symbols` links copied to the new beads (`--copy-symbols`)
+    pub symbols_copied: usize,
+    pub message: String,
+}
+
+pub fn split_bead(bead_id: &str, into: &[String], copy_symbols: bool) -> Result<SplitOutput, Box<dyn std::error::Error>> {
+    let agent_id: Option<String> = with_db(|conn| {
+        conn.query_row("SELECT agent_id FROM claims WHERE bead_id = ?1", [bead_id], |row| row.get(0))
+            .optional()
+    })?;
+    let result = try_split_bead(bead_id, into, copy_symbols, agent_id.is_some());
+    log_operation("split", bead_id, agent_id.as_deref(), &result, |r| {
+        (r.success, serde_json::json!({ "message": r.message, "created": r.created }))
+    });
+    result
+}
+
+fn try_split_bead(
+    bead_id: &str,
+    into: &[String],
+    copy_symbols: bool,
+    claimed: bool,
+) -> Result<SplitOutput, Box<dyn std::error::Error>> {
+    // There's no rollback in bd, so a failure reports the beads already created
+    let refuse = |created: Vec<String>, problem: String| SplitOutput {
+        success: false,
+        bead_id: bead_id.to_string(),
+        message: if created.is_empty() {
+            problem
+        } else {
+            format!("{} (already created: {})", problem, created.join(", "))
+        },
+        created,
+        symbols_copied: 0,
+    };
+
+    if !claimed {
+        return Ok(refuse(Vec::new(), format!("No claim found for {}; only claimed beads can be split", bead_id)));
+    }
+    let unique: HashSet<&str> = into.iter().map(String::as_str).collect();
+    if into.is_empty() || unique.len() != into.len() || unique.contains(bead_id) {
+        return Ok(refuse(
+            Vec::new(),
+            "--into needs one or more distinct new bead IDs, none equal to the original".to_string(),
+        ));
+    }
+
+    let parent = beads::get_bead(bead_id)?;
+
+    // Catch taken IDs before creating anything
+    let mut existing = Vec::new();
+    for id in into {
+        match beads::get_bead(id) {
+            Ok(_) => existing.push(id.as_str()),
+            Err(BeadsError::BeadNotFound(_)) => {}
+            Err(e) => return Err(e.into()),
+        }
+    }
+    if !existing.is_empty() {
+        return Ok(refuse(Vec::new(), format!("--into IDs already exist: {}", existing.join(", "))));
+    }
+
+    let mut created = Vec::new();
+    for (i, id) in into.iter().enumerate() {
+        let bead = BeadInfo {
+            id: id.clone(),
+            title: format!("{} (part {}/{})", parent.title, i + 1, into.len()),
+            description: Some(format!("Split from {}", bead_id)),
+            priority: parent.priority,
+            status: "open".to_string(),
+            labels: parent.labels.clone(),
+        };
+        if let Err(e) = beads::create_bead(&bead) {
+            return Ok(refuse(created, format!("Failed to create {}: {}", id, e)));
+        }
+        created.push(id.clone());
+        if let Err(e) = beads::add_dependency(bead_id, id) {
+            return Ok(refuse(created, format!("Failed to make {} depend on {}: {}", bead_id, id, e)));
+        }
+    }
+
+    match beads::update_bead_status(bead_id, "split") {
+        Err(BeadsError::CommandFailed(stderr)) => {
+            return Ok(refuse(created, beads::custom_status_error(bead_id, "split", &stderr)));
+        }
+        result => result?,
+    }
+
+    let symbols_copied = if copy_symbols { copy_bead_symbols(bead_id, into)? } else { 0 };
+
+    Ok(SplitOutput {
+        success: true,
+        bead_id: bead_id.to_string(),
+        message: format!(
+            "Split {} into {}; its worktree is kept for reference",
+            bead_id,
+            created.join(", ")
+        ),
+        created,
+        symbols_copied,
+    })
+}
+
+/// Give each new bead a contiguous share of the original's symbols
+///
+/// Symbols are ordered by fq_name, so shares mostly follow file boundaries.
+fn copy_bead_symbols(bead_id: &str, into: &[String]) -> rusqlite::Result<usize> {
+    with_db(|conn| {
+        let mut stmt = conn.prepare(
+            "SELECT symbol_fq_name, relation FROM bead_symbols WHERE bead_id = ?1 ORDER BY symbol_fq_name, relation",
+        )?;
+        let symbols = stmt
+            .query_map([bead_id], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        let tx = conn.unchecked_transaction()?;
+        for (share, target) in shares(&symbols, into.len()).into_iter().zip(into) {
+            for (fq_name, relation) in share {
+                tx.execute(
+                    "INSERT OR IGNORE INTO bead_symbols (bead_id, symbol_fq_name, relation) VALUES (?1, ?2, ?3)",
+                    [target, fq_name, relation],
+                )?;
+            }
+        }
+        tx.commit()?;
+        Ok(symbols.len())
+    })
+}
+
+/// Cut `items` into `n` contiguous parts whose sizes differ by at most one
+fn shares<T>(items: &[T], n: usize) -> Vec<&[T]> {
+    let (base, extra) = (items.len() / n, items.len() % n);
+    let mut parts = Vec::with_capacity(n);
+    let mut start = 0;
+    for i in 0..n {
+        let len = base + usize::from(i < extra);
+        parts.push(&items[start..start + len]);
+        start += len;
+    }
+    parts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shares() {
+        let items = [1, 2, 3, 4, 5, 6, 7];
+        assert_eq!(shares(&items, 3), vec![&[1, 2, 3][..], &[4, 5], &[6, 7]]);
+        assert_eq!(shares(&items[..1], 2), vec![&[1][..], &[]]);
+        assert!(shares::<i32>(&[], 2).iter().all(|s| s.is_empty()));
+    }
+}
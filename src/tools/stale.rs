@@ -1,10 +1,13 @@
 //! Stale claims tool - finds and optionally cleans up abandoned claims
 //!
-//! Detects claims older than a threshold and can clean them up.
+//! Detects claims older than a threshold and can clean them up. A heartbeat
+//! restarts a claim's clock; pinned claims are never stale.
 
+use super::audit::record_operation;
 use crate::beads;
 use crate::db::with_db;
 use crate::worktree;
+use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::path::Path;
 use std::time::{SystemTime, UNIX_EPOCH};
@@ -15,6 +18,7 @@ pub struct StaleClaim {
     pub agent_id: String,
     pub worktree_path: String,
     pub claimed_at: i64,
+    /// Minutes since the claim or its last heartbeat, whichever is later
     pub age_minutes: i64,
 }
 
@@ -25,9 +29,14 @@ pub struct StaleOutput {
     pub message: String,
 }
 
+/// Find stale claims; with `cleanup`, remove their worktrees and claims
+///
+/// Worktree directories are removed in parallel (`parallel` threads, default:
+/// one per CPU). Branch deletion, bead updates, and DB writes stay serial.
 pub fn find_stale(
     minutes: i64,
     cleanup: bool,
+    parallel: Option<usize>,
     workspace_root: &Path,
 ) -> Result<StaleOutput, Box<dyn std::error::Error>> {
     let now = SystemTime::now()
@@ -41,18 +50,19 @@ pub fn find_stale(
     // Find stale claims
     let stale_claims: Vec<StaleClaim> = with_db(|conn| {
         let mut stmt = conn.prepare(
-            "SELECT bead_id, agent_id, worktree_path, claimed_at FROM claims WHERE claimed_at < ?1",
+            "SELECT bead_id, agent_id, worktree_path, claimed_at, COALESCE(last_heartbeat, claimed_at) AS last_seen
+             FROM claims WHERE last_seen < ?1 AND pinned = 0",
         )?;
 
         let claims = stmt
             .query_map([cutoff], |row| {
-                let claimed_at: i64 = row.get(3)?;
+                let last_seen: i64 = row.get(4)?;
                 Ok(StaleClaim {
                     bead_id: row.get(0)?,
                     agent_id: row.get(1)?,
                     worktree_path: row.get(2)?,
-                    claimed_at,
-                    age_minutes: (now - claimed_at) / 60000,
+                    claimed_at: row.get(3)?,
+                    age_minutes: (now - last_seen) / 60000,
                 })
             })?
             .filter_map(|r| r.ok())
@@ -64,14 +74,37 @@ pub fn find_stale(
     let mut cleaned_up = Vec::new();
 
     if cleanup {
-        for claim in &stale_claims {
-            // Remove worktree (force to discard any changes)
-            if let Err(e) = worktree::remove_worktree(workspace_root, &claim.bead_id, true) {
-                eprintln!(
-                    "Warning: Failed to remove worktree for {}: {}",
-                    claim.bead_id, e
-                );
-                // Continue anyway - worktree might not exist
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(parallel.unwrap_or(0))
+            .build()?;
+
+        // Remove worktree dirs in parallel (force to discard any changes)
+        let removed: Vec<(&StaleClaim, bool)> = pool.install(|| {
+            stale_claims
+                .par_iter()
+                .map(|claim| {
+                    let result = worktree::remove_worktree_dir(workspace_root, &claim.bead_id, true);
+                    if let Err(e) = &result {
+                        eprintln!(
+                            "Warning: Failed to remove worktree for {}: {}",
+                            claim.bead_id, e
+                        );
+                        // Continue anyway - worktree might not exist
+                    }
+                    (claim, result.is_ok())
+                })
+                .collect()
+        });
+
+        for (claim, dir_removed) in removed {
+            // Branch deletion touches shared refs, so it runs serially
+            if dir_removed {
+                if let Err(e) = worktree::delete_branch(workspace_root, &worktree::branch_name(&claim.bead_id), true) {
+                    eprintln!(
+                        "Warning: Failed to delete branch for {}: {}",
+                        claim.bead_id, e
+                    );
+                }
             }
 
             // Reset bead status to open for retry
@@ -82,11 +115,24 @@ pub fn find_stale(
                 );
             }
 
-            // Remove claim from DB
-            let _ = with_db(|conn| conn.execute("DELETE FROM claims WHERE bead_id = ?1", [&claim.bead_id]));
-
             cleaned_up.push(claim.bead_id.clone());
         }
+
+        // Remove all claims from DB in one transaction
+        with_db(|conn| {
+            let tx = conn.unchecked_transaction()?;
+            for bead_id in &cleaned_up {
+                tx.execute("DELETE FROM claims WHERE bead_id = ?1", [bead_id])?;
+            }
+            tx.commit()
+        })?;
+
+        for claim in stale_claims.iter().filter(|c| cleaned_up.contains(&c.bead_id)) {
+            let details = serde_json::json!({ "age_minutes": claim.age_minutes, "threshold_minutes": minutes });
+            if let Err(e) = record_operation("stale_cleanup", Some(&claim.bead_id), Some(&claim.agent_id), "success", Some(&details)) {
+                eprintln!("Warning: failed to record stale cleanup of {} in audit log: {}", claim.bead_id, e);
+            }
+        }
     }
 
     let message = if cleanup {
@@ -108,3 +154,34 @@ pub fn find_stale(
         message,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{close_db, init_db, test_lock};
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_cleanup_frees_file_locks() {
+        let _lock = test_lock();
+        let dir = tempdir().unwrap();
+        init_db(Some(dir.path().join("test.db").to_str().unwrap()), true).unwrap();
+
+        with_db(|conn| {
+            conn.execute_batch(
+                "INSERT INTO claims (bead_id, agent_id, worktree_path, branch_name, start_commit, claimed_at)
+                 VALUES ('old-bead', 'agent-a', '/gone/old-bead', 'bacchus/old-bead', 'abc', 1);
+                 INSERT INTO file_locks (file_path, bead_id, agent_id, locked_at)
+                 VALUES ('src/lib.rs', 'old-bead', 'agent-a', 1);",
+            )
+        })
+        .unwrap();
+
+        let output = find_stale(15, true, Some(1), dir.path()).unwrap();
+        assert_eq!(output.cleaned_up, ["old-bead"]);
+        let locks: i64 = with_db(|conn| conn.query_row("SELECT COUNT(*) FROM file_locks", [], |row| row.get(0))).unwrap();
+        assert_eq!(locks, 0);
+
+        close_db();
+    }
+}
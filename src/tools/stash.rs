@@ -0,0 +1,127 @@
+//! Stash tool - shelve a bead's uncommitted work and bring it back later
+//!
+//! Git's stash list is shared by every worktree, so bacchus tags each stash
+//! message with its bead and pops by that tag. `claims.stashed_at` records
+//! that a claim has shelved work waiting.
+
+use crate::db::with_db;
+use crate::worktree;
+use rusqlite::OptionalExtension;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StashOutput {
+    pub success: bool,
+    pub bead_id: String,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StashInfo {
+    pub stash_ref: String,
+    /// None for stashes bacchus didn't create
+    pub bead_id: Option<String>,
+    pub message: String,
+    pub age_minutes: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StashListOutput {
+    pub stashes: Vec<StashInfo>,
+    pub total: usize,
+}
+
+fn has_claim(bead_id: &str) -> rusqlite::Result<bool> {
+    with_db(|conn| {
+        conn.query_row("SELECT 1 FROM claims WHERE bead_id = ?1", [bead_id], |_| Ok(()))
+            .optional()
+            .map(|r| r.is_some())
+    })
+}
+
+pub fn stash_bead(
+    bead_id: &str,
+    message: Option<&str>,
+    workspace_root: &Path,
+) -> Result<StashOutput, Box<dyn std::error::Error>> {
+    if !has_claim(bead_id)? {
+        return Ok(StashOutput {
+            success: false,
+            bead_id: bead_id.to_string(),
+            message: format!("No claim found for {}", bead_id),
+        });
+    }
+
+    if !worktree::stash_worktree(workspace_root, bead_id, message.unwrap_or("WIP"))? {
+        return Ok(StashOutput {
+            success: true,
+            bead_id: bead_id.to_string(),
+            message: format!("No local changes to stash in {}", bead_id),
+        });
+    }
+
+    let now = chrono::Utc::now().timestamp_millis();
+    with_db(|conn| {
+        conn.execute(
+            "UPDATE claims SET stashed_at = ?1 WHERE bead_id = ?2",
+            rusqlite::params![now, bead_id],
+        )
+    })?;
+
+    Ok(StashOutput {
+        success: true,
+        bead_id: bead_id.to_string(),
+        message: format!("Stashed changes in {}. Run `bacchus unstash {}` to restore them.", bead_id, bead_id),
+    })
+}
+
+pub fn unstash_bead(bead_id: &str, workspace_root: &Path) -> Result<StashOutput, Box<dyn std::error::Error>> {
+    if !has_claim(bead_id)? {
+        return Ok(StashOutput {
+            success: false,
+            bead_id: bead_id.to_string(),
+            message: format!("No claim found for {}", bead_id),
+        });
+    }
+
+    if let Err(e) = worktree::unstash_worktree(workspace_root, bead_id) {
+        return Ok(StashOutput {
+            success: false,
+            bead_id: bead_id.to_string(),
+            message: format!("Failed to unstash {}: {}", bead_id, e),
+        });
+    }
+
+    // Older stashes for the same bead may remain; keep the flag until they're gone
+    let remaining = worktree::list_stashes(workspace_root)?
+        .iter()
+        .any(|s| s.bead_id.as_deref() == Some(bead_id));
+    if !remaining {
+        with_db(|conn| conn.execute("UPDATE claims SET stashed_at = NULL WHERE bead_id = ?1", [bead_id]))?;
+    }
+
+    Ok(StashOutput {
+        success: true,
+        bead_id: bead_id.to_string(),
+        message: format!("Restored stashed changes in {}", bead_id),
+    })
+}
+
+pub fn list_stashes(workspace_root: &Path) -> Result<StashListOutput, Box<dyn std::error::Error>> {
+    let now = chrono::Utc::now().timestamp();
+    let stashes: Vec<StashInfo> = worktree::list_stashes(workspace_root)?
+        .into_iter()
+        .map(|s| StashInfo {
+            stash_ref: s.stash_ref,
+            bead_id: s.bead_id,
+            message: s.message,
+            age_minutes: (now - s.created_at) / 60,
+        })
+        .collect();
+
+    Ok(StashListOutput {
+        total: stashes.len(),
+        stashes,
+    })
+}
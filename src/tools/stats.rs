@@ -0,0 +1,202 @@
+//! Stats tool - project-level coordination metrics for dashboards
+//!
+//! Aggregates the operation rows of the audit log (see `bacchus log`) plus
+//! the symbol index. Everything is computed over the `--since` window.
+
+use crate::db::with_db;
+use rusqlite::{Connection, Result};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BeadRetries {
+    pub bead_id: String,
+    /// Claims after the first one
+    pub retries: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LanguageCount {
+    /// None for symbols without a detected language (e.g. virtual symbols)
+    pub language: Option<String>,
+    pub symbols: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Stats {
+    /// Start of the window (Unix ms); None means all history
+    pub since: Option<i64>,
+    pub beads_claimed: i64,
+    /// Released with status done
+    pub beads_completed: i64,
+    /// Released as blocked or failed, or cleaned up as stale
+    pub beads_abandoned: i64,
+    /// From a successful claim to the release or stale cleanup that ended it
+    pub avg_claim_duration_minutes: Option<f64>,
+    pub merge_attempts: i64,
+    /// Successful `release --status done` runs over attempts (None without attempts)
+    pub merge_success_rate: Option<f64>,
+    /// Merges completed with `bacchus resolve` after manual conflict resolution
+    pub conflicts_resolved: i64,
+    pub top_retried_beads: Vec<BeadRetries>,
+    pub symbols_by_language: Vec<LanguageCount>,
+}
+
+const TOP_RETRIED: i64 = 5;
+
+pub fn compute_stats(since_ms: Option<i64>) -> Result<Stats> {
+    with_db(|conn| {
+        // Operation rows inside the window
+        let ops = "SELECT * FROM audit_log WHERE operation IS NOT NULL AND (?1 IS NULL OR timestamp_ms >= ?1)";
+        let count = |filter: &str| -> Result<i64> {
+            conn.query_row(
+                &format!("SELECT COUNT(*) FROM ({}) WHERE {}", ops, filter),
+                [since_ms],
+                |r| r.get(0),
+            )
+        };
+
+        let beads_claimed: i64 = conn.query_row(
+            &format!("SELECT COUNT(DISTINCT bead_id) FROM ({}) WHERE operation = 'claim' AND outcome = 'success'", ops),
+            [since_ms],
+            |r| r.get(0),
+        )?;
+
+        let released_as = |statuses: &str| {
+            format!(
+                "operation = 'release' AND outcome = 'success' AND json_extract(details, '$.status') IN ({})",
+                statuses
+            )
+        };
+        let beads_completed = count(&released_as("'done'"))?;
+        let beads_abandoned = count(&format!(
+            "({}) OR (operation = 'stale_cleanup' AND outcome = 'success')",
+            released_as("'blocked', 'failed'")
+        ))?;
+
+        let avg_claim_duration_minutes = avg_claim_duration_minutes(conn, since_ms)?;
+
+        let merge_attempts = count("operation = 'release' AND json_extract(details, '$.status') = 'done'")?;
+        let merge_success_rate = (merge_attempts > 0)
+            .then(|| beads_completed as f64 / merge_attempts as f64);
+
+        let conflicts_resolved = count("operation = 'resolve' AND outcome = 'success'")?;
+
+        let mut stmt = conn.prepare(&format!(
+            "SELECT bead_id, COUNT(*) - 1 AS retries FROM ({})
+             WHERE operation = 'claim' AND outcome = 'success' AND bead_id IS NOT NULL
+             GROUP BY bead_id HAVING retries > 0
+             ORDER BY retries DESC, bead_id
+             LIMIT ?2",
+            ops
+        ))?;
+        let top_retried_beads = stmt
+            .query_map(rusqlite::params![since_ms, TOP_RETRIED], |row| {
+                Ok(BeadRetries {
+                    bead_id: row.get(0)?,
+                    retries: row.get(1)?,
+                })
+            })?
+            .collect::<Result<Vec<_>>>()?;
+
+        // The index is current state, not history, so it ignores the window
+        let mut stmt = conn.prepare(
+            "SELECT language, COUNT(*) FROM symbols GROUP BY language ORDER BY COUNT(*) DESC, language",
+        )?;
+        let symbols_by_language = stmt
+            .query_map([], |row| {
+                Ok(LanguageCount {
+                    language: row.get(0)?,
+                    symbols: row.get(1)?,
+                })
+            })?
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Stats {
+            since: since_ms,
+            beads_claimed,
+            beads_completed,
+            beads_abandoned,
+            avg_claim_duration_minutes,
+            merge_attempts,
+            merge_success_rate,
+            conflicts_resolved,
+            top_retried_beads,
+            symbols_by_language,
+        })
+    })
+}
+
+/// Mean time from a successful claim to the release or stale cleanup that ended it
+pub fn avg_claim_duration_minutes(conn: &Connection, since_ms: Option<i64>) -> Result<Option<f64>> {
+    conn.query_row(
+        "SELECT AVG(e.timestamp_ms - (
+             SELECT MAX(c.timestamp_ms) FROM audit_log c
+             WHERE c.operation = 'claim' AND c.outcome = 'success'
+               AND c.bead_id = e.bead_id AND c.timestamp_ms <= e.timestamp_ms
+         )) / 60000.0
+         FROM audit_log e
+         WHERE e.outcome = 'success' AND e.operation IN ('release', 'stale_cleanup')
+           AND (?1 IS NULL OR e.timestamp_ms >= ?1)",
+        [since_ms],
+        |r| r.get(0),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{close_db, init_db, test_lock};
+    use crate::tools::audit::record_operation;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_compute_stats() {
+        let _lock = test_lock();
+        let dir = tempdir().unwrap();
+        init_db(Some(dir.path().join("test.db").to_str().unwrap()), true).unwrap();
+
+        let op = |operation: &str, bead: &str, outcome: &str, details: serde_json::Value, at_min: i64| {
+            record_operation(operation, Some(bead), Some("agent-a"), outcome, Some(&details)).unwrap();
+            with_db(|conn| {
+                conn.execute(
+                    "UPDATE audit_log SET timestamp_ms = ?1 WHERE id = last_insert_rowid()",
+                    [at_min * 60_000],
+                )
+            })
+            .unwrap();
+        };
+        let none = serde_json::Value::Null;
+
+        // A: claimed, conflict on first merge, resolved; B: abandoned then retried to completion
+        op("claim", "A", "success", none.clone(), 0);
+        op("release", "A", "failure", serde_json::json!({"status": "done", "merged": false}), 10);
+        op("resolve", "A", "success", none.clone(), 20);
+        op("claim", "B", "success", none.clone(), 0);
+        op("release", "B", "success", serde_json::json!({"status": "failed", "merged": false}), 30);
+        op("claim", "B", "success", none.clone(), 40);
+        op("release", "B", "success", serde_json::json!({"status": "done", "merged": true}), 50);
+        op("claim", "C", "success", none.clone(), 60);
+        op("stale_cleanup", "C", "success", none.clone(), 100);
+
+        let stats = compute_stats(None).unwrap();
+        assert_eq!(stats.beads_claimed, 3);
+        assert_eq!(stats.beads_completed, 1);
+        assert_eq!(stats.beads_abandoned, 2);
+        // B: 30 and 10 minutes, C: 40 minutes
+        assert_eq!(stats.avg_claim_duration_minutes, Some(80.0 / 3.0));
+        assert_eq!(stats.merge_attempts, 2);
+        assert_eq!(stats.merge_success_rate, Some(0.5));
+        assert_eq!(stats.conflicts_resolved, 1);
+        assert_eq!(stats.top_retried_beads.len(), 1);
+        assert_eq!(stats.top_retried_beads[0].bead_id, "B");
+        assert_eq!(stats.top_retried_beads[0].retries, 1);
+
+        let recent = compute_stats(Some(45 * 60_000)).unwrap();
+        assert_eq!(recent.beads_claimed, 1);
+        assert_eq!(recent.beads_completed, 1);
+        assert_eq!(recent.merge_success_rate, Some(1.0));
+        assert!(recent.top_retried_beads.is_empty());
+
+        close_db();
+    }
+}
@@ -0,0 +1,176 @@
+//! Advisory symbol locks - lets a bead signal it is reworking a symbol
+//!
+//! Locks are advisory: nothing stops an edit, but `next` warns when a picked
+//! bead's registered symbols are locked by another bead. Locks expire after
+//! `BACCHUS_LOCK_EXPIRY_MINUTES` (default 60).
+
+use crate::db::with_db;
+use rusqlite::{OptionalExtension, Result};
+use serde::{Deserialize, Serialize};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const DEFAULT_LOCK_EXPIRY_MINUTES: i64 = 60;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SymbolLockOutput {
+    pub success: bool,
+    pub fq_name: String,
+    pub bead_id: String,
+    /// Unix timestamp (ms) when the lock lapses, if held
+    pub expires_at: Option<i64>,
+    pub message: String,
+}
+
+/// A symbol locked by a bead other than the one asking
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SymbolLockConflict {
+    pub fq_name: String,
+    pub locked_by: String,
+    pub expires_at: i64,
+}
+
+fn now_ms() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as i64)
+        .unwrap_or(0)
+}
+
+fn lock_expiry_ms() -> i64 {
+    let minutes = std::env::var("BACCHUS_LOCK_EXPIRY_MINUTES")
+        .ok()
+        .and_then(|v| v.parse::<i64>().ok())
+        .filter(|m| *m > 0)
+        .unwrap_or(DEFAULT_LOCK_EXPIRY_MINUTES);
+    minutes * 60 * 1000
+}
+
+/// Lock a symbol for a bead; re-locking by the holder extends the expiry
+pub fn lock_symbol(fq_name: &str, bead_id: &str) -> Result<SymbolLockOutput> {
+    let now = now_ms();
+    let expires_at = now + lock_expiry_ms();
+
+    with_db(|conn| {
+        let holder = conn
+            .query_row(
+                "SELECT bead_id, expires_at FROM symbol_locks WHERE fq_name = ?1 AND expires_at > ?2",
+                rusqlite::params![fq_name, now],
+                |row| Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?)),
+            )
+            .optional()?;
+
+        if let Some((holder, holder_expires)) = holder.filter(|(holder, _)| holder != bead_id) {
+            return Ok(SymbolLockOutput {
+                success: false,
+                fq_name: fq_name.to_string(),
+                bead_id: bead_id.to_string(),
+                expires_at: Some(holder_expires),
+                message: format!("{} is locked by {}", fq_name, holder),
+            });
+        }
+
+        conn.execute(
+            "INSERT OR REPLACE INTO symbol_locks (fq_name, bead_id, locked_at, expires_at) VALUES (?1, ?2, ?3, ?4)",
+            rusqlite::params![fq_name, bead_id, now, expires_at],
+        )?;
+
+        Ok(SymbolLockOutput {
+            success: true,
+            fq_name: fq_name.to_string(),
+            bead_id: bead_id.to_string(),
+            expires_at: Some(expires_at),
+            message: format!("Locked {} for {}", fq_name, bead_id),
+        })
+    })
+}
+
+/// Release a bead's lock on a symbol
+pub fn unlock_symbol(fq_name: &str, bead_id: &str) -> Result<SymbolLockOutput> {
+    let removed = with_db(|conn| {
+        conn.execute(
+            "DELETE FROM symbol_locks WHERE fq_name = ?1 AND bead_id = ?2",
+            [fq_name, bead_id],
+        )
+    })?;
+
+    let message = if removed > 0 {
+        format!("Unlocked {}", fq_name)
+    } else {
+        format!("{} is not locked by {}", fq_name, bead_id)
+    };
+
+    Ok(SymbolLockOutput {
+        success: removed > 0,
+        fq_name: fq_name.to_string(),
+        bead_id: bead_id.to_string(),
+        expires_at: None,
+        message,
+    })
+}
+
+/// Symbols registered to `bead_id` that another bead currently holds a lock on
+pub fn locked_by_others(bead_id: &str) -> Result<Vec<SymbolLockConflict>> {
+    let now = now_ms();
+    with_db(|conn| {
+        let mut stmt = conn.prepare(
+            "SELECT DISTINCT l.fq_name, l.bead_id, l.expires_at
+             FROM bead_symbols bs
+             JOIN symbol_locks l ON l.fq_name = bs.symbol_fq_name
+             WHERE bs.bead_id = ?1 AND l.bead_id != ?1 AND l.expires_at > ?2
+             ORDER BY l.fq_name",
+        )?;
+        let conflicts = stmt
+            .query_map(rusqlite::params![bead_id, now], |row| {
+                Ok(SymbolLockConflict {
+                    fq_name: row.get(0)?,
+                    locked_by: row.get(1)?,
+                    expires_at: row.get(2)?,
+                })
+            })?
+            .collect::<Result<Vec<_>>>()?;
+        Ok(conflicts)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{close_db, init_db, test_lock};
+    use crate::tools::bead_symbols::register_bead_symbol;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_symbol_locks() {
+        let _lock = test_lock();
+        let dir = tempdir().unwrap();
+        init_db(Some(dir.path().join("test.db").to_str().unwrap()), true).unwrap();
+
+        assert!(lock_symbol("src/auth.rs::login", "bead-a").unwrap().success);
+        // Holder can re-lock; others can't
+        assert!(lock_symbol("src/auth.rs::login", "bead-a").unwrap().success);
+        assert!(!lock_symbol("src/auth.rs::login", "bead-b").unwrap().success);
+
+        register_bead_symbol("bead-b", "src/auth.rs::login", "modifies").unwrap();
+        let conflicts = locked_by_others("bead-b").unwrap();
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].locked_by, "bead-a");
+        assert!(locked_by_others("bead-a").unwrap().is_empty());
+
+        // Only the holder can unlock
+        assert!(!unlock_symbol("src/auth.rs::login", "bead-b").unwrap().success);
+        assert!(unlock_symbol("src/auth.rs::login", "bead-a").unwrap().success);
+        assert!(locked_by_others("bead-b").unwrap().is_empty());
+
+        // Expired locks don't block
+        with_db(|conn| {
+            conn.execute(
+                "INSERT INTO symbol_locks (fq_name, bead_id, locked_at, expires_at) VALUES ('x', 'bead-a', 0, 1)",
+                [],
+            )
+        })
+        .unwrap();
+        assert!(lock_symbol("x", "bead-b").unwrap().success);
+
+        close_db();
+    }
+}
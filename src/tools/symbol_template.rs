@@ -0,0 +1,141 @@
+//! Render symbol search results through Handlebars templates
+//!
+//! A template is resolved in order from:
+//! 1. A file path
+//! 2. `.bacchus/templates/symbols-<name>.hbs` in the workspace
+//! 3. A built-in template (`slack` or `markdown`)
+
+use super::symbols::FindSymbolsOutput;
+use handlebars::{handlebars_helper, no_escape, Handlebars};
+use std::path::Path;
+
+const BUILTIN_SLACK: &str = include_str!("../templates/symbols-slack.hbs");
+const BUILTIN_MARKDOWN: &str = include_str!("../templates/symbols-markdown.hbs");
+
+/// Render symbols through the template named by `spec`
+pub fn render_symbols(
+    output: &FindSymbolsOutput,
+    spec: &str,
+    workspace_root: &Path,
+) -> Result<String, String> {
+    let template = load_template(spec, workspace_root)?;
+
+    let mut hb = Handlebars::new();
+    // Output is markdown or JSON, never HTML
+    hb.register_escape_fn(no_escape);
+    register_helpers(&mut hb);
+
+    hb.render_template(&template, output)
+        .map_err(|e| format!("Failed to render template '{}': {}", spec, e))
+}
+
+fn load_template(spec: &str, workspace_root: &Path) -> Result<String, String> {
+    let path = Path::new(spec);
+    if path.is_file() {
+        return std::fs::read_to_string(path).map_err(|e| e.to_string());
+    }
+
+    let custom = workspace_root
+        .join(".bacchus/templates")
+        .join(format!("symbols-{}.hbs", spec));
+    if custom.is_file() {
+        return std::fs::read_to_string(custom).map_err(|e| e.to_string());
+    }
+
+    match spec {
+        "slack" => Ok(BUILTIN_SLACK.to_string()),
+        "markdown" => Ok(BUILTIN_MARKDOWN.to_string()),
+        _ => Err(format!(
+            "Template not found: {} (expected a file, .bacchus/templates/symbols-{}.hbs, or one of: slack, markdown)",
+            spec, spec
+        )),
+    }
+}
+
+handlebars_helper!(format_lines_helper: |n: i64| format_lines(n));
+handlebars_helper!(truncate_helper: |s: str, len: u64| truncate(s, len as usize));
+handlebars_helper!(json_escape_helper: |s: str| json_escape(s));
+
+fn register_helpers(hb: &mut Handlebars) {
+    hb.register_helper("format_lines", Box::new(format_lines_helper));
+    hb.register_helper("truncate", Box::new(truncate_helper));
+    hb.register_helper("json_escape", Box::new(json_escape_helper));
+}
+
+/// Compact line count: 850, 1.2k, 12k
+fn format_lines(n: i64) -> String {
+    if n < 1000 {
+        return n.to_string();
+    }
+    let formatted = format!("{:.1}", n as f64 / 1000.0);
+    format!("{}k", formatted.trim_end_matches(".0"))
+}
+
+/// Cut a string to at most `len` characters, ending with an ellipsis if shortened
+fn truncate(s: &str, len: usize) -> String {
+    if s.chars().count() <= len {
+        return s.to_string();
+    }
+    let mut out: String = s.chars().take(len.saturating_sub(1)).collect();
+    out.push('…');
+    out
+}
+
+/// Escape a string for embedding inside a JSON string literal
+fn json_escape(s: &str) -> String {
+    let quoted = serde_json::to_string(s).unwrap_or_default();
+    quoted[1..quoted.len() - 1].to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::symbols::SymbolInfo;
+
+    fn sample_output() -> FindSymbolsOutput {
+        FindSymbolsOutput {
+            symbols: vec![SymbolInfo {
+                id: 1,
+                file: "src/auth.rs".to_string(),
+                fq_name: "src/auth.rs::login".to_string(),
+                kind: "function".to_string(),
+                span_start_line: 10,
+                span_end_line: 1250,
+                line_count: 1241,
+                hash: "abc".to_string(),
+                docstring: Some("/// Log a \"user\" in".to_string()),
+                language: Some("rust".to_string()),
+                is_virtual: false,
+                complexity: None,
+                indexed_at: None,
+                decorators: Vec::new(),
+                type_params: None,
+            }],
+            total_count: 1,
+        }
+    }
+
+    #[test]
+    fn test_helpers() {
+        assert_eq!(format_lines(850), "850");
+        assert_eq!(format_lines(1241), "1.2k");
+        assert_eq!(format_lines(12000), "12k");
+        assert_eq!(truncate("hello world", 5), "hell…");
+        assert_eq!(truncate("hi", 5), "hi");
+        assert_eq!(json_escape("a \"b\"\n"), "a \\\"b\\\"\\n");
+    }
+
+    #[test]
+    fn test_builtin_templates() {
+        let root = Path::new("/nonexistent");
+
+        let md = render_symbols(&sample_output(), "markdown", root).unwrap();
+        assert!(md.contains("| `src/auth.rs::login` | function | src/auth.rs:10 | 1.2k |"));
+
+        let slack = render_symbols(&sample_output(), "slack", root).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&slack).unwrap();
+        assert_eq!(parsed["blocks"].as_array().unwrap().len(), 2);
+
+        assert!(render_symbols(&sample_output(), "nope", root).is_err());
+    }
+}
@@ -1,18 +1,33 @@
 //! Symbol tools for querying
 
 use crate::db::with_db;
-use rusqlite::Result;
+use rusqlite::{Connection, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::Path;
 use strsim::jaro_winkler;
 
 const DEFAULT_LIMIT: i32 = 50;
 const FUZZY_THRESHOLD: f64 = 0.7;
+/// Normalized base names must be near-identical to count as the same concept
+const CROSS_REF_THRESHOLD: f64 = 0.95;
+/// Body similarity above which two symbols are flagged as fuzzy duplicates
+const DUPLICATE_THRESHOLD: f64 = 0.9;
+/// Symbols shorter than this are too generic to flag as fuzzy duplicates
+const DUPLICATE_MIN_LINES: i32 = 3;
+/// Minimum cosine similarity for a --semantic-search match
+pub const DEFAULT_SEMANTIC_THRESHOLD: f64 = 0.1;
+/// Common English words that carry no meaning for docstring search
+const STOP_WORDS: &[&str] = &[
+    "a", "an", "and", "are", "as", "at", "be", "by", "for", "from", "if", "in", "into", "is", "it",
+    "its", "of", "on", "or", "that", "the", "this", "to", "was", "will", "with",
+];
 
 // ============================================================================
 // Input/Output Types
 // ============================================================================
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct FindSymbolsInput {
     pub pattern: Option<String>,
     pub kind: Option<String>,
@@ -21,6 +36,27 @@ pub struct FindSymbolsInput {
     pub limit: Option<i32>,
     pub search: Option<String>,
     pub fuzzy: bool,
+    /// Only symbols indexed after this Unix timestamp (ms)
+    pub since: Option<i64>,
+    pub min_complexity: Option<u32>,
+    /// Only symbols registered to this bead in bead_symbols
+    pub in_bead: Option<String>,
+    /// Only symbols re-indexed after this bead was claimed
+    pub changed_since_claim: Option<String>,
+    /// "complexity" sorts by complexity descending; default is file order
+    pub sort: Option<String>,
+    /// Go/Python package or Rust module name
+    pub package: Option<String>,
+    /// TF-IDF search over docstrings
+    pub semantic_search: Option<String>,
+    /// Minimum score for semantic_search (default: DEFAULT_SEMANTIC_THRESHOLD)
+    pub semantic_threshold: Option<f64>,
+    /// Only symbols whose name this file imports (matched by name via file_imports)
+    pub imported_by: Option<String>,
+    /// Only symbols with this decorator (full dotted name or its last segment)
+    pub decorator: Option<String>,
+    /// Only generic symbols declaring a type parameter with this name
+    pub has_type_param: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -41,6 +77,74 @@ pub struct SymbolInfo {
     pub hash: String,
     pub docstring: Option<String>,
     pub language: Option<String>,
+    /// Manually registered symbol (not extracted by the indexer)
+    pub is_virtual: bool,
+    pub complexity: Option<i64>,
+    /// When the indexer last wrote this symbol (ISO 8601)
+    pub indexed_at: Option<String>,
+    /// Python decorator names, e.g. `property`, `functools.cache`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub decorators: Vec<String>,
+    /// Generic parameters as written, e.g. `T: Trait`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub type_params: Option<Vec<String>>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SymbolDiff {
+    pub fq_name: String,
+    /// added, modified, or deleted
+    pub change_type: String,
+    pub old_hash: Option<String>,
+    pub new_hash: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SymbolDiffOutput {
+    pub changes: Vec<SymbolDiff>,
+    pub total_count: i32,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RegisterSymbolOutput {
+    pub success: bool,
+    pub fq_name: String,
+    pub message: String,
+}
+
+/// Language marker for manually registered symbols
+pub const VIRTUAL_LANGUAGE: &str = "virtual";
+
+/// Map a `symbols` row (id, file, fq_name, kind, span_start_line, span_end_line,
+/// line_count, hash, docstring, language, complexity, last_indexed_at, decorators,
+/// type_params) to a `SymbolInfo`
+fn symbol_from_row(row: &rusqlite::Row) -> Result<SymbolInfo> {
+    let language: Option<String> = row.get(9)?;
+    Ok(SymbolInfo {
+        id: row.get(0)?,
+        file: row.get(1)?,
+        fq_name: row.get(2)?,
+        kind: row.get(3)?,
+        span_start_line: row.get(4)?,
+        span_end_line: row.get(5)?,
+        line_count: row.get(6)?,
+        hash: row.get(7)?,
+        docstring: row.get(8)?,
+        is_virtual: language.as_deref() == Some(VIRTUAL_LANGUAGE),
+        language,
+        complexity: row.get(10)?,
+        indexed_at: row
+            .get::<_, Option<i64>>(11)?
+            .and_then(chrono::DateTime::from_timestamp_millis)
+            .map(|d| d.to_rfc3339()),
+        decorators: row
+            .get::<_, Option<String>>(12)?
+            .and_then(|d| serde_json::from_str(&d).ok())
+            .unwrap_or_default(),
+        type_params: row
+            .get::<_, Option<String>>(13)?
+            .and_then(|t| serde_json::from_str(&t).ok()),
+    })
 }
 
 // ============================================================================
@@ -54,6 +158,14 @@ pub fn find_symbols(input: &FindSymbolsInput) -> Result<FindSymbolsOutput> {
         return search_symbols_fts(query, input.limit.unwrap_or(DEFAULT_LIMIT));
     }
 
+    if let Some(ref query) = input.semantic_search {
+        return search_symbols_semantic(
+            query,
+            input.semantic_threshold.unwrap_or(DEFAULT_SEMANTIC_THRESHOLD),
+            input.limit.unwrap_or(DEFAULT_LIMIT),
+        );
+    }
+
     if input.fuzzy {
         if let Some(ref pattern) = input.pattern {
             return find_symbols_fuzzy(pattern, input.limit.unwrap_or(DEFAULT_LIMIT));
@@ -70,6 +182,44 @@ pub fn find_symbols(input: &FindSymbolsInput) -> Result<FindSymbolsOutput> {
             params_vec.push(Box::new(pattern.replace('*', "%")));
         }
 
+        if let Some(ref bead_id) = input.changed_since_claim {
+            conditions.push("last_indexed_at > (SELECT claimed_at FROM claims WHERE bead_id = ?)");
+            params_vec.push(Box::new(bead_id.clone()));
+        }
+
+        if let Some(ref bead_id) = input.in_bead {
+            conditions.push("fq_name IN (SELECT symbol_fq_name FROM bead_symbols WHERE bead_id = ?)");
+            params_vec.push(Box::new(bead_id.clone()));
+        }
+
+        if let Some(ref decorator) = input.decorator {
+            conditions.push(
+                "EXISTS (SELECT 1 FROM json_each(decorators) d
+                         WHERE d.value = ? OR substr(d.value, -length(?) - 1) = '.' || ?)",
+            );
+            for _ in 0..3 {
+                params_vec.push(Box::new(decorator.clone()));
+            }
+        }
+
+        if let Some(ref param) = input.has_type_param {
+            // Parameters are stored with their bounds/defaults: `T`, `T: Trait`, `T extends U`
+            conditions.push(
+                "EXISTS (SELECT 1 FROM json_each(type_params) t
+                         WHERE t.value = ? OR t.value GLOB ? || '[: =]*')",
+            );
+            params_vec.push(Box::new(param.clone()));
+            params_vec.push(Box::new(param.clone()));
+        }
+
+        if let Some(ref importer) = input.imported_by {
+            conditions.push(
+                "EXISTS (SELECT 1 FROM file_imports fi WHERE fi.importer_file = ?
+                         AND substr(fq_name, -length(fi.imported_name) - 2) = '::' || fi.imported_name)",
+            );
+            params_vec.push(Box::new(importer.clone()));
+        }
+
         if let Some(ref kind) = input.kind {
             conditions.push("kind = ?");
             params_vec.push(Box::new(kind.clone()));
@@ -85,6 +235,23 @@ pub fn find_symbols(input: &FindSymbolsInput) -> Result<FindSymbolsOutput> {
             params_vec.push(Box::new(language.clone()));
         }
 
+        if let Some(since) = input.since {
+            conditions.push("last_indexed_at > ?");
+            params_vec.push(Box::new(since));
+        }
+
+        if let Some(min_complexity) = input.min_complexity {
+            conditions.push("complexity >= ?");
+            params_vec.push(Box::new(min_complexity));
+        }
+
+        if let Some(ref package) = input.package {
+            conditions.push(PACKAGE_CONDITION);
+            for pattern in package_patterns(package) {
+                params_vec.push(Box::new(pattern));
+            }
+        }
+
         let where_clause = if conditions.is_empty() {
             String::new()
         } else {
@@ -98,10 +265,15 @@ pub fn find_symbols(input: &FindSymbolsInput) -> Result<FindSymbolsOutput> {
         let params_refs: Vec<&dyn rusqlite::ToSql> = params_vec.iter().map(|p| p.as_ref()).collect();
         let total_count: i32 = conn.query_row(&count_sql, params_refs.as_slice(), |row| row.get(0))?;
 
+        let order_by = match input.sort.as_deref() {
+            Some("complexity") => "complexity DESC, file, span_start_line",
+            _ => "file, span_start_line",
+        };
+
         // Get symbols
         let query_sql = format!(
-            "SELECT id, file, fq_name, kind, span_start_line, span_end_line, line_count, hash, docstring, language FROM symbols {} ORDER BY file, span_start_line LIMIT ?",
-            where_clause
+            "SELECT id, file, fq_name, kind, span_start_line, span_end_line, line_count, hash, docstring, language, complexity, last_indexed_at, decorators, type_params FROM symbols {} ORDER BY {} LIMIT ?",
+            where_clause, order_by
         );
 
         let mut all_params: Vec<&dyn rusqlite::ToSql> = params_vec.iter().map(|p| p.as_ref()).collect();
@@ -109,20 +281,7 @@ pub fn find_symbols(input: &FindSymbolsInput) -> Result<FindSymbolsOutput> {
 
         let mut stmt = conn.prepare(&query_sql)?;
         let symbols: Vec<SymbolInfo> = stmt
-            .query_map(rusqlite::params_from_iter(all_params), |row| {
-                Ok(SymbolInfo {
-                    id: row.get(0)?,
-                    file: row.get(1)?,
-                    fq_name: row.get(2)?,
-                    kind: row.get(3)?,
-                    span_start_line: row.get(4)?,
-                    span_end_line: row.get(5)?,
-                    line_count: row.get(6)?,
-                    hash: row.get(7)?,
-                    docstring: row.get(8)?,
-                    language: row.get(9)?,
-                })
-            })?
+            .query_map(rusqlite::params_from_iter(all_params), symbol_from_row)?
             .filter_map(|r| r.ok())
             .collect();
 
@@ -133,13 +292,33 @@ pub fn find_symbols(input: &FindSymbolsInput) -> Result<FindSymbolsOutput> {
     })
 }
 
+/// Package membership computed from `symbols.file`:
+/// - Go: any directory component equals the package (`internal/auth/*.go`)
+/// - Python: the dotted package maps to a directory or module file (`a.b` -> `a/b/` or `a/b.py`)
+/// - Rust: module directory or file under `src/` (`src/auth/` or `src/auth.rs`)
+const PACKAGE_CONDITION: &str = "((language = 'go' AND '/' || file LIKE ?)
+  OR (language = 'python' AND ('/' || file LIKE ? OR '/' || file LIKE ?))
+  OR (language = 'rust' AND ('/' || file LIKE ? OR '/' || file LIKE ?)))";
+
+/// LIKE patterns for the placeholders in `PACKAGE_CONDITION`, in order
+fn package_patterns(package: &str) -> [String; 5] {
+    let path = package.replace('.', "/");
+    [
+        format!("%/{}/%", package),
+        format!("%/{}/%", path),
+        format!("%/{}.py", path),
+        format!("%/src/{}/%", path),
+        format!("%/src/{}.rs", path),
+    ]
+}
+
 /// Full-text search using FTS5
 pub fn search_symbols_fts(query: &str, limit: i32) -> Result<FindSymbolsOutput> {
     with_db(|conn| {
         // FTS5 query with ranking using bm25
         let sql = r#"
             SELECT s.id, s.file, s.fq_name, s.kind, s.span_start_line, s.span_end_line,
-                   s.line_count, s.hash, s.docstring, s.language
+                   s.line_count, s.hash, s.docstring, s.language, s.complexity, s.last_indexed_at, s.decorators, s.type_params
             FROM symbols_fts
             JOIN symbols s ON symbols_fts.rowid = s.id
             WHERE symbols_fts MATCH ?1
@@ -149,20 +328,7 @@ pub fn search_symbols_fts(query: &str, limit: i32) -> Result<FindSymbolsOutput>
 
         let mut stmt = conn.prepare(sql)?;
         let symbols: Vec<SymbolInfo> = stmt
-            .query_map(rusqlite::params![query, limit], |row| {
-                Ok(SymbolInfo {
-                    id: row.get(0)?,
-                    file: row.get(1)?,
-                    fq_name: row.get(2)?,
-                    kind: row.get(3)?,
-                    span_start_line: row.get(4)?,
-                    span_end_line: row.get(5)?,
-                    line_count: row.get(6)?,
-                    hash: row.get(7)?,
-                    docstring: row.get(8)?,
-                    language: row.get(9)?,
-                })
-            })?
+            .query_map(rusqlite::params![query, limit], symbol_from_row)?
             .filter_map(|r| r.ok())
             .collect();
 
@@ -191,7 +357,7 @@ pub fn find_symbols_fuzzy(query: &str, limit: i32) -> Result<FindSymbolsOutput>
         // Get candidate symbols with prefix filter
         let sql = r#"
             SELECT id, file, fq_name, kind, span_start_line, span_end_line,
-                   line_count, hash, docstring, language
+                   line_count, hash, docstring, language, complexity, last_indexed_at, decorators, type_params
             FROM symbols
             WHERE LOWER(fq_name) LIKE ?1 OR LOWER(fq_name) LIKE ?2
         "#;
@@ -201,20 +367,7 @@ pub fn find_symbols_fuzzy(query: &str, limit: i32) -> Result<FindSymbolsOutput>
 
         let mut stmt = conn.prepare(sql)?;
         let mut candidates: Vec<(SymbolInfo, f64)> = stmt
-            .query_map(rusqlite::params![prefix_pattern, contains_pattern], |row| {
-                Ok(SymbolInfo {
-                    id: row.get(0)?,
-                    file: row.get(1)?,
-                    fq_name: row.get(2)?,
-                    kind: row.get(3)?,
-                    span_start_line: row.get(4)?,
-                    span_end_line: row.get(5)?,
-                    line_count: row.get(6)?,
-                    hash: row.get(7)?,
-                    docstring: row.get(8)?,
-                    language: row.get(9)?,
-                })
-            })?
+            .query_map(rusqlite::params![prefix_pattern, contains_pattern], symbol_from_row)?
             .filter_map(|r| r.ok())
             .filter_map(|sym| {
                 // Score each symbol using Jaro-Winkler on the name part
@@ -245,17 +398,483 @@ pub fn find_symbols_fuzzy(query: &str, limit: i32) -> Result<FindSymbolsOutput>
     })
 }
 
+/// Semantic search: TF-IDF cosine similarity between the query and docstrings
+///
+/// IDF weights come from `symbol_tfidf_cache`, rebuilt here if it was cleared.
+pub fn search_symbols_semantic(query: &str, threshold: f64, limit: i32) -> Result<FindSymbolsOutput> {
+    with_db(|conn| {
+        let idf = load_or_build_idf(conn)?;
+        let query_vec = tfidf_vector(&tokenize(query), &idf);
+        if query_vec.is_empty() {
+            return Ok(FindSymbolsOutput {
+                symbols: Vec::new(),
+                total_count: 0,
+            });
+        }
+
+        let mut stmt = conn.prepare(
+            "SELECT id, file, fq_name, kind, span_start_line, span_end_line, line_count, hash, docstring, language, complexity, last_indexed_at, decorators, type_params
+             FROM symbols WHERE docstring IS NOT NULL AND docstring != ''",
+        )?;
+        let mut scored: Vec<(SymbolInfo, f64)> = stmt
+            .query_map([], symbol_from_row)?
+            .filter_map(|r| r.ok())
+            .filter_map(|sym| {
+                let doc_vec = tfidf_vector(&tokenize(sym.docstring.as_deref().unwrap_or("")), &idf);
+                let score = cosine_similarity(&query_vec, &doc_vec);
+                (score >= threshold).then_some((sym, score))
+            })
+            .collect();
+
+        scored.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+
+        let total_count = scored.len() as i32;
+        let symbols = scored.into_iter().take(limit as usize).map(|(s, _)| s).collect();
+
+        Ok(FindSymbolsOutput {
+            symbols,
+            total_count,
+        })
+    })
+}
+
+/// Clear cached IDF weights; call whenever symbols or docstrings change
+pub fn invalidate_tfidf_cache(conn: &Connection) -> Result<()> {
+    conn.execute("DELETE FROM symbol_tfidf_cache", [])?;
+    Ok(())
+}
+
+fn load_or_build_idf(conn: &Connection) -> Result<HashMap<String, f64>> {
+    let mut stmt = conn.prepare("SELECT term, idf FROM symbol_tfidf_cache")?;
+    let cached: HashMap<String, f64> = stmt
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect::<Result<_>>()?;
+    if !cached.is_empty() {
+        return Ok(cached);
+    }
+
+    let mut stmt = conn.prepare("SELECT docstring FROM symbols WHERE docstring IS NOT NULL AND docstring != ''")?;
+    let docs: Vec<String> = stmt.query_map([], |row| row.get(0))?.collect::<Result<_>>()?;
+
+    let mut doc_freq: HashMap<String, usize> = HashMap::new();
+    for doc in &docs {
+        let terms: HashSet<String> = tokenize(doc).into_iter().collect();
+        for term in terms {
+            *doc_freq.entry(term).or_default() += 1;
+        }
+    }
+
+    // Smoothed IDF so terms in every docstring still weigh a little
+    let n = docs.len() as f64;
+    let idf: HashMap<String, f64> = doc_freq
+        .into_iter()
+        .map(|(term, df)| (term, ((1.0 + n) / (1.0 + df as f64)).ln() + 1.0))
+        .collect();
+
+    let tx = conn.unchecked_transaction()?;
+    for (term, weight) in &idf {
+        tx.execute(
+            "INSERT OR REPLACE INTO symbol_tfidf_cache (term, idf) VALUES (?1, ?2)",
+            rusqlite::params![term, weight],
+        )?;
+    }
+    tx.commit()?;
+
+    Ok(idf)
+}
+
+/// Lowercased word tokens with stop words and single characters removed
+fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric() && c != '_')
+        .map(str::to_lowercase)
+        .filter(|t| t.chars().count() > 1 && !STOP_WORDS.contains(&t.as_str()))
+        .collect()
+}
+
+/// Term frequency times IDF; terms without an IDF weight are dropped
+fn tfidf_vector(tokens: &[String], idf: &HashMap<String, f64>) -> HashMap<String, f64> {
+    let mut vec: HashMap<String, f64> = HashMap::new();
+    for token in tokens {
+        if let Some(weight) = idf.get(token) {
+            *vec.entry(token.clone()).or_default() += weight;
+        }
+    }
+    vec
+}
+
+fn cosine_similarity(a: &HashMap<String, f64>, b: &HashMap<String, f64>) -> f64 {
+    let dot: f64 = a.iter().filter_map(|(term, x)| b.get(term).map(|y| x * y)).sum();
+    let norm = |v: &HashMap<String, f64>| v.values().map(|x| x * x).sum::<f64>().sqrt();
+    let denom = norm(a) * norm(b);
+    if denom == 0.0 {
+        0.0
+    } else {
+        dot / denom
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CrossRefOutput {
+    pub query: String,
+    /// Matching symbols grouped by language
+    pub matches: BTreeMap<String, Vec<SymbolInfo>>,
+    pub total_count: usize,
+}
+
+/// Base name with file, namespace, and case/separator conventions stripped,
+/// so `svc.py::UserService::get_user` and `svc.go::GetUser` both become `getuser`
+fn normalized_base_name(fq_name: &str) -> String {
+    let name = fq_name.rsplit("::").next().unwrap_or(fq_name);
+    let name = name.rsplit('.').next().unwrap_or(name);
+    name.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Find symbols in every language sharing the base name of `fq_name`
+pub fn find_cross_references(fq_name: &str) -> Result<CrossRefOutput> {
+    let target = normalized_base_name(fq_name);
+
+    with_db(|conn| {
+        let mut stmt = conn.prepare(
+            "SELECT id, file, fq_name, kind, span_start_line, span_end_line,
+                    line_count, hash, docstring, language, complexity, last_indexed_at, decorators, type_params
+             FROM symbols",
+        )?;
+
+        let mut scored: Vec<(SymbolInfo, f64)> = stmt
+            .query_map([], symbol_from_row)?
+            .filter_map(|r| r.ok())
+            .filter_map(|sym| {
+                let score = jaro_winkler(&normalized_base_name(&sym.fq_name), &target);
+                (score >= CROSS_REF_THRESHOLD).then_some((sym, score))
+            })
+            .collect();
+
+        scored.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+
+        let total_count = scored.len();
+        let mut matches: BTreeMap<String, Vec<SymbolInfo>> = BTreeMap::new();
+        for (sym, _) in scored {
+            let language = sym.language.clone().unwrap_or_else(|| "unknown".to_string());
+            matches.entry(language).or_default().push(sym);
+        }
+
+        Ok(CrossRefOutput {
+            query: fq_name.to_string(),
+            matches,
+            total_count,
+        })
+    })
+}
+
+// ============================================================================
+// Duplicate Detection
+// ============================================================================
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DuplicateGroup {
+    /// "exact" (identical hash) or "fuzzy" (similar bodies)
+    pub duplicate_type: String,
+    pub hash: Option<String>,
+    /// Lowest pairwise body similarity within a fuzzy group
+    pub similarity: Option<f64>,
+    pub count: usize,
+    pub symbols: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DuplicatesOutput {
+    pub duplicate_groups: Vec<DuplicateGroup>,
+    pub total_count: usize,
+}
+
+/// Find exact (same hash) and fuzzy (similar body) duplicate symbols
+pub fn find_duplicate_symbols(workspace_root: &Path) -> Result<DuplicatesOutput> {
+    let (mut groups, candidates) = with_db(|conn| {
+        let mut stmt = conn.prepare(
+            "SELECT DISTINCT hash, fq_name FROM symbols
+             WHERE language IS NOT ?1
+             ORDER BY hash, fq_name",
+        )?;
+        let mut by_hash: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for row in stmt.query_map([VIRTUAL_LANGUAGE], |row| Ok((row.get(0)?, row.get(1)?)))? {
+            let (hash, fq_name): (String, String) = row?;
+            by_hash.entry(hash).or_default().push(fq_name);
+        }
+
+        let exact: Vec<DuplicateGroup> = by_hash
+            .into_iter()
+            .filter(|(_, names)| names.len() > 1)
+            .map(|(hash, names)| DuplicateGroup {
+                duplicate_type: "exact".to_string(),
+                hash: Some(hash),
+                similarity: None,
+                count: names.len(),
+                symbols: names,
+            })
+            .collect();
+
+        let mut stmt = conn.prepare(
+            "SELECT DISTINCT fq_name, file, span_start_line, span_end_line, hash, language, kind
+             FROM symbols
+             WHERE language IS NOT ?1 AND line_count >= ?2",
+        )?;
+        let candidates: Vec<DuplicateCandidate> = stmt
+            .query_map(rusqlite::params![VIRTUAL_LANGUAGE, DUPLICATE_MIN_LINES], |row| {
+                Ok(DuplicateCandidate {
+                    fq_name: row.get(0)?,
+                    file: row.get(1)?,
+                    start: row.get(2)?,
+                    end: row.get(3)?,
+                    hash: row.get(4)?,
+                    group: (row.get::<_, Option<String>>(5)?, row.get(6)?),
+                })
+            })?
+            .filter_map(|r| r.ok())
+            .collect();
+
+        Ok((exact, candidates))
+    })?;
+
+    groups.extend(find_fuzzy_duplicates(candidates, workspace_root));
+    groups.sort_by_key(|g| std::cmp::Reverse(g.count));
+
+    Ok(DuplicatesOutput {
+        total_count: groups.len(),
+        duplicate_groups: groups,
+    })
+}
+
+struct DuplicateCandidate {
+    fq_name: String,
+    file: String,
+    start: usize,
+    end: usize,
+    hash: String,
+    group: DuplicateBucket,
+}
+
+/// (language, kind) - only symbols in the same bucket are compared
+type DuplicateBucket = (Option<String>, String);
+
+fn find_fuzzy_duplicates(candidates: Vec<DuplicateCandidate>, workspace_root: &Path) -> Vec<DuplicateGroup> {
+    let mut files: HashMap<String, Option<Vec<String>>> = HashMap::new();
+    let mut buckets: HashMap<DuplicateBucket, Vec<(DuplicateCandidate, String)>> = HashMap::new();
+
+    for candidate in candidates {
+        let lines = files.entry(candidate.file.clone()).or_insert_with(|| {
+            std::fs::read_to_string(workspace_root.join(&candidate.file))
+                .ok()
+                .map(|c| c.lines().map(String::from).collect())
+        });
+        let Some(lines) = lines else { continue };
+        if candidate.start == 0 || candidate.end > lines.len() {
+            continue;
+        }
+        let body = lines[candidate.start - 1..candidate.end].join("\n");
+        buckets.entry(candidate.group.clone()).or_default().push((candidate, body));
+    }
+
+    let mut groups = Vec::new();
+    for (_, mut bucket) in buckets {
+        // Sort by body length so each symbol only compares against similarly sized ones
+        bucket.sort_by_key(|(_, body)| body.len());
+
+        let mut parent: Vec<usize> = (0..bucket.len()).collect();
+        let mut min_score: HashMap<usize, f64> = HashMap::new();
+
+        for i in 0..bucket.len() {
+            for j in i + 1..bucket.len() {
+                let (a, a_body) = &bucket[i];
+                let (b, b_body) = &bucket[j];
+                if b_body.len() as f64 > a_body.len() as f64 * 1.25 {
+                    break;
+                }
+                if a.hash == b.hash {
+                    continue; // Already reported as an exact duplicate
+                }
+                let score = jaro_winkler(a_body, b_body);
+                if score > DUPLICATE_THRESHOLD {
+                    let (ra, rb) = (find_root(&mut parent, i), find_root(&mut parent, j));
+                    let merged = min_score
+                        .remove(&ra)
+                        .unwrap_or(1.0)
+                        .min(min_score.remove(&rb).unwrap_or(1.0))
+                        .min(score);
+                    parent[rb] = ra;
+                    min_score.insert(ra, merged);
+                }
+            }
+        }
+
+        let mut members: BTreeMap<usize, Vec<String>> = BTreeMap::new();
+        for (i, (candidate, _)) in bucket.iter().enumerate() {
+            let root = find_root(&mut parent, i);
+            members.entry(root).or_default().push(candidate.fq_name.clone());
+        }
+
+        for (root, mut names) in members {
+            if names.len() < 2 {
+                continue;
+            }
+            names.sort();
+            groups.push(DuplicateGroup {
+                duplicate_type: "fuzzy".to_string(),
+                hash: None,
+                similarity: min_score.get(&root).map(|s| (s * 1000.0).round() / 1000.0),
+                count: names.len(),
+                symbols: names,
+            });
+        }
+    }
+
+    groups
+}
+
+fn find_root(parent: &mut [usize], mut i: usize) -> usize {
+    while parent[i] != i {
+        parent[i] = parent[parent[i]];
+        i = parent[i];
+    }
+    i
+}
+
+// ============================================================================
+// Hash Snapshots
+// ============================================================================
+
+/// Capture the current hash of every symbol, keyed by fq_name
+pub fn snapshot_symbol_hashes(conn: &Connection) -> Result<HashMap<String, String>> {
+    let mut stmt = conn.prepare("SELECT fq_name, hash FROM symbols")?;
+    let hashes = stmt
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .filter_map(|r| r.ok())
+        .collect();
+    Ok(hashes)
+}
+
+/// Persist a hash snapshot so a later `symbols --changed` can diff against it
+pub fn save_symbol_snapshot(conn: &Connection, hashes: &HashMap<String, String>) -> Result<()> {
+    conn.execute("DELETE FROM symbol_hash_snapshot", [])?;
+    let mut stmt = conn.prepare("INSERT INTO symbol_hash_snapshot (fq_name, hash) VALUES (?1, ?2)")?;
+    for (fq_name, hash) in hashes {
+        stmt.execute([fq_name, hash])?;
+    }
+    Ok(())
+}
+
+/// Load the snapshot saved by the last index run
+pub fn load_symbol_snapshot(conn: &Connection) -> Result<HashMap<String, String>> {
+    let mut stmt = conn.prepare("SELECT fq_name, hash FROM symbol_hash_snapshot")?;
+    let hashes = stmt
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .filter_map(|r| r.ok())
+        .collect();
+    Ok(hashes)
+}
+
+/// Compare an old hash snapshot against the current symbols table
+pub fn diff_symbol_hashes(old: &HashMap<String, String>, conn: &Connection) -> Result<Vec<SymbolDiff>> {
+    let current = snapshot_symbol_hashes(conn)?;
+    let mut diffs = Vec::new();
+
+    for (fq_name, new_hash) in &current {
+        match old.get(fq_name) {
+            None => diffs.push(SymbolDiff {
+                fq_name: fq_name.clone(),
+                change_type: "added".to_string(),
+                old_hash: None,
+                new_hash: Some(new_hash.clone()),
+            }),
+            Some(old_hash) if old_hash != new_hash => diffs.push(SymbolDiff {
+                fq_name: fq_name.clone(),
+                change_type: "modified".to_string(),
+                old_hash: Some(old_hash.clone()),
+                new_hash: Some(new_hash.clone()),
+            }),
+            Some(_) => {}
+        }
+    }
+
+    for (fq_name, old_hash) in old {
+        if !current.contains_key(fq_name) {
+            diffs.push(SymbolDiff {
+                fq_name: fq_name.clone(),
+                change_type: "deleted".to_string(),
+                old_hash: Some(old_hash.clone()),
+                new_hash: None,
+            });
+        }
+    }
+
+    diffs.sort_by(|a, b| a.fq_name.cmp(&b.fq_name));
+    Ok(diffs)
+}
+
+/// Symbols that changed during the most recent index run
+pub fn find_changed_symbols() -> Result<SymbolDiffOutput> {
+    with_db(|conn| {
+        let old = load_symbol_snapshot(conn)?;
+        let changes = diff_symbol_hashes(&old, conn)?;
+        Ok(SymbolDiffOutput {
+            total_count: changes.len() as i32,
+            changes,
+        })
+    })
+}
+
+/// Register a virtual symbol that tree-sitter can't extract (e.g. runtime-generated)
+///
+/// Virtual symbols use `language = 'virtual'` and `hash = 'manual'` so that
+/// re-indexing leaves them alone.
+pub fn register_virtual_symbol(
+    fq_name: &str,
+    file: &str,
+    kind: &str,
+    description: Option<&str>,
+) -> Result<RegisterSymbolOutput> {
+    with_db(|conn| {
+        let exists = conn
+            .query_row("SELECT 1 FROM symbols WHERE fq_name = ?1", [fq_name], |_| Ok(true))
+            .unwrap_or(false);
+
+        if exists {
+            return Ok(RegisterSymbolOutput {
+                success: false,
+                fq_name: fq_name.to_string(),
+                message: format!("Symbol {} already exists", fq_name),
+            });
+        }
+
+        conn.execute(
+            "INSERT INTO symbols (file, fq_name, kind, span_start_line, span_end_line, line_count, hash, docstring, language) VALUES (?1, ?2, ?3, 0, 0, 0, 'manual', ?4, ?5)",
+            rusqlite::params![file, fq_name, kind, description, VIRTUAL_LANGUAGE],
+        )?;
+        invalidate_tfidf_cache(conn)?;
+
+        Ok(RegisterSymbolOutput {
+            success: true,
+            fq_name: fq_name.to_string(),
+            message: format!("Registered virtual symbol {}", fq_name),
+        })
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::db::{close_db, init_db};
+    use crate::db::{close_db, init_db, test_lock};
+    use std::sync::MutexGuard;
     use tempfile::tempdir;
 
-    fn setup_test_db() -> tempfile::TempDir {
+    fn setup_test_db() -> (tempfile::TempDir, MutexGuard<'static, ()>) {
+        let lock = test_lock();
         let dir = tempdir().unwrap();
         let db_path = dir.path().join("test.db");
         init_db(Some(db_path.to_str().unwrap()), true).unwrap();
-        dir
+        (dir, lock)
     }
 
     #[test]
@@ -263,13 +882,8 @@ mod tests {
         let _dir = setup_test_db();
 
         let input = FindSymbolsInput {
-            pattern: None,
-            kind: None,
-            file: None,
-            language: None,
             limit: Some(10),
-            search: None,
-            fuzzy: false,
+            ..Default::default()
         };
 
         let result = find_symbols(&input).unwrap();
@@ -278,4 +892,337 @@ mod tests {
 
         close_db();
     }
+
+    #[test]
+    fn test_register_virtual_symbol() {
+        let _dir = setup_test_db();
+
+        let result =
+            register_virtual_symbol("plugins::AuthHandler", "src/plugins.py", "class", Some("Registered at runtime"))
+                .unwrap();
+        assert!(result.success);
+
+        // Registering the same symbol twice is rejected
+        let result = register_virtual_symbol("plugins::AuthHandler", "src/plugins.py", "class", None).unwrap();
+        assert!(!result.success);
+
+        let input = FindSymbolsInput {
+            pattern: Some("plugins::*".to_string()),
+            limit: Some(10),
+            ..Default::default()
+        };
+        let result = find_symbols(&input).unwrap();
+        assert_eq!(result.total_count, 1);
+        assert!(result.symbols[0].is_virtual);
+        assert_eq!(result.symbols[0].docstring.as_deref(), Some("Registered at runtime"));
+
+        close_db();
+    }
+
+    #[test]
+    fn test_find_symbols_by_package() {
+        let _dir = setup_test_db();
+
+        with_db(|conn| {
+            for (file, language) in [
+                ("internal/auth/token.go", "go"),
+                ("internal/authz/policy.go", "go"),
+                ("app/auth/views.py", "python"),
+                ("app/auth.py", "python"),
+                ("src/auth/mod.rs", "rust"),
+                ("src/auth.rs", "rust"),
+                ("tests/auth/mod.rs", "rust"),
+            ] {
+                conn.execute(
+                    "INSERT INTO symbols (file, fq_name, kind, span_start_line, span_end_line, line_count, hash, language) VALUES (?1, ?1 || '::f', 'function', 1, 1, 1, 'h', ?2)",
+                    [file, language],
+                )?;
+            }
+            Ok(())
+        })
+        .unwrap();
+
+        let input = FindSymbolsInput {
+            package: Some("auth".to_string()),
+            ..Default::default()
+        };
+        let result = find_symbols(&input).unwrap();
+        let files: Vec<&str> = result.symbols.iter().map(|s| s.file.as_str()).collect();
+        assert_eq!(
+            files,
+            vec!["app/auth.py", "app/auth/views.py", "internal/auth/token.go", "src/auth.rs", "src/auth/mod.rs"]
+        );
+
+        // Dotted Python packages map to directories or module files
+        let input = FindSymbolsInput {
+            package: Some("app.auth".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(find_symbols(&input).unwrap().total_count, 2);
+
+        close_db();
+    }
+
+    #[test]
+    fn test_find_symbols_in_bead() {
+        let _dir = setup_test_db();
+
+        with_db(|conn| {
+            for name in ["a.rs::one", "a.rs::two"] {
+                conn.execute(
+                    "INSERT INTO symbols (file, fq_name, kind, span_start_line, span_end_line, line_count, hash, language) VALUES ('a.rs', ?1, 'function', 1, 1, 1, 'h', 'rust')",
+                    [name],
+                )?;
+            }
+            Ok(())
+        })
+        .unwrap();
+
+        let result = crate::tools::register_bead_symbol("BEAD-1", "a.rs::two", "modifies").unwrap();
+        assert!(result.success);
+        let again = crate::tools::register_bead_symbol("BEAD-1", "a.rs::two", "modifies").unwrap();
+        assert!(!again.success);
+
+        let input = FindSymbolsInput {
+            in_bead: Some("BEAD-1".to_string()),
+            ..Default::default()
+        };
+        let result = find_symbols(&input).unwrap();
+        assert_eq!(result.total_count, 1);
+        assert_eq!(result.symbols[0].fq_name, "a.rs::two");
+
+        close_db();
+    }
+
+    #[test]
+    fn test_find_symbols_recently_changed() {
+        let _dir = setup_test_db();
+
+        with_db(|conn| {
+            conn.execute(
+                "INSERT INTO claims (bead_id, agent_id, worktree_path, branch_name, start_commit, claimed_at) VALUES ('BEAD-1', 'a', '/wt', 'b', 'c', 1000)",
+                [],
+            )?;
+            for (name, indexed_at) in [("a.rs::old", 500), ("a.rs::new", 2000)] {
+                conn.execute(
+                    "INSERT INTO symbols (file, fq_name, kind, span_start_line, span_end_line, line_count, hash, language, last_indexed_at) VALUES ('a.rs', ?1, 'function', 1, 1, 1, 'h', 'rust', ?2)",
+                    rusqlite::params![name, indexed_at],
+                )?;
+            }
+            Ok(())
+        })
+        .unwrap();
+
+        let input = FindSymbolsInput {
+            changed_since_claim: Some("BEAD-1".to_string()),
+            ..Default::default()
+        };
+        let result = find_symbols(&input).unwrap();
+        assert_eq!(result.total_count, 1);
+        assert_eq!(result.symbols[0].fq_name, "a.rs::new");
+        assert_eq!(result.symbols[0].indexed_at.as_deref(), Some("1970-01-01T00:00:02+00:00"));
+
+        close_db();
+    }
+
+    #[test]
+    fn test_find_symbols_by_decorator() {
+        let _dir = setup_test_db();
+
+        with_db(|conn| {
+            for (name, kind, decorators) in [
+                ("m.py::A::size", "method", Some(r#"["functools.cached_property"]"#)),
+                ("m.py::A::name", "method", Some(r#"["property"]"#)),
+                ("m.py::A::plain", "method", None),
+                ("m.py::cached_property", "function", None),
+            ] {
+                conn.execute(
+                    "INSERT INTO symbols (file, fq_name, kind, span_start_line, span_end_line, line_count, hash, language, decorators) VALUES ('m.py', ?1, ?2, 1, 1, 1, 'h', 'python', ?3)",
+                    rusqlite::params![name, kind, decorators],
+                )?;
+            }
+            Ok(())
+        })
+        .unwrap();
+
+        let find = |decorator: &str| {
+            let input = FindSymbolsInput {
+                kind: Some("method".to_string()),
+                decorator: Some(decorator.to_string()),
+                ..Default::default()
+            };
+            let result = find_symbols(&input).unwrap();
+            result.symbols.into_iter().map(|s| s.fq_name).collect::<Vec<_>>()
+        };
+
+        assert_eq!(find("cached_property"), vec!["m.py::A::size"]);
+        assert_eq!(find("functools.cached_property"), vec!["m.py::A::size"]);
+        assert_eq!(find("property"), vec!["m.py::A::name"]);
+        assert!(find("staticmethod").is_empty());
+
+        close_db();
+    }
+
+    #[test]
+    fn test_find_symbols_by_type_param() {
+        let _dir = setup_test_db();
+
+        with_db(|conn| {
+            for (name, type_params) in [
+                ("a.rs::process", Some(r#"["T: Trait", "'a"]"#)),
+                ("a.ts::wrap", Some(r#"["TValue extends object"]"#)),
+                ("a.rs::plain", None),
+            ] {
+                conn.execute(
+                    "INSERT INTO symbols (file, fq_name, kind, span_start_line, span_end_line, line_count, hash, language, type_params) VALUES ('a.rs', ?1, 'function', 1, 1, 1, 'h', 'rust', ?2)",
+                    rusqlite::params![name, type_params],
+                )?;
+            }
+            Ok(())
+        })
+        .unwrap();
+
+        let find = |param: &str| {
+            let input = FindSymbolsInput {
+                has_type_param: Some(param.to_string()),
+                ..Default::default()
+            };
+            find_symbols(&input).unwrap().symbols.into_iter().map(|s| s.fq_name).collect::<Vec<_>>()
+        };
+
+        assert_eq!(find("T"), vec!["a.rs::process"]);
+        assert_eq!(find("'a"), vec!["a.rs::process"]);
+        assert_eq!(find("TValue"), vec!["a.ts::wrap"]);
+        assert!(find("U").is_empty());
+
+        close_db();
+    }
+
+    #[test]
+    fn test_find_cross_references() {
+        let _dir = setup_test_db();
+
+        with_db(|conn| {
+            for (file, fq_name, language) in [
+                ("svc/user.py", "svc/user.py::UserService::get_user", "python"),
+                ("svc/user.go", "svc/user.go::GetUser", "go"),
+                ("src/user.rs", "src/user.rs::get_user", "rust"),
+                ("src/user.rs", "src/user.rs::get_users_page", "rust"),
+                ("svc/user.go", "svc/user.go::DeleteUser", "go"),
+            ] {
+                conn.execute(
+                    "INSERT INTO symbols (file, fq_name, kind, span_start_line, span_end_line, line_count, hash, language) VALUES (?1, ?2, 'function', 1, 1, 1, 'h', ?3)",
+                    [file, fq_name, language],
+                )?;
+            }
+            Ok(())
+        })
+        .unwrap();
+
+        let result = find_cross_references("src/user.rs::get_user").unwrap();
+        assert_eq!(result.total_count, 3);
+        assert_eq!(result.matches["go"][0].fq_name, "svc/user.go::GetUser");
+        assert_eq!(result.matches["python"].len(), 1);
+        assert_eq!(result.matches["rust"].len(), 1);
+
+        close_db();
+    }
+
+    #[test]
+    fn test_find_duplicate_symbols() {
+        let dir = setup_test_db();
+        let root = dir.0.path();
+
+        let body = "fn total(items: &[u32]) -> u32 {\n    let mut sum = 0;\n    for i in items {\n        sum += i;\n    }\n    sum\n}\n";
+        let near = body.replace("total", "totals");
+        std::fs::write(root.join("a.rs"), body).unwrap();
+        std::fs::write(root.join("b.rs"), body).unwrap();
+        std::fs::write(root.join("c.rs"), &near).unwrap();
+
+        with_db(|conn| {
+            for (file, hash) in [("a.rs", "h1"), ("b.rs", "h1"), ("c.rs", "h2")] {
+                conn.execute(
+                    "INSERT INTO symbols (file, fq_name, kind, span_start_line, span_end_line, line_count, hash, language) VALUES (?1, ?1 || '::total', 'function', 1, 7, 7, ?2, 'rust')",
+                    [file, hash],
+                )?;
+            }
+            Ok(())
+        })
+        .unwrap();
+
+        let result = find_duplicate_symbols(root).unwrap();
+        let exact: Vec<_> = result.duplicate_groups.iter().filter(|g| g.duplicate_type == "exact").collect();
+        let fuzzy: Vec<_> = result.duplicate_groups.iter().filter(|g| g.duplicate_type == "fuzzy").collect();
+
+        assert_eq!(exact.len(), 1);
+        assert_eq!(exact[0].symbols, vec!["a.rs::total", "b.rs::total"]);
+        assert_eq!(fuzzy.len(), 1);
+        assert!(fuzzy[0].symbols.contains(&"c.rs::total".to_string()));
+
+        close_db();
+    }
+
+    #[test]
+    fn test_diff_symbol_hashes() {
+        let conn = Connection::open_in_memory().unwrap();
+        crate::db::apply_migrations(&conn, true).unwrap();
+
+        let insert = |fq_name: &str, hash: &str| {
+            conn.execute(
+                "INSERT INTO symbols (file, fq_name, kind, span_start_line, span_end_line, line_count, hash, language) VALUES ('a.rs', ?1, 'function', 1, 1, 1, ?2, 'rust')",
+                [fq_name, hash],
+            )
+            .unwrap();
+        };
+        insert("a.rs::kept", "h1");
+        insert("a.rs::changed", "h2");
+        insert("a.rs::removed", "h3");
+
+        let old = snapshot_symbol_hashes(&conn).unwrap();
+
+        conn.execute("DELETE FROM symbols WHERE fq_name = 'a.rs::removed'", []).unwrap();
+        conn.execute("UPDATE symbols SET hash = 'h2b' WHERE fq_name = 'a.rs::changed'", []).unwrap();
+        insert("a.rs::new", "h4");
+
+        let diffs = diff_symbol_hashes(&old, &conn).unwrap();
+        let change = |name: &str| diffs.iter().find(|d| d.fq_name == name).map(|d| d.change_type.as_str());
+        assert_eq!(diffs.len(), 3);
+        assert_eq!(change("a.rs::changed"), Some("modified"));
+        assert_eq!(change("a.rs::removed"), Some("deleted"));
+        assert_eq!(change("a.rs::new"), Some("added"));
+    }
+
+    #[test]
+    fn test_semantic_search() {
+        let _dir = setup_test_db();
+
+        register_virtual_symbol("auth::login", "src/auth.py", "function", Some("Authenticate a user with a password")).unwrap();
+        register_virtual_symbol("auth::logout", "src/auth.py", "function", Some("End the user session")).unwrap();
+        register_virtual_symbol("db::connect", "src/db.py", "function", Some("Open a database connection")).unwrap();
+
+        let input = FindSymbolsInput {
+            semantic_search: Some("user password".to_string()),
+            limit: Some(10),
+            ..Default::default()
+        };
+        let result = find_symbols(&input).unwrap();
+        assert_eq!(result.symbols[0].fq_name, "auth::login");
+        assert_eq!(result.total_count, 2);
+
+        // Weights are cached, and new symbols invalidate the cache
+        let cached: i64 = with_db(|conn| conn.query_row("SELECT COUNT(*) FROM symbol_tfidf_cache", [], |r| r.get(0))).unwrap();
+        assert!(cached > 0);
+        register_virtual_symbol("db::migrate", "src/db.py", "function", Some("Apply database migrations")).unwrap();
+        let cached: i64 = with_db(|conn| conn.query_row("SELECT COUNT(*) FROM symbol_tfidf_cache", [], |r| r.get(0))).unwrap();
+        assert_eq!(cached, 0);
+
+        // Stop words alone match nothing
+        let input = FindSymbolsInput {
+            semantic_search: Some("the and of".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(find_symbols(&input).unwrap().total_count, 0);
+
+        close_db();
+    }
 }
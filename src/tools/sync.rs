@@ -0,0 +1,75 @@
+//! Sync tool - merge the latest origin/<target branch> into a bead's worktree
+//!
+//! The merge-based alternative to `rebase`. The claim itself is untouched, so
+//! syncing doesn't reset stale detection.
+
+use crate::config::DEFAULT_TARGET_BRANCH;
+use crate::db::with_db;
+use crate::worktree::{self, SyncOutcome};
+use rusqlite::OptionalExtension;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SyncOutput {
+    pub success: bool,
+    pub bead_id: String,
+    pub commits_pulled: usize,
+    pub conflicts: Vec<String>,
+    pub message: String,
+}
+
+pub fn sync_bead(bead_id: &str, workspace_root: &Path) -> Result<SyncOutput, Box<dyn std::error::Error>> {
+    let claim_target: Option<Option<String>> = with_db(|conn| {
+        conn.query_row(
+            "SELECT target_branch FROM claims WHERE bead_id = ?1",
+            [bead_id],
+            |row| row.get(0),
+        )
+        .optional()
+    })?;
+
+    let Some(claim_target) = claim_target else {
+        return Ok(SyncOutput {
+            success: false,
+            bead_id: bead_id.to_string(),
+            commits_pulled: 0,
+            conflicts: Vec::new(),
+            message: format!("No claim found for {}", bead_id),
+        });
+    };
+
+    let target_branch = claim_target.unwrap_or_else(|| DEFAULT_TARGET_BRANCH.to_string());
+    let upstream = format!("origin/{}", target_branch);
+
+    let output = match worktree::sync_worktree(workspace_root, bead_id, &target_branch)? {
+        SyncOutcome::Merged(0) => SyncOutput {
+            success: true,
+            bead_id: bead_id.to_string(),
+            commits_pulled: 0,
+            conflicts: Vec::new(),
+            message: format!("{} is already up to date with {}", bead_id, upstream),
+        },
+        SyncOutcome::Merged(commits) => SyncOutput {
+            success: true,
+            bead_id: bead_id.to_string(),
+            commits_pulled: commits,
+            conflicts: Vec::new(),
+            message: format!("Merged {} commit(s) from {} into {}", commits, upstream, bead_id),
+        },
+        SyncOutcome::Conflicts(files) => SyncOutput {
+            success: false,
+            bead_id: bead_id.to_string(),
+            commits_pulled: 0,
+            message: format!(
+                "Merging {} stopped on conflicts in: {}. Resolve them in .bacchus/worktrees/{} and commit, or run `git merge --abort` there to roll back.",
+                upstream,
+                files.join(", "),
+                bead_id
+            ),
+            conflicts: files,
+        },
+    };
+
+    Ok(output)
+}
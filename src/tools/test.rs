@@ -0,0 +1,301 @@
+//! Test tool - run the tests relevant to a bead's changes in its worktree
+//!
+//! The runner is picked from the manifest in the workspace root. Changed files
+//! are everything that differs from the claim's start commit (or HEAD when the
+//! bead is unclaimed), so both committed and uncommitted work count. Rust
+//! changes narrow the run to one `cargo test <module>` per changed module;
+//! other runners scope to changed packages where they can, else run everything.
+
+use crate::db::with_db;
+use crate::worktree;
+use rusqlite::OptionalExtension;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::Instant;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TestOutput {
+    pub success: bool,
+    pub bead_id: String,
+    /// `cargo`, `pytest`, `npm`, `jest`, `go`, or `none`
+    pub test_runner: String,
+    pub passed: usize,
+    pub failed: usize,
+    pub duration_secs: f64,
+    /// Combined stdout and stderr of every invocation
+    pub test_output: String,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Runner {
+    Cargo,
+    Pytest,
+    Npm,
+    Jest,
+    Go,
+}
+
+impl Runner {
+    fn name(self) -> &'static str {
+        match self {
+            Runner::Cargo => "cargo",
+            Runner::Pytest => "pytest",
+            Runner::Npm => "npm",
+            Runner::Jest => "jest",
+            Runner::Go => "go",
+        }
+    }
+}
+
+/// Run the tests covering a bead's changed files inside its worktree
+pub fn test_bead(bead_id: &str, workspace_root: &Path) -> Result<TestOutput, Box<dyn std::error::Error>> {
+    let (worktree_path, base) = bead_worktree(bead_id, workspace_root)?;
+
+    let Some(runner) = detect_runner(workspace_root) else {
+        return Ok(TestOutput {
+            success: false,
+            bead_id: bead_id.to_string(),
+            test_runner: "none".to_string(),
+            passed: 0,
+            failed: 0,
+            duration_secs: 0.0,
+            test_output: String::new(),
+            message: "No test runner detected (expected Cargo.toml, pyproject.toml, package.json, or go.mod)"
+                .to_string(),
+        });
+    };
+
+    let changed = changed_files(&worktree_path, &base)?;
+    let invocations = test_commands(runner, &changed);
+
+    let started = Instant::now();
+    let mut success = true;
+    let mut test_output = String::new();
+    for args in &invocations {
+        let output = Command::new(args[0].as_str())
+            .args(&args[1..])
+            .current_dir(&worktree_path)
+            .output()
+            .map_err(|e| format!("Failed to run {}: {}", args[0], e))?;
+        success &= output.status.success();
+        test_output.push_str(&format!("$ {}\n", args.join(" ")));
+        test_output.push_str(&String::from_utf8_lossy(&output.stdout));
+        test_output.push_str(&String::from_utf8_lossy(&output.stderr));
+    }
+    let duration_secs = started.elapsed().as_secs_f64();
+    let (passed, failed) = parse_counts(runner, &test_output);
+
+    let message = if success {
+        format!("{} passed in {:.1}s ({} changed files)", passed, duration_secs, changed.len())
+    } else {
+        format!("Tests failed for {}: {} passed, {} failed", bead_id, passed, failed)
+    };
+
+    Ok(TestOutput {
+        success,
+        bead_id: bead_id.to_string(),
+        test_runner: runner.name().to_string(),
+        passed,
+        failed,
+        duration_secs,
+        test_output,
+        message,
+    })
+}
+
+/// A bead's worktree and the commit its changes are measured from
+///
+/// The claim's worktree and start commit when claimed, else the default
+/// worktree location measured against its HEAD.
+pub(super) fn bead_worktree(bead_id: &str, workspace_root: &Path) -> Result<(PathBuf, String), Box<dyn std::error::Error>> {
+    let claim: Option<(String, String)> = with_db(|conn| {
+        conn.query_row(
+            "SELECT worktree_path, start_commit FROM claims WHERE bead_id = ?1",
+            [bead_id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .optional()
+    })?;
+    let (worktree_path, base) = match claim {
+        Some((path, start_commit)) => (PathBuf::from(path), start_commit),
+        None => (worktree::get_worktrees_dir(workspace_root).join(bead_id), "HEAD".to_string()),
+    };
+    if !worktree_path.is_dir() {
+        return Err(format!("No worktree for {} at {}", bead_id, worktree_path.display()).into());
+    }
+    Ok((worktree_path, base))
+}
+
+fn detect_runner(workspace_root: &Path) -> Option<Runner> {
+    if workspace_root.join("Cargo.toml").is_file() {
+        return Some(Runner::Cargo);
+    }
+    if workspace_root.join("pyproject.toml").is_file() {
+        return Some(Runner::Pytest);
+    }
+    if let Ok(manifest) = std::fs::read_to_string(workspace_root.join("package.json")) {
+        let has_test_script = serde_json::from_str::<serde_json::Value>(&manifest)
+            .ok()
+            .is_some_and(|v| v["scripts"]["test"].is_string());
+        return Some(if has_test_script { Runner::Npm } else { Runner::Jest });
+    }
+    if workspace_root.join("go.mod").is_file() {
+        return Some(Runner::Go);
+    }
+    None
+}
+
+/// Files that differ from `base`, including uncommitted and untracked ones
+pub(super) fn changed_files(worktree_path: &Path, base: &str) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    let mut files = BTreeSet::new();
+    for args in [
+        vec!["diff", "--name-only", base],
+        vec!["ls-files", "--others", "--exclude-standard"],
+    ] {
+        let output = Command::new("git").args(&args).current_dir(worktree_path).output()?;
+        if !output.status.success() {
+            return Err(format!("git {} failed: {}", args.join(" "), String::from_utf8_lossy(&output.stderr).trim()).into());
+        }
+        files.extend(String::from_utf8_lossy(&output.stdout).lines().map(String::from));
+    }
+    Ok(files.into_iter().collect())
+}
+
+/// Commands to run, each as program followed by its arguments
+fn test_commands(runner: Runner, changed: &[String]) -> Vec<Vec<String>> {
+    let command = |args: &[&str]| args.iter().map(|a| a.to_string()).collect::<Vec<_>>();
+    match runner {
+        Runner::Cargo => {
+            let modules = rust_modules(changed);
+            if modules.is_empty() {
+                vec![command(&["cargo", "test"])]
+            } else {
+                modules.iter().map(|m| command(&["cargo", "test", m])).collect()
+            }
+        }
+        Runner::Pytest => vec![command(&["pytest"])],
+        Runner::Npm => vec![command(&["npm", "test"])],
+        Runner::Jest => vec![command(&["npx", "jest"])],
+        Runner::Go => {
+            let packages: BTreeSet<String> = changed
+                .iter()
+                .filter(|f| f.ends_with(".go"))
+                .map(|f| match Path::new(f).parent().and_then(|p| p.to_str()) {
+                    Some("") | None => ".".to_string(),
+                    Some(dir) => format!("./{}", dir),
+                })
+                .collect();
+            let mut args = command(&["go", "test"]);
+            if packages.is_empty() {
+                args.push("./...".to_string());
+            } else {
+                args.extend(packages);
+            }
+            vec![args]
+        }
+    }
+}
+
+/// Test filters for changed Rust files: `src/tools/queue.rs` -> `tools::queue`
+///
+/// Empty when any change can't be narrowed to a module (a crate root, a build
+/// script, an integration test), so the whole suite runs instead.
+fn rust_modules(changed: &[String]) -> Vec<String> {
+    let mut modules = BTreeSet::new();
+    for file in changed.iter().filter(|f| f.ends_with(".rs")) {
+        let Some(module_path) = file
+            .split_once("src/")
+            .map(|(_, rest)| rest.trim_end_matches(".rs"))
+        else {
+            return Vec::new();
+        };
+        let segments: Vec<&str> = module_path
+            .split('/')
+            .filter(|s| *s != "mod")
+            .collect();
+        if segments.is_empty() || matches!(segments.as_slice(), ["main"] | ["lib"]) {
+            return Vec::new();
+        }
+        modules.insert(segments.join("::"));
+    }
+    modules.into_iter().collect()
+}
+
+/// Sum pass/fail counts from runner summary lines
+///
+/// Handles `N passed; M failed` (cargo), `N passed, M failed` (pytest), and
+/// `Tests: M failed, N passed` (jest); go reports per package, so those are
+/// `ok`/`FAIL` package lines.
+fn parse_counts(runner: Runner, output: &str) -> (usize, usize) {
+    if runner == Runner::Go {
+        let passed = output.lines().filter(|l| l.starts_with("ok ")).count();
+        let failed = output.lines().filter(|l| l.starts_with("FAIL\t")).count();
+        return (passed, failed);
+    }
+
+    let summary = |line: &str| match runner {
+        Runner::Cargo => line.starts_with("test result:"),
+        Runner::Npm | Runner::Jest => line.trim_start().starts_with("Tests:"),
+        _ => line.contains(" passed") || line.contains(" failed"),
+    };
+    let (mut passed, mut failed) = (0, 0);
+    for line in output.lines().filter(|l| summary(l)) {
+        let words: Vec<&str> = line.split(|c: char| c.is_whitespace() || c == ',' || c == ';').collect();
+        for pair in words.windows(2) {
+            let Ok(n) = pair[0].parse::<usize>() else { continue };
+            match pair[1] {
+                "passed" => passed += n,
+                "failed" => failed += n,
+                _ => {}
+            }
+        }
+    }
+    (passed, failed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_detect_runner_and_commands() {
+        let dir = tempdir().unwrap();
+        assert_eq!(detect_runner(dir.path()), None);
+        std::fs::write(dir.path().join("package.json"), r#"{"scripts": {"test": "jest"}}"#).unwrap();
+        assert_eq!(detect_runner(dir.path()), Some(Runner::Npm));
+        std::fs::write(dir.path().join("Cargo.toml"), "").unwrap();
+        assert_eq!(detect_runner(dir.path()), Some(Runner::Cargo));
+
+        let changed = vec![
+            "src/tools/queue.rs".to_string(),
+            "src/indexer/mod.rs".to_string(),
+            "README.md".to_string(),
+        ];
+        assert_eq!(
+            test_commands(Runner::Cargo, &changed),
+            vec![vec!["cargo", "test", "indexer"], vec!["cargo", "test", "tools::queue"]]
+        );
+        // A crate root or integration test can't be narrowed
+        assert_eq!(rust_modules(&["src/main.rs".to_string()]), Vec::<String>::new());
+        assert_eq!(rust_modules(&["tests/integration.rs".to_string()]), Vec::<String>::new());
+
+        assert_eq!(
+            test_commands(Runner::Go, &["main.go".to_string(), "pkg/db/db.go".to_string()]),
+            vec![vec!["go", "test", ".", "./pkg/db"]]
+        );
+    }
+
+    #[test]
+    fn test_parse_counts() {
+        let cargo = "test result: ok. 5 passed; 0 failed; 0 ignored\n\
+                     test result: FAILED. 2 passed; 1 failed; 0 ignored";
+        assert_eq!(parse_counts(Runner::Cargo, cargo), (7, 1));
+        assert_eq!(parse_counts(Runner::Pytest, "==== 1 failed, 4 passed in 0.12s ===="), (4, 1));
+        assert_eq!(parse_counts(Runner::Jest, "Tests:       2 failed, 10 passed, 12 total"), (10, 2));
+        assert_eq!(parse_counts(Runner::Go, "ok  \texample/a\t0.1s\nFAIL\texample/b\t0.2s\nFAIL"), (1, 1));
+    }
+}
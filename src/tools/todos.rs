@@ -0,0 +1,155 @@
+//! Todos tool - list the TODO/FIXME/HACK/XXX/NOTE markers found by `index`
+//!
+//! With `--create-beads`, each listed marker becomes an open bead. Its ID is
+//! derived from the file, kind, and message, so re-running doesn't create
+//! duplicates even after the marker moves to another line.
+
+use crate::beads::{self, BeadInfo};
+use crate::db::with_db;
+use crate::indexer::TODO_KINDS;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// Priority of beads created from markers (bd's lowest)
+const TODO_BEAD_PRIORITY: i32 = 4;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TodoEntry {
+    pub file: String,
+    pub line: u32,
+    pub kind: String,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TodosOutput {
+    pub todos: Vec<TodoEntry>,
+    pub total: usize,
+    /// Beads created with `--create-beads`
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub created_beads: Vec<String>,
+    /// Markers whose bead already exists or couldn't be created
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub skipped: Vec<String>,
+}
+
+/// Markers by file and line; `file` is a GLOB pattern
+pub fn list_todos(
+    kind: Option<&str>,
+    file: Option<&str>,
+    limit: usize,
+    create_beads: bool,
+) -> Result<TodosOutput, Box<dyn std::error::Error>> {
+    let kind = kind.map(str::to_uppercase);
+    if let Some(kind) = kind.as_deref().filter(|k| !TODO_KINDS.contains(k)) {
+        return Err(format!("Unknown kind: {}. Use one of: {}", kind, TODO_KINDS.join(", ")).into());
+    }
+
+    let todos: Vec<TodoEntry> = with_db(|conn| {
+        let mut stmt = conn.prepare(
+            "SELECT file, line, kind, message FROM todos
+             WHERE (?1 IS NULL OR kind = ?1) AND (?2 IS NULL OR file GLOB ?2)
+             ORDER BY file, line
+             LIMIT ?3",
+        )?;
+        let rows = stmt.query_map(rusqlite::params![kind, file, limit as i64], |row| {
+            Ok(TodoEntry {
+                file: row.get(0)?,
+                line: row.get(1)?,
+                kind: row.get(2)?,
+                message: row.get(3)?,
+            })
+        })?;
+        rows.collect()
+    })?;
+
+    let mut created_beads = Vec::new();
+    let mut skipped = Vec::new();
+    if create_beads {
+        for todo in &todos {
+            let id = todo_bead_id(todo);
+            if beads::get_bead(&id).is_ok() {
+                skipped.push(format!("{}: bead already exists", id));
+                continue;
+            }
+            let bead = BeadInfo {
+                id: id.clone(),
+                title: todo_bead_title(todo),
+                description: Some(format!("{}:{}", todo.file, todo.line)),
+                priority: TODO_BEAD_PRIORITY,
+                status: "open".to_string(),
+                labels: vec!["todo".to_string(), todo.kind.to_lowercase()],
+            };
+            match beads::create_bead(&bead) {
+                Ok(()) => created_beads.push(id),
+                Err(e) => skipped.push(format!("{}: {}", id, e)),
+            }
+        }
+    }
+
+    Ok(TodosOutput {
+        total: todos.len(),
+        todos,
+        created_beads,
+        skipped,
+    })
+}
+
+/// `todo-` plus a short hash that survives line moves
+fn todo_bead_id(todo: &TodoEntry) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(format!("{}\0{}\0{}", todo.file, todo.kind, todo.message));
+    let hash = format!("{:x}", hasher.finalize());
+    format!("todo-{}", &hash[..8])
+}
+
+/// The comment text, or the marker and location when the comment is bare
+fn todo_bead_title(todo: &TodoEntry) -> String {
+    if todo.message.is_empty() {
+        format!("{} in {}:{}", todo.kind, todo.file, todo.line)
+    } else {
+        todo.message.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{close_db, init_db, test_lock};
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_list_todos() {
+        let _lock = test_lock();
+        let dir = tempdir().unwrap();
+        init_db(Some(dir.path().join("test.db").to_str().unwrap()), true).unwrap();
+
+        with_db(|conn| {
+            conn.execute_batch(
+                "INSERT INTO todos (file, line, kind, message, indexed_at) VALUES
+                   ('src/b.rs', 3, 'FIXME', 'leaks', 0),
+                   ('src/a.rs', 9, 'TODO', 'retry', 0),
+                   ('src/a.rs', 2, 'TODO', '', 0),
+                   ('lib/c.py', 1, 'HACK', 'sleep', 0);",
+            )
+        })
+        .unwrap();
+
+        let all = list_todos(None, None, 10, false).unwrap();
+        let order: Vec<(&str, u32)> = all.todos.iter().map(|t| (t.file.as_str(), t.line)).collect();
+        assert_eq!(order, [("lib/c.py", 1), ("src/a.rs", 2), ("src/a.rs", 9), ("src/b.rs", 3)]);
+
+        assert_eq!(list_todos(Some("todo"), None, 10, false).unwrap().total, 2);
+        assert_eq!(list_todos(None, Some("src/*"), 10, false).unwrap().total, 3);
+        assert_eq!(list_todos(None, None, 1, false).unwrap().total, 1);
+        assert!(list_todos(Some("BUG"), None, 10, false).is_err());
+
+        let bare = &all.todos[1];
+        assert_eq!(todo_bead_title(bare), "TODO in src/a.rs:2");
+        let moved = TodoEntry { line: 40, ..list_todos(None, Some("src/b.rs"), 1, false).unwrap().todos.remove(0) };
+        assert_eq!(todo_bead_id(&moved), todo_bead_id(&all.todos[3]));
+        assert!(todo_bead_id(&moved).starts_with("todo-"));
+
+        close_db();
+    }
+}
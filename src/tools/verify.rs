@@ -0,0 +1,189 @@
+//! Verify tool - workspace consistency checks for humans and CI
+//!
+//! Each check is an error or a warning; only failed errors make the run fail.
+//! With `fix`, `clean_workspace` removes orphaned worktrees and broken claims
+//! and the checks run again.
+
+use super::clean::{clean_workspace, CleanOutput};
+use super::db_integrity::check_integrity;
+use crate::beads;
+use crate::db::{get_current_version, latest_version, with_db};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+use std::process::Command;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct VerifyCheck {
+    pub name: String,
+    pub severity: Severity,
+    pub passed: bool,
+    /// What failed, one entry per problem
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub details: Vec<String>,
+    /// Whether `--fix` can remediate a failure
+    pub fixable: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct VerifyOutput {
+    /// False if any error-severity check failed
+    pub ok: bool,
+    pub checks: Vec<VerifyCheck>,
+    /// What `--fix` cleaned up, if anything needed fixing
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fixed: Option<CleanOutput>,
+    pub message: String,
+}
+
+struct ClaimRow {
+    bead_id: String,
+    worktree_path: String,
+    branch_name: String,
+}
+
+pub fn verify_workspace(fix: bool, workspace_root: &Path) -> Result<VerifyOutput, Box<dyn std::error::Error>> {
+    let mut checks = run_checks(workspace_root)?;
+    let mut fixed = None;
+
+    if fix && checks.iter().any(|c| !c.passed && c.fixable) {
+        fixed = Some(clean_workspace(workspace_root, false, false)?);
+        checks = run_checks(workspace_root)?;
+    }
+
+    let failed = |severity| checks.iter().filter(|c| !c.passed && c.severity == severity).count();
+    let (errors, warnings) = (failed(Severity::Error), failed(Severity::Warning));
+    let message = match (errors, warnings) {
+        (0, 0) => format!("All {} checks passed", checks.len()),
+        _ => format!("{} error(s), {} warning(s)", errors, warnings),
+    };
+
+    Ok(VerifyOutput {
+        ok: errors == 0,
+        checks,
+        fixed,
+        message,
+    })
+}
+
+fn run_checks(workspace_root: &Path) -> Result<Vec<VerifyCheck>, Box<dyn std::error::Error>> {
+    let claims: Vec<ClaimRow> = with_db(|conn| {
+        let mut stmt = conn.prepare("SELECT bead_id, worktree_path, branch_name FROM claims ORDER BY bead_id")?;
+        let rows = stmt.query_map([], |row| {
+            Ok(ClaimRow {
+                bead_id: row.get(0)?,
+                worktree_path: row.get(1)?,
+                branch_name: row.get(2)?,
+            })
+        })?;
+        rows.collect()
+    })?;
+    let dry_clean = clean_workspace(workspace_root, true, false)?;
+
+    let mut checks = vec![
+        check(
+            "claim_worktrees_exist",
+            Severity::Error,
+            true,
+            dry_clean.broken_claims.iter().map(|b| format!("{}: worktree is missing", b)).collect(),
+        ),
+        check(
+            "claim_branches_exist",
+            Severity::Error,
+            false,
+            claims
+                .iter()
+                .filter(|c| !branch_exists(workspace_root, &c.branch_name))
+                .map(|c| format!("{}: branch {} does not exist", c.bead_id, c.branch_name))
+                .collect(),
+        ),
+        check("unique_worktree_paths", Severity::Error, false, duplicate_paths(&claims)),
+        check(
+            "no_orphaned_worktrees",
+            Severity::Warning,
+            true,
+            dry_clean.orphaned_worktrees.iter().map(|w| format!("{}: no claim", w)).collect(),
+        ),
+    ];
+
+    let closed = match beads::list_all_beads() {
+        Ok(all) => {
+            let status: HashMap<String, String> = all.into_iter().map(|b| (b.id, b.status)).collect();
+            claims
+                .iter()
+                .filter(|c| status.get(&c.bead_id).is_some_and(|s| s == "closed"))
+                .map(|c| format!("{}: claimed but closed", c.bead_id))
+                .collect()
+        }
+        Err(e) => vec![format!("Could not read beads: {}", e)],
+    };
+    checks.push(check("no_closed_beads_claimed", Severity::Warning, false, closed));
+
+    checks.push(check("database_integrity", Severity::Error, false, check_integrity(false)?.issues));
+
+    let (current, latest) = (with_db(get_current_version)?, latest_version());
+    let schema = if current == latest {
+        Vec::new()
+    } else {
+        vec![format!("schema version {} but latest migration is {}", current, latest)]
+    };
+    checks.push(check("schema_version_current", Severity::Error, false, schema));
+
+    Ok(checks)
+}
+
+fn check(name: &str, severity: Severity, fixable: bool, details: Vec<String>) -> VerifyCheck {
+    VerifyCheck {
+        name: name.to_string(),
+        severity,
+        passed: details.is_empty(),
+        details,
+        fixable,
+    }
+}
+
+fn branch_exists(workspace_root: &Path, branch: &str) -> bool {
+    Command::new("git")
+        .args(["branch", "--list", branch])
+        .current_dir(workspace_root)
+        .output()
+        .is_ok_and(|o| o.status.success() && !o.stdout.is_empty())
+}
+
+fn duplicate_paths(claims: &[ClaimRow]) -> Vec<String> {
+    let mut by_path: HashMap<&str, Vec<&str>> = HashMap::new();
+    for claim in claims {
+        by_path.entry(&claim.worktree_path).or_default().push(&claim.bead_id);
+    }
+    let mut duplicates: Vec<String> = by_path
+        .into_iter()
+        .filter(|(_, beads)| beads.len() > 1)
+        .map(|(path, beads)| format!("{}: claimed by {}", path, beads.join(", ")))
+        .collect();
+    duplicates.sort();
+    duplicates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_duplicate_paths() {
+        let claim = |bead_id: &str, path: &str| ClaimRow {
+            bead_id: bead_id.to_string(),
+            worktree_path: path.to_string(),
+            branch_name: format!("bacchus/{}", bead_id),
+        };
+        let claims = vec![claim("a", "/wt/a"), claim("b", "/wt/shared"), claim("c", "/wt/shared")];
+        assert_eq!(duplicate_paths(&claims), vec!["/wt/shared: claimed by b, c"]);
+        assert!(duplicate_paths(&claims[..2]).is_empty());
+    }
+}
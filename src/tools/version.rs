@@ -0,0 +1,28 @@
+//! Version tool - build metadata for `bacchus version`
+
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+pub struct VersionInfo {
+    pub version: &'static str,
+    pub git_commit: &'static str,
+    pub build_date: &'static str,
+    pub rustc_version: &'static str,
+    pub sqlite_version: &'static str,
+    pub tree_sitter_version: &'static str,
+    /// Newest grammar ABI the linked tree-sitter runtime can load
+    pub tree_sitter_abi: usize,
+}
+
+/// Version and build metadata (build-time values are set by build.rs)
+pub fn version_info() -> VersionInfo {
+    VersionInfo {
+        version: env!("CARGO_PKG_VERSION"),
+        git_commit: env!("GIT_COMMIT"),
+        build_date: env!("CARGO_BUILD_DATE"),
+        rustc_version: env!("RUSTC_VERSION"),
+        sqlite_version: rusqlite::version(),
+        tree_sitter_version: env!("TREE_SITTER_VERSION"),
+        tree_sitter_abi: tree_sitter::LANGUAGE_VERSION,
+    }
+}
@@ -0,0 +1,115 @@
+//! Watch tool - per-agent claim daemon
+//!
+//! Polls for ready beads and claims them for one agent while it holds fewer
+//! than `max_concurrent` claims, printing a JSON event line to stdout for each
+//! claim so a supervisor can start work in the new worktree. On SIGINT or
+//! SIGTERM every claim the agent still holds is paused, which keeps the
+//! worktree and its commits without marking the bead `blocked` and stalling
+//! its dependents; `resume` picks it up again.
+
+use super::next::next_task;
+use super::pause::pause_bead;
+use crate::db::with_db;
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+/// Set by the SIGINT/SIGTERM handler to stop after the current poll
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WatchOutput {
+    pub agent_id: String,
+    pub polls: u64,
+    pub claimed: Vec<String>,
+    /// Claims paused on shutdown
+    pub paused: Vec<String>,
+    pub message: String,
+}
+
+/// Claim ready beads for `agent_id` until interrupted
+pub fn watch(
+    agent_id: &str,
+    interval_secs: u64,
+    max_concurrent: usize,
+    workspace_root: &Path,
+) -> Result<WatchOutput, Box<dyn std::error::Error>> {
+    // Only one handler per process; if one is already set we just can't interrupt early
+    let _ = ctrlc::set_handler(|| INTERRUPTED.store(true, Ordering::SeqCst));
+
+    let mut output = WatchOutput {
+        agent_id: agent_id.to_string(),
+        polls: 0,
+        claimed: Vec::new(),
+        paused: Vec::new(),
+        message: String::new(),
+    };
+
+    while !INTERRUPTED.load(Ordering::SeqCst) {
+        output.polls += 1;
+
+        while agent_claims(agent_id)?.len() < max_concurrent && !INTERRUPTED.load(Ordering::SeqCst) {
+            let next = match next_task(agent_id, &[], None, workspace_root) {
+                Ok(next) => next,
+                // A daemon shouldn't die on one bad bead; try again next poll
+                Err(e) => {
+                    eprintln!("[watch] claim failed: {}", e);
+                    break;
+                }
+            };
+            let (true, Some(bead_id)) = (next.success, next.bead_id) else {
+                break;
+            };
+            emit(serde_json::json!({
+                "event": "claimed",
+                "bead_id": bead_id,
+                "worktree_path": next.worktree_path,
+            }));
+            output.claimed.push(bead_id);
+        }
+
+        // Sleep in short steps so signals are noticed promptly
+        let wake = Instant::now() + Duration::from_secs(interval_secs);
+        while Instant::now() < wake && !INTERRUPTED.load(Ordering::SeqCst) {
+            std::thread::sleep(Duration::from_millis(100));
+        }
+    }
+
+    eprintln!("[watch] shutting down, pausing claims held by {}", agent_id);
+    for bead_id in agent_claims(agent_id)? {
+        match pause_bead(&bead_id) {
+            Ok(pause) if pause.success => {
+                emit(serde_json::json!({ "event": "paused", "bead_id": bead_id }));
+                output.paused.push(bead_id);
+            }
+            Ok(pause) => eprintln!("[watch] failed to pause {}: {}", bead_id, pause.message),
+            Err(e) => eprintln!("[watch] failed to pause {}: {}", bead_id, e),
+        }
+    }
+
+    output.message = format!(
+        "Stopped after {} poll(s); claimed {}, paused {}",
+        output.polls,
+        output.claimed.len(),
+        output.paused.len()
+    );
+    Ok(output)
+}
+
+/// Beads currently claimed by `agent_id`, oldest first
+fn agent_claims(agent_id: &str) -> rusqlite::Result<Vec<String>> {
+    with_db(|conn| {
+        let mut stmt = conn.prepare("SELECT bead_id FROM claims WHERE agent_id = ?1 ORDER BY claimed_at")?;
+        let rows = stmt.query_map([agent_id], |row| row.get(0))?;
+        rows.collect()
+    })
+}
+
+/// Print one event as a JSON line, flushed so pipes see it immediately
+fn emit(event: serde_json::Value) {
+    let mut stdout = std::io::stdout().lock();
+    let _ = writeln!(stdout, "{}", event);
+    let _ = stdout.flush();
+}
@@ -0,0 +1,193 @@
+//! Workflow documentation - the coordination protocol as structured data
+//!
+//! `bacchus workflow` renders this as markdown, or as JSON for agent
+//! frameworks that want to check their behavior against it.
+
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+pub struct Workflow {
+    pub title: &'static str,
+    /// The agent loop, rendered as a numbered list
+    pub steps: &'static [WorkflowStep],
+    /// Supporting commands, rendered as their own sections
+    pub reference: &'static [WorkflowStep],
+}
+
+#[derive(Debug, Serialize)]
+pub struct WorkflowStep {
+    pub name: &'static str,
+    /// Canonical invocation (`None` for steps that aren't a bacchus command)
+    pub command: Option<&'static str>,
+    pub description: &'static str,
+    /// Shell snippet; may hold several commented variants
+    pub example: Option<&'static str>,
+    pub notes: &'static [&'static str],
+}
+
+pub const WORKFLOW: Workflow = Workflow {
+    title: "Bacchus Coordination Protocol",
+    steps: &[
+        WorkflowStep {
+            name: "Get Work",
+            command: Some("bacchus next <agent_id>"),
+            description: "Claim the next ready bead and get an isolated worktree.",
+            example: Some("bacchus next <agent_id>"),
+            notes: &[
+                "Finds ready bead from beads DB (open, no blockers)",
+                "Creates worktree at .bacchus/worktrees/{bead_id}/",
+                "Claims bead, updates status to in_progress",
+            ],
+        },
+        WorkflowStep {
+            name: "Do Work",
+            command: None,
+            description: "Work in the worktree. All changes are isolated on branch bacchus/{bead_id}.",
+            example: None,
+            notes: &[],
+        },
+        WorkflowStep {
+            name: "Release When Done",
+            command: Some("bacchus release <bead_id> --status done|blocked|failed"),
+            description: "Release the claim once the work is finished, blocked, or abandoned.",
+            example: Some(
+                "# Success - merge to main and cleanup\n\
+                 bacchus release <bead_id> --status done\n\
+                 \n\
+                 # Blocked - keep worktree, release claim\n\
+                 bacchus release <bead_id> --status blocked\n\
+                 \n\
+                 # Failed - discard worktree, reset bead\n\
+                 bacchus release <bead_id> --status failed",
+            ),
+            notes: &[],
+        },
+        WorkflowStep {
+            name: "Handle Merge Conflicts",
+            command: Some("bacchus resolve <bead_id>"),
+            description: "If release fails due to conflicts:",
+            example: Some(
+                "# Option 1: Resolve manually then complete\n\
+                 # ... fix conflicts, git add resolved files ...\n\
+                 bacchus resolve <bead_id>\n\
+                 \n\
+                 # Option 2: Abort and keep working\n\
+                 bacchus abort <bead_id>",
+            ),
+            notes: &[],
+        },
+    ],
+    reference: &[
+        WorkflowStep {
+            name: "Stale Detection",
+            command: Some("bacchus stale --minutes <N> [--cleanup]"),
+            description: "Find abandoned claims:",
+            example: Some(
+                "bacchus stale --minutes 30\n\
+                 \n\
+                 # Auto-cleanup stale claims\n\
+                 bacchus stale --minutes 30 --cleanup",
+            ),
+            notes: &[],
+        },
+        WorkflowStep {
+            name: "Code Search",
+            command: Some("bacchus symbols --pattern <pattern>"),
+            description: "Index the codebase, then search its symbols:",
+            example: Some(
+                "bacchus index src/\n\
+                 bacchus symbols --pattern \"User*\" --kind class",
+            ),
+            notes: &[],
+        },
+        WorkflowStep {
+            name: "Context",
+            command: Some("bacchus context [--bead-id <bead_id>]"),
+            description: "Generate markdown context for the current agent:",
+            example: Some(
+                "bacchus context\n\
+                 bacchus context --bead-id <bead_id>",
+            ),
+            notes: &[
+                "Run from repo root for global context.",
+                "Run inside a worktree for task context.",
+            ],
+        },
+        WorkflowStep {
+            name: "Status",
+            command: Some("bacchus status"),
+            description: "Show current claims:",
+            example: Some("bacchus status"),
+            notes: &[],
+        },
+    ],
+};
+
+/// Render the workflow as `markdown` or `json`
+pub fn workflow_doc(format: &str) -> Result<String, String> {
+    match format {
+        "markdown" => Ok(render_markdown(&WORKFLOW)),
+        "json" => serde_json::to_string_pretty(&WORKFLOW).map_err(|e| e.to_string()),
+        _ => Err(format!("Unknown format: {}. Use markdown or json", format)),
+    }
+}
+
+fn render_markdown(workflow: &Workflow) -> String {
+    let mut out = format!("# {}\n\n## Agent Workflow\n", workflow.title);
+
+    for (i, step) in workflow.steps.iter().enumerate() {
+        out.push_str(&format!("\n{}. **{}**\n", i + 1, step.name));
+        push_step_body(&mut out, step, "   ");
+    }
+
+    for section in workflow.reference {
+        out.push_str(&format!("\n## {}\n\n", section.name));
+        push_step_body(&mut out, section, "");
+    }
+
+    out
+}
+
+fn push_step_body(out: &mut String, step: &WorkflowStep, indent: &str) {
+    out.push_str(&format!("{}{}\n", indent, step.description));
+
+    if let Some(example) = step.example {
+        out.push_str(&format!("{}```bash\n", indent));
+        for line in example.lines() {
+            if line.is_empty() {
+                out.push('\n');
+            } else {
+                out.push_str(&format!("{}{}\n", indent, line));
+            }
+        }
+        out.push_str(&format!("{}```\n", indent));
+    }
+
+    if !step.notes.is_empty() && indent.is_empty() {
+        out.push('\n');
+    }
+    for note in step.notes {
+        out.push_str(&format!("{}- {}\n", indent, note));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_workflow_formats() {
+        let md = workflow_doc("markdown").unwrap();
+        assert!(md.starts_with("# Bacchus Coordination Protocol\n"));
+        assert!(md.contains("1. **Get Work**\n   Claim the next ready bead"));
+        assert!(md.contains("   ```bash\n   bacchus next <agent_id>\n   ```\n   - Finds ready bead"));
+        assert!(md.contains("\n## Status\n\nShow current claims:\n```bash\nbacchus status\n```\n"));
+
+        let json: serde_json::Value = serde_json::from_str(&workflow_doc("json").unwrap()).unwrap();
+        assert_eq!(json["steps"].as_array().unwrap().len(), 4);
+        assert_eq!(json["steps"][1]["command"], serde_json::Value::Null);
+        assert_eq!(json["reference"][3]["command"], "bacchus status");
+
+        assert!(workflow_doc("yaml").is_err());
+    }
+}
@@ -0,0 +1,112 @@
+//! Worktree info tool - git-level details for a single bead's worktree
+//!
+//! Combines the `git worktree list --porcelain` entry with the claims row.
+
+use crate::db::with_db;
+use crate::worktree;
+use rusqlite::OptionalExtension;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::process::Command;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WorktreeClaim {
+    pub agent_id: String,
+    pub branch_name: String,
+    pub start_commit: String,
+    pub claimed_at: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WorktreeInfoOutput {
+    pub success: bool,
+    pub bead_id: String,
+    pub worktree_path: String,
+    pub head_commit: Option<String>,
+    pub current_branch: Option<String>,
+    pub is_locked: bool,
+    pub lock_reason: Option<String>,
+    pub prunable: bool,
+    pub prunable_reason: Option<String>,
+    /// `git status --short` lines
+    pub git_status: Vec<String>,
+    pub claim: Option<WorktreeClaim>,
+    pub message: String,
+}
+
+pub fn worktree_info(
+    bead_id: &str,
+    workspace_root: &Path,
+) -> Result<WorktreeInfoOutput, Box<dyn std::error::Error>> {
+    let expected = worktree::get_worktrees_dir(workspace_root).join(bead_id);
+    let expected_canonical = expected.canonicalize().unwrap_or_else(|_| expected.clone());
+
+    let entry = worktree::list_worktrees(workspace_root)?.into_iter().find(|e| {
+        e.path == expected || e.path.canonicalize().is_ok_and(|p| p == expected_canonical)
+    });
+
+    let claim = with_db(|conn| {
+        conn.query_row(
+            "SELECT agent_id, branch_name, start_commit, claimed_at FROM claims WHERE bead_id = ?1",
+            [bead_id],
+            |row| {
+                Ok(WorktreeClaim {
+                    agent_id: row.get(0)?,
+                    branch_name: row.get(1)?,
+                    start_commit: row.get(2)?,
+                    claimed_at: row.get(3)?,
+                })
+            },
+        )
+        .optional()
+    })?;
+
+    let Some(entry) = entry else {
+        return Ok(WorktreeInfoOutput {
+            success: false,
+            bead_id: bead_id.to_string(),
+            worktree_path: expected.to_string_lossy().to_string(),
+            head_commit: None,
+            current_branch: None,
+            is_locked: false,
+            lock_reason: None,
+            prunable: false,
+            prunable_reason: None,
+            git_status: Vec::new(),
+            claim,
+            message: format!("No worktree found for {}", bead_id),
+        });
+    };
+
+    let git_status = if entry.path.exists() {
+        git_status_short(&entry.path)
+    } else {
+        Vec::new()
+    };
+
+    Ok(WorktreeInfoOutput {
+        success: true,
+        bead_id: bead_id.to_string(),
+        worktree_path: entry.path.to_string_lossy().to_string(),
+        head_commit: entry.head,
+        current_branch: entry.branch,
+        is_locked: entry.locked.is_some(),
+        lock_reason: entry.locked.filter(|r| !r.is_empty()),
+        prunable: entry.prunable.is_some(),
+        prunable_reason: entry.prunable.filter(|r| !r.is_empty()),
+        message: format!("{} uncommitted change(s)", git_status.len()),
+        git_status,
+        claim,
+    })
+}
+
+fn git_status_short(path: &Path) -> Vec<String> {
+    Command::new("git")
+        .args(["status", "--short"])
+        .current_dir(path)
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| String::from_utf8_lossy(&o.stdout).lines().map(String::from).collect())
+        .unwrap_or_default()
+}
@@ -0,0 +1,218 @@
+//! Worktree list tool - git state of every managed worktree
+//!
+//! Entries come from `git worktree list --porcelain`, limited to worktrees under
+//! the bacchus worktrees directory, and are matched to claims by path.
+
+use crate::config::DEFAULT_TARGET_BRANCH;
+use crate::db::with_db;
+use crate::worktree;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WorktreeStatus {
+    /// Claimed bead, or the worktree directory name when unclaimed
+    pub bead_id: String,
+    pub path: String,
+    pub branch: Option<String>,
+    pub head_commit: Option<String>,
+    pub is_dirty: bool,
+    /// Commits on the branch that the claim's target branch lacks
+    pub commits_ahead: usize,
+    /// Commits on the target branch that the branch lacks
+    pub commits_behind: usize,
+    pub claimed: bool,
+    pub age_minutes: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WorktreeListOutput {
+    pub worktrees: Vec<WorktreeStatus>,
+    pub total: usize,
+}
+
+struct ClaimRow {
+    bead_id: String,
+    claimed_at: i64,
+    target_branch: Option<String>,
+}
+
+/// List managed worktrees, rendered as JSON or an ASCII table
+pub fn worktree_list(format: &str, workspace_root: &Path) -> Result<String, Box<dyn std::error::Error>> {
+    let worktrees = collect_worktree_statuses(workspace_root)?;
+
+    match format {
+        "table" => Ok(render_table(&worktrees)),
+        _ => {
+            let output = WorktreeListOutput {
+                total: worktrees.len(),
+                worktrees,
+            };
+            Ok(serde_json::to_string_pretty(&output)?)
+        }
+    }
+}
+
+fn collect_worktree_statuses(workspace_root: &Path) -> Result<Vec<WorktreeStatus>, Box<dyn std::error::Error>> {
+    let worktrees_dir = canonical(&worktree::get_worktrees_dir(workspace_root));
+
+    let claims: HashMap<PathBuf, ClaimRow> = with_db(|conn| {
+        let mut stmt = conn.prepare("SELECT bead_id, worktree_path, claimed_at, target_branch FROM claims")?;
+        let rows = stmt.query_map([], |row| {
+            Ok((
+                canonical(Path::new(&row.get::<_, String>(1)?)),
+                ClaimRow {
+                    bead_id: row.get(0)?,
+                    claimed_at: row.get(2)?,
+                    target_branch: row.get(3)?,
+                },
+            ))
+        })?;
+        rows.collect()
+    })?;
+
+    let now = chrono::Utc::now().timestamp_millis();
+    let mut statuses = Vec::new();
+
+    for entry in worktree::list_worktrees(workspace_root)? {
+        let path = canonical(&entry.path);
+        if !path.starts_with(&worktrees_dir) {
+            continue;
+        }
+
+        let claim = claims.get(&path);
+        let target = claim
+            .and_then(|c| c.target_branch.as_deref())
+            .unwrap_or(DEFAULT_TARGET_BRANCH);
+        let (commits_ahead, commits_behind) = ahead_behind(&entry.path, target);
+
+        statuses.push(WorktreeStatus {
+            bead_id: claim.map(|c| c.bead_id.clone()).unwrap_or_else(|| {
+                entry
+                    .path
+                    .file_name()
+                    .map(|n| n.to_string_lossy().to_string())
+                    .unwrap_or_default()
+            }),
+            path: entry.path.to_string_lossy().to_string(),
+            branch: entry.branch,
+            head_commit: entry.head,
+            is_dirty: is_dirty(&entry.path),
+            commits_ahead,
+            commits_behind,
+            claimed: claim.is_some(),
+            age_minutes: claim.map(|c| (now - c.claimed_at) / 60_000),
+        });
+    }
+
+    Ok(statuses)
+}
+
+fn canonical(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+fn is_dirty(path: &Path) -> bool {
+    Command::new("git")
+        .args(["status", "--porcelain"])
+        .current_dir(path)
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .is_some_and(|o| !o.stdout.is_empty())
+}
+
+/// (ahead, behind) of HEAD relative to `target`; zeros if git can't tell
+fn ahead_behind(path: &Path, target: &str) -> (usize, usize) {
+    Command::new("git")
+        .args(["rev-list", "--left-right", "--count", &format!("HEAD...{}", target)])
+        .current_dir(path)
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .and_then(|o| {
+            let out = String::from_utf8_lossy(&o.stdout).to_string();
+            let mut counts = out.split_whitespace().map(|n| n.parse().unwrap_or(0));
+            Some((counts.next()?, counts.next()?))
+        })
+        .unwrap_or((0, 0))
+}
+
+fn render_table(worktrees: &[WorktreeStatus]) -> String {
+    let headers = ["BEAD", "BRANCH", "HEAD", "DIRTY", "AHEAD", "BEHIND", "AGE"];
+    let rows: Vec<[String; 7]> = worktrees
+        .iter()
+        .map(|w| {
+            [
+                w.bead_id.clone(),
+                w.branch.clone().unwrap_or_else(|| "(detached)".to_string()),
+                w.head_commit.as_deref().map(|h| h.chars().take(8).collect()).unwrap_or_default(),
+                if w.is_dirty { "yes" } else { "no" }.to_string(),
+                w.commits_ahead.to_string(),
+                w.commits_behind.to_string(),
+                w.age_minutes.map(|m| format!("{}m", m)).unwrap_or_else(|| "-".to_string()),
+            ]
+        })
+        .collect();
+
+    let widths: Vec<usize> = (0..headers.len())
+        .map(|i| rows.iter().map(|r| r[i].chars().count()).chain([headers[i].len()]).max().unwrap_or(0))
+        .collect();
+    let line = |cells: &[&str]| {
+        cells
+            .iter()
+            .zip(&widths)
+            .map(|(c, w)| format!("{:<width$}", c, width = w))
+            .collect::<Vec<_>>()
+            .join("  ")
+            .trim_end()
+            .to_string()
+    };
+
+    let mut out = vec![line(&headers)];
+    for row in &rows {
+        out.push(line(&row.iter().map(String::as_str).collect::<Vec<_>>()));
+    }
+    out.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_table() {
+        let worktrees = vec![
+            WorktreeStatus {
+                bead_id: "bd-1".to_string(),
+                path: "/repo/.bacchus/worktrees/bd-1".to_string(),
+                branch: Some("bacchus/bd-1".to_string()),
+                head_commit: Some("0123456789abcdef".to_string()),
+                is_dirty: true,
+                commits_ahead: 2,
+                commits_behind: 0,
+                claimed: true,
+                age_minutes: Some(42),
+            },
+            WorktreeStatus {
+                bead_id: "orphan".to_string(),
+                path: "/repo/.bacchus/worktrees/orphan".to_string(),
+                branch: None,
+                head_commit: None,
+                is_dirty: false,
+                commits_ahead: 0,
+                commits_behind: 3,
+                claimed: false,
+                age_minutes: None,
+            },
+        ];
+
+        let table = render_table(&worktrees);
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines[0], "BEAD    BRANCH        HEAD      DIRTY  AHEAD  BEHIND  AGE");
+        assert_eq!(lines[1], "bd-1    bacchus/bd-1  01234567  yes    2      0       42m");
+        assert_eq!(lines[2], "orphan  (detached)              no     0      3       -");
+    }
+}
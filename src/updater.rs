@@ -3,8 +3,10 @@
 //! Handles checking for updates and downloading new versions atomically.
 
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::env;
 use std::fs;
+use std::path::Path;
 
 const GITHUB_REPO: &str = "vu1n/bacchus";
 const CURRENT_VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -19,7 +21,7 @@ struct GitHubRelease {
 #[derive(Debug, thiserror::Error)]
 pub enum UpdateError {
     #[error("HTTP request failed: {0}")]
-    HttpError(#[from] ureq::Error),
+    HttpError(Box<ureq::Error>),
 
     #[error("Failed to parse JSON: {0}")]
     JsonError(#[from] serde_json::Error),
@@ -27,11 +29,17 @@ pub enum UpdateError {
     #[error("IO error: {0}")]
     IoError(#[from] std::io::Error),
 
-    #[error("No binary found for platform {0}-{1}")]
-    BinaryNotFound(String, String),
-
     #[error("Already on latest version: {0}")]
     AlreadyLatest(String),
+
+    #[error("Checksum not available: {0}")]
+    ChecksumUnavailable(String),
+}
+
+impl From<ureq::Error> for UpdateError {
+    fn from(e: ureq::Error) -> Self {
+        UpdateError::HttpError(Box::new(e))
+    }
 }
 
 /// Result type for update operations
@@ -50,7 +58,7 @@ pub fn check_for_updates() -> UpdateResult<UpdateInfo> {
 
     // Compare versions (simple semver comparison)
     if version_compare::compare_versions(CURRENT_VERSION, latest_version)
-        .map_or(false, |v| v >= std::cmp::Ordering::Equal)
+        .is_some_and(|v| v >= std::cmp::Ordering::Equal)
     {
         return Ok(UpdateInfo {
             current_version: CURRENT_VERSION.to_string(),
@@ -145,6 +153,81 @@ pub fn self_update() -> UpdateResult<String> {
     Ok(info.latest_version)
 }
 
+/// Result of verifying the running binary against its published checksum
+#[derive(Debug, Serialize)]
+pub struct VerifyInfo {
+    pub verified: bool,
+    pub current_hash: String,
+    pub expected_hash: String,
+    pub version: String,
+    /// Where the expected hash came from: "download" or "cache"
+    pub source: String,
+}
+
+/// Verify the running binary against the release `.sha256` for this version
+///
+/// Only the small checksum file is downloaded; it is cached next to the binary.
+/// With `BACCHUS_OFFLINE=1`, the cached copy is used and nothing is downloaded.
+pub fn verify_binary() -> UpdateResult<VerifyInfo> {
+    let current_exe = env::current_exe()?;
+    let cache_path = current_exe.with_extension("sha256");
+
+    let offline = env::var("BACCHUS_OFFLINE").is_ok_and(|v| v == "1");
+
+    let (checksum_file, source) = if offline {
+        let content = fs::read_to_string(&cache_path).map_err(|_| {
+            UpdateError::ChecksumUnavailable(format!(
+                "BACCHUS_OFFLINE=1 but no cached checksum at {}",
+                cache_path.display()
+            ))
+        })?;
+        (content, "cache")
+    } else {
+        let (os, arch) = detect_platform();
+        let checksum_url = format!(
+            "https://github.com/{}/releases/download/v{}/bacchus-{}-{}.sha256",
+            GITHUB_REPO, CURRENT_VERSION, os, arch
+        );
+
+        let content = match ureq::get(&checksum_url).set("User-Agent", "bacchus").call() {
+            Ok(response) => response.into_string()?,
+            Err(ureq::Error::Status(404, _)) => {
+                return Err(UpdateError::ChecksumUnavailable(checksum_url))
+            }
+            Err(e) => return Err(e.into()),
+        };
+
+        // Cache for later offline checks; failure to cache isn't fatal
+        let _ = fs::write(&cache_path, &content);
+        (content, "download")
+    };
+
+    let expected_hash = parse_checksum(&checksum_file)
+        .ok_or_else(|| UpdateError::ChecksumUnavailable("empty checksum file".to_string()))?;
+    let current_hash = hash_file(&current_exe)?;
+
+    Ok(VerifyInfo {
+        verified: current_hash.eq_ignore_ascii_case(&expected_hash),
+        current_hash,
+        expected_hash,
+        version: CURRENT_VERSION.to_string(),
+        source: source.to_string(),
+    })
+}
+
+/// Extract the hash from `sha256sum`-style output (`<hash>  <filename>`)
+fn parse_checksum(content: &str) -> Option<String> {
+    content.split_whitespace().next().map(|h| h.to_lowercase())
+}
+
+/// SHA-256 of a file, hex encoded
+fn hash_file(path: &Path) -> UpdateResult<String> {
+    let mut file = fs::File::open(path)?;
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut file, &mut hasher)?;
+    Ok(hex::encode(hasher.finalize()))
+}
+
 /// Detect the current platform (OS and architecture)
 fn detect_platform() -> (String, String) {
     let os = env::consts::OS;
@@ -201,4 +284,21 @@ mod tests {
             Some(std::cmp::Ordering::Equal)
         );
     }
+
+    #[test]
+    fn test_checksum_helpers() {
+        assert_eq!(
+            parse_checksum("ABC123  bacchus-linux-x86_64\n").as_deref(),
+            Some("abc123")
+        );
+        assert_eq!(parse_checksum("  \n"), None);
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("bin");
+        fs::write(&path, "hello").unwrap();
+        assert_eq!(
+            hash_file(&path).unwrap(),
+            "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824"
+        );
+    }
 }
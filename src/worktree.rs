@@ -3,15 +3,21 @@
 //! This module manages git worktrees for beads, storing them in `.bacchus/worktrees/{bead_id}/`.
 //! Each worktree operates on a separate branch `bacchus/{bead_id}`.
 //!
-//! Override worktrees directory with BACCHUS_WORKTREES environment variable.
+//! Override worktrees directory with `worktrees_dir` in `.bacchus/config.toml` or
+//! the BACCHUS_WORKTREES environment variable (which wins).
+//! Commit signing follows `require_signatures`/`signed_commits` in `.bacchus/config.toml`.
 
+use crate::config::load_config;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 use thiserror::Error;
 
-/// Get the worktrees directory, checking BACCHUS_WORKTREES env var first
+/// Get the worktrees directory: BACCHUS_WORKTREES, then `worktrees_dir` from config
 pub(crate) fn get_worktrees_dir(workspace_root: &Path) -> PathBuf {
-    match std::env::var("BACCHUS_WORKTREES").ok().map(PathBuf::from) {
+    let configured = std::env::var("BACCHUS_WORKTREES")
+        .ok()
+        .or_else(|| load_config(workspace_root).worktrees_dir);
+    match configured.map(PathBuf::from) {
         Some(path) if path.is_absolute() => path,
         Some(path) => workspace_root.join(path),
         None => workspace_root.join(".bacchus/worktrees"),
@@ -20,12 +26,25 @@ pub(crate) fn get_worktrees_dir(workspace_root: &Path) -> PathBuf {
 
 #[derive(Debug, Clone)]
 pub struct WorktreeInfo {
-    pub bead_id: String,
     pub path: PathBuf,
     pub branch: String,
     pub head_commit: String,
 }
 
+/// One entry of `git worktree list --porcelain`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WorktreeEntry {
+    pub path: PathBuf,
+    pub head: Option<String>,
+    /// Branch name without the `refs/heads/` prefix (None when detached)
+    pub branch: Option<String>,
+    pub detached: bool,
+    /// Lock reason (empty string when locked without a reason)
+    pub locked: Option<String>,
+    /// Prune reason (empty string when prunable without a reason)
+    pub prunable: Option<String>,
+}
+
 #[derive(Debug, Error)]
 pub enum WorktreeError {
     #[error("Git command failed: {0}")]
@@ -34,6 +53,10 @@ pub enum WorktreeError {
     AlreadyExists(String),
     #[error("Worktree not found: {0}")]
     NotFound(String),
+    #[error("Branch protection violation: {0}")]
+    ProtectionViolation(String),
+    #[error("Commit {commit_hash} on bacchus/{bead_id} is not signed with a trusted key")]
+    UnsignedCommit { bead_id: String, commit_hash: String },
     #[error("IO error: {0}")]
     IoError(#[from] std::io::Error),
 }
@@ -41,6 +64,15 @@ pub enum WorktreeError {
 /// Create a new worktree for a bead
 /// Creates worktrees/{bead_id} on branch bacchus/{bead_id}
 pub fn create_worktree(workspace_root: &Path, bead_id: &str) -> Result<WorktreeInfo, WorktreeError> {
+    create_worktree_from(workspace_root, bead_id, None)
+}
+
+/// Create a new worktree for a bead, branching from `base` (default: HEAD)
+pub fn create_worktree_from(
+    workspace_root: &Path,
+    bead_id: &str,
+    base: Option<&str>,
+) -> Result<WorktreeInfo, WorktreeError> {
     let worktrees_dir = get_worktrees_dir(workspace_root);
     let worktree_path = worktrees_dir.join(bead_id);
     let branch_name = format!("bacchus/{}", bead_id);
@@ -53,15 +85,20 @@ pub fn create_worktree(workspace_root: &Path, bead_id: &str) -> Result<WorktreeI
         return Err(WorktreeError::AlreadyExists(bead_id.to_string()));
     }
 
-    // Run: git worktree add .bacchus/worktrees/{bead_id} -b bacchus/{bead_id}
-    let output = Command::new("git")
-        .arg("worktree")
+    // Run: git worktree add .bacchus/worktrees/{bead_id} -b bacchus/{bead_id} [base]
+    let mut cmd = Command::new("git");
+    cmd.arg("worktree")
         .arg("add")
         .arg(&worktree_path)
         .arg("-b")
         .arg(&branch_name)
-        .current_dir(workspace_root)
-        .output()?;
+        .current_dir(workspace_root);
+
+    if let Some(base) = base {
+        cmd.arg(base);
+    }
+
+    let output = cmd.output()?;
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
@@ -75,7 +112,6 @@ pub fn create_worktree(workspace_root: &Path, bead_id: &str) -> Result<WorktreeI
     let head_commit = get_head_commit_in_path(&worktree_path)?;
 
     Ok(WorktreeInfo {
-        bead_id: bead_id.to_string(),
         path: worktree_path,
         branch: branch_name,
         head_commit,
@@ -84,8 +120,20 @@ pub fn create_worktree(workspace_root: &Path, bead_id: &str) -> Result<WorktreeI
 
 /// Remove a worktree (force=true discards uncommitted changes)
 pub fn remove_worktree(workspace_root: &Path, bead_id: &str, force: bool) -> Result<(), WorktreeError> {
+    remove_worktree_dir(workspace_root, bead_id, force)?;
+
+    // Delete branch: git branch -d/-D bacchus/{bead_id}
+    delete_branch(workspace_root, &branch_name(bead_id), force)
+}
+
+/// Branch name used for a bead's worktree
+pub fn branch_name(bead_id: &str) -> String {
+    format!("bacchus/{}", bead_id)
+}
+
+/// Remove a worktree directory, leaving its branch in place
+pub fn remove_worktree_dir(workspace_root: &Path, bead_id: &str, force: bool) -> Result<(), WorktreeError> {
     let worktree_path = get_worktrees_dir(workspace_root).join(bead_id);
-    let branch_name = format!("bacchus/{}", bead_id);
 
     // Check if worktree exists
     if !worktree_path.exists() {
@@ -113,16 +161,55 @@ pub fn remove_worktree(workspace_root: &Path, bead_id: &str, force: bool) -> Res
         )));
     }
 
-    // Delete branch: git branch -d/-D bacchus/{bead_id}
-    delete_branch(workspace_root, &branch_name, force)?;
-
     Ok(())
 }
 
-/// Merge worktree branch to target (usually "main")
-pub fn merge_worktree(workspace_root: &Path, bead_id: &str, target_branch: &str) -> Result<(), WorktreeError> {
+/// Drop git's records of worktrees whose directories are gone
+///
+/// Returns git's description of each stale record (`worktrees/<name>: <reason>`).
+pub fn prune_worktrees(workspace_root: &Path, dry_run: bool) -> Result<Vec<String>, WorktreeError> {
+    let mut cmd = Command::new("git");
+    cmd.args(["worktree", "prune", "--verbose"]).current_dir(workspace_root);
+    if dry_run {
+        cmd.arg("--dry-run");
+    }
+
+    let output = cmd.output()?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(WorktreeError::GitError(format!(
+            "Failed to prune worktrees: {}",
+            stderr
+        )));
+    }
+
+    // --verbose reports on stderr
+    Ok(String::from_utf8_lossy(&output.stderr)
+        .lines()
+        .filter_map(|l| l.strip_prefix("Removing "))
+        .map(String::from)
+        .collect())
+}
+
+/// Merge worktree branch to target (the claim's `target_branch`, usually "main")
+///
+/// Fast-forwards when possible unless `no_ff`; `message` is only used when a
+/// merge commit is created. Returns the target branch's new HEAD.
+///
+/// With `require_signatures`, the worktree's HEAD must pass `git verify-commit`.
+pub fn merge_worktree(
+    workspace_root: &Path,
+    bead_id: &str,
+    target_branch: &str,
+    message: Option<&str>,
+    no_ff: bool,
+) -> Result<String, WorktreeError> {
     let branch_name = format!("bacchus/{}", bead_id);
 
+    if load_config(workspace_root).require_signatures {
+        verify_worktree_signature(workspace_root, bead_id)?;
+    }
+
     // Checkout target branch
     let output = Command::new("git")
         .arg("checkout")
@@ -139,23 +226,732 @@ pub fn merge_worktree(workspace_root: &Path, bead_id: &str, target_branch: &str)
     }
 
     // Merge the worktree branch
+    let mut cmd = Command::new("git");
+    cmd.arg("merge");
+    cmd.args(sign_args(workspace_root));
+    if no_ff {
+        cmd.arg("--no-ff");
+    }
+    if let Some(message) = message {
+        cmd.arg("--message").arg(message);
+    }
+    let output = cmd.arg(&branch_name).current_dir(workspace_root).output()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(WorktreeError::GitError(format!(
+            "Failed to merge {}: {}",
+            branch_name, stderr
+        )));
+    }
+
+    get_head_commit_in_path(workspace_root)
+}
+
+/// One entry of `git stash list`
+#[derive(Debug, Clone, PartialEq)]
+pub struct StashEntry {
+    /// e.g. `stash@{0}`
+    pub stash_ref: String,
+    /// Bead the stash was made for (None for stashes bacchus didn't create)
+    pub bead_id: Option<String>,
+    pub message: String,
+    /// Unix timestamp (seconds) the stash was created
+    pub created_at: i64,
+}
+
+/// Stashes are shared by all worktrees, so each message is tagged with its bead
+fn stash_marker(bead_id: &str) -> String {
+    format!("bacchus:{}:", bead_id)
+}
+
+/// Stash a bead's uncommitted (including untracked) changes
+///
+/// Returns false if there was nothing to stash.
+pub fn stash_worktree(workspace_root: &Path, bead_id: &str, message: &str) -> Result<bool, WorktreeError> {
+    let worktree_path = get_worktrees_dir(workspace_root).join(bead_id);
+    if !worktree_path.exists() {
+        return Err(WorktreeError::NotFound(worktree_path.to_string_lossy().to_string()));
+    }
+
+    let before = list_stashes(workspace_root)?.len();
     let output = Command::new("git")
-        .arg("merge")
-        .arg(&branch_name)
+        .args(["stash", "push", "--include-untracked", "-m"])
+        .arg(format!("{} {}", stash_marker(bead_id), message))
+        .current_dir(&worktree_path)
+        .output()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(WorktreeError::GitError(format!(
+            "Failed to stash {}: {}",
+            bead_id, stderr
+        )));
+    }
+
+    Ok(list_stashes(workspace_root)?.len() > before)
+}
+
+/// Pop the most recent stash made for a bead back into its worktree
+pub fn unstash_worktree(workspace_root: &Path, bead_id: &str) -> Result<(), WorktreeError> {
+    let worktree_path = get_worktrees_dir(workspace_root).join(bead_id);
+    if !worktree_path.exists() {
+        return Err(WorktreeError::NotFound(worktree_path.to_string_lossy().to_string()));
+    }
+
+    // Plain `git stash pop` would take whichever worktree stashed last
+    let stash = list_stashes(workspace_root)?
+        .into_iter()
+        .find(|s| s.bead_id.as_deref() == Some(bead_id))
+        .ok_or_else(|| WorktreeError::NotFound(format!("stash for {}", bead_id)))?;
+
+    let output = Command::new("git")
+        .args(["stash", "pop", &stash.stash_ref])
+        .current_dir(&worktree_path)
+        .output()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(WorktreeError::GitError(format!(
+            "Failed to pop {}: {}",
+            stash.stash_ref, stderr
+        )));
+    }
+
+    Ok(())
+}
+
+/// Record a bead's uncommitted work as a stash commit, leaving the worktree as it was
+///
+/// Returns the stash SHA, or None if there was nothing to record. The stash
+/// stays in the shared stash list under `message`, so the commit stays reachable.
+pub fn snapshot_worktree_changes(
+    workspace_root: &Path,
+    bead_id: &str,
+    message: &str,
+) -> Result<Option<String>, WorktreeError> {
+    let worktree_path = get_worktrees_dir(workspace_root).join(bead_id);
+    if !worktree_path.exists() {
+        return Err(WorktreeError::NotFound(worktree_path.to_string_lossy().to_string()));
+    }
+
+    let before = list_stashes(workspace_root)?.len();
+    let output = Command::new("git")
+        .args(["stash", "push", "--include-untracked", "-m", message])
+        .current_dir(&worktree_path)
+        .output()?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(WorktreeError::GitError(format!(
+            "Failed to snapshot {}: {}",
+            bead_id, stderr
+        )));
+    }
+    if list_stashes(workspace_root)?.len() == before {
+        return Ok(None);
+    }
+
+    let sha = resolve_commit(&worktree_path, "refs/stash")?;
+    // Push cleared the worktree; put the work back so the snapshot is non-destructive
+    apply_stash_commit(workspace_root, bead_id, &sha, true)?;
+    Ok(Some(sha))
+}
+
+/// Apply a stash commit to a bead's worktree without dropping it
+pub fn apply_stash_commit(
+    workspace_root: &Path,
+    bead_id: &str,
+    sha: &str,
+    restore_index: bool,
+) -> Result<(), WorktreeError> {
+    let worktree_path = get_worktrees_dir(workspace_root).join(bead_id);
+    if !worktree_path.exists() {
+        return Err(WorktreeError::NotFound(worktree_path.to_string_lossy().to_string()));
+    }
+
+    let mut cmd = Command::new("git");
+    cmd.args(["stash", "apply"]);
+    if restore_index {
+        cmd.arg("--index");
+    }
+    let output = cmd.arg(sha).current_dir(&worktree_path).output()?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(WorktreeError::GitError(format!(
+            "Failed to apply {}: {}",
+            sha, stderr
+        )));
+    }
+    Ok(())
+}
+
+/// All stashes of the repository, newest first
+pub fn list_stashes(workspace_root: &Path) -> Result<Vec<StashEntry>, WorktreeError> {
+    let output = Command::new("git")
+        .args(["stash", "list", "--format=%gd%x00%ct%x00%gs"])
         .current_dir(workspace_root)
         .output()?;
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
         return Err(WorktreeError::GitError(format!(
-            "Failed to merge {}: {}",
-            branch_name, stderr
+            "Failed to list stashes: {}",
+            stderr
         )));
     }
 
+    Ok(parse_stash_list(&String::from_utf8_lossy(&output.stdout)))
+}
+
+fn parse_stash_list(output: &str) -> Vec<StashEntry> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.splitn(3, '\0');
+            let stash_ref = parts.next()?.to_string();
+            let created_at = parts.next()?.parse().unwrap_or(0);
+            // Subject is "On <branch>: <message>"
+            let subject = parts.next()?;
+            let message = subject.split_once(": ").map_or(subject, |(_, m)| m);
+
+            let (bead_id, message) = match message.strip_prefix("bacchus:").and_then(|m| m.split_once(": ")) {
+                Some((bead_id, rest)) => (Some(bead_id.to_string()), rest.to_string()),
+                None => (None, message.to_string()),
+            };
+            Some(StashEntry {
+                stash_ref,
+                bead_id,
+                message,
+                created_at,
+            })
+        })
+        .collect()
+}
+
+/// Check that the HEAD commit of a bead's worktree carries a valid signature
+pub fn verify_worktree_signature(workspace_root: &Path, bead_id: &str) -> Result<(), WorktreeError> {
+    let worktree_path = get_worktrees_dir(workspace_root).join(bead_id);
+    if !worktree_path.exists() {
+        return Err(WorktreeError::NotFound(worktree_path.to_string_lossy().to_string()));
+    }
+
+    let output = Command::new("git")
+        .args(["verify-commit", "HEAD"])
+        .current_dir(&worktree_path)
+        .output()?;
+
+    if !output.status.success() {
+        return Err(WorktreeError::UnsignedCommit {
+            bead_id: bead_id.to_string(),
+            commit_hash: get_head_commit_in_path(&worktree_path)?,
+        });
+    }
+
     Ok(())
 }
 
+/// `-S` when bacchus should sign the commits it creates (`signed_commits`)
+fn sign_args(workspace_root: &Path) -> &'static [&'static str] {
+    if load_config(workspace_root).signed_commits {
+        &["-S"]
+    } else {
+        &[]
+    }
+}
+
+/// Check that merging a bead's branch keeps the target's history linear
+///
+/// Passes only when `target_branch` is an ancestor of the bead branch, so the
+/// merge can fast-forward.
+pub fn check_branch_protection(
+    workspace_root: &Path,
+    bead_id: &str,
+    target_branch: &str,
+) -> Result<(), WorktreeError> {
+    let branch = branch_name(bead_id);
+    let output = Command::new("git")
+        .args(["merge-base", "--is-ancestor", target_branch, &branch])
+        .current_dir(workspace_root)
+        .output()?;
+
+    match output.status.code() {
+        Some(0) => Ok(()),
+        // Exit code 1 means "not an ancestor"; anything else is a real error
+        Some(1) => Err(WorktreeError::ProtectionViolation(format!(
+            "{} has diverged from {} and linear history is required. Run `bacchus rebase {}` first.",
+            target_branch, branch, bead_id
+        ))),
+        _ => {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            Err(WorktreeError::GitError(format!(
+                "Failed to compare {} with {}: {}",
+                branch, target_branch, stderr
+            )))
+        }
+    }
+}
+
+/// Where a worktree branch was pushed
+#[derive(Debug, Clone)]
+pub struct PushInfo {
+    pub remote_url: String,
+    pub branch: String,
+    /// True when this push set up the branch's upstream for the first time
+    pub tracking_created: bool,
+}
+
+/// Push a bead's branch to `remote`, setting it as the branch's upstream
+///
+/// `force` uses `--force-with-lease` so a remote branch someone else updated
+/// is never overwritten.
+pub fn push_worktree(
+    workspace_root: &Path,
+    bead_id: &str,
+    remote: &str,
+    force: bool,
+) -> Result<PushInfo, WorktreeError> {
+    let branch = branch_name(bead_id);
+
+    let output = Command::new("git")
+        .args(["remote", "get-url", remote])
+        .current_dir(workspace_root)
+        .output()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(WorktreeError::GitError(format!(
+            "Unknown remote {}: {}",
+            remote, stderr
+        )));
+    }
+    let remote_url = String::from_utf8_lossy(&output.stdout).trim().to_string();
+
+    let had_upstream = Command::new("git")
+        .args(["rev-parse", "--abbrev-ref", &format!("{}@{{upstream}}", branch)])
+        .current_dir(workspace_root)
+        .output()?
+        .status
+        .success();
+
+    let mut cmd = Command::new("git");
+    cmd.args(["push", "--set-upstream"]);
+    if force {
+        cmd.arg("--force-with-lease");
+    }
+    let output = cmd.arg(remote).arg(&branch).current_dir(workspace_root).output()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(WorktreeError::GitError(format!(
+            "Failed to push {} to {}: {}",
+            branch, remote, stderr
+        )));
+    }
+
+    Ok(PushInfo {
+        remote_url,
+        branch,
+        tracking_created: !had_upstream,
+    })
+}
+
+/// Remote a worktree syncs from
+const SYNC_REMOTE: &str = "origin";
+
+/// Result of syncing a worktree with the target branch
+#[derive(Debug, Clone, PartialEq)]
+pub enum SyncOutcome {
+    /// Merge finished; number of target commits brought in
+    Merged(usize),
+    /// Merge stopped on conflicts in these files (still in progress)
+    Conflicts(Vec<String>),
+}
+
+/// Fetch `origin` and merge `origin/<target_branch>` into a bead's worktree
+///
+/// On conflicts the merge is left in progress inside the worktree.
+pub fn sync_worktree(
+    workspace_root: &Path,
+    bead_id: &str,
+    target_branch: &str,
+) -> Result<SyncOutcome, WorktreeError> {
+    let worktree_path = get_worktrees_dir(workspace_root).join(bead_id);
+    if !worktree_path.exists() {
+        return Err(WorktreeError::NotFound(worktree_path.to_string_lossy().to_string()));
+    }
+    let upstream = format!("{}/{}", SYNC_REMOTE, target_branch);
+
+    let output = Command::new("git")
+        .args(["fetch", SYNC_REMOTE])
+        .current_dir(&worktree_path)
+        .output()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(WorktreeError::GitError(format!(
+            "Failed to fetch {}: {}",
+            SYNC_REMOTE, stderr
+        )));
+    }
+
+    let output = Command::new("git")
+        .args(["rev-list", "--count", &format!("HEAD..{}", upstream)])
+        .current_dir(&worktree_path)
+        .output()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(WorktreeError::GitError(format!(
+            "Failed to compare with {}: {}",
+            upstream, stderr
+        )));
+    }
+    let commits = String::from_utf8_lossy(&output.stdout).trim().parse().unwrap_or(0);
+
+    let output = Command::new("git")
+        .args(["merge", "--no-edit", &upstream])
+        .current_dir(&worktree_path)
+        .output()?;
+
+    if output.status.success() {
+        return Ok(SyncOutcome::Merged(commits));
+    }
+
+    if is_in_merge_conflict(&worktree_path)? && has_unresolved_conflicts(&worktree_path)? {
+        return Ok(SyncOutcome::Conflicts(conflicted_files(&worktree_path)?));
+    }
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    Err(WorktreeError::GitError(format!(
+        "Failed to merge {}: {}",
+        upstream, stderr
+    )))
+}
+
+/// Result of rebasing a worktree branch
+#[derive(Debug, Clone, PartialEq)]
+pub enum RebaseOutcome {
+    /// Rebase finished; number of branch commits replayed onto the target
+    Rebased(usize),
+    /// Rebase stopped on conflicts in these files (still in progress)
+    Conflicts(Vec<String>),
+}
+
+/// Rebase a bead's worktree branch onto `target_branch`
+///
+/// On conflicts the rebase is left in progress inside the worktree so it can
+/// be resolved and continued, or rolled back with `abort_rebase`.
+pub fn rebase_worktree(
+    workspace_root: &Path,
+    bead_id: &str,
+    target_branch: &str,
+) -> Result<RebaseOutcome, WorktreeError> {
+    let worktree_path = get_worktrees_dir(workspace_root).join(bead_id);
+    if !worktree_path.exists() {
+        return Err(WorktreeError::NotFound(worktree_path.to_string_lossy().to_string()));
+    }
+
+    let output = Command::new("git")
+        .args(["rev-list", "--count", &format!("{}..HEAD", target_branch)])
+        .current_dir(&worktree_path)
+        .output()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(WorktreeError::GitError(format!(
+            "Failed to compare with {}: {}",
+            target_branch, stderr
+        )));
+    }
+    let commits = String::from_utf8_lossy(&output.stdout).trim().parse().unwrap_or(0);
+
+    let output = Command::new("git")
+        .args(["rebase", target_branch])
+        .current_dir(&worktree_path)
+        .output()?;
+
+    if output.status.success() {
+        return Ok(RebaseOutcome::Rebased(commits));
+    }
+
+    if is_rebase_in_progress(&worktree_path)? {
+        return Ok(RebaseOutcome::Conflicts(conflicted_files(&worktree_path)?));
+    }
+
+    // Refused to start (e.g. uncommitted changes)
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    Err(WorktreeError::GitError(format!(
+        "Failed to rebase onto {}: {}",
+        target_branch, stderr
+    )))
+}
+
+/// Check if a rebase is in progress in the given worktree
+pub fn is_rebase_in_progress(worktree_path: &Path) -> Result<bool, WorktreeError> {
+    Ok(git_path(worktree_path, "rebase-merge").exists() || git_path(worktree_path, "rebase-apply").exists())
+}
+
+/// Path of a file inside the git dir of `path`
+///
+/// A linked worktree's git dir isn't `.git/`, so ask git where it is; outside
+/// a repository this falls back to `.git/<name>`.
+fn git_path(path: &Path, name: &str) -> PathBuf {
+    Command::new("git")
+        .args(["rev-parse", "--git-path", name])
+        .current_dir(path)
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| path.join(String::from_utf8_lossy(&o.stdout).trim()))
+        .unwrap_or_else(|| path.join(".git").join(name))
+}
+
+/// Abort an in-progress rebase in the given worktree
+pub fn abort_rebase(worktree_path: &Path) -> Result<(), WorktreeError> {
+    let output = Command::new("git")
+        .args(["rebase", "--abort"])
+        .current_dir(worktree_path)
+        .output()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(WorktreeError::GitError(format!(
+            "Failed to abort rebase: {}",
+            stderr
+        )));
+    }
+
+    Ok(())
+}
+
+/// Files with unresolved conflicts
+fn conflicted_files(path: &Path) -> Result<Vec<String>, WorktreeError> {
+    let output = Command::new("git")
+        .args(["diff", "--name-only", "--diff-filter=U"])
+        .current_dir(path)
+        .output()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(WorktreeError::GitError(format!(
+            "Failed to list conflicts: {}",
+            stderr
+        )));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter(|l| !l.is_empty())
+        .map(String::from)
+        .collect())
+}
+
+/// Collapse a bead's worktree commits since its merge base with `target_branch` into one
+///
+/// Returns how many commits were squashed (0 leaves the branch untouched).
+pub fn squash_worktree(
+    workspace_root: &Path,
+    bead_id: &str,
+    target_branch: &str,
+    message: &str,
+) -> Result<usize, WorktreeError> {
+    let worktree_path = get_worktrees_dir(workspace_root).join(bead_id);
+    if !worktree_path.exists() {
+        return Err(WorktreeError::NotFound(worktree_path.to_string_lossy().to_string()));
+    }
+
+    let output = Command::new("git")
+        .args(["merge-base", "HEAD", target_branch])
+        .current_dir(&worktree_path)
+        .output()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(WorktreeError::GitError(format!(
+            "Failed to find merge base with {}: {}",
+            target_branch, stderr
+        )));
+    }
+    let base = String::from_utf8_lossy(&output.stdout).trim().to_string();
+
+    let output = Command::new("git")
+        .args(["rev-list", "--count", &format!("{}..HEAD", base)])
+        .current_dir(&worktree_path)
+        .output()?;
+    let commits: usize = String::from_utf8_lossy(&output.stdout).trim().parse().unwrap_or(0);
+    if commits == 0 {
+        return Ok(0);
+    }
+
+    let output = Command::new("git")
+        .args(["reset", "--soft", &base])
+        .current_dir(&worktree_path)
+        .output()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(WorktreeError::GitError(format!(
+            "Failed to reset to merge base: {}",
+            stderr
+        )));
+    }
+
+    let output = Command::new("git")
+        .args(["commit", "--allow-empty", "-m", message])
+        .args(sign_args(workspace_root))
+        .current_dir(&worktree_path)
+        .output()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(WorktreeError::GitError(format!(
+            "Failed to commit squashed changes: {}",
+            stderr
+        )));
+    }
+
+    Ok(commits)
+}
+
+/// Create an annotated tag on `commit`
+pub fn create_release_tag(
+    workspace_root: &Path,
+    tag_name: &str,
+    commit: &str,
+    message: &str,
+) -> Result<(), WorktreeError> {
+    let output = Command::new("git")
+        .args(["tag", "-a", tag_name, commit, "-m", message])
+        .current_dir(workspace_root)
+        .output()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(WorktreeError::GitError(format!(
+            "Failed to create tag {}: {}",
+            tag_name, stderr
+        )));
+    }
+
+    Ok(())
+}
+
+/// Revert `revision` (a commit or `base..tip` range) on the current branch
+///
+/// `mainline` reverts a merge commit against its first parent. With a
+/// `message` the result is committed; without one it is left staged. A failed
+/// revert is aborted so the working tree is left as it was.
+pub fn revert_commits(
+    workspace_root: &Path,
+    revision: &str,
+    mainline: bool,
+    message: Option<&str>,
+) -> Result<(), WorktreeError> {
+    let mut cmd = Command::new("git");
+    cmd.args(["revert", "--no-commit"]);
+    if mainline {
+        cmd.args(["-m", "1"]);
+    }
+    let output = cmd.arg(revision).current_dir(workspace_root).output()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let _ = Command::new("git")
+            .args(["revert", "--abort"])
+            .current_dir(workspace_root)
+            .output();
+        return Err(WorktreeError::GitError(format!(
+            "Failed to revert {}: {}",
+            revision, stderr
+        )));
+    }
+
+    if let Some(message) = message {
+        let output = Command::new("git")
+            .arg("commit")
+            .args(sign_args(workspace_root))
+            .args(["-m", message])
+            .current_dir(workspace_root)
+            .output()?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(WorktreeError::GitError(format!(
+                "Failed to commit revert of {}: {}",
+                revision, stderr
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+/// Full hash of the commit `revision` points at
+pub fn resolve_commit(workspace_root: &Path, revision: &str) -> Result<String, WorktreeError> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--verify", &format!("{}^{{commit}}", revision)])
+        .current_dir(workspace_root)
+        .output()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(WorktreeError::GitError(format!(
+            "Failed to resolve {}: {}",
+            revision, stderr
+        )));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Name of the branch checked out at `path` (None when detached)
+pub fn current_branch(path: &Path) -> Result<Option<String>, WorktreeError> {
+    let output = Command::new("git")
+        .args(["branch", "--show-current"])
+        .current_dir(path)
+        .output()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(WorktreeError::GitError(format!(
+            "Failed to get current branch: {}",
+            stderr
+        )));
+    }
+
+    let branch = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    Ok((!branch.is_empty()).then_some(branch))
+}
+
+/// Trimmed stdout of `git <args>` run in `path`
+pub fn git_output(path: &Path, args: &[&str]) -> Result<String, WorktreeError> {
+    let output = Command::new("git").args(args).current_dir(path).output()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(WorktreeError::GitError(format!(
+            "git {}: {}",
+            args.join(" "),
+            stderr.trim()
+        )));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Abbreviated commit hash for messages
+pub fn short_commit(commit: &str) -> &str {
+    &commit[..commit.len().min(8)]
+}
+
+/// Run git in a test fixture repo, panicking if it fails; returns trimmed stdout
+#[cfg(test)]
+pub fn test_git(dir: &Path, args: &[&str]) -> String {
+    let output = Command::new("git").args(args).current_dir(dir).output().unwrap();
+    assert!(output.status.success(), "git {:?} failed: {}", args, String::from_utf8_lossy(&output.stderr));
+    String::from_utf8_lossy(&output.stdout).trim().to_string()
+}
+
 /// Get current HEAD commit hash
 pub fn get_head_commit(workspace_root: &Path) -> Result<String, WorktreeError> {
     get_head_commit_in_path(workspace_root)
@@ -180,14 +976,66 @@ fn get_head_commit_in_path(path: &Path) -> Result<String, WorktreeError> {
     Ok(commit)
 }
 
+/// List all worktrees of the repository (via `git worktree list --porcelain`)
+pub fn list_worktrees(workspace_root: &Path) -> Result<Vec<WorktreeEntry>, WorktreeError> {
+    let output = Command::new("git")
+        .args(["worktree", "list", "--porcelain"])
+        .current_dir(workspace_root)
+        .output()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(WorktreeError::GitError(format!(
+            "Failed to list worktrees: {}",
+            stderr
+        )));
+    }
+
+    Ok(parse_worktree_porcelain(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Parse porcelain output: blank-line separated blocks of `key [value]` lines
+fn parse_worktree_porcelain(output: &str) -> Vec<WorktreeEntry> {
+    let mut entries = Vec::new();
+    let mut current: Option<WorktreeEntry> = None;
+
+    for line in output.lines() {
+        let (key, value) = line.split_once(' ').unwrap_or((line, ""));
+        match key {
+            "worktree" => {
+                entries.extend(current.take());
+                current = Some(WorktreeEntry {
+                    path: PathBuf::from(value),
+                    ..Default::default()
+                });
+            }
+            _ => {
+                let Some(entry) = current.as_mut() else { continue };
+                match key {
+                    "HEAD" => entry.head = Some(value.to_string()),
+                    "branch" => {
+                        entry.branch = Some(value.strip_prefix("refs/heads/").unwrap_or(value).to_string())
+                    }
+                    "detached" => entry.detached = true,
+                    "locked" => entry.locked = Some(value.to_string()),
+                    "prunable" => entry.prunable = Some(value.to_string()),
+                    _ => {}
+                }
+            }
+        }
+    }
+    entries.extend(current);
+
+    entries
+}
+
 // ============================================================================
 // Merge Conflict Handling
 // ============================================================================
 
-/// Check if the repository is in a merge conflict state
+/// Check if the repository (or a linked worktree) is in a merge conflict state
 pub fn is_in_merge_conflict(workspace_root: &Path) -> Result<bool, WorktreeError> {
-    let merge_head = workspace_root.join(".git/MERGE_HEAD");
-    Ok(merge_head.exists())
+    Ok(git_path(workspace_root, "MERGE_HEAD").exists())
 }
 
 /// Get the branch name being merged (from MERGE_HEAD)
@@ -284,6 +1132,7 @@ pub fn complete_merge(workspace_root: &Path) -> Result<(), WorktreeError> {
     // Commit using the MERGE_MSG (git will use it automatically)
     let output = Command::new("git")
         .args(["commit", "--no-edit"])
+        .args(sign_args(workspace_root))
         .current_dir(workspace_root)
         .output()?;
 
@@ -338,37 +1187,12 @@ mod tests {
         let temp = TempDir::new().unwrap();
         let repo_path = temp.path().to_path_buf();
 
-        Command::new("git")
-            .arg("init")
-            .current_dir(&repo_path)
-            .output()
-            .unwrap();
-
-        Command::new("git")
-            .args(["config", "user.name", "Test"])
-            .current_dir(&repo_path)
-            .output()
-            .unwrap();
-
-        Command::new("git")
-            .args(["config", "user.email", "test@test.com"])
-            .current_dir(&repo_path)
-            .output()
-            .unwrap();
-
+        test_git(&repo_path, &["init"]);
+        test_git(&repo_path, &["config", "user.name", "Test"]);
+        test_git(&repo_path, &["config", "user.email", "test@test.com"]);
         fs::write(repo_path.join("test.txt"), "test").unwrap();
-
-        Command::new("git")
-            .args(["add", "."])
-            .current_dir(&repo_path)
-            .output()
-            .unwrap();
-
-        Command::new("git")
-            .args(["commit", "-m", "init"])
-            .current_dir(&repo_path)
-            .output()
-            .unwrap();
+        test_git(&repo_path, &["add", "."]);
+        test_git(&repo_path, &["commit", "-m", "init"]);
 
         (temp, repo_path)
     }
@@ -378,11 +1202,256 @@ mod tests {
         let (_temp, repo_path) = init_test_repo();
         let info = create_worktree(&repo_path, "test-bead").unwrap();
 
-        assert_eq!(info.bead_id, "test-bead");
         assert_eq!(info.branch, "bacchus/test-bead");
         assert!(info.path.exists());
     }
 
+    #[test]
+    fn test_create_worktree_from_base() {
+        let (_temp, repo_path) = init_test_repo();
+        let base_commit = get_head_commit(&repo_path).unwrap();
+
+        test_git(&repo_path, &["branch", "base"]);
+        test_git(&repo_path, &["commit", "--allow-empty", "-m", "later"]);
+
+        let info = create_worktree_from(&repo_path, "from-base", Some("base")).unwrap();
+        assert_eq!(info.head_commit, base_commit);
+        assert_ne!(get_head_commit(&repo_path).unwrap(), base_commit);
+    }
+
+    #[test]
+    fn test_parse_worktree_porcelain() {
+        let output = "worktree /repo\nHEAD abc\nbranch refs/heads/main\n\n\
+                      worktree /repo/.bacchus/worktrees/b1\nHEAD def\nbranch refs/heads/bacchus/b1\nlocked in use\n\n\
+                      worktree /tmp/gone\nHEAD 123\ndetached\nprunable gitdir file points to non-existent location\n";
+        let entries = parse_worktree_porcelain(output);
+
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].branch.as_deref(), Some("main"));
+        assert_eq!(entries[1].branch.as_deref(), Some("bacchus/b1"));
+        assert_eq!(entries[1].locked.as_deref(), Some("in use"));
+        assert!(entries[2].detached);
+        assert!(entries[2].prunable.is_some());
+    }
+
+    #[test]
+    fn test_rebase_worktree() {
+        let (_temp, repo_path) = init_test_repo();
+        let target = test_git(&repo_path, &["branch", "--show-current"]);
+
+        let info = create_worktree(&repo_path, "rb").unwrap();
+        fs::write(info.path.join("feature.txt"), "feature").unwrap();
+        test_git(&info.path, &["add", "."]);
+        test_git(&info.path, &["commit", "-m", "feature"]);
+
+        fs::write(repo_path.join("other.txt"), "other").unwrap();
+        test_git(&repo_path, &["add", "other.txt"]);
+        test_git(&repo_path, &["commit", "-m", "other"]);
+
+        let outcome = rebase_worktree(&repo_path, "rb", &target).unwrap();
+        assert_eq!(outcome, RebaseOutcome::Rebased(1));
+        assert!(info.path.join("other.txt").exists());
+
+        // Both sides edit the same file
+        fs::write(info.path.join("test.txt"), "branch").unwrap();
+        test_git(&info.path, &["commit", "-am", "branch edit"]);
+        fs::write(repo_path.join("test.txt"), "target").unwrap();
+        test_git(&repo_path, &["commit", "-am", "target edit"]);
+
+        let outcome = rebase_worktree(&repo_path, "rb", &target).unwrap();
+        assert_eq!(outcome, RebaseOutcome::Conflicts(vec!["test.txt".to_string()]));
+        assert!(is_rebase_in_progress(&info.path).unwrap());
+
+        abort_rebase(&info.path).unwrap();
+        assert!(!is_rebase_in_progress(&info.path).unwrap());
+    }
+
+    #[test]
+    fn test_push_worktree() {
+        let (_temp, repo_path) = init_test_repo();
+        let remote = TempDir::new().unwrap();
+        test_git(remote.path(), &["init", "--bare"]);
+        test_git(&repo_path, &["remote", "add", "origin", &remote.path().to_string_lossy()]);
+
+        create_worktree(&repo_path, "pb").unwrap();
+
+        let info = push_worktree(&repo_path, "pb", "origin", false).unwrap();
+        assert_eq!(info.branch, "bacchus/pb");
+        assert_eq!(info.remote_url, remote.path().to_string_lossy());
+        assert!(info.tracking_created);
+
+        let info = push_worktree(&repo_path, "pb", "origin", true).unwrap();
+        assert!(!info.tracking_created);
+
+        assert!(push_worktree(&repo_path, "pb", "nope", false).is_err());
+    }
+
+    #[test]
+    fn test_sync_worktree() {
+        let (_temp, repo_path) = init_test_repo();
+        let remote = TempDir::new().unwrap();
+        test_git(remote.path(), &["init", "--bare"]);
+        test_git(&repo_path, &["branch", "-M", "main"]);
+        test_git(&repo_path, &["remote", "add", "origin", &remote.path().to_string_lossy()]);
+        test_git(&repo_path, &["push", "origin", "main"]);
+
+        let info = create_worktree(&repo_path, "sb").unwrap();
+        fs::write(info.path.join("feature.txt"), "feature").unwrap();
+        test_git(&info.path, &["add", "."]);
+        test_git(&info.path, &["commit", "-m", "feature"]);
+
+        fs::write(repo_path.join("other.txt"), "other").unwrap();
+        test_git(&repo_path, &["add", "other.txt"]);
+        test_git(&repo_path, &["commit", "-m", "other"]);
+        test_git(&repo_path, &["push", "origin", "main"]);
+
+        assert_eq!(sync_worktree(&repo_path, "sb", "main").unwrap(), SyncOutcome::Merged(1));
+        assert!(info.path.join("other.txt").exists());
+        assert_eq!(sync_worktree(&repo_path, "sb", "main").unwrap(), SyncOutcome::Merged(0));
+
+        // Both sides edit the same file
+        fs::write(info.path.join("test.txt"), "branch").unwrap();
+        test_git(&info.path, &["commit", "-am", "branch edit"]);
+        fs::write(repo_path.join("test.txt"), "target").unwrap();
+        test_git(&repo_path, &["commit", "-am", "target edit"]);
+        test_git(&repo_path, &["push", "origin", "main"]);
+
+        let outcome = sync_worktree(&repo_path, "sb", "main").unwrap();
+        assert_eq!(outcome, SyncOutcome::Conflicts(vec!["test.txt".to_string()]));
+        assert!(is_in_merge_conflict(&info.path).unwrap());
+        assert!(!is_in_merge_conflict(&repo_path).unwrap());
+    }
+
+    #[test]
+    fn test_squash_worktree() {
+        let (_temp, repo_path) = init_test_repo();
+        test_git(&repo_path, &["branch", "-M", "main"]);
+        let base = get_head_commit(&repo_path).unwrap();
+
+        let info = create_worktree(&repo_path, "sq").unwrap();
+        for i in 0..3 {
+            fs::write(info.path.join(format!("wip{}.txt", i)), "wip").unwrap();
+            test_git(&info.path, &["add", "."]);
+            test_git(&info.path, &["commit", "-m", &format!("wip {}", i)]);
+        }
+
+        assert_eq!(squash_worktree(&repo_path, "sq", "main", "Add wip files").unwrap(), 3);
+        assert_eq!(test_git(&info.path, &["rev-list", "--count", &format!("{}..HEAD", base)]), "1");
+        assert_eq!(test_git(&info.path, &["log", "-1", "--format=%s"]), "Add wip files");
+        assert!(info.path.join("wip2.txt").exists());
+
+        // The merge brings in the single squashed commit
+        let merge_commit = merge_worktree(&repo_path, "sq", "main", None, false).unwrap();
+        assert_eq!(merge_commit, get_head_commit(&repo_path).unwrap());
+        assert_eq!(test_git(&repo_path, &["rev-list", "--count", &format!("{}..main", base)]), "1");
+
+        create_release_tag(&repo_path, "bacchus/sq/1700000000", &merge_commit, "Release bead sq").unwrap();
+        assert_eq!(test_git(&repo_path, &["rev-list", "-n", "1", "bacchus/sq/1700000000"]), merge_commit);
+        assert_eq!(test_git(&repo_path, &["cat-file", "-t", "bacchus/sq/1700000000"]), "tag");
+        assert!(create_release_tag(&repo_path, "bacchus/sq/1700000000", &merge_commit, "again").is_err());
+
+        assert_eq!(squash_worktree(&repo_path, "sq", "main", "nothing").unwrap(), 0);
+    }
+
+    #[test]
+    fn test_merge_worktree_fast_forward() {
+        let (_temp, repo_path) = init_test_repo();
+        test_git(&repo_path, &["branch", "-M", "main"]);
+
+        // A message alone doesn't force a merge commit
+        for bead in ["ff", "noff"] {
+            let info = create_worktree(&repo_path, bead).unwrap();
+            fs::write(info.path.join(format!("{}.txt", bead)), bead).unwrap();
+            test_git(&info.path, &["add", "."]);
+            test_git(&info.path, &["commit", "-m", bead]);
+        }
+        let tip = test_git(&repo_path, &["rev-parse", "bacchus/ff"]);
+        assert_eq!(merge_worktree(&repo_path, "ff", "main", Some("Merge ff"), false).unwrap(), tip);
+
+        let merge_commit = merge_worktree(&repo_path, "noff", "main", Some("Merge noff"), true).unwrap();
+        assert_eq!(test_git(&repo_path, &["log", "-1", "--format=%s", &merge_commit]), "Merge noff");
+        assert_eq!(test_git(&repo_path, &["rev-list", "--parents", "-n", "1", &merge_commit]).split(' ').count(), 3);
+    }
+
+    #[test]
+    fn test_check_branch_protection() {
+        let (_temp, repo_path) = init_test_repo();
+        let target = test_git(&repo_path, &["branch", "--show-current"]);
+
+        let info = create_worktree(&repo_path, "lin").unwrap();
+        fs::write(info.path.join("feature.txt"), "feature").unwrap();
+        test_git(&info.path, &["add", "."]);
+        test_git(&info.path, &["commit", "-m", "feature"]);
+        assert!(check_branch_protection(&repo_path, "lin", &target).is_ok());
+
+        test_git(&repo_path, &["commit", "--allow-empty", "-m", "diverge"]);
+        assert!(matches!(
+            check_branch_protection(&repo_path, "lin", &target),
+            Err(WorktreeError::ProtectionViolation(_))
+        ));
+
+        rebase_worktree(&repo_path, "lin", &target).unwrap();
+        assert!(check_branch_protection(&repo_path, "lin", &target).is_ok());
+    }
+
+    #[test]
+    fn test_merge_requires_signature() {
+        let (_temp, repo_path) = init_test_repo();
+        let target = test_git(&repo_path, &["branch", "--show-current"]);
+
+        let info = create_worktree(&repo_path, "sig").unwrap();
+        fs::create_dir_all(repo_path.join(".bacchus")).unwrap();
+        fs::write(repo_path.join(".bacchus/config.toml"), "require_signatures = true\n").unwrap();
+
+        match merge_worktree(&repo_path, "sig", &target, None, false) {
+            Err(WorktreeError::UnsignedCommit { bead_id, commit_hash }) => {
+                assert_eq!(bead_id, "sig");
+                assert_eq!(commit_hash, info.head_commit);
+            }
+            other => panic!("expected UnsignedCommit, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_stash_list() {
+        let output = "stash@{0}\x001700000100\x00On bacchus/b2: bacchus:b2: switching to urgent fix\n\
+                      stash@{1}\x001700000000\x00WIP on main: abc123 init\n";
+        let entries = parse_stash_list(output);
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].stash_ref, "stash@{0}");
+        assert_eq!(entries[0].bead_id.as_deref(), Some("b2"));
+        assert_eq!(entries[0].message, "switching to urgent fix");
+        assert_eq!(entries[0].created_at, 1700000100);
+        assert_eq!(entries[1].bead_id, None);
+        assert_eq!(entries[1].message, "abc123 init");
+    }
+
+    #[test]
+    fn test_stash_worktree() {
+        let (_temp, repo_path) = init_test_repo();
+        let a = create_worktree(&repo_path, "sa").unwrap();
+        let b = create_worktree(&repo_path, "sb").unwrap();
+
+        assert!(!stash_worktree(&repo_path, "sa", "nothing yet").unwrap());
+
+        fs::write(a.path.join("a.txt"), "a").unwrap();
+        fs::write(b.path.join("b.txt"), "b").unwrap();
+        assert!(stash_worktree(&repo_path, "sa", "shelve a").unwrap());
+        assert!(stash_worktree(&repo_path, "sb", "shelve b").unwrap());
+        assert!(!a.path.join("a.txt").exists());
+
+        // sb's stash is newer, but unstashing sa must restore sa's changes
+        unstash_worktree(&repo_path, "sa").unwrap();
+        assert!(a.path.join("a.txt").exists());
+        assert!(!a.path.join("b.txt").exists());
+
+        let stashes = list_stashes(&repo_path).unwrap();
+        assert_eq!(stashes.len(), 1);
+        assert_eq!(stashes[0].bead_id.as_deref(), Some("sb"));
+        assert!(unstash_worktree(&repo_path, "sa").is_err());
+    }
+
     #[test]
     fn test_get_head_commit() {
         let (_temp, repo_path) = init_test_repo();
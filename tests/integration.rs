@@ -238,6 +238,526 @@ mod worktree_tests {
         // Merge should fail due to conflict
         assert!(!output.status.success() || repo_path.join(".git/MERGE_HEAD").exists());
     }
+
+    #[test]
+    fn test_create_worktree_no_claim() {
+        let (_temp, repo_path) = init_test_repo();
+        let db_path = repo_path.join("test.db");
+
+        let output = Command::new("cargo")
+            .args(["run", "--", "create-worktree", "manual-bead", "--no-claim"])
+            .current_dir(env!("CARGO_MANIFEST_DIR"))
+            .env("CLAUDE_PROJECT_DIR", &repo_path)
+            .env("BACCHUS_DB_PATH", &db_path)
+            .output()
+            .unwrap();
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(output.status.success(), "create-worktree failed: {}", stdout);
+        assert!(stdout.contains("\"claimed\": false"));
+        assert!(repo_path.join(".bacchus/worktrees/manual-bead").exists());
+
+        // No claim recorded
+        let output = Command::new("cargo")
+            .args(["run", "--", "list"])
+            .current_dir(env!("CARGO_MANIFEST_DIR"))
+            .env("CLAUDE_PROJECT_DIR", &repo_path)
+            .env("BACCHUS_DB_PATH", &db_path)
+            .output()
+            .unwrap();
+        assert!(String::from_utf8_lossy(&output.stdout).contains("\"total\": 0"));
+    }
+
+    #[test]
+    fn test_clean_orphaned_worktree() {
+        let (_temp, repo_path) = init_test_repo();
+        let db_path = repo_path.join("test.db");
+
+        let run = |args: &[&str]| {
+            let output = Command::new("cargo")
+                .arg("run")
+                .arg("--")
+                .args(args)
+                .current_dir(env!("CARGO_MANIFEST_DIR"))
+                .env("CLAUDE_PROJECT_DIR", &repo_path)
+                .env("BACCHUS_DB_PATH", &db_path)
+                .output()
+                .unwrap();
+            assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+            serde_json::from_slice::<serde_json::Value>(&output.stdout).unwrap()
+        };
+
+        run(&["create-worktree", "orphan-bead", "--no-claim"]);
+
+        let report = run(&["clean", "--dry-run"]);
+        assert_eq!(report["orphaned_worktrees"][0], "orphan-bead");
+        assert!(repo_path.join(".bacchus/worktrees/orphan-bead").exists());
+
+        let report = run(&["clean"]);
+        assert_eq!(report["orphaned_worktrees"][0], "orphan-bead");
+        assert!(!repo_path.join(".bacchus/worktrees/orphan-bead").exists());
+
+        // Uncommitted work keeps the worktree unless forced
+        run(&["create-worktree", "wip-bead", "--no-claim"]);
+        fs::write(repo_path.join(".bacchus/worktrees/wip-bead/wip.txt"), "wip").unwrap();
+        let report = run(&["clean"]);
+        assert_eq!(report["orphaned_worktrees"].as_array().unwrap().len(), 0);
+        assert_eq!(report["skipped_worktrees"][0], "wip-bead: uncommitted changes");
+        assert!(repo_path.join(".bacchus/worktrees/wip-bead/wip.txt").exists());
+
+        let report = run(&["clean", "--force"]);
+        assert_eq!(report["orphaned_worktrees"][0], "wip-bead");
+        assert!(!repo_path.join(".bacchus/worktrees/wip-bead").exists());
+    }
+
+    /// PATH with a fake `bd` that knows every bead but those in `$FAKE_BD_MISSING` and
+    /// accepts every status update except `$FAKE_BD_REJECT_STATUS`; `bd ready` prints
+    /// `$FAKE_BD_READY` (default: no beads)
+    fn fake_bd_path(dir: &std::path::Path) -> String {
+        use std::os::unix::fs::PermissionsExt;
+
+        let bin = dir.join("bin");
+        fs::create_dir(&bin).unwrap();
+        fs::write(
+            bin.join("bd"),
+            "#!/bin/sh\n\
+             [ \"$1\" = show ] && case \" $FAKE_BD_MISSING \" in *\" $2 \"*) echo \"no issue found: $2\" >&2; exit 1;; esac\n\
+             [ \"$1\" = show ] && printf '[{\"id\": \"%s\", \"title\": \"T\", \"status\": \"open\", \"priority\": 2}]' \"$2\"\n\
+             [ \"$1\" = ready ] && printf '%s' \"${FAKE_BD_READY:-[]}\"\n\
+             [ \"$1\" = update ] && [ \"$4\" = \"$FAKE_BD_REJECT_STATUS\" ] && echo \"invalid status: $4\" >&2 && exit 1\n\
+             exit 0\n",
+        )
+        .unwrap();
+        fs::set_permissions(bin.join("bd"), fs::Permissions::from_mode(0o755)).unwrap();
+        format!("{}:{}", bin.display(), std::env::var("PATH").unwrap())
+    }
+
+    #[test]
+    fn test_resolve_frees_file_locks() {
+        let (temp, repo_path) = init_test_repo();
+        let db_path = repo_path.join("test.db");
+
+        let path = fake_bd_path(temp.path());
+
+        // The built binary directly: a different PATH would make cargo rebuild
+        let run = |args: &[&str]| {
+            let output = Command::new(env!("CARGO_BIN_EXE_bacchus"))
+                .args(args)
+                .current_dir(&repo_path)
+                .env("CLAUDE_PROJECT_DIR", &repo_path)
+                .env("BACCHUS_DB_PATH", &db_path)
+                .env("PATH", &path)
+                .output()
+                .unwrap();
+            serde_json::from_slice::<serde_json::Value>(&output.stdout)
+                .unwrap_or_else(|_| panic!("{:?}: {}", args, String::from_utf8_lossy(&output.stderr)))
+        };
+        let git = |dir: &std::path::Path, args: &[&str]| {
+            let output = Command::new("git").args(args).current_dir(dir).output().unwrap();
+            assert!(output.status.success(), "git {:?}: {}", args, String::from_utf8_lossy(&output.stderr));
+        };
+        git(&repo_path, &["branch", "-M", "main"]);
+
+        // Conflicting edits on the bead's branch and on main
+        run(&["create-worktree", "conflict-bead"]);
+        let worktree = repo_path.join(".bacchus/worktrees/conflict-bead");
+        fs::write(worktree.join("test.txt"), "bead").unwrap();
+        git(&worktree, &["commit", "-qam", "bead edit"]);
+        fs::write(repo_path.join("test.txt"), "main").unwrap();
+        git(&repo_path, &["commit", "-qam", "main edit"]);
+
+        assert_eq!(run(&["lock", "test.txt", "--bead", "conflict-bead"])["success"], true);
+        assert_eq!(run(&["release", "conflict-bead", "--status", "done"])["success"], false);
+
+        fs::write(repo_path.join("test.txt"), "resolved").unwrap();
+        git(&repo_path, &["add", "test.txt"]);
+        assert_eq!(run(&["resolve", "conflict-bead"])["success"], true);
+
+        let conn = rusqlite::Connection::open(&db_path).unwrap();
+        let locks: i64 = conn.query_row("SELECT COUNT(*) FROM file_locks", [], |row| row.get(0)).unwrap();
+        assert_eq!(locks, 0);
+    }
+
+    #[test]
+    fn test_release_commit_message_forces_merge_commit() {
+        let (temp, repo_path) = init_test_repo();
+        let db_path = repo_path.join("test.db");
+        let path = fake_bd_path(temp.path());
+
+        let run = |args: &[&str]| {
+            let output = Command::new(env!("CARGO_BIN_EXE_bacchus"))
+                .args(args)
+                .current_dir(&repo_path)
+                .env("CLAUDE_PROJECT_DIR", &repo_path)
+                .env("BACCHUS_DB_PATH", &db_path)
+                .env("PATH", &path)
+                .output()
+                .unwrap();
+            serde_json::from_slice::<serde_json::Value>(&output.stdout)
+                .unwrap_or_else(|_| panic!("{:?}: {}", args, String::from_utf8_lossy(&output.stderr)))
+        };
+        let git = |dir: &std::path::Path, args: &[&str]| {
+            let output = Command::new("git").args(args).current_dir(dir).output().unwrap();
+            assert!(output.status.success(), "git {:?}: {}", args, String::from_utf8_lossy(&output.stderr));
+            String::from_utf8_lossy(&output.stdout).trim().to_string()
+        };
+        git(&repo_path, &["branch", "-M", "main"]);
+        let commit_on_bead = |bead_id: &str| {
+            run(&["create-worktree", bead_id]);
+            let worktree = repo_path.join(".bacchus/worktrees").join(bead_id);
+            fs::write(worktree.join(format!("{}.txt", bead_id)), bead_id).unwrap();
+            git(&worktree, &["add", "."]);
+            git(&worktree, &["commit", "-qm", bead_id]);
+        };
+
+        // The branch could fast-forward, but the message needs a merge commit
+        commit_on_bead("msg-bead");
+        let released = run(&["release", "msg-bead", "--status", "done", "--commit-message", "ship {bead_id}"]);
+        assert_eq!(released["success"], true);
+        assert_eq!(released["message"], "Released msg-bead with status done");
+        assert_eq!(git(&repo_path, &["log", "-1", "--format=%s"]), "ship msg-bead");
+        assert_eq!(git(&repo_path, &["rev-list", "--parents", "-n", "1", "HEAD"]).split(' ').count(), 3);
+
+        // Linear history only fast-forwards, so the release says the message went unused
+        fs::write(repo_path.join(".bacchus/config.toml"), "require_linear_history = true\n").unwrap();
+        commit_on_bead("linear-bead");
+        let released = run(&["release", "linear-bead", "--status", "done", "--commit-message", "ship {bead_id}"]);
+        assert_eq!(released["success"], true);
+        assert!(released["message"].as_str().unwrap().contains("--commit-message not applied"));
+        assert_eq!(git(&repo_path, &["log", "-1", "--format=%s"]), "linear-bead");
+
+        // A configured template is skipped on a fast-forward, and the release says so
+        fs::write(repo_path.join(".bacchus/config.toml"), "merge_commit_template = \"ship {bead_id}\"\n").unwrap();
+        commit_on_bead("template-bead");
+        let released = run(&["release", "template-bead", "--status", "done"]);
+        assert!(released["message"].as_str().unwrap().contains("merge_commit_template not applied: the merge fast-forwarded"));
+        assert_eq!(git(&repo_path, &["log", "-1", "--format=%s"]), "template-bead");
+    }
+
+    #[test]
+    fn test_watch_pauses_claims_on_shutdown() {
+        use std::io::{BufRead, BufReader, Read};
+
+        let (temp, repo_path) = init_test_repo();
+        let db_path = repo_path.join("test.db");
+        let mut child = Command::new(env!("CARGO_BIN_EXE_bacchus"))
+            .args(["watch", "--agent", "agent-w", "--interval", "1"])
+            .current_dir(&repo_path)
+            .env("CLAUDE_PROJECT_DIR", &repo_path)
+            .env("BACCHUS_DB_PATH", &db_path)
+            .env("PATH", fake_bd_path(temp.path()))
+            .env("FAKE_BD_READY", r#"[{"id": "watch-bead", "title": "W", "status": "open", "priority": 2}]"#)
+            .stdout(std::process::Stdio::piped())
+            .spawn()
+            .unwrap();
+        let mut stdout = BufReader::new(child.stdout.take().unwrap());
+
+        let mut line = String::new();
+        stdout.read_line(&mut line).unwrap();
+        let event: serde_json::Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(event["event"], "claimed");
+        assert_eq!(event["bead_id"], "watch-bead");
+
+        Command::new("kill").args(["-TERM", &child.id().to_string()]).status().unwrap();
+        let mut rest = String::new();
+        stdout.read_to_string(&mut rest).unwrap();
+        assert!(child.wait().unwrap().success(), "{}", rest);
+        assert!(rest.starts_with(r#"{"bead_id":"watch-bead","event":"paused"}"#), "{}", rest);
+
+        // The claim moved to paused_claims; the worktree and its branch stay
+        let conn = rusqlite::Connection::open(&db_path).unwrap();
+        let claims: i64 = conn.query_row("SELECT COUNT(*) FROM claims", [], |row| row.get(0)).unwrap();
+        assert_eq!(claims, 0);
+        let paused: String = conn.query_row("SELECT bead_id FROM paused_claims", [], |row| row.get(0)).unwrap();
+        assert_eq!(paused, "watch-bead");
+        assert!(repo_path.join(".bacchus/worktrees/watch-bead").is_dir());
+    }
+
+    #[test]
+    fn test_pause_explains_unregistered_status() {
+        let (temp, repo_path) = init_test_repo();
+        let db_path = repo_path.join("test.db");
+        let path = fake_bd_path(temp.path());
+
+        let run = |args: &[&str]| {
+            let output = Command::new(env!("CARGO_BIN_EXE_bacchus"))
+                .args(args)
+                .current_dir(&repo_path)
+                .env("CLAUDE_PROJECT_DIR", &repo_path)
+                .env("BACCHUS_DB_PATH", &db_path)
+                .env("PATH", &path)
+                .env("FAKE_BD_REJECT_STATUS", "paused")
+                .output()
+                .unwrap();
+            serde_json::from_slice::<serde_json::Value>(&output.stdout)
+                .unwrap_or_else(|_| panic!("{:?}: {}", args, String::from_utf8_lossy(&output.stderr)))
+        };
+
+        run(&["create-worktree", "pause-bead"]);
+        let paused = run(&["pause", "pause-bead"]);
+        assert_eq!(paused["success"], false);
+        let message = paused["message"].as_str().unwrap();
+        assert!(message.contains("invalid status: paused"), "{}", message);
+        assert!(message.contains("bd config set status.custom \"paused,split\""), "{}", message);
+
+        // The claim is untouched
+        let conn = rusqlite::Connection::open(&db_path).unwrap();
+        let claims: i64 = conn.query_row("SELECT COUNT(*) FROM claims", [], |row| row.get(0)).unwrap();
+        assert_eq!(claims, 1);
+    }
+
+    #[test]
+    fn test_split_reports_partial_progress() {
+        let (temp, repo_path) = init_test_repo();
+        let db_path = repo_path.join("test.db");
+        let path = fake_bd_path(temp.path());
+
+        let run = |args: &[&str]| {
+            let output = Command::new(env!("CARGO_BIN_EXE_bacchus"))
+                .args(args)
+                .current_dir(&repo_path)
+                .env("CLAUDE_PROJECT_DIR", &repo_path)
+                .env("BACCHUS_DB_PATH", &db_path)
+                .env("PATH", &path)
+                .env("FAKE_BD_MISSING", "part-a part-b")
+                .env("FAKE_BD_REJECT_STATUS", "split")
+                .output()
+                .unwrap();
+            serde_json::from_slice::<serde_json::Value>(&output.stdout)
+                .unwrap_or_else(|_| panic!("{:?}: {}", args, String::from_utf8_lossy(&output.stderr)))
+        };
+
+        run(&["create-worktree", "big-bead"]);
+
+        // An ID that already exists stops the split before anything is created
+        let split = run(&["split", "big-bead", "--into", "part-a", "taken"]);
+        assert_eq!(split["success"], false);
+        assert_eq!(split["message"], "--into IDs already exist: taken");
+        assert_eq!(split["created"], serde_json::json!([]));
+
+        // A later failure lists the beads already created
+        let split = run(&["split", "big-bead", "--into", "part-a", "part-b"]);
+        assert_eq!(split["success"], false);
+        assert_eq!(split["created"], serde_json::json!(["part-a", "part-b"]));
+        assert!(split["message"].as_str().unwrap().ends_with("(already created: part-a, part-b)"));
+    }
+
+    #[test]
+    fn test_reset_requires_confirm() {
+        let (_temp, repo_path) = init_test_repo();
+        let db_path = repo_path.join("test.db");
+
+        let run = |args: &[&str]| {
+            let output = Command::new("cargo")
+                .arg("run")
+                .arg("--")
+                .args(args)
+                .current_dir(env!("CARGO_MANIFEST_DIR"))
+                .env("CLAUDE_PROJECT_DIR", &repo_path)
+                .env("BACCHUS_DB_PATH", &db_path)
+                .output()
+                .unwrap();
+            assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+            serde_json::from_slice::<serde_json::Value>(&output.stdout).unwrap()
+        };
+
+        run(&["create-worktree", "broken-bead", "--no-claim"]);
+        let worktree = repo_path.join(".bacchus/worktrees/broken-bead");
+        fs::write(worktree.join("scratch.txt"), "uncommitted").unwrap();
+
+        assert_eq!(run(&["reset", "broken-bead"])["success"], false);
+        assert!(worktree.exists());
+
+        let report = run(&["reset", "broken-bead", "--confirm"]);
+        assert_eq!(report["success"], true);
+        assert_eq!(report["worktree_removed"], true);
+        assert_eq!(report["branch_deleted"], true);
+        assert!(!worktree.exists());
+
+        // Nothing left to reset still succeeds
+        assert_eq!(run(&["reset", "broken-bead", "--confirm"])["success"], true);
+    }
+
+    #[test]
+    fn test_verify_fix() {
+        let (_temp, repo_path) = init_test_repo();
+        let db_path = repo_path.join("test.db");
+
+        let run = |args: &[&str]| {
+            let output = Command::new("cargo")
+                .arg("run")
+                .arg("--")
+                .args(args)
+                .current_dir(env!("CARGO_MANIFEST_DIR"))
+                .env("CLAUDE_PROJECT_DIR", &repo_path)
+                .env("BACCHUS_DB_PATH", &db_path)
+                .output()
+                .unwrap();
+            assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+            serde_json::from_slice::<serde_json::Value>(&output.stdout).unwrap()
+        };
+
+        run(&["create-worktree", "orphan-bead", "--no-claim"]);
+        let check = |report: &serde_json::Value, name: &str| {
+            report["checks"].as_array().unwrap().iter().find(|c| c["name"] == name).unwrap()["passed"].clone()
+        };
+
+        // An orphaned worktree is only a warning
+        let report = run(&["verify"]);
+        assert_eq!(report["ok"], true);
+        assert_eq!(check(&report, "no_orphaned_worktrees"), false);
+        assert_eq!(check(&report, "schema_version_current"), true);
+
+        let report = run(&["verify", "--fix"]);
+        assert_eq!(report["fixed"]["orphaned_worktrees"][0], "orphan-bead");
+        assert_eq!(check(&report, "no_orphaned_worktrees"), true);
+        assert!(!repo_path.join(".bacchus/worktrees/orphan-bead").exists());
+    }
+
+    #[test]
+    fn test_recover_missing_claim() {
+        let (_temp, repo_path) = init_test_repo();
+        let db_path = repo_path.join("test.db");
+
+        let run = |args: &[&str]| {
+            let output = Command::new("cargo")
+                .arg("run")
+                .arg("--")
+                .args(args)
+                .current_dir(env!("CARGO_MANIFEST_DIR"))
+                .env("CLAUDE_PROJECT_DIR", &repo_path)
+                .env("BACCHUS_DB_PATH", &db_path)
+                .output()
+                .unwrap();
+            assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+            serde_json::from_slice::<serde_json::Value>(&output.stdout).unwrap()
+        };
+
+        // Worktree without a claim, as if the claim row was lost
+        run(&["create-worktree", "lost-bead", "--no-claim"]);
+
+        let report = run(&["recover", "lost-bead", "--dry-run"]);
+        assert_eq!(report["steps"][0]["status"], "ok");
+        assert_eq!(report["steps"][1]["check"], "claim");
+        assert_eq!(report["steps"][1]["status"], "would_fix");
+        assert_eq!(run(&["list"])["total"], 0);
+
+        let report = run(&["recover", "lost-bead"]);
+        assert_eq!(report["steps"][1]["status"], "fixed");
+        let list = run(&["list"]);
+        assert_eq!(list["total"], 1);
+        assert_eq!(list["claims"][0]["agent_id"], "recovered");
+
+        // Missing worktree can't be recovered automatically
+        let report = run(&["recover", "no-such-bead"]);
+        assert_eq!(report["success"], false);
+        assert_eq!(report["steps"][0]["status"], "failed");
+    }
+
+    #[test]
+    fn test_stale_parallel_cleanup() {
+        let (_temp, repo_path) = init_test_repo();
+        let db_path = repo_path.join("test.db");
+
+        let run = |args: &[&str]| {
+            let output = Command::new("cargo")
+                .arg("run")
+                .arg("--")
+                .args(args)
+                .current_dir(env!("CARGO_MANIFEST_DIR"))
+                .env("CLAUDE_PROJECT_DIR", &repo_path)
+                .env("BACCHUS_DB_PATH", &db_path)
+                .output()
+                .unwrap();
+            assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+            serde_json::from_slice::<serde_json::Value>(&output.stdout).unwrap()
+        };
+
+        // Claimed worktrees without needing bd: create unclaimed, then recover the claim
+        for bead in ["stale-a", "stale-b", "stale-c"] {
+            run(&["create-worktree", bead, "--no-claim"]);
+            run(&["recover", bead]);
+        }
+        std::thread::sleep(std::time::Duration::from_millis(10));
+
+        let report = run(&["stale", "--minutes", "0", "--cleanup", "--parallel", "2"]);
+        assert_eq!(report["cleaned_up"].as_array().unwrap().len(), 3);
+        assert_eq!(run(&["list"])["total"], 0);
+
+        for bead in ["stale-a", "stale-b", "stale-c"] {
+            assert!(!repo_path.join(".bacchus/worktrees").join(bead).exists());
+        }
+        let branches = Command::new("git")
+            .args(["branch", "--list", "bacchus/*"])
+            .current_dir(&repo_path)
+            .output()
+            .unwrap();
+        assert!(String::from_utf8_lossy(&branches.stdout).trim().is_empty());
+    }
+
+    #[test]
+    fn test_remove_worktree_keep_branch() {
+        let (_temp, repo_path) = init_test_repo();
+        let db_path = repo_path.join("test.db");
+
+        let run = |args: &[&str]| {
+            Command::new("cargo")
+                .arg("run")
+                .arg("--")
+                .args(args)
+                .current_dir(env!("CARGO_MANIFEST_DIR"))
+                .env("CLAUDE_PROJECT_DIR", &repo_path)
+                .env("BACCHUS_DB_PATH", &db_path)
+                .output()
+                .unwrap()
+        };
+
+        assert!(run(&["create-worktree", "rm-bead", "--no-claim"]).status.success());
+
+        let output = run(&["remove-worktree", "rm-bead", "--keep-branch"]);
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(output.status.success(), "remove-worktree failed: {}", stdout);
+        assert!(stdout.contains("\"removed\": true"));
+        assert!(stdout.contains("\"branch_deleted\": false"));
+        assert!(stdout.contains("\"claim_removed\": false"));
+        assert!(!repo_path.join(".bacchus/worktrees/rm-bead").exists());
+
+        let output = Command::new("git")
+            .args(["branch", "--list", "bacchus/rm-bead"])
+            .current_dir(&repo_path)
+            .output()
+            .unwrap();
+        assert!(String::from_utf8_lossy(&output.stdout).contains("bacchus/rm-bead"));
+    }
+
+    #[test]
+    fn test_worktree_info() {
+        let (_temp, repo_path) = init_test_repo();
+        let db_path = repo_path.join("test.db");
+
+        let run = |args: &[&str]| {
+            Command::new("cargo")
+                .arg("run")
+                .arg("--")
+                .args(args)
+                .current_dir(env!("CARGO_MANIFEST_DIR"))
+                .env("CLAUDE_PROJECT_DIR", &repo_path)
+                .env("BACCHUS_DB_PATH", &db_path)
+                .output()
+                .unwrap()
+        };
+
+        assert!(run(&["create-worktree", "info-bead", "--no-claim"]).status.success());
+        fs::write(repo_path.join(".bacchus/worktrees/info-bead/new.txt"), "x").unwrap();
+
+        let output = run(&["worktree-info", "info-bead"]);
+        let info: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        assert_eq!(info["success"], true);
+        assert_eq!(info["current_branch"], "bacchus/info-bead");
+        assert_eq!(info["is_locked"], false);
+        assert_eq!(info["git_status"], serde_json::json!(["?? new.txt"]));
+        assert_eq!(info["claim"], serde_json::Value::Null);
+    }
 }
 
 // ============================================================================
@@ -279,6 +799,52 @@ mod db_tests {
         assert!(stdout.contains("\"count\": 0"));
     }
 
+    #[test]
+    fn test_status_disk_usage() {
+        let temp = TempDir::new().unwrap();
+        let db_path = init_test_db(&temp);
+        let worktrees = temp.path().join("worktrees");
+        fs::create_dir_all(worktrees.join("orphan/src")).unwrap();
+        fs::write(worktrees.join("orphan/a.txt"), vec![b'x'; 1000]).unwrap();
+        fs::write(worktrees.join("orphan/src/b.txt"), vec![b'y'; 24]).unwrap();
+
+        let status = |args: &[&str]| {
+            let output = Command::new("cargo")
+                .args(["run", "--", "status"])
+                .args(args)
+                .current_dir(env!("CARGO_MANIFEST_DIR"))
+                .env("BACCHUS_DB_PATH", &db_path)
+                .env("BACCHUS_WORKTREES", &worktrees)
+                .output()
+                .unwrap();
+            assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+            serde_json::from_slice::<serde_json::Value>(&output.stdout).unwrap()
+        };
+
+        assert_eq!(status(&[])["worktrees_total_bytes"], 1024);
+        assert!(status(&["--no-disk-usage"]).get("worktrees_total_bytes").is_none());
+    }
+
+    #[test]
+    fn test_status_uses_configured_worktrees_dir() {
+        let temp = TempDir::new().unwrap();
+        let db_path = init_test_db(&temp);
+        fs::create_dir_all(temp.path().join(".bacchus")).unwrap();
+        fs::write(temp.path().join(".bacchus/config.toml"), "worktrees_dir = \"trees\"\n").unwrap();
+        fs::create_dir_all(temp.path().join("trees/orphan")).unwrap();
+
+        let output = Command::new(env!("CARGO_BIN_EXE_bacchus"))
+            .args(["status", "--no-disk-usage"])
+            .current_dir(temp.path())
+            .env("BACCHUS_DB_PATH", &db_path)
+            .env_remove("BACCHUS_WORKTREES")
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+        let status: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        assert_eq!(status["orphaned_worktrees"], serde_json::json!(["orphan"]));
+    }
+
     #[test]
     fn test_list_empty_claims() {
         let temp = TempDir::new().unwrap();
@@ -312,6 +878,103 @@ mod db_tests {
         let stdout = String::from_utf8_lossy(&output.stdout);
         assert!(stdout.contains("stale_claims"));
     }
+
+    #[test]
+    fn test_db_integrity_check() {
+        let temp = TempDir::new().unwrap();
+        let db_path = init_test_db(&temp);
+
+        let output = Command::new("cargo")
+            .args(["run", "--", "db", "integrity-check"])
+            .current_dir(env!("CARGO_MANIFEST_DIR"))
+            .env("BACCHUS_DB_PATH", &db_path)
+            .output()
+            .unwrap();
+
+        assert!(output.status.success());
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(stdout.contains("\"ok\": true"));
+    }
+
+    #[test]
+    fn test_db_stats() {
+        let temp = TempDir::new().unwrap();
+        let db_path = init_test_db(&temp);
+
+        let output = Command::new("cargo")
+            .args(["run", "--", "db", "stats"])
+            .current_dir(env!("CARGO_MANIFEST_DIR"))
+            .env("BACCHUS_DB_PATH", &db_path)
+            .output()
+            .unwrap();
+
+        assert!(output.status.success());
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(stdout.contains("\"claims\": 0"));
+        assert!(stdout.contains("page_size"));
+    }
+
+    #[test]
+    fn test_audit_log() {
+        let temp = TempDir::new().unwrap();
+        let db_path = init_test_db(&temp);
+
+        Command::new("cargo")
+            .args(["run", "--", "release", "audited-bead", "--status", "failed"])
+            .current_dir(env!("CARGO_MANIFEST_DIR"))
+            .env("BACCHUS_DB_PATH", &db_path)
+            .output()
+            .unwrap();
+
+        let output = Command::new("cargo")
+            .args(["run", "--", "audit", "--bead-id", "audited-bead"])
+            .current_dir(env!("CARGO_MANIFEST_DIR"))
+            .env("BACCHUS_DB_PATH", &db_path)
+            .output()
+            .unwrap();
+
+        assert!(output.status.success());
+        let audit: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        // Newest first: the audit query itself (still running), then the release
+        assert_eq!(audit["total"], 2);
+        assert_eq!(audit["entries"][0]["command"], "audit");
+        assert_eq!(audit["entries"][0]["exit_code"], serde_json::Value::Null);
+        assert_eq!(audit["entries"][1]["command"], "release");
+        assert_eq!(audit["entries"][1]["args"]["release"]["status"], "failed");
+        assert_eq!(audit["entries"][1]["exit_code"], 0);
+    }
+
+    #[test]
+    fn test_audit_log_without_db_init() {
+        let temp = TempDir::new().unwrap();
+        let db_path = init_test_db(&temp);
+
+        let run = |args: &[&str]| {
+            Command::new("cargo")
+                .arg("run")
+                .arg("--")
+                .args(args)
+                .current_dir(env!("CARGO_MANIFEST_DIR"))
+                .env("BACCHUS_DB_PATH", &db_path)
+                .env("BEADS_DB_PATH", temp.path().join("missing-beads.db"))
+                .output()
+                .unwrap()
+        };
+
+        // depends-on skips DB init but is still audited; version never touches the DB
+        assert!(!run(&["depends-on", "bd-1"]).status.success());
+        assert!(run(&["version"]).status.success());
+
+        let output = run(&["audit"]);
+        assert!(output.status.success());
+        let audit: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        // Newest first: audit, depends-on, then the status run by init_test_db
+        assert_eq!(audit["total"], 3);
+        assert_eq!(audit["entries"][1]["command"], "depends-on");
+        assert_eq!(audit["entries"][1]["bead_id"], "bd-1");
+        assert_eq!(audit["entries"][1]["exit_code"], 1);
+        assert_eq!(audit["entries"][2]["command"], "status");
+    }
 }
 
 // ============================================================================
@@ -385,6 +1048,306 @@ export class Greeter {
 
         assert!(output.status.success());
     }
+
+    #[test]
+    fn test_index_emit_symbols() {
+        let temp = TempDir::new().unwrap();
+        let db_path = temp.path().join("test.db");
+
+        let test_file = temp.path().join("lib.rs");
+        fs::write(&test_file, "fn one() {}\n\nstruct Two;\n").unwrap();
+
+        let output = Command::new("cargo")
+            .args(["run", "--", "index", "--emit-symbols"])
+            .arg(&test_file)
+            .current_dir(env!("CARGO_MANIFEST_DIR"))
+            .env("BACCHUS_DB_PATH", &db_path)
+            .output()
+            .unwrap();
+
+        assert!(output.status.success());
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let lines: Vec<&str> = stdout.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines.iter().all(|l| l.starts_with('{') && l.contains("fq_name")));
+
+        // Dry run never creates the database
+        assert!(!db_path.exists());
+    }
+
+    #[test]
+    fn test_index_language_filter() {
+        let temp = TempDir::new().unwrap();
+        let db_path = temp.path().join("test.db");
+
+        fs::write(temp.path().join("lib.rs"), "fn one() {}\n").unwrap();
+        fs::write(temp.path().join("gen.js"), "function two() {}\n").unwrap();
+        fs::write(temp.path().join("app.py"), "def three():\n    pass\n").unwrap();
+
+        let emit = |extra: &[&str], env_languages: Option<&str>| {
+            let mut cmd = Command::new("cargo");
+            cmd.args(["run", "--", "index", "--emit-symbols"])
+                .args(extra)
+                .arg(temp.path())
+                .current_dir(env!("CARGO_MANIFEST_DIR"))
+                .env("BACCHUS_DB_PATH", &db_path)
+                .env_remove("BACCHUS_INDEX_LANGUAGES");
+            if let Some(languages) = env_languages {
+                cmd.env("BACCHUS_INDEX_LANGUAGES", languages);
+            }
+            let output = cmd.output().unwrap();
+            assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+            let mut langs: Vec<String> = String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .map(|l| serde_json::from_str::<serde_json::Value>(l).unwrap()["language"].as_str().unwrap().to_string())
+                .collect();
+            langs.sort();
+            langs
+        };
+
+        assert_eq!(emit(&["--languages", "rust,python"], None), vec!["python", "rust"]);
+        assert_eq!(emit(&["--exclude-languages", "javascript"], None), vec!["python", "rust"]);
+        assert_eq!(emit(&["--exclude-languages", "python"], Some("rust,python")), vec!["rust"]);
+
+        let output = Command::new("cargo")
+            .args(["run", "--", "index", "--emit-symbols", "--languages", "cobol"])
+            .arg(temp.path())
+            .current_dir(env!("CARGO_MANIFEST_DIR"))
+            .env("BACCHUS_DB_PATH", &db_path)
+            .output()
+            .unwrap();
+        assert!(!output.status.success());
+    }
+
+    #[test]
+    fn test_index_incremental() {
+        let (_temp, repo_path) = init_test_repo();
+        let db_path = repo_path.join("test.db");
+
+        let git = |args: &[&str]| {
+            Command::new("git").args(args).current_dir(&repo_path).output().unwrap();
+        };
+        let run = |args: &[&str]| {
+            let output = Command::new("cargo")
+                .arg("run")
+                .arg("--")
+                .args(args)
+                .current_dir(env!("CARGO_MANIFEST_DIR"))
+                .env("CLAUDE_PROJECT_DIR", &repo_path)
+                .env("BACCHUS_DB_PATH", &db_path)
+                .env_remove("BACCHUS_INDEX_LANGUAGES")
+                .output()
+                .unwrap();
+            assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+            serde_json::from_slice::<serde_json::Value>(&output.stdout).unwrap()
+        };
+        let symbols_in = |file: &str| run(&["symbols", "--file", file])["total_count"].as_i64().unwrap();
+
+        fs::write(repo_path.join("a.rs"), "fn alpha() {}\n").unwrap();
+        fs::write(repo_path.join("b.rs"), "fn beta() {}\n").unwrap();
+        fs::write(repo_path.join("c.rs"), "fn gamma() {}\n").unwrap();
+        git(&["add", "."]);
+        git(&["commit", "-m", "add sources"]);
+
+        assert_eq!(run(&["index", "."])["files_indexed"], 3);
+
+        fs::write(repo_path.join("a.rs"), "fn alpha() {}\n\nfn alpha2() {}\n").unwrap();
+        git(&["rm", "-q", "b.rs"]);
+        git(&["commit", "-am", "edit a, drop b"]);
+
+        // Only a.rs is re-parsed; b.rs's symbols go away and c.rs is untouched
+        let result = run(&["index", ".", "--incremental"]);
+        assert_eq!(result["files_indexed"], 1);
+        assert_eq!(result["files_removed"], 1);
+        assert_eq!(symbols_in("a.rs"), 2);
+        assert_eq!(symbols_in("b.rs"), 0);
+        assert_eq!(symbols_in("c.rs"), 1);
+
+        // Nothing new since the last run
+        assert_eq!(run(&["index", ".", "--incremental"])["files_indexed"], 0);
+
+        // A filtered run doesn't advance the marker, so c.rs is still picked up later
+        fs::write(repo_path.join("c.rs"), "fn gamma() {}\n\nfn gamma2() {}\n").unwrap();
+        git(&["commit", "-am", "edit c"]);
+        assert_eq!(run(&["index", ".", "--incremental", "--languages", "python"])["files_indexed"], 0);
+        assert_eq!(run(&["index", ".", "--incremental"])["files_indexed"], 1);
+        assert_eq!(symbols_in("c.rs"), 2);
+    }
+
+    #[test]
+    fn test_blame_symbol() {
+        let (_temp, repo_path) = init_test_repo();
+        let db_path = repo_path.join("test.db");
+
+        let git = |args: &[&str]| {
+            let output = Command::new("git").args(args).current_dir(&repo_path).output().unwrap();
+            String::from_utf8_lossy(&output.stdout).trim().to_string()
+        };
+        let run = |args: &[&str]| {
+            let output = Command::new("cargo")
+                .arg("run")
+                .arg("--")
+                .args(args)
+                .current_dir(env!("CARGO_MANIFEST_DIR"))
+                .env("CLAUDE_PROJECT_DIR", &repo_path)
+                .env("BACCHUS_DB_PATH", &db_path)
+                .env_remove("BACCHUS_INDEX_LANGUAGES")
+                .output()
+                .unwrap();
+            assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+            serde_json::from_slice::<serde_json::Value>(&output.stdout).unwrap()
+        };
+
+        fs::write(repo_path.join("a.rs"), "fn alpha() {}\n\nfn beta() {}\n").unwrap();
+        git(&["add", "."]);
+        git(&["commit", "-m", "add a"]);
+        run(&["index", "."]);
+        let original_hash = run(&["blame", "a.rs::alpha"])["hash"].clone();
+
+        fs::write(repo_path.join("a.rs"), "fn alpha() { beta(); }\n\nfn beta() {}\n").unwrap();
+        git(&["commit", "-am", "alpha calls beta"]);
+        let head = git(&["rev-parse", "HEAD"]);
+        run(&["index", "."]);
+
+        let blame = run(&["blame", "a.rs::alpha"]);
+        assert_eq!(blame["last_changed"]["previous_hash"], original_hash);
+        assert_eq!(blame["last_changed"]["commit"], head.as_str());
+        assert_ne!(blame["hash"], original_hash);
+
+        // Re-indexed but unchanged
+        assert!(run(&["blame", "a.rs::beta"])["history"].as_array().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_index_removes_deleted_files() {
+        let temp = TempDir::new().unwrap();
+        let db_path = temp.path().join("test.db");
+        fs::create_dir_all(temp.path().join("src")).unwrap();
+        fs::write(temp.path().join("src/a.rs"), "fn alpha() {}\n").unwrap();
+        fs::write(temp.path().join("src/b.rs"), "fn beta() {}\n").unwrap();
+        fs::write(temp.path().join("top.rs"), "fn top() {}\n").unwrap();
+
+        let run = |args: &[&str]| {
+            let output = Command::new("cargo")
+                .arg("run")
+                .arg("--")
+                .args(args)
+                .current_dir(env!("CARGO_MANIFEST_DIR"))
+                .env("CLAUDE_PROJECT_DIR", temp.path())
+                .env("BACCHUS_DB_PATH", &db_path)
+                .env_remove("BACCHUS_INDEX_LANGUAGES")
+                .output()
+                .unwrap();
+            assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+            serde_json::from_slice::<serde_json::Value>(&output.stdout).unwrap()
+        };
+        let symbols_in = |file: &str| run(&["symbols", "--file", file])["total_count"].as_i64().unwrap();
+
+        run(&["index", "."]);
+        assert_eq!(symbols_in("src/b.rs"), 1);
+
+        // Rename a.rs and delete b.rs, then re-index only the src directory
+        fs::rename(temp.path().join("src/a.rs"), temp.path().join("src/renamed.rs")).unwrap();
+        fs::remove_file(temp.path().join("src/b.rs")).unwrap();
+        fs::remove_file(temp.path().join("top.rs")).unwrap();
+
+        let result = run(&["index", "src"]);
+        assert_eq!(result["files_indexed"], 1);
+        assert_eq!(result["files_removed"], 2);
+        assert_eq!(symbols_in("src/a.rs"), 0);
+        assert_eq!(symbols_in("src/b.rs"), 0);
+        assert_eq!(symbols_in("src/renamed.rs"), 1);
+        // Outside the re-indexed directory, so left alone
+        assert_eq!(symbols_in("top.rs"), 1);
+    }
+
+    #[test]
+    fn test_index_call_graph() {
+        let temp = TempDir::new().unwrap();
+        let db_path = temp.path().join("test.db");
+
+        fs::write(
+            temp.path().join("app.py"),
+            "def save(x):\n    pass\n\ndef handle(x):\n    save(x)\n\ndef main():\n    handle(1)\n",
+        )
+        .unwrap();
+
+        let run = |args: &[&str]| {
+            let output = Command::new("cargo")
+                .arg("run")
+                .arg("--")
+                .args(args)
+                .current_dir(env!("CARGO_MANIFEST_DIR"))
+                .env("CLAUDE_PROJECT_DIR", temp.path())
+                .env("BACCHUS_DB_PATH", &db_path)
+                .env_remove("BACCHUS_INDEX_LANGUAGES")
+                .output()
+                .unwrap();
+            assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+            serde_json::from_slice::<serde_json::Value>(&output.stdout).unwrap()
+        };
+
+        run(&["index", "."]);
+
+        let callers = run(&["callers", "app.py::save"]);
+        assert_eq!(callers["total"], 2);
+        assert_eq!(callers["callers"][0]["fq_name"], "app.py::handle");
+        assert_eq!(callers["callers"][0]["call_site_line"], 5);
+        assert_eq!(callers["callers"][1]["fq_name"], "app.py::main");
+        assert_eq!(callers["callers"][1]["depth"], 2);
+
+        assert_eq!(run(&["callers", "app.py::save", "--depth", "1"])["total"], 1);
+
+        // Re-indexing replaces edges instead of duplicating them
+        run(&["index", "."]);
+        assert_eq!(run(&["callers", "app.py::save"])["total"], 2);
+    }
+
+    #[test]
+    fn test_symbols_imported_by() {
+        let temp = TempDir::new().unwrap();
+        let db_path = temp.path().join("test.db");
+
+        fs::write(
+            temp.path().join("auth.ts"),
+            "export function login() {}\nexport function logout() {}\nexport function reset() {}\n",
+        )
+        .unwrap();
+        fs::write(
+            temp.path().join("app.ts"),
+            "import { login, logout } from './auth';\n\nexport function main() { login(); }\n",
+        )
+        .unwrap();
+
+        let run = |args: &[&str]| {
+            let output = Command::new("cargo")
+                .arg("run")
+                .arg("--")
+                .args(args)
+                .current_dir(env!("CARGO_MANIFEST_DIR"))
+                .env("CLAUDE_PROJECT_DIR", temp.path())
+                .env("BACCHUS_DB_PATH", &db_path)
+                .env_remove("BACCHUS_INDEX_LANGUAGES")
+                .output()
+                .unwrap();
+            assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+            serde_json::from_slice::<serde_json::Value>(&output.stdout).unwrap()
+        };
+
+        run(&["index", "."]);
+
+        let result = run(&["symbols", "--imported-by", "app.ts"]);
+        let mut names: Vec<&str> = result["symbols"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|s| s["fq_name"].as_str().unwrap())
+            .collect();
+        names.sort();
+        assert_eq!(names, vec!["auth.ts::login", "auth.ts::logout"]);
+
+        assert_eq!(run(&["symbols", "--imported-by", "auth.ts"])["total_count"], 0);
+    }
 }
 
 // ============================================================================
@@ -426,6 +1389,41 @@ mod cli_tests {
         assert!(stdout.contains("bacchus release"));
     }
 
+    #[test]
+    fn test_init() {
+        let (_temp, repo_path) = init_test_repo();
+
+        let init = |args: &[&str]| {
+            Command::new("cargo")
+                .args(["run", "--", "init"])
+                .args(args)
+                .current_dir(env!("CARGO_MANIFEST_DIR"))
+                .env("CLAUDE_PROJECT_DIR", &repo_path)
+                .env_remove("BACCHUS_DB_PATH")
+                .env_remove("BACCHUS_WORKTREES")
+                .output()
+                .unwrap()
+        };
+
+        let output = init(&[]);
+        assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+        let result: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        assert_eq!(result["success"], true);
+        assert!(String::from_utf8_lossy(&output.stderr).contains("Bacchus Coordination Protocol"));
+        assert!(repo_path.join(".bacchus/worktrees").is_dir());
+        assert!(repo_path.join(".bacchus/bacchus.db").is_file());
+        assert!(repo_path.join(".bacchus/config.toml").is_file());
+        assert!(repo_path.join(".beads/beads.db").is_file());
+
+        // Refuses to clobber an existing workspace without --force
+        let output = init(&[]);
+        assert!(!output.status.success());
+        fs::write(repo_path.join(".bacchus/marker"), "x").unwrap();
+
+        assert!(init(&["--force"]).status.success());
+        assert!(!repo_path.join(".bacchus/marker").exists());
+    }
+
     #[test]
     fn test_version() {
         let output = Command::new("cargo")
@@ -438,6 +1436,22 @@ mod cli_tests {
         let stdout = String::from_utf8_lossy(&output.stdout);
         assert!(stdout.contains("bacchus"));
     }
+
+    #[test]
+    fn test_version_json() {
+        let output = Command::new("cargo")
+            .args(["run", "--", "version", "--json"])
+            .current_dir(env!("CARGO_MANIFEST_DIR"))
+            .output()
+            .unwrap();
+
+        assert!(output.status.success());
+        let info: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        assert_eq!(info["version"], env!("CARGO_PKG_VERSION"));
+        assert!(info["rustc_version"].as_str().unwrap().starts_with("rustc"));
+        assert!(info["sqlite_version"].as_str().unwrap().starts_with('3'));
+        assert_eq!(info["build_date"].as_str().unwrap().len(), 10);
+    }
 }
 
 // ============================================================================
@@ -479,6 +1493,34 @@ mod workflow_tests {
         );
     }
 
+    #[test]
+    fn test_next_wait_timeout() {
+        if !bd_available() {
+            eprintln!("Skipping test: bd not available");
+            return;
+        }
+
+        let temp = TempDir::new().unwrap();
+        let db_path = temp.path().join("test.db");
+
+        let started = std::time::Instant::now();
+        let output = Command::new("cargo")
+            .args(["run", "--", "next", "test-agent", "--wait", "--timeout", "1"])
+            .current_dir(env!("CARGO_MANIFEST_DIR"))
+            .env("BACCHUS_DB_PATH", &db_path)
+            .output()
+            .unwrap();
+
+        // Returns on its own instead of waiting forever
+        assert!(started.elapsed() < std::time::Duration::from_secs(60));
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(
+            stdout.contains("success") || stderr.contains("bd") || stderr.contains("Failed"),
+            "Unexpected output: stdout={}, stderr={}", stdout, stderr
+        );
+    }
+
     #[test]
     fn test_release_without_claim() {
         let temp = TempDir::new().unwrap();
@@ -539,6 +1581,61 @@ mod workflow_tests {
             "Expected merge error, got: stdout={}, stderr={}", stdout, stderr
         );
     }
+
+    #[test]
+    fn test_export_context_unclaimed() {
+        let temp = TempDir::new().unwrap();
+        let db_path = temp.path().join("test.db");
+        let bundle_path = temp.path().join("bundle.json");
+
+        let output = Command::new("cargo")
+            .args(["run", "--", "export-context", "test-bead", "--output"])
+            .arg(&bundle_path)
+            .current_dir(env!("CARGO_MANIFEST_DIR"))
+            .env("BACCHUS_DB_PATH", &db_path)
+            .output()
+            .unwrap();
+
+        assert!(output.status.success());
+
+        let bundle: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&bundle_path).unwrap()).unwrap();
+        assert!(bundle["context_text"].as_str().unwrap().contains("test-bead"));
+        assert_eq!(bundle["claims"], serde_json::json!([]));
+        assert_eq!(bundle["git_log"], serde_json::json!([]));
+    }
+
+    #[test]
+    fn test_context_reviewer_persona() {
+        let temp = TempDir::new().unwrap();
+        let db_path = temp.path().join("test.db");
+
+        let run = |args: &[&str]| {
+            let output = Command::new("cargo")
+                .arg("run")
+                .arg("--")
+                .args(args)
+                .current_dir(env!("CARGO_MANIFEST_DIR"))
+                .env("BACCHUS_DB_PATH", &db_path)
+                .env("CLAUDE_PROJECT_DIR", temp.path())
+                .output()
+                .unwrap();
+            assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+            String::from_utf8_lossy(&output.stdout).to_string()
+        };
+
+        run(&["session", "start", "agent", "--bead-id", "bead-a"]);
+        run(&["register-symbol", "bead-a", "src/auth.rs::login"]);
+        run(&["register-symbol", "bead-b", "src/auth.rs::login"]);
+
+        let context = run(&["context", "--bead-id", "bead-a", "--agent-persona", "reviewer"]);
+        assert!(context.contains("## Related Work by Other Agents"));
+        assert!(context.contains("| bead-b | - | `src/auth.rs::login` | modifies |"));
+
+        // The persona sticks to the session for later calls
+        let context = run(&["context", "--bead-id", "bead-a"]);
+        assert!(context.contains("**Persona**: reviewer"));
+    }
 }
 
 // ============================================================================